@@ -1,25 +1,152 @@
+use fs_mod_parser::maps::structs::GrowthPeriodMode;
+use fs_mod_parser::mod_detail::structs::MotorUnits;
+use fs_mod_parser::shared::files::FolderHashMode;
 use fs_mod_parser::{parse_mod_with_options, ModParserOptions};
 use std::env;
 use std::path;
-
-static QUICK_SCAN: ModParserOptions = ModParserOptions {
-    include_mod_detail: false,
-    include_save_game: false,
-    skip_detail_icons: true,
-    skip_mod_icons: false,
-};
+use std::time::Duration;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    if args.len() < 2 {
-        println!("Usage:\n  parse_file [path_to_mod]\n");
+    let mut options = ModParserOptions {
+        skip_detail_icons: true,
+        ..Default::default()
+    };
+    let mut compact = false;
+    let mut summary = false;
+    let mut verbose = false;
+    let mut input_file: Option<&String> = None;
+
+    for arg in args.iter().skip(1) {
+        match arg.as_str() {
+            "--case-check" => options.reference_case = true,
+            "--compact" => compact = true,
+            "--density" => options.map_density_check = true,
+            "--detail" => options.include_mod_detail = true,
+            "--detail-icons" => options.skip_detail_icons = false,
+            "--diagnostics" => options.detail_diagnostics = true,
+            "--disabled" => options.include_disabled = true,
+            "--dlcs" => options.store_item_dlcs = true,
+            "--external" => options.external_references = true,
+            "--farmlands" => options.map_farmlands = true,
+            "--full-overview" => options.full_map_overview = true,
+            "--no-icons" => options.skip_mod_icons = true,
+            "--savegame" => options.include_save_game = true,
+            "--scripts" => options.script_api = true,
+            "--slim" => options.slim_output = true,
+            "--statistics" => options.save_game_statistics = true,
+            "--summary" => summary = true,
+            "--textures" => options.texture_report = true,
+            "--verbose" => verbose = true,
+            "--weight-maps" => options.weight_images = true,
+            limit if limit.starts_with("--time-limit=") => {
+                options.time_limit = limit["--time-limit=".len()..]
+                    .parse::<u64>()
+                    .ok()
+                    .map(Duration::from_millis);
+            }
+            size if size.starts_with("--map-size=") => {
+                options.map_image_size = size["--map-size=".len()..].parse::<u32>().ok();
+            }
+            count if count.starts_with("--gallery=") => {
+                options.gallery_images = count["--gallery=".len()..].parse::<usize>().unwrap_or(0);
+            }
+            limit if limit.starts_with("--file-limit=") => {
+                options.read_limits.per_file = limit["--file-limit=".len()..].parse::<u64>().ok();
+            }
+            limit if limit.starts_with("--mod-limit=") => {
+                options.read_limits.per_mod = limit["--mod-limit=".len()..].parse::<u64>().ok();
+            }
+            threads if threads.starts_with("--threads=") => {
+                options.detail_threads = threads["--threads=".len()..].parse().unwrap_or(0);
+            }
+            "--folder-hash=content" => options.folder_hash = FolderHashMode::Content,
+            "--folder-hash=listing" => options.folder_hash = FolderHashMode::Listing,
+            "--growth=clamp" => options.growth_periods = GrowthPeriodMode::Clamp,
+            "--growth=skip" => options.growth_periods = GrowthPeriodMode::Skip,
+            "--growth=wrap" => options.growth_periods = GrowthPeriodMode::Wrap,
+            "--units=imperial" => options.motor_units = MotorUnits::Imperial,
+            "--units=metric" => options.motor_units = MotorUnits::Metric,
+            lang if lang.starts_with("--languages=") => {
+                options.languages = Some(
+                    lang["--languages=".len()..]
+                        .split(',')
+                        .map(str::to_owned)
+                        .collect(),
+                );
+            }
+            allow if allow.starts_with("--allow-extra=") => {
+                options.allowed_extra_files = allow["--allow-extra=".len()..]
+                    .split(',')
+                    .map(str::to_owned)
+                    .collect();
+            }
+            raw if raw.starts_with("--raw=") => {
+                options.raw_mod_desc_tags = Some(
+                    raw["--raw=".len()..]
+                        .split(',')
+                        .map(str::to_owned)
+                        .collect(),
+                );
+            }
+            unknown if unknown.starts_with("--") => {
+                eprintln!("Unknown option: {unknown}");
+                std::process::exit(2);
+            }
+            _ => input_file = Some(arg),
+        }
+    }
+
+    let Some(input_file) = input_file else {
+        println!("Usage:\n  parse_file [options] [path_to_mod]\n");
+        println!("Options:");
+        println!("  --allow-extra=  comma separated extra file extensions or names to allow");
+        println!("  --case-check    check file reference case in XML and I3D files");
+        println!("  --compact       single line JSON output");
+        println!("  --density       check map density and weight files");
+        println!("  --detail        include store item detail");
+        println!("  --detail-icons  process store item icons");
+        println!("  --diagnostics   include store item parse times");
+        println!("  --disabled      parse disabled mods (.zip.disabled, .zip.bak)");
+        println!("  --dlcs          check store items for required DLCs");
+        println!("  --external      flag file references outside the mod");
+        println!("  --farmlands     draw farmland borders on the map image");
+        println!("  --file-limit=   largest single file to read, in bytes");
+        println!("  --folder-hash=  hash folder mods: listing or content");
+        println!("  --full-overview include the full map overview image");
+        println!("  --gallery=      collect up to this many gallery images");
+        println!("  --growth=       growth file periods past 12: wrap, clamp or skip");
+        println!("  --languages=    comma separated language codes to keep");
+        println!("  --map-size=     map image size in pixels, 64 to 4096 (default 512)");
+        println!("  --mod-limit=    total bytes to read per mod");
+        println!("  --no-icons      skip the mod icon");
+        println!("  --raw=          comma separated modDesc tags to pass through");
+        println!("  --savegame      include save game detail");
+        println!("  --scripts       summarize the game API use of lua scripts");
+        println!("  --slim          leave images, file lists and descriptions out");
+        println!("  --statistics    include save game farm statistics");
+        println!("  --summary       compact summary record only");
+        println!("  --textures      audit every DDS texture");
+        println!("  --threads=      worker threads for store item detail");
+        println!("  --time-limit=   parse time budget in milliseconds");
+        println!("  --units=        motor units: metric or imperial (default both)");
+        println!("  --verbose       add issue severity and messages");
+        println!("  --weight-maps   list terrain weight map images\n");
         println!("No input file specified");
         std::process::exit(0);
-    }
+    };
+
+    if let Ok(file) = path::absolute(input_file) {
+        let record = parse_mod_with_options(file.as_path(), &options);
 
-    if let Ok(file) = path::absolute(&args[1]) {
-        let output = parse_mod_with_options(file.as_path(), &QUICK_SCAN).to_json_pretty();
+        let output = match (summary, verbose, compact) {
+            (true, _, true) => record.to_summary().to_json(),
+            (true, _, false) => record.to_summary().to_json_pretty(),
+            (false, true, _) => record.to_json_verbose("en", !compact),
+            (false, false, true) => record.to_json(),
+            (false, false, false) => record.to_json_pretty(),
+        };
 
         println!("{output}")
     }
@@ -0,0 +1,181 @@
+//! Skip re-parsing unchanged mods
+//!
+//! A [`ParseCache`] remembers the record for each path along with a
+//! fingerprint of the file (size and modified time, or an MD5 of the
+//! contents). [`parse_mod_cached`] only runs the full parser when the
+//! fingerprint changes. Records are also keyed by the parser options, so
+//! a mod parsed again with different options is not served a stale
+//! record. `modhub_hashes` and `badge_rules` are not part of that key,
+//! keep one cache per set of those.
+use crate::mod_basic::parser_with_options;
+use crate::sandbox::worker_args;
+use crate::shared::files::file_md5;
+use crate::shared::structs::ModRecord;
+use crate::ModParserOptions;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+/// What identifies an unchanged file
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Fingerprint {
+    /// file size (or total size of a folder)
+    size: u64,
+    /// modified time (or newest modified time in a folder)
+    modified: Option<SystemTime>,
+    /// MD5 of the file contents, when hashing is on
+    md5_sum: Option<String>,
+}
+
+/// Previously parsed records keyed by path
+#[derive(Default)]
+pub struct ParseCache {
+    /// compare file contents instead of modified time
+    use_hash: bool,
+    /// cached records, with the options (as worker flags) used to parse them
+    entries: HashMap<PathBuf, (Fingerprint, Vec<String>, Arc<ModRecord>)>,
+}
+
+impl ParseCache {
+    /// Create an empty cache, keyed by size and modified time
+    #[must_use]
+    pub fn new() -> Self {
+        ParseCache::default()
+    }
+
+    /// Create an empty cache, keyed by size and MD5 of the file contents
+    ///
+    /// Slower than [`ParseCache::new`], but not fooled by tools that
+    /// preserve modified times. Folders still use modified times.
+    #[must_use]
+    pub fn new_hashed() -> Self {
+        ParseCache {
+            use_hash: true,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Number of cached records
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Cache is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drop the cached record for a path
+    pub fn remove<P: AsRef<Path>>(&mut self, full_path: P) {
+        self.entries.remove(full_path.as_ref());
+    }
+
+    /// Drop every cached record
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Fingerprint a file or folder, `None` if it can't be read
+    fn fingerprint(&self, full_path: &Path) -> Option<Fingerprint> {
+        fingerprint(full_path, self.use_hash)
+    }
+}
+
+/// Fingerprint a file or folder, `None` if it can't be read
+///
+/// With `use_hash`, files also get an MD5 of their contents
+pub(crate) fn fingerprint(full_path: &Path, use_hash: bool) -> Option<Fingerprint> {
+    let meta = std::fs::metadata(full_path).ok()?;
+
+    if meta.is_dir() {
+        let mut fingerprint = Fingerprint {
+            size: 0,
+            modified: meta.modified().ok(),
+            md5_sum: None,
+        };
+        fingerprint_folder(full_path, &mut fingerprint);
+        return Some(fingerprint);
+    }
+
+    let md5_sum = if use_hash {
+        Some(file_md5(full_path)?)
+    } else {
+        None
+    };
+
+    Some(Fingerprint {
+        size: meta.len(),
+        modified: meta.modified().ok(),
+        md5_sum,
+    })
+}
+
+/// Add folder contents to a fingerprint
+fn fingerprint_folder(folder: &Path, fingerprint: &mut Fingerprint) {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if let Ok(modified) = meta.modified() {
+            fingerprint.modified = fingerprint.modified.max(Some(modified));
+        }
+        if meta.is_dir() {
+            fingerprint_folder(&entry.path(), fingerprint);
+        } else {
+            fingerprint.size += meta.len();
+        }
+    }
+}
+
+/// [`crate::mod_basic::parser_with_options`], reusing cached records
+///
+/// If the file is unchanged since it was last parsed into this cache,
+/// with the same options, the cached record is returned (and counted as a cache hit in
+/// [`ModParserOptions::metrics`]). Otherwise the mod is parsed and the
+/// cache updated. Files that can't be fingerprinted are parsed and not
+/// cached.
+pub fn parse_mod_cached<P: AsRef<Path>>(
+    full_path: P,
+    options: &ModParserOptions,
+    cache: &mut ParseCache,
+) -> Arc<ModRecord> {
+    let full_path = full_path.as_ref();
+    let fingerprint = cache.fingerprint(full_path);
+    let options_key = worker_args(options);
+
+    if let (Some(fingerprint), Some((cached_print, cached_options, record))) =
+        (&fingerprint, cache.entries.get(full_path))
+    {
+        if fingerprint == cached_print && &options_key == cached_options {
+            if let Some(metrics) = &options.metrics {
+                metrics.cache_hit();
+            }
+            return Arc::clone(record);
+        }
+    }
+
+    let record = Arc::new(parser_with_options(full_path, options));
+
+    match fingerprint {
+        Some(fingerprint) => {
+            cache.entries.insert(
+                full_path.to_path_buf(),
+                (fingerprint, options_key, Arc::clone(&record)),
+            );
+        }
+        None => {
+            cache.entries.remove(full_path);
+        }
+    }
+    record
+}
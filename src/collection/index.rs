@@ -0,0 +1,233 @@
+//! Persistent index of a parsed collection
+//!
+//! An [`Index`] keeps parsed records with the fingerprint of their file, and
+//! saves them to a single compressed file. Loading it back drops records
+//! whose file changed (or is gone), so only those need parsing again.
+use crate::cache::{fingerprint, Fingerprint};
+use crate::mod_basic::parser_with_options;
+use crate::shared::errors::FsModParserError;
+use crate::shared::structs::ModRecord;
+use crate::ModParserOptions;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the JSON document inside the index file
+const INDEX_ENTRY_NAME: &str = "index.json";
+
+/// Parser version written to (and expected in) index files
+pub const INDEX_PARSER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Single indexed mod
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexEntry {
+    /// size and modified time of the file when it was parsed
+    fingerprint: Fingerprint,
+    /// parsed record
+    pub record: ModRecord,
+}
+
+/// Parsed collection, saved to and loaded from a single index file
+///
+/// Records depend on the parser options, so keep one index per set of
+/// options.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Index {
+    /// parser version that wrote the index, see [`INDEX_PARSER_VERSION`]
+    pub parser_version: String,
+    /// when the index was last saved (RFC 3339)
+    pub saved: Option<String>,
+    /// free form details for the application (collection name, folder, etc.)
+    pub metadata: BTreeMap<String, String>,
+    /// indexed records keyed by path
+    entries: BTreeMap<PathBuf, IndexEntry>,
+}
+
+impl Default for Index {
+    fn default() -> Self {
+        Index {
+            parser_version: INDEX_PARSER_VERSION.to_owned(),
+            saved: None,
+            metadata: BTreeMap::new(),
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl Index {
+    /// Create an empty index
+    #[must_use]
+    pub fn new() -> Self {
+        Index::default()
+    }
+
+    /// Number of indexed records
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Index is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Indexed record for a path
+    #[must_use]
+    pub fn get<P: AsRef<Path>>(&self, full_path: P) -> Option<&ModRecord> {
+        self.entries
+            .get(full_path.as_ref())
+            .map(|entry| &entry.record)
+    }
+
+    /// Every indexed record, ordered by path
+    pub fn records(&self) -> impl Iterator<Item = &ModRecord> {
+        self.entries.values().map(|entry| &entry.record)
+    }
+
+    /// Indexed paths, in order
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(PathBuf::as_path)
+    }
+
+    /// Drop the record for a path
+    pub fn remove<P: AsRef<Path>>(&mut self, full_path: P) {
+        self.entries.remove(full_path.as_ref());
+    }
+
+    /// Add a record parsed elsewhere
+    ///
+    /// Returns false (and leaves the index alone) when the file can't be
+    /// fingerprinted
+    pub fn insert<P: AsRef<Path>>(&mut self, full_path: P, record: ModRecord) -> bool {
+        let full_path = full_path.as_ref();
+        let Some(fingerprint) = fingerprint(full_path, false) else {
+            return false;
+        };
+        self.entries.insert(
+            full_path.to_path_buf(),
+            IndexEntry {
+                fingerprint,
+                record,
+            },
+        );
+        true
+    }
+
+    /// Return the indexed record, parsing the mod if it is new or changed
+    ///
+    /// Hits are counted in [`ModParserOptions::metrics`], like
+    /// [`crate::parse_mod_cached`]. Files that can't be fingerprinted are
+    /// parsed and not indexed.
+    pub fn update<P: AsRef<Path>>(
+        &mut self,
+        full_path: P,
+        options: &ModParserOptions,
+    ) -> ModRecord {
+        let full_path = full_path.as_ref();
+        let fingerprint = fingerprint(full_path, false);
+
+        if let (Some(fingerprint), Some(entry)) = (&fingerprint, self.entries.get(full_path)) {
+            if *fingerprint == entry.fingerprint {
+                if let Some(metrics) = &options.metrics {
+                    metrics.cache_hit();
+                }
+                return entry.record.clone();
+            }
+        }
+
+        let record = parser_with_options(full_path, options);
+
+        match fingerprint {
+            Some(fingerprint) => {
+                self.entries.insert(
+                    full_path.to_path_buf(),
+                    IndexEntry {
+                        fingerprint,
+                        record: record.clone(),
+                    },
+                );
+            }
+            None => {
+                self.entries.remove(full_path);
+            }
+        }
+        record
+    }
+
+    /// Drop records whose file changed or is gone
+    ///
+    /// Returns the dropped paths
+    pub fn invalidate(&mut self) -> Vec<PathBuf> {
+        let stale: Vec<PathBuf> = self
+            .entries
+            .iter()
+            .filter(|(path, entry)| fingerprint(path, false).as_ref() != Some(&entry.fingerprint))
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in &stale {
+            self.entries.remove(path);
+        }
+        stale
+    }
+
+    /// Write the index to a compressed file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FsModParserError::Io`] when the file can't be written
+    pub fn save<P: AsRef<Path>>(&mut self, index_path: P) -> Result<(), FsModParserError> {
+        self.saved = Some(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+        let contents = serde_json::to_vec(&self).map_err(std::io::Error::other)?;
+
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(index_path)?);
+        writer
+            .start_file(
+                INDEX_ENTRY_NAME,
+                zip::write::SimpleFileOptions::default()
+                    .compression_method(zip::CompressionMethod::Deflated),
+            )
+            .map_err(std::io::Error::other)?;
+        writer.write_all(&contents)?;
+        writer.finish().map_err(std::io::Error::other)?;
+        Ok(())
+    }
+
+    /// Read an index file, dropping records whose file changed
+    ///
+    /// An index written by a different parser version loads empty, as its
+    /// records may be missing fields or findings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FsModParserError::NotFound`] or [`FsModParserError::Io`]
+    /// when the file can't be read, and [`FsModParserError::InvalidIndex`]
+    /// when it is not an index
+    pub fn load<P: AsRef<Path>>(index_path: P) -> Result<Self, FsModParserError> {
+        let index_path = index_path.as_ref();
+        FsModParserError::check_path(index_path)?;
+
+        let invalid = || FsModParserError::InvalidIndex(index_path.to_path_buf());
+        let mut archive =
+            zip::ZipArchive::new(std::fs::File::open(index_path)?).map_err(|_| invalid())?;
+        let mut contents = String::new();
+        archive
+            .by_name(INDEX_ENTRY_NAME)
+            .map_err(|_| invalid())?
+            .read_to_string(&mut contents)?;
+
+        let mut index: Index = serde_json::from_str(&contents).map_err(|_| invalid())?;
+
+        if index.parser_version == INDEX_PARSER_VERSION {
+            index.invalidate();
+        } else {
+            index.entries.clear();
+            INDEX_PARSER_VERSION.clone_into(&mut index.parser_version);
+        }
+        Ok(index)
+    }
+}
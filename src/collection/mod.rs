@@ -0,0 +1,770 @@
+//! Analysis across a collection of mods
+//!
+//! Functions here take a list of parsed [`ModRecord`]s and look for
+//! problems that only show up when mods are loaded together.
+use crate::maps::merge_crop_calendar;
+use crate::maps::structs::CropCalendarMerge;
+use crate::mod_detail::structs::{ModDetailVehicle, MotorEntry, MotorValue, VehicleCapability};
+use crate::shared::structs::ModRecord;
+use crate::shared::{categories_overlap, normalize_input};
+use serde::ser::{Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet};
+use std::f32::consts::PI;
+
+mod index;
+
+pub use index::{Index, IndexEntry, INDEX_PARSER_VERSION};
+
+/// A single mod's use of a conflicting input
+#[derive(serde::Serialize, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyBindEntry {
+    /// short name of the mod
+    pub short_name: String,
+    /// action name bound to the input
+    pub action: String,
+    /// action category (`ALL` if not set)
+    pub category: String,
+}
+
+/// Default keyboard input bound by more than one mod
+#[derive(serde::Serialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyBindConflict {
+    /// normalized input (e.g. `KEY_lalt KEY_x`)
+    pub input: String,
+    /// mods and actions that use this input
+    pub bindings: Vec<KeyBindEntry>,
+}
+
+/// Find conflicting default keyboard bindings across a collection
+///
+/// An input conflicts when two different mods bind it in overlapping
+/// action categories (`ALL` overlaps every category). Results are sorted
+/// by input.
+#[must_use]
+pub fn key_conflicts(records: &[ModRecord]) -> Vec<KeyBindConflict> {
+    let mut by_input: BTreeMap<String, Vec<KeyBindEntry>> = BTreeMap::new();
+
+    for record in records {
+        for (action, inputs) in &record.mod_desc.binds {
+            let category = record
+                .mod_desc
+                .actions
+                .get(action)
+                .cloned()
+                .unwrap_or_else(|| String::from("ALL"));
+
+            for input in inputs {
+                by_input
+                    .entry(normalize_input(input))
+                    .or_default()
+                    .push(KeyBindEntry {
+                        short_name: record.file_detail.short_name.clone(),
+                        action: action.clone(),
+                        category: category.clone(),
+                    });
+            }
+        }
+    }
+
+    by_input
+        .into_iter()
+        .filter_map(|(input, entries)| {
+            let mut bindings: Vec<KeyBindEntry> = entries
+                .iter()
+                .filter(|this| {
+                    entries.iter().any(|other| {
+                        other.short_name != this.short_name
+                            && categories_overlap(&this.category, &other.category)
+                    })
+                })
+                .cloned()
+                .collect();
+
+            bindings.sort();
+            (!bindings.is_empty()).then_some(KeyBindConflict { input, bindings })
+        })
+        .collect()
+}
+
+/// Dependency analysis of a collection
+#[derive(serde::Serialize, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyReport {
+    /// suggested load order, dependencies first (short names)
+    ///
+    /// Mods in a cycle are listed together, at the point the whole
+    /// cycle can be loaded
+    pub load_order: Vec<String>,
+    /// groups of mods that depend on each other in a circle
+    pub cycles: Vec<Vec<String>>,
+    /// mods that list themselves as a dependency
+    pub self_dependent: Vec<String>,
+    /// dependencies not found in the collection, by mod
+    pub missing: BTreeMap<String, Vec<String>>,
+    /// dependencies found in the collection, but older than required, by mod
+    pub outdated: BTreeMap<String, Vec<OutdatedDependency>>,
+}
+
+/// A dependency that is in the collection, but too old
+#[derive(serde::Serialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OutdatedDependency {
+    /// short name of the required mod
+    pub name: String,
+    /// lowest version that will do
+    pub min_version: String,
+    /// version in the collection
+    pub version: String,
+}
+
+/// Build the dependency graph of a collection
+///
+/// Dependencies come from `<dependencies>` in each modDesc. Output is
+/// sorted by short name wherever the graph allows a choice, so it is
+/// stable between runs.
+#[must_use]
+pub fn dependency_order(records: &[ModRecord]) -> DependencyReport {
+    let mut report = DependencyReport::default();
+    let mut graph: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let mut versions: BTreeMap<&str, &str> = BTreeMap::new();
+
+    for record in records {
+        let short_name = record.file_detail.short_name.as_str();
+        graph.entry(short_name).or_default();
+        versions.insert(short_name, record.mod_desc.version.as_str());
+    }
+
+    for record in records {
+        let short_name = record.file_detail.short_name.as_str();
+        for depend in &record.mod_desc.depend {
+            if depend.name == short_name {
+                report.self_dependent.push(short_name.to_owned());
+            } else if let Some(version) = versions.get(depend.name.as_str()) {
+                graph
+                    .entry(short_name)
+                    .or_default()
+                    .insert(depend.name.as_str());
+                if let Some(min_version) = depend
+                    .min_version
+                    .as_ref()
+                    .filter(|_| !depend.is_met_by(version))
+                {
+                    report
+                        .outdated
+                        .entry(short_name.to_owned())
+                        .or_default()
+                        .push(OutdatedDependency {
+                            name: depend.name.clone(),
+                            min_version: min_version.clone(),
+                            version: (*version).to_owned(),
+                        });
+                }
+            } else {
+                report
+                    .missing
+                    .entry(short_name.to_owned())
+                    .or_default()
+                    .push(depend.name.clone());
+            }
+        }
+    }
+    report.self_dependent.sort();
+    report.self_dependent.dedup();
+
+    let mut walker = DependencyWalker::default();
+    for name in graph.keys() {
+        if !walker.index.contains_key(name) {
+            walker.visit(name, &graph);
+        }
+    }
+
+    for mut group in walker.groups {
+        group.sort_unstable();
+        let names: Vec<String> = group.into_iter().map(str::to_owned).collect();
+        report.load_order.extend(names.iter().cloned());
+        if names.len() > 1 {
+            report.cycles.push(names);
+        }
+    }
+    report
+}
+
+/// Strongly connected component search (Tarjan) over the dependency graph
+///
+/// Groups are found dependencies first, which is the load order
+#[derive(Default)]
+struct DependencyWalker<'a> {
+    /// visit order of each mod
+    index: BTreeMap<&'a str, usize>,
+    /// lowest visit order reachable from each mod
+    low_link: BTreeMap<&'a str, usize>,
+    /// mods of groups still being built
+    stack: Vec<&'a str>,
+    /// mods currently on the stack
+    on_stack: BTreeSet<&'a str>,
+    /// finished groups, dependencies first
+    groups: Vec<Vec<&'a str>>,
+}
+
+impl<'a> DependencyWalker<'a> {
+    /// Visit a mod and everything it depends on
+    fn visit(&mut self, name: &'a str, graph: &BTreeMap<&'a str, BTreeSet<&'a str>>) {
+        let order = self.index.len();
+        self.index.insert(name, order);
+        self.low_link.insert(name, order);
+        self.stack.push(name);
+        self.on_stack.insert(name);
+
+        for depend in graph.get(name).into_iter().flatten() {
+            if !self.index.contains_key(depend) {
+                self.visit(depend, graph);
+                let low = self.low_link[name].min(self.low_link[depend]);
+                self.low_link.insert(name, low);
+            } else if self.on_stack.contains(depend) {
+                let low = self.low_link[name].min(self.index[depend]);
+                self.low_link.insert(name, low);
+            }
+        }
+
+        if self.low_link[name] == self.index[name] {
+            let mut group = vec![];
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                group.push(member);
+                if member == name {
+                    break;
+                }
+            }
+            self.groups.push(group);
+        }
+    }
+}
+
+/// Group a collection by author
+///
+/// Maps each name in [`crate::shared::structs::ModDesc::authors`] to the
+/// sorted short names of its mods. Names match ignoring case, the first
+/// spelling seen is used.
+#[must_use]
+pub fn group_by_author(records: &[ModRecord]) -> BTreeMap<String, Vec<String>> {
+    let mut spelling: BTreeMap<String, String> = BTreeMap::new();
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for record in records {
+        for author in &record.mod_desc.authors {
+            let name = spelling
+                .entry(author.to_lowercase())
+                .or_insert_with(|| author.clone());
+            groups
+                .entry(name.clone())
+                .or_default()
+                .push(record.file_detail.short_name.clone());
+        }
+    }
+
+    for mods in groups.values_mut() {
+        mods.sort();
+        mods.dedup();
+    }
+    groups
+}
+
+/// Effective crop calendar of each map in a collection
+///
+/// Every crop addon (a non-map mod with crop data, see
+/// [`crate::maps::read_crop_addon`]) is merged into every map, in
+/// collection order. Results follow collection order.
+#[must_use]
+pub fn crop_calendars(records: &[ModRecord]) -> Vec<CropCalendarMerge> {
+    let addons: Vec<&ModRecord> = records
+        .iter()
+        .filter(|n| n.mod_desc.map_config_file.is_none() && !n.mod_desc.crop_info.is_empty())
+        .collect();
+
+    records
+        .iter()
+        .filter(|n| n.mod_desc.map_config_file.is_some() && !n.mod_desc.crop_info.is_empty())
+        .map(|map| merge_crop_calendar(map, &addons))
+        .collect()
+}
+
+/// Power check of a vehicle / implement pair
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CompatibilityPower {
+    /// vehicle power covers the implement's `neededPower`
+    Enough,
+    /// vehicle is weaker than the implement's `neededPower`
+    TooLow,
+    /// vehicle power or implement need is not known
+    Unknown,
+}
+
+impl Serialize for CompatibilityPower {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            CompatibilityPower::Enough => {
+                serializer.serialize_unit_variant("CompatibilityPower", 0, "ENOUGH")
+            }
+            CompatibilityPower::TooLow => {
+                serializer.serialize_unit_variant("CompatibilityPower", 1, "TOO_LOW")
+            }
+            CompatibilityPower::Unknown => {
+                serializer.serialize_unit_variant("CompatibilityPower", 2, "UNKNOWN")
+            }
+        }
+    }
+}
+
+/// A vehicle or implement of a compatibility matrix
+#[derive(serde::Serialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityItem {
+    /// short name of the mod
+    pub short_name: String,
+    /// store item file in the mod
+    pub file: String,
+    /// store item name
+    pub name: String,
+    /// joint types offered (vehicles) or needed (implements)
+    pub joints: Vec<String>,
+    /// power in hp - offered (vehicles) or needed (implements), if known
+    pub power: Option<u32>,
+}
+
+/// A vehicle that can use an implement
+#[derive(serde::Serialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityPair {
+    /// index in [`CompatibilityMatrix::vehicles`]
+    pub vehicle: usize,
+    /// index in [`CompatibilityMatrix::implements`]
+    pub implement: usize,
+    /// joint types both sides share
+    pub joints: Vec<String>,
+    /// power check
+    pub power: CompatibilityPower,
+}
+
+/// Which vehicles of a collection can use which implements
+#[derive(serde::Serialize, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityMatrix {
+    /// motorized vehicles with attacher joints, sorted by mod and file
+    pub vehicles: Vec<CompatibilityItem>,
+    /// items that attach to a vehicle, sorted by mod and file
+    pub implements: Vec<CompatibilityItem>,
+    /// every vehicle / implement pair sharing a joint type
+    pub pairs: Vec<CompatibilityPair>,
+}
+
+impl CompatibilityMatrix {
+    /// Implements a vehicle can use, with the power check of each
+    #[must_use]
+    pub fn implements_for(&self, vehicle: usize) -> Vec<(&CompatibilityItem, CompatibilityPower)> {
+        self.pairs
+            .iter()
+            .filter(|pair| pair.vehicle == vehicle)
+            .filter_map(|pair| Some((self.implements.get(pair.implement)?, pair.power)))
+            .collect()
+    }
+
+    /// Vehicles that can use an implement, with the power check of each
+    #[must_use]
+    pub fn vehicles_for(&self, implement: usize) -> Vec<(&CompatibilityItem, CompatibilityPower)> {
+        self.pairs
+            .iter()
+            .filter(|pair| pair.implement == implement)
+            .filter_map(|pair| Some((self.vehicles.get(pair.vehicle)?, pair.power)))
+            .collect()
+    }
+}
+
+/// Build the vehicle / implement compatibility matrix of a collection
+///
+/// Only records parsed with detail (see
+/// [`crate::ModParserOptions::include_mod_detail`]) are used. A vehicle
+/// can use an implement when one of its `attacherJoint` types matches an
+/// `inputAttacherJoint` type of the implement. Vehicle power is the
+/// `power` store spec, or the strongest motor configuration.
+#[must_use]
+pub fn compatibility_matrix(records: &[ModRecord]) -> CompatibilityMatrix {
+    let mut matrix = CompatibilityMatrix::default();
+
+    for record in records {
+        let Some(detail) = &record.include_detail else {
+            continue;
+        };
+        for (file, vehicle) in &detail.vehicles {
+            let item = |joints: &[String], power: Option<u32>| CompatibilityItem {
+                short_name: record.file_detail.short_name.clone(),
+                file: file.clone(),
+                name: vehicle.specs.name.clone(),
+                joints: joints.to_vec(),
+                power,
+            };
+            if matches!(vehicle.flags.motorized, VehicleCapability::Yes)
+                && !vehicle.specs.joint_accepts.is_empty()
+            {
+                matrix
+                    .vehicles
+                    .push(item(&vehicle.specs.joint_accepts, vehicle_power(vehicle)));
+            }
+            if !vehicle.specs.joint_requires.is_empty() {
+                let needed = vehicle
+                    .power_requirements
+                    .as_ref()
+                    .and_then(|n| n.needed_power);
+                matrix
+                    .implements
+                    .push(item(&vehicle.specs.joint_requires, needed));
+            }
+        }
+    }
+
+    let order = |a: &CompatibilityItem, b: &CompatibilityItem| {
+        (&a.short_name, &a.file).cmp(&(&b.short_name, &b.file))
+    };
+    matrix.vehicles.sort_by(order);
+    matrix.implements.sort_by(order);
+
+    for (vehicle_index, vehicle) in matrix.vehicles.iter().enumerate() {
+        for (implement_index, implement) in matrix.implements.iter().enumerate() {
+            let joints: Vec<String> = implement
+                .joints
+                .iter()
+                .filter(|joint| vehicle.joints.contains(joint))
+                .cloned()
+                .collect();
+            if joints.is_empty() {
+                continue;
+            }
+            let power = match (vehicle.power, implement.power) {
+                (Some(offered), Some(needed)) if offered >= needed => CompatibilityPower::Enough,
+                (Some(_), Some(_)) => CompatibilityPower::TooLow,
+                _ => CompatibilityPower::Unknown,
+            };
+            matrix.pairs.push(CompatibilityPair {
+                vehicle: vehicle_index,
+                implement: implement_index,
+                joints,
+                power,
+            });
+        }
+    }
+    matrix
+}
+
+/// Power of a vehicle in hp - the `power` store spec, or the strongest motor
+fn vehicle_power(vehicle: &ModDetailVehicle) -> Option<u32> {
+    vehicle
+        .specs
+        .specs
+        .get("power")
+        .copied()
+        .or_else(|| vehicle.motor.motors.iter().filter_map(motor_power).max())
+}
+
+/// Peak power of a motor in hp
+///
+/// Motors parsed with metric-only [`crate::mod_detail::structs::MotorUnits`]
+/// have no hp curve, so it is worked out from the raw torque curve
+fn motor_power(motor: &MotorEntry) -> Option<u32> {
+    if !motor.horse_power.is_empty() {
+        return motor.horse_power.iter().map(|n| n.value).max();
+    }
+    motor
+        .torque_curve
+        .iter()
+        .map(|point| {
+            let kilowatts = motor.torque_scale * (PI * point.rpm * point.torque) / 30.0;
+            MotorValue::new(point.rpm, kilowatts * 1.359_621_6).value
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mod_detail::structs::{ModDetail, ModDetailVehiclePower, MotorUnits};
+    use crate::mod_detail::vehicles::vehicle_parse;
+    use crate::shared::files::{AbstractFileHandle, AbstractNull};
+    use crate::shared::structs::ModDependency;
+    use crate::ModParserOptions;
+
+    fn bound_mod(name: &str, action: &str, category: Option<&str>, input: &str) -> ModRecord {
+        let mut record = ModRecord::new(format!("{name}.zip"), false);
+        record
+            .mod_desc
+            .binds
+            .insert(action.to_owned(), vec![input.to_owned()]);
+        if let Some(category) = category {
+            record
+                .mod_desc
+                .actions
+                .insert(action.to_owned(), category.to_owned());
+        }
+        record
+    }
+
+    fn depend_mod(name: &str, depends: &[&str]) -> ModRecord {
+        let mut record = ModRecord::new(format!("{name}.zip"), false);
+        record.mod_desc.depend = depends.iter().map(|n| ModDependency::new(n)).collect();
+        record
+    }
+
+    #[test]
+    fn dependency_load_order() {
+        let records = vec![
+            depend_mod("FS22_Tractor", &["FS22_Lib"]),
+            depend_mod("FS22_Lib", &[]),
+            depend_mod("FS22_Trailer", &["FS22_Tractor", "FS22_Missing"]),
+        ];
+
+        let report = dependency_order(&records);
+        assert_eq!(
+            report.load_order,
+            vec!["FS22_Lib", "FS22_Tractor", "FS22_Trailer"]
+        );
+        assert!(report.cycles.is_empty());
+        assert!(report.self_dependent.is_empty());
+        assert_eq!(
+            report.missing.get("FS22_Trailer"),
+            Some(&vec![String::from("FS22_Missing")])
+        );
+    }
+
+    #[test]
+    fn dependency_versions() {
+        let mut lib = depend_mod("FS22_Lib", &[]);
+        lib.mod_desc.version = String::from("1.0.0.0");
+        let mut tractor = depend_mod("FS22_Tractor", &[]);
+        tractor.mod_desc.depend = vec![ModDependency {
+            name: String::from("FS22_Lib"),
+            min_version: Some(String::from("1.1.0.0")),
+        }];
+        let mut trailer = depend_mod("FS22_Trailer", &[]);
+        trailer.mod_desc.depend = vec![ModDependency {
+            name: String::from("FS22_Lib"),
+            min_version: Some(String::from("1.0")),
+        }];
+
+        let report = dependency_order(&[lib, tractor, trailer]);
+        assert_eq!(
+            report.load_order,
+            vec!["FS22_Lib", "FS22_Tractor", "FS22_Trailer"]
+        );
+        assert_eq!(report.outdated.len(), 1);
+        assert_eq!(
+            report.outdated.get("FS22_Tractor"),
+            Some(&vec![OutdatedDependency {
+                name: String::from("FS22_Lib"),
+                min_version: String::from("1.1.0.0"),
+                version: String::from("1.0.0.0"),
+            }])
+        );
+    }
+
+    #[test]
+    fn author_groups() {
+        let mut records = vec![
+            ModRecord::new("FS22_Tractor.zip", false),
+            ModRecord::new("FS22_Trailer.zip", false),
+            ModRecord::new("FS22_Lib.zip", false),
+        ];
+        records[0].mod_desc.authors = vec![String::from("Lizard"), String::from("Other")];
+        records[1].mod_desc.authors = vec![String::from("LIZARD")];
+
+        let groups = group_by_author(&records);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups.get("Lizard"),
+            Some(&vec![
+                String::from("FS22_Tractor"),
+                String::from("FS22_Trailer")
+            ])
+        );
+        assert_eq!(
+            groups.get("Other"),
+            Some(&vec![String::from("FS22_Tractor")])
+        );
+    }
+
+    #[test]
+    fn dependency_cycles() {
+        let records = vec![
+            depend_mod("FS22_Add_On", &["FS22_Ping"]),
+            depend_mod("FS22_Ping", &["FS22_Pong"]),
+            depend_mod("FS22_Pong", &["FS22_Ping", "FS22_Base"]),
+            depend_mod("FS22_Base", &["FS22_Base"]),
+        ];
+
+        let report = dependency_order(&records);
+        assert_eq!(
+            report.load_order,
+            vec!["FS22_Base", "FS22_Ping", "FS22_Pong", "FS22_Add_On"]
+        );
+        assert_eq!(report.cycles, vec![vec!["FS22_Ping", "FS22_Pong"]]);
+        assert_eq!(report.self_dependent, vec!["FS22_Base"]);
+        assert!(report.missing.is_empty());
+    }
+
+    #[test]
+    fn conflicting_binds() {
+        let records = vec![
+            bound_mod(
+                "FS22_First",
+                "FIRST_ACTION",
+                Some("VEHICLE"),
+                "KEY_lalt KEY_x",
+            ),
+            bound_mod("FS22_Second", "SECOND_ACTION", None, "KEY_x KEY_lalt"),
+            bound_mod("FS22_Third", "THIRD_ACTION", Some("ONFOOT"), "KEY_y"),
+        ];
+
+        let conflicts = key_conflicts(&records);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].input, "KEY_lalt KEY_x");
+        assert_eq!(conflicts[0].bindings.len(), 2);
+        assert_eq!(conflicts[0].bindings[0].short_name, "FS22_First");
+        assert_eq!(conflicts[0].bindings[1].category, "ALL");
+    }
+
+    #[test]
+    fn separate_categories_do_not_conflict() {
+        let records = vec![
+            bound_mod("FS22_First", "FIRST_ACTION", Some("VEHICLE"), "KEY_z"),
+            bound_mod("FS22_Second", "SECOND_ACTION", Some("ONFOOT"), "KEY_z"),
+            bound_mod("FS22_Second_Again", "THIRD_ACTION", Some("ONFOOT"), "KEY_q"),
+        ];
+
+        assert!(key_conflicts(&records).is_empty());
+    }
+
+    fn detail_mod(name: &str, vehicles: Vec<(&str, ModDetailVehicle)>) -> ModRecord {
+        let mut record = ModRecord::new(format!("{name}.zip"), false);
+        let mut detail = ModDetail::default();
+        for (file, vehicle) in vehicles {
+            detail.vehicles.insert(file.to_owned(), vehicle);
+        }
+        record.include_detail = Some(detail);
+        record
+    }
+
+    fn tractor(joints: &[&str], power: Option<u32>, motor_hp: f32) -> ModDetailVehicle {
+        let mut vehicle = ModDetailVehicle::new();
+        vehicle.flags.motorized = VehicleCapability::Yes;
+        vehicle.specs.joint_accepts = joints.iter().map(|n| (*n).to_owned()).collect();
+        if let Some(power) = power {
+            vehicle.specs.specs.insert(String::from("power"), power);
+        }
+        let mut motor = MotorEntry::new(String::from("motor"), 40);
+        motor.horse_power.push(MotorValue::new(2000.0, motor_hp));
+        vehicle.motor.motors.push(motor);
+        vehicle
+    }
+
+    fn implement(joints: &[&str], needed_power: Option<u32>) -> ModDetailVehicle {
+        let mut vehicle = ModDetailVehicle::new();
+        vehicle.specs.joint_requires = joints.iter().map(|n| (*n).to_owned()).collect();
+        vehicle.power_requirements = Some(ModDetailVehiclePower {
+            needed_power,
+            ..Default::default()
+        });
+        vehicle
+    }
+
+    #[test]
+    fn vehicle_compatibility() {
+        let records = vec![
+            detail_mod(
+                "FS22_Tractors",
+                vec![
+                    (
+                        "small.xml",
+                        tractor(&["implement", "trailer"], Some(90), 95.0),
+                    ),
+                    ("large.xml", tractor(&["implement", "trailer"], None, 310.0)),
+                    ("truck.xml", tractor(&["semitrailer"], None, 500.0)),
+                ],
+            ),
+            detail_mod(
+                "FS22_Tools",
+                vec![
+                    ("plow.xml", implement(&["implement"], Some(200))),
+                    ("trailer.xml", implement(&["trailer", "trailerLow"], None)),
+                    ("weight.xml", implement(&["frontloader"], Some(10))),
+                ],
+            ),
+            ModRecord::new("FS22_No_Detail.zip", false),
+        ];
+
+        let matrix = compatibility_matrix(&records);
+        let files = |items: &[CompatibilityItem]| -> Vec<String> {
+            items.iter().map(|n| n.file.clone()).collect()
+        };
+        assert_eq!(
+            files(&matrix.vehicles),
+            vec!["large.xml", "small.xml", "truck.xml"]
+        );
+        assert_eq!(
+            files(&matrix.implements),
+            vec!["plow.xml", "trailer.xml", "weight.xml"]
+        );
+        assert_eq!(matrix.vehicles[0].power, Some(310));
+        assert_eq!(matrix.vehicles[1].power, Some(90));
+        assert_eq!(matrix.pairs.len(), 4);
+
+        let large_tools = matrix.implements_for(0);
+        assert_eq!(large_tools.len(), 2);
+        assert_eq!(large_tools[0].0.file, "plow.xml");
+        assert_eq!(large_tools[0].1, CompatibilityPower::Enough);
+        assert_eq!(large_tools[1].1, CompatibilityPower::Unknown);
+
+        let plow_tractors = matrix.vehicles_for(0);
+        assert_eq!(plow_tractors.len(), 2);
+        assert_eq!(plow_tractors[1].0.file, "small.xml");
+        assert_eq!(plow_tractors[1].1, CompatibilityPower::TooLow);
+
+        assert!(matrix.implements_for(2).is_empty());
+        assert!(matrix.vehicles_for(2).is_empty());
+        assert_eq!(
+            serde_json::to_value(&matrix.pairs[0]).unwrap()["power"],
+            "ENOUGH"
+        );
+    }
+
+    #[test]
+    fn metric_motor_power() {
+        let minimum_xml = r#"<vehicle><motorized /><motorConfigurations>
+            <motorConfiguration name="Pickup 2017" hp="300" price="0">
+                <motor torqueScale="0.6" minRpm="1000" maxRpm="6000" maxForwardSpeed="120">
+                    <torque rpm="1000" torque="0.9"/>
+                    <torque rpm="2400" torque="1"/>
+                    <torque rpm="3480" torque="1"/>
+                    <torque rpm="6000" torque="0.2"/>
+                </motor>
+            </motorConfiguration>
+        </motorConfigurations></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(minimum_xml).unwrap();
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+        let mut power = |motor_units| {
+            let options = ModParserOptions {
+                motor_units,
+                ..Default::default()
+            };
+            let vehicle = vehicle_parse(&minimum_doc, &mut file_handle, &options);
+            (
+                vehicle.motor.motors[0].horse_power.len(),
+                vehicle_power(&vehicle),
+            )
+        };
+
+        assert_eq!(power(MotorUnits::Imperial), (4, Some(297)));
+        assert_eq!(power(MotorUnits::Metric), (0, Some(297)));
+    }
+}
@@ -0,0 +1,288 @@
+//! Collection exporters
+//!
+//! Write a parsed collection of [`ModRecord`]s in flat formats that
+//! spreadsheets and ingest pipelines can consume directly.
+use crate::shared::errors::ModError;
+use crate::shared::structs::ModRecord;
+use std::io::Write;
+
+/// Write a collection as NDJSON, one [`ModRecord`] per line
+///
+/// When `slim` is set, each line is the [`ModRecord::to_summary`] view
+/// instead of the full record.
+///
+/// # Errors
+///
+/// Returns any error raised by the underlying writer
+pub fn write_ndjson<W: Write>(
+    records: &[ModRecord],
+    slim: bool,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    for record in records {
+        let line = if slim {
+            serde_json::to_string(&record.to_summary())?
+        } else {
+            serde_json::to_string(record)?
+        };
+        writer.write_all(line.as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Write a CSV summary of a collection
+///
+/// Columns are `shortName,title,version,author,badges,issues` - badges and
+/// issues are space separated lists, custom badges follow the built in ones.
+///
+/// # Errors
+///
+/// Returns any error raised by the underlying writer
+pub fn write_csv<W: Write>(records: &[ModRecord], writer: &mut W) -> std::io::Result<()> {
+    writer.write_all(b"shortName,title,version,author,badges,issues\n")?;
+
+    for record in records {
+        let mut badges = record.badge_array.names();
+        badges.extend(record.custom_badges.iter().cloned());
+        let row = [
+            csv_escape(&record.file_detail.short_name),
+            csv_escape(record.title()),
+            csv_escape(&record.mod_desc.version),
+            csv_escape(&record.mod_desc.author),
+            csv_escape(&badges.join(" ")),
+            csv_escape(&issue_codes(record).join(" ")),
+        ];
+        writer.write_all(row.join(",").as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// A single store item, for a price list
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceListRow {
+    /// short name of the mod holding the item
+    pub mod_name: String,
+    /// store item file in the mod
+    pub item_file: String,
+    /// item kind - `vehicle`, `placeable` or `other`
+    pub item_type: &'static str,
+    /// item name (may be an l10n key)
+    pub name: String,
+    /// brand KEY (vehicles only)
+    pub brand: String,
+    /// store category
+    pub category: String,
+    /// store price
+    pub price: u32,
+    /// store functions text (may be l10n keys)
+    pub functions: Vec<String>,
+}
+
+/// Flatten the store items of a collection into price list rows
+///
+/// Only records parsed with [`crate::ModParserOptions::include_mod_detail`]
+/// have store items. Rows are sorted by mod, then item file.
+#[must_use]
+pub fn price_list(records: &[ModRecord]) -> Vec<PriceListRow> {
+    let mut rows: Vec<PriceListRow> = vec![];
+
+    for record in records {
+        let Some(detail) = &record.include_detail else {
+            continue;
+        };
+        let mod_name = &record.file_detail.short_name;
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+
+        rows.extend(detail.vehicles.iter().map(|(file, item)| PriceListRow {
+            mod_name: mod_name.clone(),
+            item_file: file.clone(),
+            item_type: "vehicle",
+            name: item.specs.name.clone(),
+            brand: text(&item.sorting.brand),
+            category: text(&item.sorting.category),
+            price: item.specs.price,
+            functions: item.specs.functions.clone(),
+        }));
+        rows.extend(detail.placeables.iter().map(|(file, item)| PriceListRow {
+            mod_name: mod_name.clone(),
+            item_file: file.clone(),
+            item_type: "placeable",
+            name: text(&item.sorting.name),
+            brand: String::new(),
+            category: text(&item.sorting.category),
+            price: item.sorting.price,
+            functions: item.sorting.functions.clone(),
+        }));
+        rows.extend(detail.others.iter().map(|(file, item)| PriceListRow {
+            mod_name: mod_name.clone(),
+            item_file: file.clone(),
+            item_type: "other",
+            name: text(&item.name),
+            brand: String::new(),
+            category: text(&item.category),
+            price: item.price,
+            functions: vec![],
+        }));
+    }
+
+    rows.sort_by(|a, b| (&a.mod_name, &a.item_file).cmp(&(&b.mod_name, &b.item_file)));
+    rows
+}
+
+/// Write the [`price_list`] of a collection as CSV
+///
+/// Columns are `mod,file,type,name,brand,category,price,functions` -
+/// functions are joined with ` / `.
+///
+/// # Errors
+///
+/// Returns any error raised by the underlying writer
+pub fn write_price_list<W: Write>(records: &[ModRecord], writer: &mut W) -> std::io::Result<()> {
+    writer.write_all(b"mod,file,type,name,brand,category,price,functions\n")?;
+
+    for row in price_list(records) {
+        let fields = [
+            csv_escape(&row.mod_name),
+            csv_escape(&row.item_file),
+            row.item_type.to_owned(),
+            csv_escape(&row.name),
+            csv_escape(&row.brand),
+            csv_escape(&row.category),
+            row.price.to_string(),
+            csv_escape(&row.functions.join(" / ")),
+        ];
+        writer.write_all(fields.join(",").as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Get the sorted serialized issue codes of a mod
+fn issue_codes(record: &ModRecord) -> Vec<String> {
+    let mut codes: Vec<String> = record.issues.iter().filter_map(issue_code).collect();
+    codes.sort();
+    codes
+}
+
+/// Get the serialized code for a single issue
+fn issue_code(issue: &ModError) -> Option<String> {
+    serde_json::to_value(issue)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_owned))
+}
+
+/// Quote a CSV field if needed
+fn csv_escape(field: &str) -> String {
+    if field.contains(['"', ',', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn csv_field_quoting() {
+        assert_eq!(csv_escape("simple"), "simple");
+        assert_eq!(csv_escape("one, two"), "\"one, two\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn csv_summary_rows() {
+        let mut record = ModRecord::new("FS22_Example.zip", false);
+        record.add_issue(ModError::ModDescNoModIcon);
+        record.mod_desc.author = String::from("Some, Author");
+        record.update_badges();
+
+        let mut output: Vec<u8> = vec![];
+        write_csv(&[record], &mut output).unwrap();
+
+        let text = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            "FS22_Example,--,--,\"Some, Author\",noMP problem,MOD_ERROR_NO_MOD_ICON"
+        );
+    }
+
+    #[test]
+    fn price_list_rows() {
+        use crate::mod_detail::structs::{
+            ModDetail, ModDetailOther, ModDetailPlace, ModDetailVehicle,
+        };
+
+        let mut vehicle = ModDetailVehicle::new();
+        vehicle.specs.name = String::from("Tractor, Large");
+        vehicle.specs.price = 150_000;
+        vehicle.specs.functions = vec![String::from("Pulls"), String::from("Lifts")];
+        vehicle.sorting.brand = Some(String::from("LIZARD"));
+        vehicle.sorting.category = Some(String::from("tractorsL"));
+
+        let mut place = ModDetailPlace::new();
+        place.sorting.name = Some(String::from("Shed"));
+        place.sorting.price = 20_000;
+
+        let mut other = ModDetailOther::new("other");
+        other.name = Some(String::from("Bucket"));
+        other.price = 50;
+
+        let mut detail = ModDetail::new();
+        detail
+            .vehicles
+            .insert(String::from("b_tractor.xml"), vehicle);
+        detail.placeables.insert(String::from("c_shed.xml"), place);
+        detail.others.insert(String::from("a_bucket.xml"), other);
+
+        let mut record = ModRecord::new("FS22_Example.zip", false);
+        record.include_detail = Some(detail);
+        let records = vec![record, ModRecord::new("FS22_Empty.zip", false)];
+
+        let rows = price_list(&records);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].item_type, "other");
+        assert_eq!(rows[1].brand, "LIZARD");
+        assert_eq!(rows[2].price, 20_000);
+
+        let mut output: Vec<u8> = vec![];
+        write_price_list(&records, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            "FS22_Example,b_tractor.xml,vehicle,\"Tractor, Large\",LIZARD,tractorsL,150000,Pulls / Lifts"
+        );
+    }
+
+    #[test]
+    fn ndjson_lines() {
+        let records = vec![
+            ModRecord::new("FS22_First.zip", false),
+            ModRecord::new("FS22_Second.zip", false),
+        ];
+
+        let mut full_output: Vec<u8> = vec![];
+        write_ndjson(&records, false, &mut full_output).unwrap();
+        let mut slim_output: Vec<u8> = vec![];
+        write_ndjson(&records, true, &mut slim_output).unwrap();
+
+        let full_text = String::from_utf8(full_output).unwrap();
+        let slim_text = String::from_utf8(slim_output).unwrap();
+        assert_eq!(full_text.lines().count(), 2);
+        assert_eq!(slim_text.lines().count(), 2);
+        assert!(slim_text.len() < full_text.len());
+
+        let first_slim: serde_json::Value =
+            serde_json::from_str(slim_text.lines().next().unwrap()).unwrap();
+        assert_eq!(first_slim["shortName"], "FS22_First");
+        assert!(first_slim.get("l10n").is_none());
+    }
+}
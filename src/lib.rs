@@ -11,11 +11,23 @@
 #![warn(clippy::unwrap_in_result)]
 #![warn(clippy::unwrap_used)]
 
+pub mod cache;
+pub mod collection;
+pub mod export;
 pub mod maps;
+pub mod metrics;
 pub mod mod_basic;
 pub mod mod_detail;
+pub mod sandbox;
 pub mod savegame;
+pub mod server;
 pub mod shared;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Default)]
 #[expect(clippy::struct_excessive_bools)]
@@ -29,12 +41,156 @@ pub struct ModParserOptions {
     pub skip_detail_icons: bool,
     /// Skip icon processing for mod
     pub skip_mod_icons: bool,
+    /// Parse disabled mods (see [`mod_basic::DISABLED_SUFFIXES`]) rather than
+    /// rejecting them as garbage files
+    pub include_disabled: bool,
+    /// Language codes to retain for l10n entries (english is always kept)
+    ///
+    /// `None` retains every language
+    pub languages: Option<Vec<String>>,
+    /// Known `ModHub` file hashes (lowercase hex MD5 of the zip file)
+    ///
+    /// When set, the file hash is computed and the mod source is
+    /// decided by this list rather than by heuristics
+    pub modhub_hashes: Option<HashSet<String>>,
+    /// Wall clock budget for a single mod
+    ///
+    /// Once spent, remaining optional stages (icons, content scans, map
+    /// data, detail) are skipped and [`shared::errors::ModError::InfoTimedOut`]
+    /// is recorded. The malware scan of lua files (and the script API
+    /// summary, which shares its reads) always runs.
+    pub time_limit: Option<Duration>,
+    /// modDesc tag names to pass through as raw JSON-converted subtrees
+    ///
+    /// Every matching element is collected in [`shared::structs::ModDesc::raw`],
+    /// for fields the parser does not model yet
+    pub raw_mod_desc_tags: Option<Vec<String>>,
+    /// Caps on file reads, to guard against oversized or malformed entries
+    ///
+    /// Refused reads are recorded as [`shared::errors::ModError::PerformanceReadLimit`]
+    pub read_limits: shared::files::ReadLimits,
+    /// Worker threads for store item detail parsing
+    ///
+    /// 0 or 1 parses serially. Each worker opens its own handle to the
+    /// mod, so the per-mod read limit applies per worker.
+    pub detail_threads: usize,
+    /// Record store item parse times in [`mod_detail::structs::ModDetail::diagnostics`]
+    pub detail_diagnostics: bool,
+    /// Activity counters, updated as each mod finishes parsing
+    pub metrics: Option<Arc<dyn metrics::Metrics>>,
+    /// Handling of out of range periods in map growth files
+    pub growth_periods: maps::structs::GrowthPeriodMode,
+    /// Audit every DDS texture in the mod, see [`shared::structs::TextureReport`]
+    ///
+    /// Reads the header of each DDS file, so slower on large mods
+    pub texture_report: bool,
+    /// Include the full, uncropped map overview in
+    /// [`maps::structs::MapOverview::full_image`]
+    pub full_map_overview: bool,
+    /// Check file references in every XML and I3D file against the case of
+    /// the files in the mod, see [`shared::structs::ReferenceCaseMismatch`]
+    ///
+    /// Reads every XML and I3D file, so slower on large mods
+    pub reference_case: bool,
+    /// Flag file references that point outside of the mod, see
+    /// [`shared::structs::ModFile::external_references`]
+    ///
+    /// Checks modDesc.xml, store items (with detail) and, with
+    /// `reference_case`, every XML and I3D file
+    pub external_references: bool,
+    /// Also check the store item files for official DLCs the mod needs, see
+    /// [`shared::structs::ModRecord::required_dlcs`]
+    ///
+    /// modDesc.xml is always checked
+    pub store_item_dlcs: bool,
+    /// Summarize the game API use of script mods, see
+    /// [`shared::structs::ModRecord::script_api`]
+    ///
+    /// Shares the lua file reads of the malware scan
+    pub script_api: bool,
+    /// Content hash for folder mods, in
+    /// [`shared::structs::ModFile::content_hash`]
+    ///
+    /// Zip files are not hashed, their date and size are reliable
+    pub folder_hash: shared::files::FolderHashMode,
+    /// Include per farm statistics in save games, see
+    /// [`savegame::SaveGameFarm::statistics`]
+    pub save_game_statistics: bool,
+    /// Unit tables built for vehicle motors (hp, kW, kph, mph)
+    pub motor_units: mod_detail::structs::MotorUnits,
+    /// Size of the cropped map image in pixels (the full overview is twice
+    /// this), `None` uses 512
+    ///
+    /// Clamped to [`shared::MAP_IMAGE_SIZES`]
+    pub map_image_size: Option<u32>,
+    /// Draw farmland borders from the map farmland info layer on the map
+    /// images, see [`maps::structs::MapOverview::farmland_borders`]
+    pub map_farmlands: bool,
+    /// Check the density, info layer and weight maps a map references, see
+    /// [`shared::structs::ModDesc::map_density_missing`]
+    ///
+    /// Reads the map i3d, so slower on maps
+    pub map_density_check: bool,
+    /// Collect up to this many screenshot / gallery images in
+    /// [`shared::structs::ModDesc::gallery`] (0 skips the pass)
+    ///
+    /// Images named in modDesc.xml under a gallery tag come first, then DDS
+    /// and PNG files with a name matching [`mod_basic::GALLERY_HINTS`]
+    pub gallery_images: usize,
+    /// Keep terrain weight maps (`_weight.png`) in
+    /// [`shared::structs::ModImages::weight_maps`]
+    ///
+    /// Maps have hundreds of them, so they are left out by default
+    pub weight_images: bool,
+    /// Extra file extensions (`md`) or file names (`LICENSE`) to allow
+    ///
+    /// Matching files are still listed in
+    /// [`shared::structs::ModFile::extra_files`], but not flagged as
+    /// [`shared::errors::ModError::PerformanceQuantityExtra`]. Dangerous and
+    /// piracy checks still apply.
+    pub allowed_extra_files: Vec<String>,
+    /// Leave heavy fields (base64 images, file lists, l10n descriptions,
+    /// detail and save game records) out of the record, see
+    /// [`shared::structs::ModRecord::slim`]
+    ///
+    /// Custom badge rules still see the full record
+    pub slim_output: bool,
+    /// Custom badge rules, checked once the built in badges are set
+    ///
+    /// Active badges are listed in [`shared::structs::ModRecord::custom_badges`]
+    pub badge_rules: Vec<shared::structs::BadgeRule>,
+}
+
+impl ModParserOptions {
+    /// Check if a language code should be retained
+    #[must_use]
+    pub fn wants_language(&self, code: &str) -> bool {
+        match &self.languages {
+            Some(list) => code == "en" || list.iter().any(|n| n == code),
+            None => true,
+        }
+    }
 }
 
 pub use savegame::parser as parse_savegame;
+pub use savegame::parser_with_options as parse_savegame_with_options;
+pub use savegame::try_parser as try_parse_savegame;
+pub use savegame::try_parser_with_options as try_parse_savegame_with_options;
 
 pub use mod_basic::parser as parse_mod;
 pub use mod_basic::parser_with_options as parse_mod_with_options;
+pub use mod_basic::try_parser as try_parse_mod;
+pub use mod_basic::try_parser_with_options as try_parse_mod_with_options;
+
+pub use cache::parse_mod_cached;
+
+pub use server::scan_server_mods;
 
 pub use mod_detail::parser as parse_detail;
 pub use mod_detail::parser_with_options as parse_detail_with_options;
+pub use mod_detail::plan as plan_detail;
+pub use mod_detail::plan_with_options as plan_detail_with_options;
+pub use mod_detail::try_parser as try_parse_detail;
+pub use mod_detail::try_parser_with_options as try_parse_detail_with_options;
+
+pub use shared::errors::FsModParserError;
@@ -0,0 +1,134 @@
+//! Farmland info layer
+//!
+//! Maps store the farmland of each spot of the playable area in a GRLE
+//! info layer (one farmland id per pixel). This reads the layer, so the
+//! farmland borders can be drawn on the map overview.
+
+/// GRLE header size - magic, version, width / 256, height / 256 and
+/// compression details
+const GRLE_HEADER_SIZE: usize = 20;
+
+/// Decoded farmland info layer, covering the playable area of the map
+#[derive(Debug)]
+pub struct FarmlandLayer {
+    /// layer width in pixels
+    pub width: u32,
+    /// layer height in pixels
+    pub height: u32,
+    /// farmland id of each pixel, row by row (0 is no farmland)
+    pub ids: Vec<u8>,
+}
+
+impl FarmlandLayer {
+    /// Decode a GRLE info layer
+    ///
+    /// The pixel data is run length encoded - a value repeated twice is
+    /// followed by the count of further repeats (255 means another count
+    /// byte follows). Returns `None` for anything that is not an 8 bit
+    /// GRLE file, or does not decode to exactly width * height pixels.
+    #[must_use]
+    pub fn from_grle(bin_file: &[u8]) -> Option<Self> {
+        if bin_file.len() < GRLE_HEADER_SIZE || !bin_file.starts_with(b"GRLE") {
+            return None;
+        }
+        let read_size = |offset: usize| {
+            u32::from(u16::from_le_bytes([bin_file[offset], bin_file[offset + 1]])) * 256
+        };
+        let (width, height) = (read_size(6), read_size(8));
+        let pixels = usize::try_from(width * height).ok()?;
+
+        let mut ids: Vec<u8> = Vec::with_capacity(pixels);
+        let mut data = bin_file[GRLE_HEADER_SIZE..].iter().copied();
+        let mut previous: Option<u8> = None;
+
+        while let Some(value) = data.next() {
+            ids.push(value);
+            if previous == Some(value) {
+                let mut repeats = 0_usize;
+                loop {
+                    let count = data.next()?;
+                    repeats += usize::from(count);
+                    if count != 255 {
+                        break;
+                    }
+                }
+                ids.resize(ids.len() + repeats, value);
+                previous = None;
+            } else {
+                previous = Some(value);
+            }
+            if ids.len() > pixels {
+                return None;
+            }
+        }
+
+        (pixels > 0 && ids.len() == pixels).then_some(FarmlandLayer { width, height, ids })
+    }
+
+    /// Farmland id at a pixel of the layer (0 if outside the layer)
+    #[must_use]
+    pub fn id_at(&self, x: u32, y: u32) -> u8 {
+        if x >= self.width || y >= self.height {
+            return 0;
+        }
+        usize::try_from(y * self.width + x)
+            .ok()
+            .and_then(|index| self.ids.get(index))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Is this pixel of a `width` x `height` image of the layer on the edge
+    /// of a farmland
+    ///
+    /// A pixel is on the edge when the farmland to its right or below is a
+    /// different one
+    #[must_use]
+    pub fn is_border(&self, x: u32, y: u32, width: u32, height: u32) -> bool {
+        let id_at = |x: u32, y: u32| {
+            self.id_at(
+                x * self.width / width.max(1),
+                y * self.height / height.max(1),
+            )
+        };
+        let id = id_at(x, y);
+        (x + 1 < width && id_at(x + 1, y) != id) || (y + 1 < height && id_at(x, y + 1) != id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grle(data: &[u8]) -> Vec<u8> {
+        let mut file = b"GRLE".to_vec();
+        file.extend([1, 0, 1, 0, 1, 0]);
+        file.resize(GRLE_HEADER_SIZE, 0);
+        file.extend(data);
+        file
+    }
+
+    #[test]
+    fn decode_grle() {
+        // a row of 128 pixels of farmland 1 and 128 of farmland 2, then
+        // 255 rows of farmland 3
+        let mut data = vec![1, 1, 126, 2, 2, 126, 3, 3];
+        data.extend([255; 255]);
+        data.push(253);
+        let layer = FarmlandLayer::from_grle(&grle(&data)).unwrap();
+
+        assert_eq!((layer.width, layer.height), (256, 256));
+        assert_eq!(layer.id_at(0, 0), 1);
+        assert_eq!(layer.id_at(128, 0), 2);
+        assert_eq!(layer.id_at(0, 1), 3);
+        assert_eq!(layer.id_at(255, 255), 3);
+        assert_eq!(layer.id_at(256, 0), 0);
+
+        assert!(layer.is_border(127, 0, 256, 256));
+        assert!(!layer.is_border(126, 1, 256, 256));
+        assert!(layer.is_border(63, 0, 128, 128));
+
+        assert!(FarmlandLayer::from_grle(&grle(&[1, 1, 10])).is_none());
+        assert!(FarmlandLayer::from_grle(b"GDM!").is_none());
+    }
+}
@@ -2,16 +2,26 @@
 //!
 //! Reads crop data, weather data, and the map overview image
 use crate::maps::structs::CropList;
-use crate::shared::files::AbstractFileHandle;
+use crate::shared::errors::ModError;
+use crate::shared::files::{resolve_mod_path, AbstractFileHandle, FileDefinition};
 use crate::shared::structs::ModRecord;
-use crate::shared::{convert_map_image, normalize_image_file};
+use crate::shared::{
+    convert_map_overview_with_options, normalize_image_file, MapImageOptions, MAP_IMAGE_SIZES,
+};
+use crate::ModParserOptions;
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 mod data;
+pub mod farmlands;
 pub mod structs;
 
 use data::{BG_CROPS, BG_CROP_TYPES, BG_CROP_WEATHER, SKIP_CROP_TYPES};
-use structs::{CropOutput, CropTypeStateBuilder, CropWeatherType};
+use farmlands::FarmlandLayer;
+use structs::{
+    CropCalendarMerge, CropOutput, CropTypeStateBuilder, CropWeatherType, GrowthPeriodMode,
+    ImageRect, MapOverview,
+};
 
 #[cfg(test)]
 mod tests {
@@ -138,13 +148,221 @@ mod tests {
         assert_eq!(decode_max_range(None), 0_u8);
     }
 
+    #[test]
+    fn test_range_validation() {
+        assert!(is_valid_range(None));
+        assert!(is_valid_range(Some("3")));
+        assert!(is_valid_range(Some("1-4")));
+        assert!(!is_valid_range(Some("13-2")));
+        assert!(!is_valid_range(Some("1-4-8")));
+        assert!(!is_valid_range(Some("-6")));
+        assert_eq!(decode_clamped_range(Some("13-2")), 13_u8);
+        assert_eq!(decode_clamped_range(None), 0_u8);
+    }
+
+    #[test]
+    fn growth_out_of_range_periods() {
+        let minimum_xml = r#"<growth><seasonal>
+            <fruit name="wheat">
+                <period index="14" plantingAllowed="true" />
+                <period index="3">
+                    <update range="13-2" add="1" />
+                </period>
+            </fruit>
+            </seasonal></growth>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let crop_builder = vec![CropTypeStateBuilder {
+            max_harvest: 14,
+            min_harvest: 14,
+            name: String::from("wheat"),
+            states: 14,
+        }];
+
+        let mut warnings: Vec<String> = vec![];
+        let mut crops = crop_growth_from_tree(
+            &minimum_doc,
+            &crop_builder,
+            GrowthPeriodMode::Wrap,
+            &mut warnings,
+        )
+        .unwrap();
+        let wheat = crops.get("wheat").unwrap();
+        assert_eq!(wheat.plant_periods, vec![2]);
+        assert!(wheat.harvest_periods.is_empty());
+        assert_eq!(
+            warnings,
+            vec![
+                "wheat: period index 14 is out of range",
+                "wheat: period 3 has an invalid range \"13-2\""
+            ]
+        );
+
+        let mut warnings: Vec<String> = vec![];
+        let mut crops = crop_growth_from_tree(
+            &minimum_doc,
+            &crop_builder,
+            GrowthPeriodMode::Clamp,
+            &mut warnings,
+        )
+        .unwrap();
+        let wheat = crops.get("wheat").unwrap();
+        assert_eq!(wheat.plant_periods, vec![12]);
+        assert_eq!(wheat.harvest_periods, vec![4]);
+        assert_eq!(warnings.len(), 2);
+
+        let mut warnings: Vec<String> = vec![];
+        let mut crops = crop_growth_from_tree(
+            &minimum_doc,
+            &crop_builder,
+            GrowthPeriodMode::Skip,
+            &mut warnings,
+        )
+        .unwrap();
+        let wheat = crops.get("wheat").unwrap();
+        assert!(wheat.plant_periods.is_empty());
+        assert!(wheat.harvest_periods.is_empty());
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn growth_month_name_periods() {
+        let minimum_xml = r#"<growth><seasonal>
+            <fruit name="wheat">
+                <period index="sep" plantingAllowed="true" />
+                <period month="October" plantingAllowed="true" />
+                <period name="jan" plantingAllowed="true" />
+                <period month="4" plantingAllowed="true" />
+                <period month="13" plantingAllowed="true" />
+                <period index="smarch" plantingAllowed="true" />
+            </fruit>
+            </seasonal></growth>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let crop_builder = vec![CropTypeStateBuilder {
+            max_harvest: 8,
+            min_harvest: 8,
+            name: String::from("wheat"),
+            states: 8,
+        }];
+
+        let mut warnings: Vec<String> = vec![];
+        let mut crops = crop_growth_from_tree(
+            &minimum_doc,
+            &crop_builder,
+            GrowthPeriodMode::Wrap,
+            &mut warnings,
+        )
+        .unwrap();
+        let wheat = crops.get("wheat").unwrap();
+        assert_eq!(wheat.plant_periods, vec![7, 8, 11, 2]);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn growth_fs25_format() {
+        let minimum_xml = r#"<growth>
+            <fruit name="wheat">
+                <periods>
+                    <period index="7" plantingAllowed="true" />
+                    <period index="8">
+                        <update range="1-7" add="1" />
+                    </period>
+                    <period index="9" />
+                </periods>
+            </fruit>
+            </growth>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let crop_builder = vec![CropTypeStateBuilder {
+            max_harvest: 8,
+            min_harvest: 8,
+            name: String::from("wheat"),
+            states: 8,
+        }];
+
+        let mut warnings: Vec<String> = vec![];
+        let mut crops = crop_growth_from_tree(
+            &minimum_doc,
+            &crop_builder,
+            GrowthPeriodMode::Wrap,
+            &mut warnings,
+        )
+        .unwrap();
+        let wheat = crops.get("wheat").unwrap();
+        assert_eq!(wheat.plant_periods, vec![7]);
+        assert_eq!(wheat.harvest_periods, vec![9, 10]);
+
+        let not_growth = roxmltree::Document::parse("<growth><other /></growth>").unwrap();
+        assert!(crop_growth_from_tree(
+            &not_growth,
+            &crop_builder,
+            GrowthPeriodMode::Wrap,
+            &mut warnings
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn density_file_paths() {
+        assert!(is_density_file("maps/data/densityMap_fruits.gdm"));
+        assert!(is_density_file("data/infoLayer_farmlands.GRLE"));
+        assert!(is_density_file("data/asphalt01_weight.png"));
+        assert!(!is_density_file("data/overview.dds"));
+
+        assert_eq!(
+            resolve_mod_path("maps/map", "./../terrain/grass_weight.gdm"),
+            Some(String::from("maps/terrain/grass_weight.gdm"))
+        );
+        assert_eq!(
+            resolve_mod_path("", "./maps/data/soilMap.grle"),
+            Some(String::from("maps/data/soilMap.grle"))
+        );
+        assert_eq!(resolve_mod_path("", "../outside.grle"), None);
+        assert_eq!(resolve_mod_path("", "$data/maps/mapUS/soil.grle"), None);
+    }
+
+    #[test]
+    fn missing_density_files() {
+        let map_xml = r#"<map>
+            <filename>maps/map.i3d</filename>
+            <soilMap filename="maps/data/soilMap.grle"/>
+            <fieldGround filename="maps/fieldGround.xml"/>
+            <weed filename="$data/maps/mapUS/weed.grle"/>
+            <farmlands densityMapFilename="maps/data/farmlands.grle"/>
+        </map>"#;
+        let map_doc = roxmltree::Document::parse(map_xml).unwrap();
+        let file_list = vec![
+            FileDefinition {
+                extension: String::from("grle"),
+                is_folder: false,
+                name: String::from("maps/data/SoilMap.grle"),
+                size: 1024,
+            },
+            FileDefinition {
+                extension: String::from("grle"),
+                is_folder: false,
+                name: String::from("maps/data/farmlands.grle"),
+                size: 0,
+            },
+        ];
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+
+        assert_eq!(
+            check_density_files(&map_doc, &mut file_handle, &file_list),
+            vec!["maps/data/farmlands.grle"]
+        );
+    }
+
     #[test]
     fn missing_overview() {
         let minimum_xml = r#"<map></map>"#;
         let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
         let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
-        let result = process_overview(&minimum_doc, &mut file_handle);
-        assert_eq!(result, None);
+        let options = ModParserOptions {
+            full_map_overview: true,
+            map_farmlands: true,
+            ..Default::default()
+        };
+        let result = process_overview(&minimum_doc, &mut file_handle, &options);
+        assert!(result.is_none());
     }
 }
 
@@ -244,11 +462,22 @@ impl MapFiles {
 }
 /// Read basic details about the map
 ///
-/// Includes weather, crops, if it's southern, and the map image
+/// Includes weather, crops (with a 12 month calendar), if it's southern,
+/// and the map image
+///
+/// Problems in an included growth file are listed in
+/// [`crate::shared::structs::ModDesc::map_growth_warnings`] and handled
+/// per [`ModParserOptions::growth_periods`]
+///
+/// Density, info layer and weight maps referenced by the map config or
+/// the map i3d that are missing or empty are listed in
+/// [`crate::shared::structs::ModDesc::map_density_missing`]
 pub fn read_map_basics(
     desc_version: u32,
     mod_record: &mut ModRecord,
     file_handle: &mut Box<dyn AbstractFileHandle>,
+    file_list: &[FileDefinition],
+    options: &ModParserOptions,
 ) {
     if desc_version < 60 {
         return;
@@ -262,12 +491,25 @@ pub fn read_map_basics(
 
     if let Ok(contents) = file_handle.as_text(map_config_file_name) {
         if let Ok(map_config_tree) = roxmltree::Document::parse(&contents) {
-            mod_record.mod_desc.map_image = process_overview(&map_config_tree, file_handle);
+            if let Some((map_image, map_overview)) =
+                process_overview(&map_config_tree, file_handle, options)
+            {
+                mod_record.mod_desc.map_image = Some(map_image);
+                mod_record.mod_desc.map_overview = Some(map_overview);
+            }
 
             map_config.fruits = nullify_base_game_entry(&map_config_tree, "fruitTypes");
             map_config.growth = nullify_base_game_entry(&map_config_tree, "growth");
             map_config.env_in = nullify_base_game_entry(&map_config_tree, "environment");
             map_config.env_base = get_base_game_entry_key(&map_config_tree);
+
+            if options.map_density_check {
+                let missing = check_density_files(&map_config_tree, file_handle, file_list);
+                if !missing.is_empty() {
+                    mod_record.mod_desc.map_density_missing = missing;
+                    mod_record.add_issue(ModError::MapDensityFileMissing);
+                }
+            }
         }
     }
 
@@ -277,20 +519,191 @@ pub fn read_map_basics(
 
     let this_map_environment =
         populate_weather(file_handle, map_config.env_base, map_config.env_in);
-    mod_record.mod_desc.map_is_south = this_map_environment.0;
+    let is_south = this_map_environment.0;
+    mod_record.mod_desc.map_is_south = is_south;
     mod_record.mod_desc.crop_weather = this_map_environment.1;
 
     if map_config.growth.is_none() {
         mod_record.mod_desc.crop_info = crops_from_base_game();
+        mod_record.mod_desc.crop_calendar = mod_record.mod_desc.crop_info.calendar(is_south);
         return;
     }
 
     let crop_builder = populate_crop_builder(file_handle, map_config.fruits);
 
-    match populate_crop_growth(file_handle, map_config.growth, &crop_builder) {
-        Some(value) => mod_record.mod_desc.crop_info = value,
+    let mut warnings: Vec<String> = vec![];
+
+    match populate_crop_growth(
+        file_handle,
+        map_config.growth,
+        &crop_builder,
+        options.growth_periods,
+        &mut warnings,
+    ) {
+        Some(value) => {
+            mod_record.mod_desc.crop_diff = Some(value.diff(&crops_from_base_game()));
+            mod_record.mod_desc.crop_info = value;
+        }
         None => mod_record.mod_desc.crop_info = crops_from_base_game(),
     }
+    mod_record.mod_desc.crop_calendar = mod_record.mod_desc.crop_info.calendar(is_south);
+
+    if !warnings.is_empty() {
+        warnings.dedup();
+        mod_record.mod_desc.map_growth_warnings = warnings;
+        mod_record.add_issue(ModError::MapGrowthOutOfRange);
+    }
+}
+
+/// Read the crops added by a crop addon mod
+///
+/// Crop addons are non-map mods that list their files in modDesc.xml the
+/// way a map config does, with `<fruitTypes filename="..."/>` and
+/// `<growth filename="..."/>`. Crops in the fruitTypes file take
+/// precedence over the base game fruit types. The result is stored in
+/// [`crate::shared::structs::ModDesc::crop_info`], for
+/// [`merge_crop_calendar`]
+pub fn read_crop_addon(
+    mod_record: &mut ModRecord,
+    file_handle: &mut Box<dyn AbstractFileHandle>,
+    mod_desc_doc: &roxmltree::Document,
+    options: &ModParserOptions,
+) {
+    if mod_record.mod_desc.desc_version < 60 || mod_record.mod_desc.map_config_file.is_some() {
+        return;
+    }
+
+    let Some(growth) = nullify_base_game_entry(mod_desc_doc, "growth") else {
+        return;
+    };
+
+    let mut crop_builder = nullify_base_game_entry(mod_desc_doc, "fruitTypes")
+        .and_then(|file_name| fruits_from_file(file_handle, &file_name))
+        .unwrap_or_default();
+    crop_builder.extend(fruits_from_base_game());
+
+    let mut warnings: Vec<String> = vec![];
+    if let Some(value) = populate_crop_growth(
+        file_handle,
+        Some(growth),
+        &crop_builder,
+        options.growth_periods,
+        &mut warnings,
+    ) {
+        mod_record.mod_desc.crop_info = value;
+    }
+
+    if !warnings.is_empty() {
+        warnings.dedup();
+        mod_record.mod_desc.map_growth_warnings = warnings;
+        mod_record.add_issue(ModError::MapGrowthOutOfRange);
+    }
+}
+
+/// Merge the crops of crop addon mods into a map's crop list
+///
+/// Addon crops are applied in order, replacing map crops (or crops from
+/// an earlier addon) of the same name. Mods without crop data are ignored.
+#[must_use]
+pub fn merge_crop_calendar(map: &ModRecord, addons: &[&ModRecord]) -> CropCalendarMerge {
+    let mut merged = CropCalendarMerge {
+        map: map.file_detail.short_name.clone(),
+        is_south: map.mod_desc.map_is_south,
+        ..Default::default()
+    };
+
+    merged.crop_info.merge(&map.mod_desc.crop_info);
+    for name in map.mod_desc.crop_info.names() {
+        merged
+            .sources
+            .insert(name.to_lowercase(), map.file_detail.short_name.clone());
+    }
+
+    for addon in addons {
+        for name in merged.crop_info.merge(&addon.mod_desc.crop_info) {
+            merged.overridden.push(name.to_lowercase());
+        }
+        for name in addon.mod_desc.crop_info.names() {
+            merged
+                .sources
+                .insert(name.to_lowercase(), addon.file_detail.short_name.clone());
+        }
+    }
+
+    merged.overridden.sort();
+    merged.overridden.dedup();
+    merged.crop_calendar = merged.crop_info.calendar(merged.is_south);
+    merged
+}
+
+/// Check if a referenced file is a density, info layer or weight map
+fn is_density_file(file_name: &str) -> bool {
+    let path = Path::new(file_name);
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+    let is_weight = path.file_stem().is_some_and(|stem| {
+        stem.to_string_lossy()
+            .to_ascii_lowercase()
+            .ends_with("_weight")
+    });
+
+    is_weight || matches!(extension.as_deref(), Some("gdm" | "grle"))
+}
+
+/// Find density, info layer and weight maps that are missing or empty
+///
+/// Map config references are relative to the mod root, map i3d
+/// references are relative to the i3d file
+fn check_density_files(
+    map_config_tree: &roxmltree::Document,
+    file_handle: &mut Box<dyn AbstractFileHandle>,
+    file_list: &[FileDefinition],
+) -> Vec<String> {
+    let mut referenced: Vec<String> = map_config_tree
+        .descendants()
+        .flat_map(|n| n.attributes())
+        .filter(|attribute| is_density_file(attribute.value()))
+        .filter_map(|attribute| resolve_mod_path("", attribute.value()))
+        .collect();
+
+    let i3d_file = map_config_tree
+        .root_element()
+        .children()
+        .find(|n| n.has_tag_name("filename"))
+        .and_then(|n| n.text())
+        .and_then(|text| resolve_mod_path("", text.trim()));
+
+    if let Some(i3d_file) = i3d_file {
+        if let Ok(contents) = file_handle.as_text(&i3d_file) {
+            if let Ok(i3d_tree) = roxmltree::Document::parse(&contents) {
+                let i3d_folder = i3d_file.rsplit_once('/').map_or("", |(folder, _)| folder);
+                referenced.extend(
+                    i3d_tree
+                        .descendants()
+                        .filter(|n| n.has_tag_name("File"))
+                        .filter_map(|n| n.attribute("filename"))
+                        .filter(|file_name| is_density_file(file_name))
+                        .filter_map(|file_name| resolve_mod_path(i3d_folder, file_name)),
+                );
+            }
+        }
+    }
+
+    let sizes: HashMap<String, u64> = file_list
+        .iter()
+        .filter(|n| !n.is_folder)
+        .map(|n| (n.name.to_ascii_lowercase(), n.size))
+        .collect();
+
+    referenced.retain(|file_name| {
+        sizes
+            .get(&file_name.to_ascii_lowercase())
+            .is_none_or(|size| *size == 0)
+    });
+    referenced.sort();
+    referenced.dedup();
+    referenced
 }
 
 /// Decode a range argument and get the maximum from it
@@ -307,23 +720,120 @@ fn decode_max_range(range: Option<&str>) -> u8 {
     0
 }
 
+/// Check a range argument, `false` if it is reversed or malformed (e.g. "13-2")
+#[inline]
+fn is_valid_range(range: Option<&str>) -> bool {
+    let Some(value) = range else {
+        return true;
+    };
+    let parts: Vec<Option<u8>> = value
+        .split('-')
+        .map(|part| part.trim().parse::<u8>().ok())
+        .collect();
+
+    match parts.as_slice() {
+        [Some(_)] => true,
+        [Some(low), Some(high)] => low <= high,
+        _ => false,
+    }
+}
+
+/// Decode a range argument and get the largest value anywhere in it
+#[inline]
+fn decode_clamped_range(range: Option<&str>) -> u8 {
+    range
+        .and_then(|value| {
+            value
+                .split('-')
+                .filter_map(|part| part.trim().parse::<u8>().ok())
+                .max()
+        })
+        .unwrap_or(0)
+}
+
+/// Decode the range of a growth update, `None` if the update should be skipped
+#[inline]
+fn decode_update_range(range: Option<&str>, mode: GrowthPeriodMode) -> Option<u8> {
+    if is_valid_range(range) {
+        return Some(decode_max_range(range));
+    }
+    match mode {
+        GrowthPeriodMode::Wrap => Some(decode_max_range(range)),
+        GrowthPeriodMode::Clamp => Some(decode_clamped_range(range)),
+        GrowthPeriodMode::Skip => None,
+    }
+}
+
+/// Map size used when the map config does not set one, in meters
+const DEFAULT_MAP_SIZE: u32 = 2048;
+
 /// Load and convert the overview image
 ///
 /// Automatically crops to the center 1/4 of the image that contains the map
-/// and constrains the size to 512x512px
+/// and constrains the size to 512x512px (or [`ModParserOptions::map_image_size`])
+///
+/// Returns the cropped image, and the layout of the full image so other map
+/// data can be aligned to it
 #[inline]
 fn process_overview(
     xml_tree: &roxmltree::Document,
     file_handle: &mut Box<dyn AbstractFileHandle>,
-) -> Option<String> {
+    options: &ModParserOptions,
+) -> Option<(String, MapOverview)> {
     let image_file = normalize_image_file(xml_tree.root_element().attribute("imageFilename"));
+    let content = file_handle.as_bin(&image_file.local_file?).ok()?;
+    let farmlands = if options.map_farmlands {
+        read_farmlands(xml_tree, file_handle)
+    } else {
+        None
+    };
+    let images = convert_map_overview_with_options(
+        content,
+        &MapImageOptions {
+            include_full: options.full_map_overview,
+            size: options.map_image_size.map_or(0, |size| {
+                size.clamp(*MAP_IMAGE_SIZES.start(), *MAP_IMAGE_SIZES.end())
+            }),
+            farmlands: farmlands.as_ref(),
+        },
+    )?;
+
+    let map_size = |name: &str| {
+        xml_tree
+            .root_element()
+            .attribute(name)
+            .and_then(|value| value.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_MAP_SIZE)
+    };
+    let [x, y, width, height] = images.crop;
+    let mut overview = MapOverview::new(
+        (map_size("width"), map_size("height")),
+        (images.width, images.height),
+        ImageRect {
+            x,
+            y,
+            width,
+            height,
+        },
+    );
+    overview.full_image = images.full;
+    overview.farmland_borders = farmlands.is_some();
 
-    if let Some(filename) = image_file.local_file {
-        if let Ok(content) = file_handle.as_bin(&filename) {
-            return convert_map_image(content);
-        }
-    }
-    None
+    Some((images.cropped, overview))
+}
+
+/// Read the farmland info layer named in the map config
+fn read_farmlands(
+    xml_tree: &roxmltree::Document,
+    file_handle: &mut Box<dyn AbstractFileHandle>,
+) -> Option<FarmlandLayer> {
+    let file_name = xml_tree
+        .descendants()
+        .find(|n| n.has_tag_name("farmlands"))
+        .and_then(|n| n.attribute("densityMapFilename"))
+        .and_then(|file_name| resolve_mod_path("", file_name))?;
+    let content = file_handle.as_bin(&file_name).ok()?;
+    FarmlandLayer::from_grle(&content)
 }
 
 /// Build the crop builder struct from crop constraints
@@ -331,59 +841,54 @@ fn populate_crop_builder(
     file_handle: &mut Box<dyn AbstractFileHandle>,
     fruits: Option<String>,
 ) -> Vec<CropTypeStateBuilder> {
-    if let Some(file_name) = fruits {
-        if let Ok(contents) = file_handle.as_text(&file_name) {
-            if let Ok(tree) = roxmltree::Document::parse(&contents) {
-                let mut new_build: Vec<CropTypeStateBuilder> = vec![];
+    fruits
+        .and_then(|file_name| fruits_from_file(file_handle, &file_name))
+        .unwrap_or_else(fruits_from_base_game)
+}
 
-                for item in tree.descendants().filter(|n| n.has_tag_name("fruitType")) {
-                    let item_name = item
-                        .attribute("name")
-                        .unwrap_or("unknown")
-                        .to_owned()
-                        .to_lowercase();
+/// Read crop constraints from a fruitTypes file
+fn fruits_from_file(
+    file_handle: &mut Box<dyn AbstractFileHandle>,
+    file_name: &str,
+) -> Option<Vec<CropTypeStateBuilder>> {
+    let contents = file_handle.as_text(file_name).ok()?;
+    let tree = roxmltree::Document::parse(&contents).ok()?;
+    let mut new_build: Vec<CropTypeStateBuilder> = vec![];
+
+    for item in tree.descendants().filter(|n| n.has_tag_name("fruitType")) {
+        let item_name = item
+            .attribute("name")
+            .unwrap_or("unknown")
+            .to_owned()
+            .to_lowercase();
 
-                    if SKIP_CROP_TYPES.contains(&item_name.as_str()) {
-                        continue;
-                    }
+        if SKIP_CROP_TYPES.contains(&item_name.as_str()) {
+            continue;
+        }
 
-                    let mut item_struct = CropTypeStateBuilder {
-                        name: item_name,
-                        max_harvest: get_crop_attribute(
-                            &item,
-                            "harvest",
-                            "maxHarvestingGrowthState",
-                            20_u8,
-                        ),
-                        min_harvest: get_crop_attribute(
-                            &item,
-                            "harvest",
-                            "minHarvestingGrowthState",
-                            20_u8,
-                        ),
-                        states: get_crop_attribute(&item, "growth", "numGrowthStates", 20_u8),
-                    };
+        let mut item_struct = CropTypeStateBuilder {
+            name: item_name,
+            max_harvest: get_crop_attribute(&item, "harvest", "maxHarvestingGrowthState", 20_u8),
+            min_harvest: get_crop_attribute(&item, "harvest", "minHarvestingGrowthState", 20_u8),
+            states: get_crop_attribute(&item, "growth", "numGrowthStates", 20_u8),
+        };
 
-                    item_struct.min_harvest = get_crop_attribute(
-                        &item,
-                        "preparing",
-                        "minGrowthState",
-                        item_struct.min_harvest,
-                    );
-                    item_struct.max_harvest = get_crop_attribute(
-                        &item,
-                        "preparing",
-                        "maxGrowthState",
-                        item_struct.max_harvest,
-                    );
+        item_struct.min_harvest = get_crop_attribute(
+            &item,
+            "preparing",
+            "minGrowthState",
+            item_struct.min_harvest,
+        );
+        item_struct.max_harvest = get_crop_attribute(
+            &item,
+            "preparing",
+            "maxGrowthState",
+            item_struct.max_harvest,
+        );
 
-                    new_build.push(item_struct);
-                }
-                return new_build;
-            }
-        }
+        new_build.push(item_struct);
     }
-    fruits_from_base_game()
+    Some(new_build)
 }
 
 #[inline]
@@ -470,7 +975,8 @@ fn populate_weather(
 
 /// Convert the read index into the real harvest index
 ///
-/// This is +1 for all crops except olives (+2)
+/// This is +1 for all crops except olives (+2). Expects a period index
+/// of 1-12, see [`check_period_index`]
 fn get_real_index(index: u8, name: &str) -> u8 {
     let test_index = if name == "olive" {
         index + 2
@@ -480,6 +986,54 @@ fn get_real_index(index: u8, name: &str) -> u8 {
     ((test_index - 1) % 12) + 1
 }
 
+/// Month names, January first
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Read the period of a growth `<period>` entry
+///
+/// Accepts a numeric `index`, or a month (`jan`..`dec`, full names, or
+/// 1-12) in `index`, `month` or `name`. Months are converted to periods
+/// with March as period 1, as in the base game files.
+fn decode_period_index(period: &roxmltree::Node) -> Option<u8> {
+    if let Some(index) = period.attribute("index") {
+        if let Ok(value) = index.trim().parse::<u8>() {
+            return Some(value);
+        }
+    }
+
+    let month_value = ["index", "month", "name"]
+        .iter()
+        .find_map(|attribute| period.attribute(*attribute))?
+        .trim()
+        .to_lowercase();
+
+    let month = match month_value.parse::<u8>() {
+        Ok(value) if (1..=12).contains(&value) => value,
+        Ok(_) => return None,
+        Err(_) => {
+            let short_name = month_value.get(..3)?;
+            let position = MONTH_NAMES.iter().position(|name| *name == short_name)?;
+            u8::try_from(position + 1).ok()?
+        }
+    };
+
+    Some(((month + 9) % 12) + 1)
+}
+
+/// Bring a period index into 1-12, `None` if the period should be skipped
+fn check_period_index(index: u8, mode: GrowthPeriodMode) -> Option<u8> {
+    if index <= 12 {
+        return Some(index);
+    }
+    match mode {
+        GrowthPeriodMode::Wrap => Some(((index - 1) % 12) + 1),
+        GrowthPeriodMode::Clamp => Some(12),
+        GrowthPeriodMode::Skip => None,
+    }
+}
+
 /// Populate crop growth from loaded XML file (FS22)
 ///
 /// This is only used when a map includes a growth file, the base game data is pre-calculated
@@ -487,13 +1041,42 @@ fn populate_crop_growth(
     file_handle: &mut Box<dyn AbstractFileHandle>,
     growth: Option<String>,
     crop_builder: &[CropTypeStateBuilder],
+    mode: GrowthPeriodMode,
+    warnings: &mut Vec<String>,
 ) -> Option<CropList> {
     let file_name = growth?;
     let contents = file_handle.as_text(&file_name).ok()?;
     let full_tree = roxmltree::Document::parse(&contents).ok()?;
-    let seasonal_tree = full_tree
+    crop_growth_from_tree(&full_tree, crop_builder, mode, warnings)
+}
+
+/// Find the node holding the `<fruit>` growth entries
+///
+/// FS25 style files list fruits without a `<seasonal>` wrapper
+fn find_seasonal_tree<'a, 'input>(
+    full_tree: &'a roxmltree::Document<'input>,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    full_tree
         .descendants()
-        .find(|n| n.has_tag_name("seasonal"))?;
+        .find(|n| n.has_tag_name("seasonal"))
+        .or_else(|| {
+            let root = full_tree.root_element();
+            root.children()
+                .any(|n| n.has_tag_name("fruit"))
+                .then_some(root)
+        })
+}
+
+/// Calculate crop growth from a parsed growth file
+///
+/// Out of range periods and reversed ranges are added to `warnings`
+fn crop_growth_from_tree(
+    full_tree: &roxmltree::Document,
+    crop_builder: &[CropTypeStateBuilder],
+    mode: GrowthPeriodMode,
+    warnings: &mut Vec<String>,
+) -> Option<CropList> {
+    let seasonal_tree = find_seasonal_tree(full_tree)?;
 
     let mut crop_list = CropList::new();
     for fruit in seasonal_tree
@@ -520,21 +1103,25 @@ fn populate_crop_growth(
 
         let mut possible_states: HashSet<u8> = HashSet::new();
 
-        for period in fruit
-            .children()
-            .filter(|n| n.has_tag_name("period") && n.has_attribute("index"))
-        {
+        for period in fruit.descendants().filter(|n| n.has_tag_name("period")) {
             let mut die_back_happened = false;
-            let current_period_index = period
-                .attribute("index")
-                .unwrap_or("0")
-                .parse::<u8>()
-                .unwrap_or(0_u8);
+            let Some(current_period_index) = decode_period_index(&period) else {
+                continue;
+            };
 
             if current_period_index == 0_u8 {
                 continue;
             }
 
+            if current_period_index > 12 {
+                warnings.push(format!(
+                    "{fruit_name}: period index {current_period_index} is out of range"
+                ));
+            }
+            let Some(current_period_index) = check_period_index(current_period_index, mode) else {
+                continue;
+            };
+
             if let Some(value) = period.attribute("plantingAllowed") {
                 if value == "true" {
                     crop_def.plant_periods.push(current_period_index);
@@ -560,10 +1147,20 @@ fn populate_crop_growth(
 
                 possible_states.clear();
                 for update in updates {
+                    let range_attribute = update.attribute("range");
+                    if !is_valid_range(range_attribute) {
+                        warnings.push(format!(
+                            "{fruit_name}: period {current_period_index} has an invalid range \"{}\"",
+                            range_attribute.unwrap_or_default()
+                        ));
+                    }
+                    let Some(range) = decode_update_range(range_attribute, mode) else {
+                        continue;
+                    };
+
                     if update.attribute("set").is_some() {
                         // if set range > growth_time, it's a regrow.
                         // if set range <= growth_time, it's die back
-                        let range = decode_max_range(update.attribute("range"));
                         let new_value = decode_max_range(update.attribute("set"));
                         if range > new_value {
                             possible_states.insert(new_value);
@@ -572,9 +1169,9 @@ fn populate_crop_growth(
                     }
                     if !die_back_happened {
                         if let Some(add_value) = update.attribute("add") {
-                            let mut new_possible_max = decode_max_range(update.attribute("range"));
-                            new_possible_max += add_value.parse::<u8>().unwrap_or(0_u8);
-                            possible_states.insert(new_possible_max);
+                            possible_states.insert(
+                                range.saturating_add(add_value.parse::<u8>().unwrap_or(0_u8)),
+                            );
                         }
                     }
                 }
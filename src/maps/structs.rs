@@ -1,6 +1,7 @@
 //! Map data structures
+use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, SerializeSeq, Serializer};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Shared nested hashmap for map weather
 pub type CropWeatherType = HashMap<String, HashMap<String, i8>>;
@@ -52,8 +53,21 @@ pub struct Crop {
     pub plant_periods: [bool; 12],
 }
 
+/// How out of range periods and reversed ranges in a growth file are handled
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthPeriodMode {
+    /// Wrap periods past 12 back to the start of the year, use the last
+    /// value of a reversed range (as the game would read it)
+    #[default]
+    Wrap,
+    /// Clamp periods past 12 to 12, use the largest value of a reversed range
+    Clamp,
+    /// Ignore out of range periods and updates with a reversed range
+    Skip,
+}
+
 /// Dynamic crop definition
-#[derive(serde::Serialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "camelCase")]
 pub struct CropOutput {
     /// Periods for full growth
@@ -76,8 +90,82 @@ impl CropOutput {
     }
 }
 
+/// Single month of a crop calendar
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CropCalendarMonth {
+    /// crop can be planted this month
+    pub plant: bool,
+    /// crop can be harvested this month
+    pub harvest: bool,
+    /// crop planted in an earlier month may still be growing
+    pub growing: bool,
+}
+
+/// 12 month calendar for a crop, January first
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CropCalendar {
+    /// Name of crop
+    pub name: String,
+    /// Calendar months, January to December
+    pub months: [CropCalendarMonth; 12],
+}
+
+/// Differences between a crop list and the base game crop list
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CropListDiff {
+    /// crops not in the base game
+    pub added: Vec<String>,
+    /// base game crops that are missing
+    pub removed: Vec<String>,
+    /// crops with a different growth time or plant / harvest window
+    pub changed: Vec<CropChange>,
+}
+
+impl CropListDiff {
+    /// No differences found
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Changes to a single crop, periods are growth periods (1-12)
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CropChange {
+    /// Name of crop
+    pub name: String,
+    /// Base game periods for full growth
+    pub base_growth_time: u8,
+    /// Periods for full growth
+    pub growth_time: u8,
+    /// Harvest periods not in the base game
+    pub harvest_added: Vec<u8>,
+    /// Base game harvest periods that are missing
+    pub harvest_removed: Vec<u8>,
+    /// Planting periods not in the base game
+    pub plant_added: Vec<u8>,
+    /// Base game planting periods that are missing
+    pub plant_removed: Vec<u8>,
+}
+
+/// Periods in `left` that are not in `right`, sorted
+fn period_difference(left: &[u8], right: &[u8]) -> Vec<u8> {
+    let mut periods: Vec<u8> = left
+        .iter()
+        .filter(|period| !right.contains(period))
+        .copied()
+        .collect();
+    periods.sort_unstable();
+    periods.dedup();
+    periods
+}
+
 /// Temporary struct for serializing crop data properly
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 struct CropSerializerOutput {
     /// Name of crop
     pub name: String,
@@ -90,6 +178,7 @@ struct CropSerializerOutput {
 }
 
 /// Crop listing
+#[derive(Clone)]
 pub struct CropList {
     /// Internal List
     list: HashMap<String, CropOutput>,
@@ -117,6 +206,106 @@ impl CropList {
     pub fn get(&mut self, key: &str) -> Option<&CropOutput> {
         self.list.get(key)
     }
+    /// Crop names, in list order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.order.iter().map(String::as_str)
+    }
+    /// Copy every crop of `other` into this list
+    ///
+    /// Crops already in the list are replaced in place, new crops are added
+    /// at the end. Returns the names of the replaced crops
+    pub fn merge(&mut self, other: &CropList) -> Vec<String> {
+        let mut replaced: Vec<String> = vec![];
+        for key in &other.order {
+            let item = other.list[key].clone();
+            if self.list.insert(key.clone(), item).is_some() {
+                replaced.push(key.clone());
+            } else {
+                self.order.push(key.clone());
+            }
+        }
+        replaced
+    }
+    /// Build a 12 month calendar for each crop, in list order
+    ///
+    /// Periods are shifted to calendar months - period 1 is March in the
+    /// northern hemisphere, and September in the southern hemisphere
+    #[must_use]
+    pub fn calendar(&self, is_south: bool) -> Vec<CropCalendar> {
+        let offset: usize = if is_south { 8 } else { 2 };
+        let to_month = |period: u8| (usize::from(period) + 11 + offset) % 12;
+
+        self.order
+            .iter()
+            .filter_map(|key| {
+                let item = self.list.get(key)?;
+                let mut months = [CropCalendarMonth::default(); 12];
+
+                for period in &item.plant_periods {
+                    let plant_month = to_month(*period);
+                    months[plant_month].plant = true;
+                    for grow in 1..usize::from(item.growth_time) {
+                        months[(plant_month + grow) % 12].growing = true;
+                    }
+                }
+                for period in &item.harvest_periods {
+                    months[to_month(*period)].harvest = true;
+                }
+
+                Some(CropCalendar {
+                    name: key.to_lowercase(),
+                    months,
+                })
+            })
+            .collect()
+    }
+    /// Compare against a base game crop list
+    ///
+    /// Added and changed crops follow list order, removed crops follow
+    /// the order of `base`
+    #[must_use]
+    pub fn diff(&self, base: &CropList) -> CropListDiff {
+        let mut diff = CropListDiff::default();
+
+        for key in &self.order {
+            let Some(item) = self.list.get(key) else {
+                continue;
+            };
+            let Some(base_item) = base.list.get(key) else {
+                diff.added.push(key.to_lowercase());
+                continue;
+            };
+            let change = CropChange {
+                name: key.to_lowercase(),
+                base_growth_time: base_item.growth_time,
+                growth_time: item.growth_time,
+                harvest_added: period_difference(&item.harvest_periods, &base_item.harvest_periods),
+                harvest_removed: period_difference(
+                    &base_item.harvest_periods,
+                    &item.harvest_periods,
+                ),
+                plant_added: period_difference(&item.plant_periods, &base_item.plant_periods),
+                plant_removed: period_difference(&base_item.plant_periods, &item.plant_periods),
+            };
+            if change.base_growth_time != change.growth_time
+                || !change.harvest_added.is_empty()
+                || !change.harvest_removed.is_empty()
+                || !change.plant_added.is_empty()
+                || !change.plant_removed.is_empty()
+            {
+                diff.changed.push(change);
+            }
+        }
+
+        diff.removed = base
+            .order
+            .iter()
+            .filter(|key| !self.list.contains_key(*key))
+            .map(|key| key.to_lowercase())
+            .collect();
+
+        diff
+    }
     #[must_use]
     /// Create new crop list
     pub fn new() -> Self {
@@ -155,14 +344,301 @@ impl Serialize for CropList {
     }
 }
 
+impl<'de> Deserialize<'de> for CropList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut crop_list = CropList::new();
+        for item in
+            Option::<Vec<CropSerializerOutput>>::deserialize(deserializer)?.unwrap_or_default()
+        {
+            crop_list.insert(
+                item.name,
+                CropOutput {
+                    growth_time: item.growth_time,
+                    harvest_periods: item.harvest_periods,
+                    plant_periods: item.plant_periods,
+                },
+            );
+        }
+        Ok(crop_list)
+    }
+}
+
+/// Effective crops of a map with crop addon mods loaded, see
+/// [`crate::maps::merge_crop_calendar`]
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CropCalendarMerge {
+    /// short name of the map mod
+    pub map: String,
+    /// map is in the southern hemisphere
+    pub is_south: bool,
+    /// effective crop list, map crops first
+    pub crop_info: CropList,
+    /// effective crop calendar by month, aligned to the map hemisphere
+    pub crop_calendar: Vec<CropCalendar>,
+    /// crop name to the short name of the mod that provides it
+    pub sources: BTreeMap<String, String>,
+    /// crops defined by the map (or an earlier addon) and replaced by an addon
+    pub overridden: Vec<String>,
+}
+
+impl CropCalendarMerge {
+    /// Output as pretty-print JSON
+    #[must_use]
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or(String::from("{}"))
+    }
+
+    /// Output as JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for CropCalendarMerge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&serde_json::to_string(&self).unwrap_or(String::from("{}")))
+    }
+}
+
+/// Pixel rectangle in an image
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ImageRect {
+    /// left edge
+    pub x: u32,
+    /// top edge
+    pub y: u32,
+    /// width in pixels
+    pub width: u32,
+    /// height in pixels
+    pub height: u32,
+}
+
+/// World area covered by an image, in meters from the map center
+///
+/// `min` is the top left corner of the image, `max` the bottom right
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldBounds {
+    /// west edge
+    pub min_x: f32,
+    /// north edge
+    pub min_z: f32,
+    /// east edge
+    pub max_x: f32,
+    /// south edge
+    pub max_z: f32,
+}
+
+/// Map overview image layout, for aligning other map data to the images
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MapOverview {
+    /// world area of the cropped image ([`crate::shared::structs::ModDesc::map_image`])
+    pub crop_bounds: WorldBounds,
+    /// crop rectangle, in full image pixels
+    pub crop_rect: ImageRect,
+    /// farmland borders are drawn on the images, see
+    /// [`crate::ModParserOptions::map_farmlands`]
+    pub farmland_borders: bool,
+    /// world area of the full image
+    pub full_bounds: WorldBounds,
+    /// full overview, if requested - base64 webp, see
+    /// [`crate::ModParserOptions::full_map_overview`]
+    pub full_image: Option<String>,
+    /// height of the full image in pixels
+    pub full_height: u32,
+    /// width of the full image in pixels
+    pub full_width: u32,
+    /// map height in meters
+    pub map_height: u32,
+    /// map width in meters
+    pub map_width: u32,
+}
+
+impl MapOverview {
+    /// Work out the world area of the full and cropped images
+    ///
+    /// The crop rectangle holds the playable map, centered on 0,0
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn new(map_size: (u32, u32), full_size: (u32, u32), crop_rect: ImageRect) -> Self {
+        let (map_width, map_height) = map_size;
+        let (full_width, full_height) = full_size;
+        let meters_x = map_width as f32 / crop_rect.width.max(1) as f32;
+        let meters_z = map_height as f32 / crop_rect.height.max(1) as f32;
+        let center_x = crop_rect.x as f32 + crop_rect.width as f32 / 2.0;
+        let center_z = crop_rect.y as f32 + crop_rect.height as f32 / 2.0;
+
+        MapOverview {
+            crop_bounds: WorldBounds {
+                min_x: -(map_width as f32) / 2.0,
+                min_z: -(map_height as f32) / 2.0,
+                max_x: map_width as f32 / 2.0,
+                max_z: map_height as f32 / 2.0,
+            },
+            crop_rect,
+            farmland_borders: false,
+            full_bounds: WorldBounds {
+                min_x: -center_x * meters_x,
+                min_z: -center_z * meters_z,
+                max_x: (full_width as f32 - center_x) * meters_x,
+                max_z: (full_height as f32 - center_z) * meters_z,
+            },
+            full_image: None,
+            full_height,
+            full_width,
+            map_height,
+            map_width,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn map_overview_bounds() {
+        let crop_rect = ImageRect {
+            x: 256,
+            y: 256,
+            width: 512,
+            height: 512,
+        };
+        let overview = MapOverview::new((4096, 2048), (1024, 1024), crop_rect);
+
+        assert_eq!(
+            overview.crop_bounds,
+            WorldBounds {
+                min_x: -2048.0,
+                min_z: -1024.0,
+                max_x: 2048.0,
+                max_z: 1024.0,
+            }
+        );
+        assert_eq!(
+            overview.full_bounds,
+            WorldBounds {
+                min_x: -4096.0,
+                min_z: -2048.0,
+                max_x: 4096.0,
+                max_z: 2048.0,
+            }
+        );
+    }
+
     #[test]
     fn empty_crop_list() {
         let mine = CropList::default();
 
         assert_eq!(String::from("null"), serde_json::to_string(&mine).unwrap())
     }
+
+    #[test]
+    fn crop_calendar_unlisted_crop() {
+        let mine = CropList {
+            list: HashMap::new(),
+            order: vec![String::from("wheat")],
+        };
+
+        assert!(mine.calendar(false).is_empty());
+    }
+
+    #[test]
+    fn crop_calendar_hemispheres() {
+        let mut mine = CropList::default();
+        mine.insert(
+            String::from("wheat"),
+            CropOutput {
+                growth_time: 3,
+                harvest_periods: vec![4],
+                plant_periods: vec![1],
+            },
+        );
+
+        let north = mine.calendar(false);
+        assert_eq!(north.len(), 1);
+        assert_eq!(north[0].name, "wheat");
+        assert!(north[0].months[2].plant);
+        assert!(north[0].months[3].growing);
+        assert!(north[0].months[4].growing);
+        assert!(!north[0].months[5].growing);
+        assert!(north[0].months[5].harvest);
+
+        let south = mine.calendar(true);
+        assert!(south[0].months[8].plant);
+        assert!(south[0].months[11].harvest);
+    }
+
+    #[test]
+    fn crop_list_merge() {
+        let crop = |growth_time: u8| CropOutput::new(growth_time);
+
+        let mut mine = CropList::default();
+        mine.insert(String::from("wheat"), crop(8));
+        mine.insert(String::from("barley"), crop(7));
+
+        let mut addon = CropList::default();
+        addon.insert(String::from("rye"), crop(6));
+        addon.insert(String::from("wheat"), crop(5));
+
+        assert_eq!(mine.merge(&addon), vec!["wheat"]);
+        assert_eq!(mine.len(), 3);
+        assert_eq!(mine.names().collect::<Vec<_>>(), ["wheat", "barley", "rye"]);
+        assert_eq!(mine.get("wheat").map(|n| n.growth_time), Some(5));
+    }
+
+    #[test]
+    fn crop_list_diff_unlisted_crop() {
+        let mine = CropList {
+            list: HashMap::new(),
+            order: vec![String::from("wheat")],
+        };
+
+        let diff = mine.diff(&CropList::default());
+        assert!(diff.added.is_empty());
+        assert!(diff.changed.is_empty());
+    }
+
+    #[test]
+    fn crop_list_diff() {
+        let crop = |growth_time: u8, harvest: Vec<u8>, plant: Vec<u8>| CropOutput {
+            growth_time,
+            harvest_periods: harvest,
+            plant_periods: plant,
+        };
+
+        let mut base = CropList::default();
+        base.insert(String::from("wheat"), crop(8, vec![5, 6], vec![7, 8]));
+        base.insert(String::from("barley"), crop(7, vec![5], vec![7]));
+        base.insert(String::from("oat"), crop(5, vec![6], vec![1]));
+
+        let mut mine = CropList::default();
+        mine.insert(String::from("wheat"), crop(8, vec![6, 7], vec![7, 8]));
+        mine.insert(String::from("barley"), crop(7, vec![5], vec![7]));
+        mine.insert(String::from("rye"), crop(6, vec![4], vec![9]));
+
+        let diff = mine.diff(&base);
+
+        assert_eq!(diff.added, vec!["rye"]);
+        assert_eq!(diff.removed, vec!["oat"]);
+        assert_eq!(
+            diff.changed,
+            vec![CropChange {
+                name: String::from("wheat"),
+                base_growth_time: 8,
+                growth_time: 8,
+                harvest_added: vec![7],
+                harvest_removed: vec![5],
+                plant_added: vec![],
+                plant_removed: vec![],
+            }]
+        );
+        assert!(base.diff(&base).is_empty());
+    }
 }
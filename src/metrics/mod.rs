@@ -0,0 +1,161 @@
+//! Counters for long running services
+//!
+//! Implement [`Metrics`] to bridge parser activity into your own
+//! monitoring (Prometheus, statsd, logs), then pass it in
+//! [`crate::ModParserOptions::metrics`]. [`CounterMetrics`] is a simple
+//! in-memory implementation.
+use crate::shared::errors::ModError;
+use crate::shared::structs::ModRecord;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Parser activity hooks
+///
+/// Every method has an empty default, implement only what you need.
+/// Methods are called from the parsing thread, so they should be cheap.
+pub trait Metrics: Send + Sync {
+    /// A mod was parsed (successfully or not)
+    fn mod_parsed(&self) {}
+    /// A parsed mod was flagged with an issue
+    fn mod_issue(&self, _issue: &ModError) {}
+    /// Size in bytes of a parsed mod file or folder
+    fn bytes_processed(&self, _bytes: u64) {}
+    /// A record was served from a cache instead of being parsed
+    fn cache_hit(&self) {}
+}
+
+/// Report a finished mod record
+pub(crate) fn report_mod(metrics: &dyn Metrics, record: &ModRecord) {
+    metrics.mod_parsed();
+    metrics.bytes_processed(record.file_detail.file_size);
+    for issue in &record.issues {
+        metrics.mod_issue(issue);
+    }
+}
+
+/// In-memory [`Metrics`] using atomic counters
+#[derive(Default, Debug)]
+pub struct CounterMetrics {
+    /// mods parsed
+    mods_parsed: AtomicU64,
+    /// bytes processed
+    bytes_processed: AtomicU64,
+    /// cache hits
+    cache_hits: AtomicU64,
+    /// issue counts by issue code
+    issues: Mutex<BTreeMap<String, u64>>,
+}
+
+/// Point in time copy of [`CounterMetrics`]
+#[derive(serde::Serialize, Default, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterSnapshot {
+    /// mods parsed
+    pub mods_parsed: u64,
+    /// bytes processed
+    pub bytes_processed: u64,
+    /// cache hits
+    pub cache_hits: u64,
+    /// issue counts by issue code (e.g. `PERF_GRLE_TOO_MANY`)
+    pub issues: BTreeMap<String, u64>,
+}
+
+impl CounterMetrics {
+    /// Create empty counters
+    #[must_use]
+    pub fn new() -> Self {
+        CounterMetrics::default()
+    }
+
+    /// Copy the current counter values
+    #[must_use]
+    pub fn snapshot(&self) -> CounterSnapshot {
+        CounterSnapshot {
+            mods_parsed: self.mods_parsed.load(Ordering::Relaxed),
+            bytes_processed: self.bytes_processed.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            issues: self
+                .issues
+                .lock()
+                .map(|issues| issues.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Metrics for CounterMetrics {
+    fn mod_parsed(&self) {
+        self.mods_parsed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn mod_issue(&self, issue: &ModError) {
+        let Some(code) = serde_json::to_value(issue)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_owned))
+        else {
+            return;
+        };
+        if let Ok(mut issues) = self.issues.lock() {
+            *issues.entry(code).or_default() += 1;
+        }
+    }
+
+    fn bytes_processed(&self, bytes: u64) {
+        self.bytes_processed.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl CounterSnapshot {
+    /// Output as pretty-print JSON
+    #[must_use]
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or(String::from("{}"))
+    }
+
+    /// Output as JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for CounterSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&serde_json::to_string(&self).unwrap_or(String::from("{}")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counts_reported_mods() {
+        let metrics = CounterMetrics::new();
+
+        let mut record = ModRecord::new("FS22_Example.zip", false);
+        record.file_detail.file_size = 1024;
+        record.add_issue(ModError::PerformanceQuantityGRLE);
+        report_mod(&metrics, &record);
+
+        let mut record = ModRecord::new("FS22_Other.zip", false);
+        record.file_detail.file_size = 512;
+        record.add_issue(ModError::PerformanceQuantityGRLE);
+        record.add_issue(ModError::ModDescNoModIcon);
+        report_mod(&metrics, &record);
+
+        metrics.cache_hit();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.mods_parsed, 2);
+        assert_eq!(snapshot.bytes_processed, 1536);
+        assert_eq!(snapshot.cache_hits, 1);
+        assert_eq!(snapshot.issues.get("PERF_GRLE_TOO_MANY"), Some(&2));
+        assert_eq!(snapshot.issues.get("MOD_ERROR_NO_MOD_ICON"), Some(&1));
+    }
+}
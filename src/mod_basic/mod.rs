@@ -1,15 +1,40 @@
 //! Parser functions for basic mod reading
-use crate::maps::read_map_basics;
+use crate::maps::{read_crop_addon, read_map_basics};
+use crate::metrics::report_mod;
 use crate::mod_detail::parse_open_file as detail_parse;
-use crate::savegame::parse_open_file as savegame_parse;
-use crate::shared::errors::ModError;
-use crate::shared::files::{AbstractFileHandle, AbstractFolder, AbstractZipFile, FileDefinition};
-use crate::shared::structs::{ModRecord, ZipPackFile};
-use crate::shared::{convert_mod_icon, extract_and_normalize_image, ImageFile};
+use crate::mod_detail::structs::ModDetailError;
+use crate::savegame::parse_open_file_with_options as savegame_parse;
+use crate::shared::errors::{FsModParserError, ModError};
+use crate::shared::files::{
+    external_references, file_md5, folder_hash, nested_folder, AbstractFileHandle, AbstractFolder,
+    AbstractZipFile, FileDefinition,
+};
+use crate::shared::structs::{
+    AudioFile, ExternalReference, ModDependency, ModDescRawNode, ModFramework, ModImages,
+    ModRecord, ModSource, MpRiskKind, ScriptApiSummary, TextureReport, ZipPackFile,
+};
+use crate::shared::xml::tolerant_xml;
+use crate::shared::{
+    analyze_dds, convert_gallery_image, convert_mod_icon, extract_and_normalize_image, ImageFile,
+    DDS_HEADER_SIZE,
+};
 use crate::ModParserOptions;
 
 use chrono::{DateTime, SecondsFormat, Utc};
-use std::{path::Path, time::SystemTime};
+use std::{
+    collections::HashMap,
+    path::Path,
+    time::{Instant, SystemTime},
+};
+
+mod references;
+mod scripts;
+mod validate;
+
+/// modDesc tags holding website, contact, and support links
+pub const LINK_TAGS: [&str; 6] = [
+    "contact", "donation", "homepage", "support", "url", "website",
+];
 
 /// Known false positives for the malware check
 pub const NOT_MALWARE: [&str; 16] = [
@@ -31,6 +56,13 @@ pub const NOT_MALWARE: [&str; 16] = [
     "FS19_GlobalCompany",
 ];
 
+/// File, folder and modDesc tag name parts that mark a DDS or PNG file as
+/// a gallery image
+pub const GALLERY_HINTS: [&str; 4] = ["gallery", "preview", "screenshot", "store_image"];
+
+/// Suffixes mod managers add after `.zip` to disable a mod
+pub const DISABLED_SUFFIXES: [&str; 5] = [".disabled", ".bak", ".backup", ".off", ".old"];
+
 /// one megabyte
 const MB: u64 = 0x0010_0000;
 /// max size allowed for I3D Cache files, 10MB
@@ -43,6 +75,8 @@ const SIZE_GDM: u64 = 18 * MB;
 const SIZE_SHAPES: u64 = 256 * MB;
 /// max size allowed for XML files, 256KB / 0.25MB
 const SIZE_XML: u64 = MB / 4;
+/// max total size allowed for audio (OGG / GLS) files, 64MB
+const SIZE_AUDIO: u64 = 64 * MB;
 
 /// max allowed GRLE files
 const MAX_GRLE: u32 = 10;
@@ -52,6 +86,19 @@ const MAX_PDF: u32 = 1;
 const MAX_PNG: u32 = 128;
 /// max allowed TXT files
 const MAX_TXT: u32 = 2;
+/// expected mod icon width and height
+const ICON_SIZE: u32 = 256;
+/// windows path length limit (`MAX_PATH`), including the terminating null
+const MAX_PATH: usize = 260;
+/// length of a typical mods folder with a 10 character user name -
+/// `C:\Users\<user>\Documents\My Games\FarmingSimulator2022\mods\`
+const MODS_FOLDER_LENGTH: usize = 65;
+/// store item count above which a mod is a multiplayer risk
+const MP_RISK_STORE_ITEMS: usize = 100;
+/// I3D file count above which a mod is a multiplayer risk
+const MP_RISK_I3D_FILES: usize = 150;
+/// lua file name words of client only (GUI) scripts, see [`name_words`]
+const CLIENT_SCRIPT_HINTS: [&str; 5] = ["dialog", "frame", "gui", "hud", "screen"];
 
 /* cSpell: disable */
 /// Test a mod file against known game limitations
@@ -88,6 +135,8 @@ const MAX_TXT: u32 = 2;
 /// const SIZE_SHAPES:u64 = 256 * MB;
 /// /// max size allowed for XML files, 256KB / 0.25MB
 /// const SIZE_XML:u64    = MB / 4;
+/// /// max total size allowed for audio (OGG / GLS) files, 64MB
+/// const SIZE_AUDIO:u64  = 64 * MB;
 /// ```
 ///
 /// # Size Limits (in bytes)
@@ -109,6 +158,7 @@ const MAX_TXT: u32 = 2;
 ///  "badgeArray": [ "problem" ],
 ///  "canNotUse": false,
 ///  "currentCollection": "",
+///  "customBadges": [],
 ///  "fileDetail": {
 ///    "copyName": null,
 ///    "extraFiles": [],
@@ -116,18 +166,16 @@ const MAX_TXT: u32 = 2;
 ///    "fileSize": 461383317,
 ///    "fullPath": "C:\\...\\FS22_Test.zip",
 ///    "i3dFiles": [],
-///    "imageDDS": [
-///      "icon_eldoradoMap.dds",
-///    ],
-///    "imageNonDDS": [
-///      "maps/data/map_dem.png"
-///    ],
+///    "images": {
+///      "icons": [ "icon_eldoradoMap.dds" ],
+///      "pdaMaps": [ "maps/data/pda_map.dds" ],
+///      "storeImages": [],
+///      "textures": [ "maps/data/map_dem.png" ],
+///      "weightMaps": []
+///    },
 ///    "isFolder": false,
 ///    "isSaveGame": false,
 ///    "isModPack": false,
-///    "pngTexture": [
-///      "maps/data/map_dem.png"
-///    ],
 ///    "shortName": "FS22_EldoradoMap",
 ///    "spaceFiles": [],
 ///    "tooBigFiles": []
@@ -148,6 +196,8 @@ const MAX_TXT: u32 = 2;
 ///    "actions": {},
 ///    "binds": {},
 ///    "author": "Case IH Brasil, Connect Modding",
+///    "authors": [ "Case IH Brasil", "Connect Modding" ],
+///    "authorUrls": [],
 ///    "scriptFiles": 0,
 ///    "storeItems": 41,
 ///    "cropInfo": [
@@ -167,15 +217,34 @@ const MAX_TXT: u32 = 2;
 ///    "depend": [
 ///      "FS22_Cerca_BR"
 ///    ],
+///    "dependVersions": {},
 ///    "descVersion": 79,
 ///    "iconFileName": "icon_eldoradoMap.dds",
 ///    "iconImage": "data:image/webp;base64, ...",
+///    "iconInfo": {
+///      "compressed": true,
+///      "format": "DXT1",
+///      "height": 256,
+///      "mipLevels": 1,
+///      "powerOfTwo": true,
+///      "width": 256
+///    },
 ///    "mapConfigFile": "xml/map.xml",
 ///    "mapIsSouth": true,
 ///    "multiPlayer": true,
 ///    "version": "1.0.0.0"
 ///  },
-///  "uuid": "e4d48eaebd40e7f8d160081dad9c8802"
+///  "source": "likelyModHub",
+///  "uuid": "e4d48eaebd40e7f8d160081dad9c8802",
+///  "validation": [
+///    {
+///      "kind": "MODDESC_EMPTY_ELEMENT",
+///      "path": "modDesc/dependencies/dependency",
+///      "line": 14,
+///      "column": 9,
+///      "message": "<dependency> is empty"
+///    }
+///  ]
 ///}
 /// ```
 /* cSpell: enable */
@@ -185,35 +254,105 @@ pub fn parser<P: AsRef<Path>>(full_path: P) -> ModRecord {
 
 /// [`crate::mod_basic::parser`] with options
 pub fn parser_with_options<P: AsRef<Path>>(full_path: P, options: &ModParserOptions) -> ModRecord {
+    let mut mod_record = parse_record(full_path, options);
+
+    if !options.badge_rules.is_empty() {
+        mod_record.apply_badge_rules(&options.badge_rules);
+    }
+    if let Some(metrics) = &options.metrics {
+        report_mod(metrics.as_ref(), &mod_record);
+    }
+    if options.slim_output {
+        mod_record.slim();
+    }
+    mod_record
+}
+
+/// [`crate::mod_basic::parser`], returning an error when the input can't be read
+///
+/// # Errors
+///
+/// Returns a [`FsModParserError`] when the path is missing, can't be read, or
+/// is not a readable zip file or folder
+pub fn try_parser<P: AsRef<Path>>(full_path: P) -> Result<ModRecord, FsModParserError> {
+    try_parser_with_options(full_path, &ModParserOptions::default())
+}
+
+/// [`crate::mod_basic::parser_with_options`], returning an error when the input can't be read
+///
+/// # Errors
+///
+/// Returns a [`FsModParserError`] when the path is missing, can't be read, or
+/// is not a readable zip file or folder
+pub fn try_parser_with_options<P: AsRef<Path>>(
+    full_path: P,
+    options: &ModParserOptions,
+) -> Result<ModRecord, FsModParserError> {
+    let path = full_path.as_ref();
+    FsModParserError::check_path(path)?;
+
+    let mod_record = parser_with_options(path, options);
+    if mod_record
+        .issues
+        .contains(&ModError::FileErrorUnreadableZip)
+    {
+        return Err(FsModParserError::Unreadable(path.to_path_buf()));
+    }
+    Ok(mod_record)
+}
+
+/// Open a mod file or folder
+fn open_mod(full_path: &Path, is_folder: bool) -> Result<Box<dyn AbstractFileHandle>, ModError> {
+    if is_folder {
+        Ok(Box::new(AbstractFolder::new(full_path)?))
+    } else {
+        Ok(Box::new(AbstractZipFile::new(full_path)?))
+    }
+}
+
+/// Parse a mod file or folder into a record
+fn parse_record<P: AsRef<Path>>(full_path: P, options: &ModParserOptions) -> ModRecord {
+    let start_time = Instant::now();
     let is_folder = full_path.as_ref().is_dir();
     let mut mod_record = ModRecord::new(&full_path, is_folder);
 
+    if is_folder {
+        if let Some(list) = check_mod_folder(full_path.as_ref()) {
+            mod_record.file_detail.zip_files = list;
+            mod_record
+                .add_fatal(ModError::FileErrorLikelyModFolder)
+                .update_badges();
+            return mod_record;
+        }
+        mod_record.add_issue(ModError::InfoNoMultiplayerUnzipped);
+    }
+
+    if options.include_disabled && !is_folder {
+        check_disabled_name(&mut mod_record);
+    }
+
     if !check_file_name(&mut mod_record) {
         mod_record.can_not_use = true;
         mod_record.add_issue(ModError::FileErrorNameInvalid);
     }
 
-    let mut abstract_file: Box<dyn AbstractFileHandle> = if is_folder {
-        mod_record.add_issue(ModError::InfoNoMultiplayerUnzipped);
-        match AbstractFolder::new(&full_path) {
-            Ok(archive) => Box::new(archive),
-            Err(e) => {
-                mod_record.add_fatal(e).update_badges();
-                return mod_record;
-            }
-        }
-    } else {
-        match AbstractZipFile::new(&full_path) {
-            Ok(archive) => Box::new(archive),
-            Err(e) => {
-                mod_record.add_fatal(e).update_badges();
-                return mod_record;
-            }
+    let mut abstract_file = match open_mod(full_path.as_ref(), is_folder) {
+        Ok(archive) => archive,
+        Err(e) => {
+            mod_record.add_fatal(e).update_badges();
+            return mod_record;
         }
     };
 
+    abstract_file.set_limits(options.read_limits);
+    abstract_file.set_case_fallback(true);
     let abstract_file_list = abstract_file.list();
 
+    if is_folder {
+        mod_record.file_detail.content_hash =
+            folder_hash(full_path.as_ref(), &abstract_file_list, options.folder_hash);
+    }
+
     if let Ok(meta) = std::fs::metadata(full_path) {
         mod_record.file_detail.file_date = sys_time_to_string(meta.created().ok());
 
@@ -225,13 +364,15 @@ pub fn parser_with_options<P: AsRef<Path>>(full_path: P, options: &ModParserOpti
         }
     }
 
-    if abstract_file.exists("careerSavegame.xml") {
+    if abstract_file.exists("careerSavegame.xml")
+        || nested_folder(abstract_file.as_mut(), "careerSavegame.xml").is_some()
+    {
         mod_record.file_detail.is_save_game = true;
         mod_record
             .add_fatal(ModError::FileErrorLikelySaveGame)
             .update_badges();
         if options.include_save_game {
-            mod_record.include_save_game = Some(savegame_parse(abstract_file));
+            mod_record.include_save_game = Some(savegame_parse(abstract_file, options));
         }
         return mod_record;
     }
@@ -247,10 +388,8 @@ pub fn parser_with_options<P: AsRef<Path>>(full_path: P, options: &ModParserOpti
         }
     }
 
-    let Ok(mod_desc_content) = abstract_file.as_text("modDesc.xml") else {
-        mod_record
-            .add_fatal(ModError::ModDescMissing)
-            .update_badges();
+    let Some(mod_desc_content) = read_mod_desc(&mut mod_record, &mut abstract_file) else {
+        mod_record.update_badges();
         return mod_record;
     };
 
@@ -261,67 +400,510 @@ pub fn parser_with_options<P: AsRef<Path>>(full_path: P, options: &ModParserOpti
         return mod_record;
     };
 
-    do_file_counts(&mut mod_record, &abstract_file_list);
-    mod_desc_basics(&mut mod_record, &mod_desc_doc);
+    do_file_counts(&mut mod_record, &abstract_file_list, options);
+    check_file_paths(&mut mod_record, &abstract_file_list);
+    mod_desc_basics(&mut mod_record, &mod_desc_doc, options);
+    mod_record.validation = validate::validate_mod_desc(&mod_desc_doc);
+    if options.external_references {
+        add_external_references(
+            &mut mod_record,
+            external_references(&mod_desc_doc, "")
+                .into_iter()
+                .map(|reference| ExternalReference {
+                    source: String::from("modDesc.xml"),
+                    reference,
+                })
+                .collect(),
+        );
+    }
 
-    if !options.skip_mod_icons {
-        if let Some(filename) = &mod_record.mod_desc.icon_file_name {
-            if let Ok(binary_file) = abstract_file.as_bin(filename) {
-                mod_record.mod_desc.icon_image = convert_mod_icon(binary_file);
-            } else {
-                mod_record.add_issue(ModError::ModDescNoModIcon);
-            }
+    parse_optional_stages(
+        &mut mod_record,
+        abstract_file,
+        &mod_desc_doc,
+        &abstract_file_list,
+        options,
+        start_time,
+    );
+
+    mod_record
+}
+
+/// Read modDesc.xml, repairing encoding and entity problems
+///
+/// A repaired modDesc is flagged as damaged. Returns `None` (with the
+/// issue added) when there is no modDesc.xml to read
+fn read_mod_desc(
+    mod_record: &mut ModRecord,
+    abstract_file: &mut Box<dyn AbstractFileHandle>,
+) -> Option<String> {
+    let Ok(mod_desc_bytes) = abstract_file.as_bin("modDesc.xml") else {
+        if abstract_file.limit_exceeded() {
+            mod_record.add_issue(ModError::PerformanceReadLimit);
         }
+        mod_record.add_fatal(ModError::ModDescMissing);
+        return None;
+    };
+
+    let (mod_desc_content, repaired) = tolerant_xml(mod_desc_bytes);
+    if repaired {
+        mod_record.add_issue(ModError::ModDescDamaged);
     }
+    Some(mod_desc_content)
+}
 
-    if check_lua(
+/// Icons, content scans, map data and detail
+///
+/// Each stage is skipped once the time budget in [`ModParserOptions`] is spent
+#[expect(clippy::too_many_lines)]
+fn parse_optional_stages(
+    mod_record: &mut ModRecord,
+    mut abstract_file: Box<dyn AbstractFileHandle>,
+    mod_desc_doc: &roxmltree::Document,
+    abstract_file_list: &[FileDefinition],
+    options: &ModParserOptions,
+    start_time: Instant,
+) {
+    // file list only, always run
+    detect_frameworks(mod_record, mod_desc_doc, abstract_file_list);
+    assess_mp_risk(mod_record, abstract_file_list);
+    check_icon_listed(mod_record, abstract_file_list);
+
+    // malware scan, always run (and first, before the read budget is spent)
+    let (malware, script_api) = check_lua(
         &mod_record.file_detail.short_name,
         &mut abstract_file,
-        &abstract_file_list,
-    ) {
+        abstract_file_list,
+        options.script_api,
+    );
+    if malware {
         mod_record.add_issue(ModError::InfoMaliciousCode);
     }
+    mod_record.script_api = script_api;
+
+    let out_of_time = |mod_record: &mut ModRecord| {
+        let timed_out = options
+            .time_limit
+            .is_some_and(|limit| start_time.elapsed() > limit);
+        if timed_out {
+            mod_record.add_issue(ModError::InfoTimedOut);
+        }
+        timed_out
+    };
 
-    // Map Parsing not implemented for <FS22
-    read_map_basics(
-        mod_record.mod_desc.desc_version,
-        &mut mod_record,
-        &mut abstract_file,
-    );
+    if !out_of_time(mod_record) {
+        if let Some(filename) = &mod_record.mod_desc.icon_file_name {
+            // only the DDS header is needed when the icon is not converted
+            let icon_bytes = if options.skip_mod_icons {
+                abstract_file.as_bin_head(filename, DDS_HEADER_SIZE)
+            } else {
+                abstract_file.as_bin(filename)
+            };
+            match icon_bytes {
+                Ok(binary_file) => {
+                    check_mod_icon(mod_record, &binary_file);
+                    if !options.skip_mod_icons {
+                        mod_record.mod_desc.icon_image = convert_mod_icon(binary_file);
+                    }
+                }
+                Err(_) if !options.skip_mod_icons => {
+                    mod_record.add_issue(ModError::ModDescNoModIcon);
+                }
+                Err(_) => {}
+            }
+        }
+    }
 
-    mod_record.update_badges();
+    if !out_of_time(mod_record) {
+        check_store_items(
+            mod_record,
+            &mut abstract_file,
+            mod_desc_doc,
+            abstract_file_list,
+            options,
+        );
+    }
 
-    if options.include_mod_detail {
-        mod_record.detail_icon_loaded = !options.skip_detail_icons;
-        mod_record.include_detail = Some(detail_parse(
-            abstract_file,
-            &mod_desc_doc,
-            &abstract_file_list,
+    if options.gallery_images > 0 && !out_of_time(mod_record) {
+        mod_record.mod_desc.gallery = collect_gallery(
+            &mut abstract_file,
+            abstract_file_list,
+            mod_desc_doc,
+            options.gallery_images,
+        );
+    }
+
+    if options.texture_report && !out_of_time(mod_record) {
+        mod_record.texture_report = Some(check_textures(&mut abstract_file, abstract_file_list));
+    }
+
+    if options.reference_case && !out_of_time(mod_record) {
+        let scan = references::check_references(&mut abstract_file, abstract_file_list);
+        for mismatch in &scan.case_mismatches {
+            mod_record.add_issue_context(ModError::FileErrorReferenceCase, &mismatch.source);
+        }
+        mod_record.reference_case = Some(scan.case_mismatches);
+        if options.external_references {
+            add_external_references(mod_record, scan.external);
+        }
+    }
+
+    if !out_of_time(mod_record) {
+        // Map Parsing not implemented for <FS22
+        read_map_basics(
+            mod_record.mod_desc.desc_version,
+            mod_record,
+            &mut abstract_file,
+            abstract_file_list,
             options,
-        ));
+        );
+        read_crop_addon(mod_record, &mut abstract_file, mod_desc_doc, options);
     }
 
-    mod_record
+    if abstract_file.limit_exceeded() {
+        mod_record.add_issue(ModError::PerformanceReadLimit);
+    }
+    // the detail stage takes the file handle, and returns its own fallbacks
+    let mut case_fallbacks = abstract_file.case_fallbacks();
+
+    if options.include_mod_detail && !out_of_time(mod_record) {
+        mod_record.detail_icon_loaded = !options.skip_detail_icons;
+        let mut mod_detail = detail_parse(abstract_file, mod_desc_doc, abstract_file_list, options);
+        case_fallbacks.append(&mut mod_detail.case_fallbacks);
+
+        if mod_detail.issues.contains(&ModDetailError::FileReadLimit) {
+            mod_record.add_issue(ModError::PerformanceReadLimit);
+        }
+        // moved to the file detail, so the record holds one copy
+        let item_references: Vec<ExternalReference> =
+            std::mem::take(&mut mod_detail.external_references)
+                .into_iter()
+                .flat_map(|(source, references)| {
+                    references
+                        .into_iter()
+                        .map(move |reference| ExternalReference {
+                            source: source.clone(),
+                            reference,
+                        })
+                })
+                .collect();
+        add_external_references(mod_record, item_references);
+        mod_record.include_detail = Some(mod_detail);
+    }
+    for actual in case_fallbacks.into_values() {
+        mod_record.add_issue_context(ModError::FileErrorNameCase, actual);
+    }
+
+    detect_source(mod_record.update_badges(), options);
 }
 
-/// Check LUA files for malware
+/// Flag a mod icon reference that is not in the file list
+///
+/// Runs even when icons are skipped. Case is ignored, as it is when reading
+fn check_icon_listed(mod_record: &mut ModRecord, file_list: &[FileDefinition]) {
+    let Some(icon_file_name) = &mod_record.mod_desc.icon_file_name else {
+        return;
+    };
+    if !file_list
+        .iter()
+        .any(|file| !file.is_folder && file.name.eq_ignore_ascii_case(icon_file_name))
+    {
+        mod_record.add_issue(ModError::ModDescNoModIcon);
+    }
+}
+
+/// Record the icon DDS details, and flag icons the game or `ModHub` won't like
+fn check_mod_icon(mod_record: &mut ModRecord, binary_file: &[u8]) {
+    let Some(icon_info) = analyze_dds(binary_file) else {
+        return;
+    };
+
+    if icon_info.width != ICON_SIZE || icon_info.height != ICON_SIZE {
+        mod_record.add_issue(ModError::ModDescIconWrongSize);
+    }
+    if !icon_info.compressed {
+        mod_record.add_issue(ModError::ModDescIconUncompressed);
+    }
+    mod_record.mod_desc.icon_info = Some(icon_info);
+}
+
+/// Gallery images, in the order they are used
+///
+/// DDS and PNG files named in modDesc.xml under a tag matching
+/// [`GALLERY_HINTS`] (e.g. `<screenshots>`) come first, in modDesc order.
+/// Then DDS and PNG files with a name matching [`GALLERY_HINTS`], sorted.
+fn gallery_candidates<'a>(
+    abstract_file_list: &'a [FileDefinition],
+    mod_desc_doc: &roxmltree::Document,
+) -> Vec<&'a str> {
+    let images: Vec<&FileDefinition> = abstract_file_list
+        .iter()
+        .filter(|n| !n.is_folder && (n.extension == "dds" || n.extension == "png"))
+        .collect();
+    let is_hint = |name: &str| {
+        let name = name.to_lowercase();
+        GALLERY_HINTS.iter().any(|hint| name.contains(hint))
+    };
+
+    let referenced = mod_desc_doc
+        .descendants()
+        .filter(|n| n.is_element() && n.ancestors().any(|tag| is_hint(tag.tag_name().name())))
+        .flat_map(|n| n.attributes().map(|a| a.value()).chain(n.text()))
+        .filter_map(|value| {
+            let value = value.trim().replace('\\', "/");
+            images
+                .iter()
+                .find(|file| file.name.eq_ignore_ascii_case(&value))
+                .map(|file| file.name.as_str())
+        });
+
+    let mut named: Vec<&str> = images
+        .iter()
+        .map(|n| n.name.as_str())
+        .filter(|name| is_hint(name))
+        .collect();
+    named.sort_unstable();
+
+    let mut candidates: Vec<&str> = vec![];
+    for name in referenced.chain(named) {
+        if !candidates.contains(&name) {
+            candidates.push(name);
+        }
+    }
+    candidates
+}
+
+/// Convert up to `limit` gallery images, see [`gallery_candidates`]
+fn collect_gallery(
+    abstract_file: &mut Box<dyn AbstractFileHandle>,
+    abstract_file_list: &[FileDefinition],
+    mod_desc_doc: &roxmltree::Document,
+    limit: usize,
+) -> Vec<String> {
+    gallery_candidates(abstract_file_list, mod_desc_doc)
+        .into_iter()
+        .filter_map(|file_name| abstract_file.as_bin(file_name).ok())
+        .filter_map(convert_gallery_image)
+        .take(limit)
+        .collect()
+}
+
+/// Read the header of every DDS texture in the mod
+fn check_textures(
+    abstract_file: &mut Box<dyn AbstractFileHandle>,
+    abstract_file_list: &[FileDefinition],
+) -> TextureReport {
+    let mut report = TextureReport::default();
+
+    for file in abstract_file_list
+        .iter()
+        .filter(|n| !n.is_folder && n.extension == "dds")
+    {
+        let info = abstract_file
+            .as_bin_head(&file.name, DDS_HEADER_SIZE)
+            .ok()
+            .and_then(|header| analyze_dds(&header));
+        report.add(&file.name, info);
+    }
+
+    report
+}
+
+/// Record references that point outside of the mod
+///
+/// The same reference can be found by more than one stage, it is only
+/// listed once
+fn add_external_references(mod_record: &mut ModRecord, references: Vec<ExternalReference>) {
+    if references.is_empty() {
+        return;
+    }
+    for reference in &references {
+        mod_record.add_issue_context(ModError::FileErrorExternalReference, &reference.source);
+    }
+
+    let list = &mut mod_record.file_detail.external_references;
+    list.extend(references);
+    list.sort();
+    list.dedup();
+}
+
+/// Add required DLCs and missing light files found in the store items
+fn check_store_items(
+    mod_record: &mut ModRecord,
+    abstract_file: &mut Box<dyn AbstractFileHandle>,
+    mod_desc_doc: &roxmltree::Document,
+    abstract_file_list: &[FileDefinition],
+    options: &ModParserOptions,
+) {
+    let scan = references::scan_store_items(
+        abstract_file,
+        mod_desc_doc,
+        abstract_file_list,
+        options.store_item_dlcs,
+    );
+    for missing in &scan.missing_lights {
+        mod_record.add_issue_context(ModError::FileErrorMissingLight, missing);
+    }
+    mod_record.required_dlcs = scan.required_dlcs;
+}
+
+/// Framework a bundled XML file configures, from its name
+///
+/// `AutoDrive` routes are `AutoDrive_*.xml` (or in an `autoDrive` folder),
+/// `Courseplay` courses live in a `courseplay` folder, `GlobalCompany`
+/// files are `GlobalCompany*.xml`. Case is ignored
+fn framework_config(file: &FileDefinition) -> Option<ModFramework> {
+    let lower_name = file.name.to_lowercase();
+    let (folders, base_name) = lower_name.rsplit_once('/').unwrap_or(("", &lower_name));
+    let in_folder = |name: &str| folders.split('/').any(|folder| folder == name);
+
+    if file.extension != "xml" {
+        None
+    } else if base_name.starts_with("autodrive_") || in_folder("autodrive") {
+        Some(ModFramework::AutoDrive)
+    } else if base_name.starts_with("courseplay") || in_folder("courseplay") {
+        Some(ModFramework::Courseplay)
+    } else if base_name.starts_with("globalcompany") || in_folder("globalcompany") {
+        Some(ModFramework::GlobalCompany)
+    } else {
+        None
+    }
+}
+
+/// Find bundled configuration for third party frameworks
+///
+/// A `<globalCompany>` entry in the modDesc also counts as `GlobalCompany`
+/// configuration
+fn detect_frameworks(
+    mod_record: &mut ModRecord,
+    mod_desc_doc: &roxmltree::Document,
+    file_list: &[FileDefinition],
+) {
+    for file in file_list.iter().filter(|n| !n.is_folder) {
+        if let Some(framework) = framework_config(file) {
+            mod_record
+                .framework_configs
+                .entry(framework)
+                .or_default()
+                .push(file.name.clone());
+        }
+    }
+
+    if mod_desc_doc
+        .root_element()
+        .children()
+        .any(|n| n.has_tag_name("globalCompany"))
+    {
+        mod_record
+            .framework_configs
+            .entry(ModFramework::GlobalCompany)
+            .or_default()
+            .insert(0, String::from("modDesc.xml"));
+    }
+}
+
+/// Lowercase words of a file path
+///
+/// Splits on path separators and punctuation, and on case changes inside
+/// a name (`MyModGUIFrame` is `my`, `mod`, `gui`, `frame`), so a hint only
+/// matches a whole word - `frame` does not match `Framework`
+fn name_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = vec![];
+    let mut word = String::new();
+
+    for (index, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+            continue;
+        }
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+        let word_start = c.is_uppercase()
+            && previous.is_some_and(|p| {
+                p.is_lowercase()
+                    || p.is_ascii_digit()
+                    || p.is_uppercase() && next.is_some_and(|n| n.is_lowercase())
+            });
+        if word_start && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.extend(c.to_lowercase());
+    }
+    words.extend((!word.is_empty()).then_some(word));
+    words
+}
+
+/// Look for patterns that can cause multiplayer sync problems
+///
+/// See [`MpRiskKind`] - runs after the file counts and modDesc basics
+fn assess_mp_risk(mod_record: &mut ModRecord, file_list: &[FileDefinition]) {
+    let client_scripts: Vec<&str> = file_list
+        .iter()
+        .filter(|n| !n.is_folder && n.extension == "lua")
+        .map(|n| n.name.as_str())
+        .filter(|name| {
+            name_words(name).iter().any(|word| {
+                CLIENT_SCRIPT_HINTS
+                    .iter()
+                    .any(|hint| word == hint || word.strip_suffix('s') == Some(hint))
+            })
+        })
+        .collect();
+    if !client_scripts.is_empty() {
+        mod_record.add_mp_risk(MpRiskKind::ClientScripts, client_scripts.join(", "));
+    }
+
+    let store_items = mod_record.mod_desc.store_items;
+    if store_items > MP_RISK_STORE_ITEMS {
+        mod_record.add_mp_risk(
+            MpRiskKind::ManyStoreItems,
+            format!("{store_items} store items"),
+        );
+    }
+
+    let i3d_files = mod_record.file_detail.i3d_files.len();
+    if i3d_files > MP_RISK_I3D_FILES {
+        mod_record.add_mp_risk(MpRiskKind::DenseI3d, format!("{i3d_files} i3d files"));
+    }
+
+    let audio_size = mod_record.file_detail.audio_size;
+    if audio_size > SIZE_AUDIO {
+        mod_record.add_mp_risk(MpRiskKind::OversizeAudio, format!("{audio_size} bytes"));
+    }
+}
+
+/// Check LUA files for malware, and summarize their game API use when asked
+///
+/// Both share one read of each lua file
 fn check_lua(
     short_name: &String,
     file_handle: &mut Box<dyn AbstractFileHandle>,
     file_list: &[FileDefinition],
-) -> bool {
-    if NOT_MALWARE.iter().any(|&s| s == short_name) {
-        return false;
-    }
+    script_api: bool,
+) -> (bool, Option<ScriptApiSummary>) {
+    let check_malware = !NOT_MALWARE.iter().any(|&s| s == short_name);
+    let mut malware = false;
+    let mut api_scan = script_api.then(scripts::ScriptApiScan::default);
+    let mut script_files = 0;
 
     for lua_file in file_list.iter().filter(|n| n.extension == "lua") {
+        if (malware || !check_malware) && api_scan.is_none() {
+            break;
+        }
         if let Ok(content) = file_handle.as_text(&lua_file.name) {
-            if content.contains(".deleteFolder") || content.contains(".deleteFile") {
-                return true;
+            malware |= check_malware
+                && (content.contains(".deleteFolder") || content.contains(".deleteFile"));
+            if let Some(api_scan) = api_scan.as_mut() {
+                script_files += 1;
+                api_scan.add_script(&content);
             }
         }
     }
-    false
+    let summary = api_scan
+        .filter(|_| script_files > 0)
+        .map(|api_scan| api_scan.into_summary(script_files));
+    (malware, summary)
 }
 /// Check if mod is actually a mod pack
 fn check_mod_pack(file_list: &Vec<FileDefinition>) -> Option<Vec<ZipPackFile>> {
@@ -354,9 +936,70 @@ fn check_mod_pack(file_list: &Vec<FileDefinition>) -> Option<Vec<ZipPackFile>> {
     Some(zip_list)
 }
 
+/// Check if a folder is a mods folder rather than an unzipped mod
+///
+/// A mods folder has no modDesc.xml and holds two or more zip files.
+/// Only the top level is read, mods folders can be very large
+fn check_mod_folder(full_path: &Path) -> Option<Vec<ZipPackFile>> {
+    if full_path.join("modDesc.xml").exists() {
+        return None;
+    }
+
+    let mut zip_list: Vec<ZipPackFile> = std::fs::read_dir(full_path)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|n| n.eq_ignore_ascii_case("zip"))
+        })
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            meta.is_file().then(|| ZipPackFile {
+                name: entry.file_name().to_string_lossy().to_string(),
+                size: meta.len(),
+            })
+        })
+        .collect();
+
+    if zip_list.len() < 2 {
+        return None;
+    }
+
+    zip_list.sort();
+    Some(zip_list)
+}
+
+/// Recognize a disabled mod (e.g. `FS22_Mod.zip.disabled`)
+///
+/// Sets the disabled flag and fixes the short name
+fn check_disabled_name(mod_record: &mut ModRecord) {
+    let file_name = Path::new(&mod_record.file_detail.full_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let lower_name = file_name.to_ascii_lowercase();
+
+    for suffix in DISABLED_SUFFIXES {
+        let Some(base_name) = lower_name.strip_suffix(suffix) else {
+            continue;
+        };
+        if Path::new(base_name)
+            .extension()
+            .is_some_and(|ext| ext == "zip")
+        {
+            let name_length = base_name.len() - ".zip".len();
+            file_name[..name_length].clone_into(&mut mod_record.file_detail.short_name);
+            mod_record.file_detail.is_disabled = true;
+            return;
+        }
+    }
+}
+
 /// Test a mod file name against known game limitations
 fn check_file_name(mod_record: &mut ModRecord) -> bool {
-    if !mod_record.file_detail.is_folder {
+    if !mod_record.file_detail.is_folder && !mod_record.file_detail.is_disabled {
         let file_path = Path::new(&mod_record.file_detail.full_path);
         let extension = match file_path.extension() {
             Some(ext) => ext.to_str().unwrap_or("").to_owned().to_ascii_lowercase(),
@@ -418,8 +1061,97 @@ fn check_file_name(mod_record: &mut ModRecord) -> bool {
     true
 }
 
+/// Decide the likely origin of a mod
+///
+/// With a list of known `ModHub` hashes the file hash decides, otherwise
+/// a zipped, uncopied, issue-free mod with a DDS icon looks like `ModHub`
+fn detect_source(mod_record: &mut ModRecord, options: &ModParserOptions) {
+    if let Some(known_hashes) = &options.modhub_hashes {
+        if mod_record.file_detail.is_folder {
+            mod_record.source = ModSource::ThirdParty;
+            return;
+        }
+        if let Some(hash) = file_md5(Path::new(&mod_record.file_detail.full_path)) {
+            mod_record.source = if known_hashes.contains(&hash) {
+                ModSource::ModHub
+            } else {
+                ModSource::ThirdParty
+            };
+            mod_record.md5_sum = Some(hash);
+        }
+        return;
+    }
+
+    let short_name = mod_record.file_detail.short_name.as_bytes();
+    let has_game_prefix = short_name.len() > 5
+        && short_name.starts_with(b"FS")
+        && short_name[2].is_ascii_digit()
+        && short_name[3].is_ascii_digit()
+        && short_name[4] == b'_';
+    let has_dds_icon = mod_record
+        .mod_desc
+        .icon_file_name
+        .as_ref()
+        .is_some_and(|n| n.to_ascii_lowercase().ends_with(".dds"));
+
+    mod_record.source = if !mod_record.file_detail.is_folder
+        && has_game_prefix
+        && has_dds_icon
+        && mod_record.file_detail.copy_name.is_none()
+        && !mod_record.issues.contains(&ModError::InfoLikelyPiracy)
+        && !mod_record.badge_array.broken
+        && !mod_record.badge_array.problem
+        && !mod_record.badge_array.malware
+    {
+        ModSource::LikelyModHub
+    } else {
+        ModSource::Unknown
+    };
+}
+
+/// Sort an image into the list for its role, by file name
+///
+/// Weight maps are dropped unless [`ModParserOptions::weight_images`] is set
+fn add_image(images: &mut ModImages, file_name: &str, options: &ModParserOptions) {
+    let lower_name = file_name.to_lowercase();
+    let (folder, stem) = lower_name
+        .rsplit_once('/')
+        .map_or(("", lower_name.as_str()), |(folder, stem)| (folder, stem));
+    let stem = stem.rsplit_once('.').map_or(stem, |(stem, _)| stem);
+
+    let list = if stem.ends_with("_weight") {
+        if !options.weight_images {
+            return;
+        }
+        &mut images.weight_maps
+    } else if stem.starts_with("pda") || stem.contains("pda_map") || stem.contains("overview") {
+        &mut images.pda_maps
+    } else if stem.contains("icon") || folder.split('/').any(|n| n == "icons") {
+        &mut images.icons
+    } else if stem.starts_with("store_") || stem.starts_with("brand") || stem.contains("logo") {
+        &mut images.store_images
+    } else {
+        &mut images.textures
+    };
+    list.push(file_name.to_owned());
+}
+
+/// Check an extra file against [`ModParserOptions::allowed_extra_files`]
+///
+/// Entries match the extension or the file name, ignoring case
+fn is_allowed_extra(file: &FileDefinition, options: &ModParserOptions) -> bool {
+    let base_name = file.name.rsplit('/').next().unwrap_or(&file.name);
+    options.allowed_extra_files.iter().any(|allowed| {
+        allowed.eq_ignore_ascii_case(&file.extension) || allowed.eq_ignore_ascii_case(base_name)
+    })
+}
+
 /// Count contained files in the mod
-fn do_file_counts(mod_record: &mut ModRecord, file_list: &Vec<FileDefinition>) {
+fn do_file_counts(
+    mod_record: &mut ModRecord,
+    file_list: &Vec<FileDefinition>,
+    options: &ModParserOptions,
+) {
     let mut found_grle: u32 = 0;
     let mut found_pdf: u32 = 0;
     let mut found_png: u32 = 0;
@@ -437,79 +1169,111 @@ fn do_file_counts(mod_record: &mut ModRecord, file_list: &Vec<FileDefinition>) {
 
         if known_good.contains(&file.extension.as_str()) {
             if file.name.contains(' ') {
-                mod_record.add_issue(ModError::PerformanceFileSpaces);
+                mod_record.add_issue_context(ModError::PerformanceFileSpaces, &file.name);
                 mod_record.file_detail.space_files.push(file.name.clone());
             }
+            if file.extension == "png" || file.extension == "dds" {
+                add_image(&mut mod_record.file_detail.images, &file.name, options);
+            }
             match file.extension.as_str() {
                 "lua" => mod_record.mod_desc.script_files += 1,
-                "png" => {
-                    if !file.name.ends_with("_weight.png") {
-                        mod_record.file_detail.image_non_dds.push(file.name.clone());
-                        mod_record.file_detail.png_texture.push(file.name.clone());
-                    }
-                    found_png += 1;
-                }
+                "png" => found_png += 1,
                 "pdf" => found_pdf += 1,
                 "grle" => found_grle += 1,
                 "txt" => found_txt += 1,
+                "ogg" | "gls" => {
+                    mod_record.file_detail.audio_size += file.size;
+                    mod_record.file_detail.audio_files.push(AudioFile {
+                        name: file.name.clone(),
+                        size: file.size,
+                    });
+                }
                 "cache" => {
                     if file.size > SIZE_CACHE {
-                        mod_record.add_issue(ModError::PerformanceOversizeI3D);
+                        mod_record.add_issue_context(ModError::PerformanceOversizeI3D, &file.name);
                         mod_record.file_detail.too_big_files.push(file.name.clone());
                     }
                 }
-                "dds" => {
-                    mod_record.file_detail.image_dds.push(file.name.clone());
-                    if file.size > SIZE_DDS {
-                        mod_record.add_issue(ModError::PerformanceOversizeDDS);
-                        mod_record.file_detail.too_big_files.push(file.name.clone());
-                    }
+                "dds" if file.size > SIZE_DDS => {
+                    mod_record.add_issue_context(ModError::PerformanceOversizeDDS, &file.name);
+                    mod_record.file_detail.too_big_files.push(file.name.clone());
                 }
                 "gdm" => {
                     if file.size > SIZE_GDM {
-                        mod_record.add_issue(ModError::PerformanceOversizeGDM);
+                        mod_record.add_issue_context(ModError::PerformanceOversizeGDM, &file.name);
                         mod_record.file_detail.too_big_files.push(file.name.clone());
                     }
                 }
                 "shapes" => {
                     if file.size > SIZE_SHAPES {
-                        mod_record.add_issue(ModError::PerformanceOversizeSHAPES);
+                        mod_record
+                            .add_issue_context(ModError::PerformanceOversizeSHAPES, &file.name);
                         mod_record.file_detail.too_big_files.push(file.name.clone());
                     }
                 }
                 "xml" => {
                     if file.size > SIZE_XML {
-                        mod_record.add_issue(ModError::PerformanceOversizeXML);
+                        mod_record.add_issue_context(ModError::PerformanceOversizeXML, &file.name);
                         mod_record.file_detail.too_big_files.push(file.name.clone());
                     }
                 }
                 _ => {}
             }
-
-            if found_grle > MAX_GRLE {
-                mod_record.add_issue(ModError::PerformanceQuantityGRLE);
-            }
-            if found_pdf > MAX_PDF {
-                mod_record.add_issue(ModError::PerformanceQuantityPDF);
-            }
-            if found_png > MAX_PNG {
-                mod_record.add_issue(ModError::PerformanceQuantityPNG);
-            }
-            if found_txt > MAX_TXT {
-                mod_record.add_issue(ModError::PerformanceQuantityTXT);
-            }
         } else {
             if file.extension == "dat" || file.extension == "l64" {
-                mod_record.add_issue(ModError::InfoLikelyPiracy);
+                mod_record.add_issue_context(ModError::InfoLikelyPiracy, &file.name);
             }
             if file.extension == "exe" || file.extension == "bat" || file.extension == "ps1" {
                 mod_record.can_not_use = true;
-                mod_record.add_issue(ModError::InfoDangerousFile);
+                mod_record.add_issue_context(ModError::InfoDangerousFile, &file.name);
+            }
+            if !is_allowed_extra(file, options) {
+                mod_record.add_issue_context(ModError::PerformanceQuantityExtra, &file.name);
             }
-            mod_record.add_issue(ModError::PerformanceQuantityExtra);
             mod_record.file_detail.extra_files.push(file.name.clone());
         }
     }
+
+    for (issue, found, limit) in [
+        (ModError::PerformanceQuantityGRLE, found_grle, MAX_GRLE),
+        (ModError::PerformanceQuantityPDF, found_pdf, MAX_PDF),
+        (ModError::PerformanceQuantityPNG, found_png, MAX_PNG),
+        (ModError::PerformanceQuantityTXT, found_txt, MAX_TXT),
+    ] {
+        if found > limit {
+            mod_record.add_issue_context(issue, format!("{found} files"));
+        }
+    }
+
+    if mod_record.file_detail.audio_size > SIZE_AUDIO {
+        let audio_size = mod_record.file_detail.audio_size;
+        mod_record.add_issue_context(
+            ModError::PerformanceOversizeAudio,
+            format!("{audio_size} bytes"),
+        );
+    }
+}
+
+/// Check internal file paths for names that break on some systems
+///
+/// Paths are flagged when they are longer than [`MAX_PATH`] once placed in a
+/// typical mods folder, or when they contain non-ASCII characters. Lengths
+/// are counted in UTF-16 units, as windows does
+fn check_file_paths(mod_record: &mut ModRecord, file_list: &[FileDefinition]) {
+    let base_length =
+        MODS_FOLDER_LENGTH + mod_record.file_detail.short_name.encode_utf16().count() + 1;
+
+    for file in file_list {
+        if file.is_folder {
+            continue;
+        }
+        if base_length + file.name.encode_utf16().count() >= MAX_PATH {
+            mod_record.add_issue_context(ModError::FileErrorLongPath, &file.name);
+        }
+        if !file.name.is_ascii() {
+            mod_record.add_issue_context(ModError::FileErrorNonAsciiName, &file.name);
+        }
+    }
 }
 
 /// Convert a system time to a ISO JSON string
@@ -524,7 +1288,11 @@ fn sys_time_to_string(now: Option<SystemTime>) -> String {
 }
 
 /// Load basic details from the modDesc.xml file
-fn mod_desc_basics(mod_record: &mut ModRecord, mod_desc: &roxmltree::Document) {
+fn mod_desc_basics(
+    mod_record: &mut ModRecord,
+    mod_desc: &roxmltree::Document,
+    options: &ModParserOptions,
+) {
     match mod_desc.root_element().attribute("descVersion") {
         Some(val) => mod_record.mod_desc.desc_version = val.parse().unwrap_or(0_u32),
         None => {
@@ -546,6 +1314,8 @@ fn mod_desc_basics(mod_record: &mut ModRecord, mod_desc: &roxmltree::Document) {
         node.text()
             .unwrap_or("--")
             .clone_into(&mut mod_record.mod_desc.author);
+        (mod_record.mod_desc.authors, mod_record.mod_desc.author_urls) =
+            split_authors(&mod_record.mod_desc.author);
     }
 
     if let Some(node) = mod_desc
@@ -568,15 +1338,8 @@ fn mod_desc_basics(mod_record: &mut ModRecord, mod_desc: &roxmltree::Document) {
         }
     }
 
-    for depend in mod_desc
-        .descendants()
-        .filter(|n| n.has_tag_name("dependency"))
-    {
-        mod_record
-            .mod_desc
-            .depend
-            .push(depend.text().unwrap_or("--").to_owned());
-    }
+    mod_desc_depends(mod_record, mod_desc);
+    mod_desc_links(mod_record, mod_desc);
 
     if mod_desc.descendants().any(|n| n.has_tag_name("productId")) {
         mod_record.add_issue(ModError::InfoLikelyPiracy);
@@ -596,6 +1359,133 @@ fn mod_desc_basics(mod_record: &mut ModRecord, mod_desc: &roxmltree::Document) {
 
     mod_desc_actions(mod_record, mod_desc);
     mod_desc_l10n(mod_record, mod_desc);
+    mod_record
+        .l10n
+        .retain_languages(|code| options.wants_language(code));
+
+    if let Some(tags) = &options.raw_mod_desc_tags {
+        mod_desc_raw(mod_record, mod_desc, tags);
+    }
+}
+
+/// Load website and support links
+///
+/// Only direct children of `<modDesc>` are read. The link is the element
+/// text, or an `href` / `url` attribute. The first of each tag is kept.
+fn mod_desc_links(mod_record: &mut ModRecord, mod_desc: &roxmltree::Document) {
+    for node in mod_desc.root_element().children().filter(|n| {
+        n.is_element() && LINK_TAGS.contains(&n.tag_name().name().to_ascii_lowercase().as_str())
+    }) {
+        let link = node
+            .text()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .or_else(|| node.attribute("href"))
+            .or_else(|| node.attribute("url"))
+            .map(str::trim);
+
+        if let Some(link) = link.filter(|link| !link.is_empty()) {
+            mod_record
+                .mod_desc
+                .links
+                .entry(node.tag_name().name().to_ascii_lowercase())
+                .or_insert_with(|| link.to_owned());
+        }
+    }
+}
+
+/// Separators between names in a free-form author string
+const AUTHOR_SEPARATORS: [char; 6] = [',', '/', '&', ';', '|', '+'];
+
+/// Split a free-form author string into names and urls
+///
+/// Urls (`http://`, `https://`, `www.`) are pulled out first, then the
+/// rest is split on [`AUTHOR_SEPARATORS`] and the word "and". Names are
+/// trimmed and deduplicated (ignoring case), keeping the first spelling.
+fn split_authors(author: &str) -> (Vec<String>, Vec<String>) {
+    let is_url = |word: &str| {
+        let word = word.to_lowercase();
+        word.starts_with("http://") || word.starts_with("https://") || word.starts_with("www.")
+    };
+    let trim_name = |name: &str| {
+        name.trim_matches(|c: char| c.is_whitespace() || "-()[]\"'".contains(c))
+            .to_owned()
+    };
+
+    let mut urls: Vec<String> = vec![];
+    let mut remainder: Vec<&str> = vec![];
+
+    for word in author.split_whitespace() {
+        let bare = word.trim_matches(|c: char| "()[]<>,;".contains(c));
+        if is_url(bare) {
+            if !urls.iter().any(|n| n == bare) {
+                urls.push(bare.to_owned());
+            }
+            remainder.push(",");
+        } else if word.eq_ignore_ascii_case("and") {
+            remainder.push(",");
+        } else {
+            remainder.push(word);
+        }
+    }
+
+    let mut names: Vec<String> = vec![];
+    for name in remainder.join(" ").split(AUTHOR_SEPARATORS).map(trim_name) {
+        if !name.is_empty() && !names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            names.push(name);
+        }
+    }
+    (names, urls)
+}
+
+/// Parse `<dependency>` and `<requiredMod>` entries
+///
+/// A required version may be given in a `minVersion` or `version` attribute.
+/// `<requiredMod>` names the mod in a `modName` or `name` attribute, or as text.
+fn mod_desc_depends(mod_record: &mut ModRecord, mod_desc: &roxmltree::Document) {
+    for depend in mod_desc
+        .descendants()
+        .filter(|n| n.has_tag_name("dependency") || n.has_tag_name("requiredMod"))
+    {
+        let name = if depend.has_tag_name("requiredMod") {
+            depend
+                .attribute("modName")
+                .or(depend.attribute("name"))
+                .or(depend.text())
+        } else {
+            depend.text()
+        };
+
+        let dependency = ModDependency {
+            name: name.unwrap_or("--").to_owned(),
+            min_version: depend
+                .attribute("minVersion")
+                .or(depend.attribute("version"))
+                .map(str::to_owned),
+        };
+        if let Some(min_version) = &dependency.min_version {
+            mod_record
+                .mod_desc
+                .depend_versions
+                .insert(dependency.name.clone(), min_version.clone());
+        }
+        mod_record.mod_desc.depend.push(dependency);
+    }
+}
+
+/// Pass through requested modDesc subtrees
+fn mod_desc_raw(mod_record: &mut ModRecord, mod_desc: &roxmltree::Document, tags: &[String]) {
+    for tag in tags {
+        let nodes: Vec<ModDescRawNode> = mod_desc
+            .descendants()
+            .filter(|n| n.has_tag_name(tag.as_str()))
+            .map(|n| ModDescRawNode::from_node(&n))
+            .collect();
+
+        if !nodes.is_empty() {
+            mod_record.mod_desc.raw.insert(tag.clone(), nodes);
+        }
+    }
 }
 
 /// Parse title and description entries
@@ -652,42 +1542,366 @@ fn mod_desc_l10n(mod_record: &mut ModRecord, mod_desc: &roxmltree::Document) {
 
 /// Parse actions and key binds in the mod
 fn mod_desc_actions(mod_record: &mut ModRecord, mod_desc: &roxmltree::Document) {
-    for action in mod_desc.descendants().filter(|n| n.has_tag_name("action")) {
-        if let Some(name) = action.attribute("name") {
-            mod_record.mod_desc.actions.insert(
-                name.to_owned(),
-                match action.attribute("category") {
-                    Some(cat) => cat.to_owned(),
-                    None => String::from("ALL"),
-                },
-            );
-        }
+    mod_record.mod_desc.actions = read_actions(mod_desc);
+    mod_record.mod_desc.binds = read_key_binds(mod_desc);
+
+    for input in mod_record.mod_desc.duplicate_binds().into_keys() {
+        mod_record.add_issue_context(ModError::ModDescDuplicateBinding, input);
     }
+}
+
+/// Declared actions, mapped to their category (`ALL` when not set)
+pub(crate) fn read_actions(mod_desc: &roxmltree::Document) -> HashMap<String, String> {
+    mod_desc
+        .descendants()
+        .filter(|n| n.has_tag_name("action"))
+        .filter_map(|action| {
+            action.attribute("name").map(|name| {
+                (
+                    name.to_owned(),
+                    action.attribute("category").unwrap_or("ALL").to_owned(),
+                )
+            })
+        })
+        .collect()
+}
 
-    for action in mod_desc
+/// Default keyboard and mouse inputs, mapped by action name
+pub(crate) fn read_key_binds(mod_desc: &roxmltree::Document) -> HashMap<String, Vec<String>> {
+    mod_desc
         .descendants()
         .filter(|n| n.has_tag_name("actionBinding"))
-    {
-        if let Some(name) = action.attribute("action") {
-            mod_record.mod_desc.binds.insert(
-                name.to_owned(),
-                action
-                    .children()
-                    .filter(|n| {
-                        n.has_tag_name("binding")
-                            && n.attribute("device") == Some("KB_MOUSE_DEFAULT")
-                            && n.has_attribute("input")
-                    })
-                    .filter_map(|x| x.attribute("input").map(std::borrow::ToOwned::to_owned))
-                    .collect(),
-            );
-        }
-    }
+        .filter_map(|action| {
+            action.attribute("action").map(|name| {
+                (
+                    name.to_owned(),
+                    action
+                        .children()
+                        .filter(|n| {
+                            n.has_tag_name("binding")
+                                && n.attribute("device") == Some("KB_MOUSE_DEFAULT")
+                        })
+                        .filter_map(|x| x.attribute("input").map(std::borrow::ToOwned::to_owned))
+                        .collect(),
+                )
+            })
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use assert_json_diff::assert_json_include;
+    use ddsfile::{D3DFormat, Dds, NewD3dParams};
+    use std::collections::BTreeMap;
+
+    /// File list entry, shared with the references tests
+    pub(super) fn file(name: &str) -> FileDefinition {
+        FileDefinition {
+            extension: name.rsplit_once('.').map_or("", |n| n.1).to_lowercase(),
+            name: name.to_owned(),
+            size: 1,
+            is_folder: false,
+        }
+    }
+
+    fn dds_bytes(width: u32, height: u32, format: D3DFormat) -> Vec<u8> {
+        let dds = Dds::new_d3d(NewD3dParams {
+            height,
+            width,
+            depth: None,
+            format,
+            mipmap_levels: None,
+            caps2: None,
+        })
+        .unwrap();
+        let mut output = vec![];
+        dds.write(&mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn mod_icon_checks() {
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        check_mod_icon(&mut mod_record, &dds_bytes(256, 256, D3DFormat::DXT1));
+        assert!(mod_record.issues.is_empty());
+        let icon_info = mod_record.mod_desc.icon_info.unwrap();
+        assert_eq!(icon_info.format, "DXT1");
+        assert!(icon_info.compressed);
+        assert_eq!(icon_info.mip_levels, 1);
+
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        check_mod_icon(&mut mod_record, &dds_bytes(512, 128, D3DFormat::A8R8G8B8));
+        assert_eq!(
+            mod_record.issues,
+            std::collections::HashSet::from([
+                ModError::ModDescIconWrongSize,
+                ModError::ModDescIconUncompressed
+            ])
+        );
+        let icon_info = mod_record.mod_desc.icon_info.unwrap();
+        assert_eq!((icon_info.width, icon_info.height), (512, 128));
+        assert!(icon_info.power_of_two);
+        assert!(!icon_info.compressed);
+
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        check_mod_icon(&mut mod_record, b"not a dds file");
+        assert!(mod_record.issues.is_empty());
+        assert!(mod_record.mod_desc.icon_info.is_none());
+    }
+
+    #[test]
+    fn mod_icon_listed() {
+        let file_list = vec![file("modDesc.xml"), file("icons/ModIcon.dds")];
+
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        mod_record.mod_desc.icon_file_name = Some(String::from("icons/modIcon.dds"));
+        check_icon_listed(&mut mod_record, &file_list);
+        assert!(mod_record.issues.is_empty());
+
+        mod_record.mod_desc.icon_file_name = Some(String::from("icon_missing.dds"));
+        check_icon_listed(&mut mod_record, &file_list);
+        assert!(mod_record.issues.contains(&ModError::ModDescNoModIcon));
+    }
+
+    #[test]
+    fn image_roles() {
+        let mut images = ModImages::default();
+        for name in [
+            "modIcon.dds",
+            "store/icons/tractor.dds",
+            "store/store_tractor.dds",
+            "brands/brand_lizard.dds",
+            "maps/data/pda_map_H.dds",
+            "maps/overview.dds",
+            "vehicles/tractor_diffuse.dds",
+            "maps/data/asphalt01_weight.png",
+            "textures/Glass_Normal.png",
+        ] {
+            add_image(&mut images, name, &ModParserOptions::default());
+        }
+
+        assert_eq!(images.icons, vec!["modIcon.dds", "store/icons/tractor.dds"]);
+        assert_eq!(
+            images.store_images,
+            vec!["store/store_tractor.dds", "brands/brand_lizard.dds"]
+        );
+        assert_eq!(
+            images.pda_maps,
+            vec!["maps/data/pda_map_H.dds", "maps/overview.dds"]
+        );
+        assert_eq!(
+            images.textures,
+            vec!["vehicles/tractor_diffuse.dds", "textures/Glass_Normal.png"]
+        );
+        assert!(images.weight_maps.is_empty());
+        assert_eq!(images.all().count(), 8);
+
+        let options = ModParserOptions {
+            weight_images: true,
+            ..Default::default()
+        };
+        add_image(&mut images, "maps/data/asphalt01_weight.png", &options);
+        assert_eq!(images.weight_maps, vec!["maps/data/asphalt01_weight.png"]);
+    }
+
+    #[test]
+    fn audio_inventory() {
+        let audio_file = |name: &str, size: u64| FileDefinition { size, ..file(name) };
+        let mut file_list = vec![
+            audio_file("sounds/engine.ogg", 40 * MB),
+            audio_file("sounds/horn.gls", 2 * MB),
+        ];
+
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        do_file_counts(&mut mod_record, &file_list, &ModParserOptions::default());
+        assert_eq!(mod_record.file_detail.audio_size, 42 * MB);
+        assert_eq!(
+            mod_record.file_detail.audio_files,
+            vec![
+                AudioFile {
+                    name: String::from("sounds/engine.ogg"),
+                    size: 40 * MB
+                },
+                AudioFile {
+                    name: String::from("sounds/horn.gls"),
+                    size: 2 * MB
+                },
+            ]
+        );
+        assert!(mod_record.issues.is_empty());
+
+        file_list.push(audio_file("sounds/music.ogg", 30 * MB));
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        do_file_counts(&mut mod_record, &file_list, &ModParserOptions::default());
+        assert_eq!(mod_record.file_detail.audio_files.len(), 3);
+        assert!(mod_record
+            .issues
+            .contains(&ModError::PerformanceOversizeAudio));
+    }
+
+    #[test]
+    fn file_path_checks() {
+        let long_name = format!("textures/{}.dds", "a".repeat(180));
+        // long in UTF-8 bytes, but not in UTF-16 units
+        let wide_name = format!("textures/{}.dds", "ü".repeat(120));
+        let file_list = vec![
+            file("textures/diffuse.dds"),
+            file("textures/grün_diffuse.dds"),
+            file(&long_name),
+            file(&wide_name),
+        ];
+
+        let mut mod_record = ModRecord::new("FS22_Example.zip", false);
+        check_file_paths(&mut mod_record, &file_list);
+        assert_eq!(
+            mod_record.issue_context[&ModError::FileErrorNonAsciiName],
+            vec!["textures/grün_diffuse.dds", wide_name.as_str()]
+        );
+        assert_eq!(
+            mod_record.issue_context[&ModError::FileErrorLongPath],
+            vec![long_name.as_str()]
+        );
+    }
+
+    #[test]
+    fn gallery_files() {
+        let file_list = vec![
+            file("Screenshots/field.dds"),
+            file("icon.dds"),
+            file("gallery/02.dds"),
+            file("gallery/01.dds"),
+            file("preview.png"),
+            file("textures/screen_diffuse.dds"),
+            file("images/farm.png"),
+            file("images/brand.png"),
+        ];
+        let mod_desc_doc = roxmltree::Document::parse(
+            r#"<modDesc>
+                <brands><brand image="images/brand.png" /></brands>
+                <screenshots>
+                    <screenshot filename="images/farm.png" />
+                    <screenshot>gallery/02.dds</screenshot>
+                </screenshots>
+            </modDesc>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            gallery_candidates(&file_list, &mod_desc_doc),
+            vec![
+                "images/farm.png",
+                "gallery/02.dds",
+                "Screenshots/field.dds",
+                "gallery/01.dds",
+                "preview.png"
+            ]
+        );
+    }
+
+    #[test]
+    fn framework_files() {
+        let file_list = vec![
+            file("AutoDrive_Riverbend_config.xml"),
+            file("courseplay/courses/field1.xml"),
+            file("scripts/AutoDrive_helper.lua"),
+            file("xml/autodriveSettings.xml"),
+            file("modDesc.xml"),
+        ];
+        let mod_desc_doc = roxmltree::Document::parse(
+            r#"<modDesc descVersion="72"><globalCompany minimumVersion="1.0.0.0" /></modDesc>"#,
+        )
+        .unwrap();
+
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        detect_frameworks(&mut mod_record, &mod_desc_doc, &file_list);
+
+        assert_eq!(
+            mod_record.framework_configs,
+            BTreeMap::from([
+                (
+                    ModFramework::AutoDrive,
+                    vec![String::from("AutoDrive_Riverbend_config.xml")]
+                ),
+                (
+                    ModFramework::Courseplay,
+                    vec![String::from("courseplay/courses/field1.xml")]
+                ),
+                (
+                    ModFramework::GlobalCompany,
+                    vec![String::from("modDesc.xml")]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn file_name_words() {
+        assert_eq!(
+            name_words("scripts/gui/MyModGUIFrame.lua"),
+            vec!["scripts", "gui", "my", "mod", "gui", "frame", "lua"]
+        );
+        assert_eq!(
+            name_words("my_mod-v2Screens.lua"),
+            vec!["my", "mod", "v2", "screens", "lua"]
+        );
+    }
+
+    #[test]
+    fn mp_risk_factors() {
+        let file_list = vec![
+            file("scripts/MyMod.lua"),
+            file("scripts/gui/MyModSettingsFrame.lua"),
+            file("scripts/MyModHud.lua"),
+            file("scripts/MyFramework.lua"),
+            file("scripts/ScreenshotTool.lua"),
+            file("scripts/guide_loader.lua"),
+        ];
+
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        assess_mp_risk(&mut mod_record, &file_list);
+        assert_eq!(mod_record.mp_risk_score, MpRiskKind::ClientScripts.weight());
+        assert_eq!(
+            mod_record.mp_risk_factors[0].detail,
+            "scripts/gui/MyModSettingsFrame.lua, scripts/MyModHud.lua"
+        );
+
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        mod_record.mod_desc.store_items = MP_RISK_STORE_ITEMS + 1;
+        mod_record.file_detail.audio_size = SIZE_AUDIO + 1;
+        assess_mp_risk(&mut mod_record, &[file("scripts/MyMod.lua")]);
+        assert_eq!(
+            mod_record
+                .mp_risk_factors
+                .iter()
+                .map(|factor| factor.kind)
+                .collect::<Vec<_>>(),
+            vec![MpRiskKind::ManyStoreItems, MpRiskKind::OversizeAudio]
+        );
+        assert_eq!(mod_record.mp_risk_score, 30);
+    }
+
+    #[test]
+    fn texture_report() {
+        let mut report = TextureReport::default();
+        for (name, width, height) in [
+            ("good.dds", 1024, 1024),
+            ("huge.dds", 4096, 2048),
+            ("odd.dds", 300, 256),
+        ] {
+            let header = dds_bytes(width, height, D3DFormat::DXT1);
+            report.add(name, analyze_dds(&header));
+        }
+        report.add("broken.dds", analyze_dds(b"not a dds file"));
+
+        assert_eq!(report.checked, 4);
+        assert_eq!(report.large, vec!["huge.dds"]);
+        assert_eq!(report.not_power_of_two, vec!["odd.dds"]);
+        assert_eq!(report.unreadable, vec!["broken.dds"]);
+        assert_eq!(report.no_mipmaps.len(), 3);
+        assert!(!report.is_clean());
+    }
 
     #[test]
     fn test_file_name_assumptions() {
@@ -803,6 +2017,56 @@ mod test {
 
 
 
+    #[test]
+    fn author_list() {
+        let (names, urls) = split_authors(
+            "Lizard Modding, Some Person / some person & Other (https://example.com/mods) and Last",
+        );
+        assert_eq!(
+            names,
+            vec!["Lizard Modding", "Some Person", "Other", "Last"]
+        );
+        assert_eq!(urls, vec!["https://example.com/mods"]);
+
+        let (names, urls) = split_authors("FSModAssist Test");
+        assert_eq!(names, vec!["FSModAssist Test"]);
+        assert!(urls.is_empty());
+
+        let (names, urls) = split_authors("--");
+        assert!(names.is_empty());
+        assert!(urls.is_empty());
+    }
+
+    #[test]
+    fn read_links() {
+        let xml = r#"<modDesc descVersion="92">
+            <website>https://example.com/mods</website>
+            <Contact> mods@example.com </Contact>
+            <donation href="https://example.com/donate"/>
+            <support></support>
+            <website>https://example.com/second</website>
+            <storeItems><url>https://example.com/nested</url></storeItems>
+        </modDesc>"#;
+        let doc = roxmltree::Document::parse(xml).unwrap();
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        mod_desc_links(&mut mod_record, &doc);
+
+        assert_eq!(
+            mod_record.mod_desc.links,
+            std::collections::BTreeMap::from([
+                (String::from("contact"), String::from("mods@example.com")),
+                (
+                    String::from("donation"),
+                    String::from("https://example.com/donate")
+                ),
+                (
+                    String::from("website"),
+                    String::from("https://example.com/mods")
+                ),
+            ])
+        );
+    }
+
     #[test]
     fn read_dependency() {
         let minimum_xml = r#"<modDesc descVersion="66">
@@ -813,10 +2077,157 @@ mod test {
     
         let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
         let mut mod_record = ModRecord::new("Example.zip", false);
-        mod_desc_basics(&mut mod_record, &minimum_doc);
+        mod_desc_basics(&mut mod_record, &minimum_doc, &ModParserOptions::default());
 
         assert_eq!(mod_record.mod_desc.depend.len(), 1);
-        assert!(mod_record.mod_desc.depend.contains(&String::from("FS22_RedBarnPack")));
+        assert!(mod_record
+            .mod_desc
+            .depend
+            .contains(&ModDependency::new("FS22_RedBarnPack")));
+    }
+
+    #[test]
+    fn read_dependency_versions() {
+        let minimum_xml = r#"<modDesc descVersion="66">
+            <dependencies>
+                <dependency minVersion="1.2.0.0">FS22_RedBarnPack</dependency>
+            </dependencies>
+            <requiredMods>
+                <requiredMod modName="FS22_Lib" version="2.0.0.0"/>
+                <requiredMod>FS22_Other</requiredMod>
+            </requiredMods>
+        </modDesc>"#;
+
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        mod_desc_basics(&mut mod_record, &minimum_doc, &ModParserOptions::default());
+
+        assert_eq!(
+            mod_record.mod_desc.depend,
+            vec![
+                ModDependency {
+                    name: String::from("FS22_RedBarnPack"),
+                    min_version: Some(String::from("1.2.0.0")),
+                },
+                ModDependency {
+                    name: String::from("FS22_Lib"),
+                    min_version: Some(String::from("2.0.0.0")),
+                },
+                ModDependency::new("FS22_Other"),
+            ]
+        );
+        assert!(mod_record.mod_desc.depend[0].is_met_by("1.2.0.1"));
+        assert!(!mod_record.mod_desc.depend[0].is_met_by("1.1.9.0"));
+        assert!(mod_record.mod_desc.depend[2].is_met_by("0.0.0.1"));
+        assert_eq!(
+            serde_json::json!(mod_record.mod_desc)["dependVersions"],
+            serde_json::json!({ "FS22_Lib": "2.0.0.0", "FS22_RedBarnPack": "1.2.0.0" })
+        );
+
+        let json = serde_json::to_value(&mod_record.mod_desc).unwrap();
+        assert_eq!(
+            json["depend"],
+            serde_json::json!(["FS22_RedBarnPack", "FS22_Lib", "FS22_Other"])
+        );
+    }
+
+    #[test]
+    fn likely_modhub_source() {
+        let mut mod_record = ModRecord::new("FS22_Example_Mod.zip", false);
+        mod_record.mod_desc.icon_file_name = Some(String::from("icon_example.dds"));
+        mod_record.update_badges();
+
+        detect_source(&mut mod_record, &ModParserOptions::default());
+        assert_eq!(mod_record.source, ModSource::LikelyModHub);
+
+        mod_record.add_issue(ModError::InfoLikelyPiracy);
+        detect_source(&mut mod_record, &ModParserOptions::default());
+        assert_eq!(mod_record.source, ModSource::Unknown);
+
+        let mut mod_record = ModRecord::new("Example_Mod.zip", false);
+        mod_record.mod_desc.icon_file_name = Some(String::from("icon_example.dds"));
+        mod_record.update_badges();
+
+        detect_source(&mut mod_record, &ModParserOptions::default());
+        assert_eq!(mod_record.source, ModSource::Unknown);
+    }
+
+    #[test]
+    fn duplicate_binds_in_mod() {
+        let minimum_xml = r#"<modDesc descVersion="66">
+            <actions>
+                <action name="FIRST_ACTION" category="VEHICLE" />
+                <action name="SECOND_ACTION" />
+                <action name="THIRD_ACTION" category="ONFOOT" />
+            </actions>
+            <inputBinding>
+                <actionBinding action="FIRST_ACTION">
+                    <binding device="KB_MOUSE_DEFAULT" input="KEY_lctrl KEY_k" />
+                </actionBinding>
+                <actionBinding action="SECOND_ACTION">
+                    <binding device="KB_MOUSE_DEFAULT" input="KEY_k KEY_lctrl" />
+                </actionBinding>
+                <actionBinding action="THIRD_ACTION">
+                    <binding device="KB_MOUSE_DEFAULT" input="KEY_j" />
+                </actionBinding>
+            </inputBinding>
+        </modDesc>"#;
+
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        mod_desc_actions(&mut mod_record, &minimum_doc);
+
+        assert!(mod_record
+            .issues
+            .contains(&ModError::ModDescDuplicateBinding));
+        assert!(!mod_record.update_badges().badge_array.problem);
+
+        let duplicates = mod_record.mod_desc.duplicate_binds();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(
+            duplicates.get("KEY_k KEY_lctrl"),
+            Some(&vec![
+                String::from("FIRST_ACTION"),
+                String::from("SECOND_ACTION")
+            ])
+        );
+
+        let grouped = mod_record.mod_desc.binds_by_category();
+        assert_eq!(grouped.len(), 3);
+        assert!(grouped["ONFOOT"].contains_key("THIRD_ACTION"));
+    }
+
+    #[test]
+    fn raw_mod_desc_passthrough() {
+        let minimum_xml = r#"<modDesc descVersion="66">
+            <futureTag mode="fast">
+                <entry name="first">Some Text</entry>
+                <entry name="second" />
+            </futureTag>
+        </modDesc>"#;
+
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut mod_record = ModRecord::new("Example.zip", false);
+        mod_desc_raw(
+            &mut mod_record,
+            &minimum_doc,
+            &[String::from("futureTag"), String::from("missingTag")],
+        );
+
+        assert!(!mod_record.mod_desc.raw.contains_key("missingTag"));
+        assert_json_include!(
+            actual: serde_json::to_value(&mod_record.mod_desc.raw).unwrap(),
+            expected: serde_json::json!({
+                "futureTag": [{
+                    "tag": "futureTag",
+                    "attributes": { "mode": "fast" },
+                    "text": null,
+                    "children": [
+                        { "tag": "entry", "attributes": { "name": "first" }, "text": "Some Text", "children": [] },
+                        { "tag": "entry", "attributes": { "name": "second" }, "text": null, "children": [] }
+                    ]
+                }]
+            })
+        );
     }
-    
 }
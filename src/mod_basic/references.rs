@@ -0,0 +1,363 @@
+//! File reference checks
+//!
+//! Windows ignores case in file names, so a mod can refer to
+//! `Textures/foo.dds` while the archive holds `textures/foo.dds` and still
+//! work there. Linux dedicated servers will not find the file. This pass
+//! reads every XML and I3D file and lists references that only match a
+//! file when case is ignored, and references that point outside the mod.
+use crate::shared::files::{
+    external_references, has_reference_extension, resolve_mod_path, AbstractFileHandle,
+    FileDefinition,
+};
+use crate::shared::structs::{ExternalReference, ReferenceCaseMismatch};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// File names in a mod, exact and case folded
+struct FileIndex<'a> {
+    /// exact file names
+    exact: HashSet<&'a str>,
+    /// lowercase file name to file name
+    folded: HashMap<String, &'a str>,
+}
+
+impl<'a> FileIndex<'a> {
+    /// Index the files (not folders) of a mod
+    fn new(file_list: &'a [FileDefinition]) -> Self {
+        let files = file_list.iter().filter(|n| !n.is_folder);
+        FileIndex {
+            exact: files.clone().map(|n| n.name.as_str()).collect(),
+            folded: files
+                .map(|n| (n.name.to_lowercase(), n.name.as_str()))
+                .collect(),
+        }
+    }
+
+    /// A file matches the name, ignoring case
+    fn contains(&self, name: &str) -> bool {
+        self.exact.contains(name) || self.folded.contains_key(&name.to_lowercase())
+    }
+
+    /// Find the file a reference only matches when case is ignored
+    ///
+    /// References are tried against the mod root and the folder of the file
+    /// holding them. PNG references also match the DDS the game loads in
+    /// their place. Returns `None` if any candidate matches exactly, or
+    /// nothing matches at all (missing files are not a case problem)
+    fn case_mismatch(&self, base_folder: &str, reference: &str) -> Option<&'a str> {
+        let mut candidates: Vec<String> = ["", base_folder]
+            .iter()
+            .filter_map(|base| resolve_mod_path(base, reference))
+            .collect();
+
+        let dds_swaps: Vec<String> = candidates
+            .iter()
+            .filter_map(|name| {
+                let stem = name.strip_suffix(".png").or(name.strip_suffix(".PNG"))?;
+                Some(format!("{stem}.dds"))
+            })
+            .collect();
+        candidates.extend(dds_swaps);
+
+        if candidates
+            .iter()
+            .any(|name| self.exact.contains(name.as_str()))
+        {
+            return None;
+        }
+
+        candidates
+            .iter()
+            .find_map(|name| self.folded.get(&name.to_lowercase()).copied())
+    }
+}
+
+/// Value looks like a relative file reference
+fn is_file_reference(value: &str) -> bool {
+    !value.starts_with('$') && !value.contains(':') && has_reference_extension(value)
+}
+
+/// File references in a document, from attribute values and element text
+fn document_references<'a>(doc: &'a roxmltree::Document) -> impl Iterator<Item = &'a str> {
+    doc.descendants()
+        .filter(roxmltree::Node::is_element)
+        .flat_map(|node| {
+            node.attributes()
+                .map(|attribute| attribute.value().trim())
+                .chain(node.text().map(str::trim))
+        })
+        .filter(|value| is_file_reference(value))
+}
+
+/// Official DLCs named in a text, as `pdlc_` short names
+///
+/// Finds `$pdlcdir$name/...` paths and `pdlc_name` references
+pub(crate) fn dlc_references(text: &str) -> BTreeSet<String> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let name_at = |rest: &str| -> Option<String> {
+        let end = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+        (end > 0).then(|| format!("pdlc_{}", &rest[..end]))
+    };
+    let mut found = BTreeSet::new();
+
+    for (index, _) in text.match_indices("$pdlcdir$") {
+        found.extend(name_at(&text[index + "$pdlcdir$".len()..]));
+    }
+    for (index, _) in text.match_indices("pdlc_") {
+        if text[..index].chars().next_back().is_some_and(is_name_char) {
+            continue;
+        }
+        found.extend(name_at(&text[index + "pdlc_".len()..]));
+    }
+    found
+}
+
+/// Official DLCs named in the attribute values of a document, and in the
+/// text of `<dependency>` entries
+///
+/// Other element text (descriptions, l10n) is not checked
+fn document_dlc_references(doc: &roxmltree::Document) -> BTreeSet<String> {
+    doc.descendants()
+        .filter(roxmltree::Node::is_element)
+        .flat_map(|node| {
+            let dependency = node.text().filter(|_| node.has_tag_name("dependency"));
+            node.attributes()
+                .map(|attribute| attribute.value())
+                .chain(dependency)
+        })
+        .flat_map(dlc_references)
+        .collect()
+}
+
+/// Findings from the store item files of a mod
+#[derive(Default)]
+pub(crate) struct StoreItemScan {
+    /// official DLCs the mod needs, as sorted `pdlc_` short names (as used by
+    /// [`crate::savegame::SaveGameMod`])
+    pub required_dlcs: Vec<String>,
+    /// light and beacon files referenced by a store item but not in the mod,
+    /// as `source: reference`
+    pub missing_lights: Vec<String>,
+}
+
+/// Light and beacon file references in a store item, relative to the mod root
+///
+/// Any element with `light` in its tag name (`sharedLight`, `beaconLight`,
+/// ...) and a `filename`. Base game (`$data`) references are skipped
+fn light_references<'a>(doc: &'a roxmltree::Document) -> impl Iterator<Item = &'a str> {
+    doc.descendants()
+        .filter(|n| n.tag_name().name().to_lowercase().contains("light"))
+        .filter_map(|n| n.attribute("filename"))
+        .map(str::trim)
+        .filter(|value| is_file_reference(value))
+}
+
+/// Read the modDesc and its store item files for required DLCs and
+/// missing light files
+///
+/// Store items are only checked for DLCs with `scan_dlcs`, the modDesc
+/// always is
+pub(crate) fn scan_store_items(
+    file_handle: &mut Box<dyn AbstractFileHandle>,
+    mod_desc: &roxmltree::Document,
+    file_list: &[FileDefinition],
+    scan_dlcs: bool,
+) -> StoreItemScan {
+    let index = FileIndex::new(file_list);
+    let mut found = document_dlc_references(mod_desc);
+    let mut missing_lights: BTreeSet<String> = BTreeSet::new();
+
+    for file_name in mod_desc
+        .descendants()
+        .filter(|n| n.has_tag_name("storeItem"))
+        .filter_map(|n| n.attribute("xmlFilename"))
+        .filter_map(|file_name| resolve_mod_path("", file_name))
+    {
+        let Ok(content) = file_handle.as_text(&file_name) else {
+            continue;
+        };
+        let Ok(doc) = roxmltree::Document::parse(&content) else {
+            continue;
+        };
+        if scan_dlcs {
+            found.extend(document_dlc_references(&doc));
+        }
+        for reference in light_references(&doc) {
+            if !resolve_mod_path("", reference).is_some_and(|name| index.contains(&name)) {
+                missing_lights.insert(format!("{file_name}: {reference}"));
+            }
+        }
+    }
+
+    StoreItemScan {
+        required_dlcs: found.into_iter().collect(),
+        missing_lights: missing_lights.into_iter().collect(),
+    }
+}
+
+/// Reference problems found in the XML and I3D files of a mod
+#[derive(Default)]
+pub(crate) struct ReferenceScan {
+    /// references that only match a file with different case
+    pub case_mismatches: Vec<ReferenceCaseMismatch>,
+    /// references that point outside of the mod
+    pub external: Vec<ExternalReference>,
+}
+
+/// Check every XML and I3D file for references with the wrong case, or
+/// that point outside of the mod
+///
+/// XML references are relative to the mod root, I3D references to the I3D
+/// file. Results are sorted by source file, then reference
+pub(crate) fn check_references(
+    file_handle: &mut Box<dyn AbstractFileHandle>,
+    file_list: &[FileDefinition],
+) -> ReferenceScan {
+    let index = FileIndex::new(file_list);
+    let mut found: BTreeSet<ReferenceCaseMismatch> = BTreeSet::new();
+    let mut scan = ReferenceScan::default();
+
+    for file in file_list
+        .iter()
+        .filter(|n| !n.is_folder && (n.extension == "xml" || n.extension == "i3d"))
+    {
+        let Ok(content) = file_handle.as_text(&file.name) else {
+            continue;
+        };
+        let Ok(doc) = roxmltree::Document::parse(&content) else {
+            continue;
+        };
+        let base_folder = file.name.rsplit_once('/').map_or("", |(folder, _)| folder);
+        let external_base = if file.extension == "i3d" {
+            base_folder
+        } else {
+            ""
+        };
+
+        scan.external
+            .extend(
+                external_references(&doc, external_base)
+                    .into_iter()
+                    .map(|reference| ExternalReference {
+                        source: file.name.clone(),
+                        reference,
+                    }),
+            );
+
+        for reference in document_references(&doc) {
+            if let Some(actual) = index.case_mismatch(base_folder, reference) {
+                found.insert(ReferenceCaseMismatch {
+                    source: file.name.clone(),
+                    reference: reference.to_owned(),
+                    actual: actual.to_owned(),
+                });
+            }
+        }
+    }
+
+    scan.case_mismatches = found.into_iter().collect();
+    scan
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mod_basic::test::file;
+
+    #[test]
+    fn reference_values() {
+        assert!(is_file_reference("textures/foo.dds"));
+        assert!(is_file_reference("Store_Icon.PNG"));
+        assert!(is_file_reference("..\\shared\\model.i3d"));
+        assert!(!is_file_reference("$data/shared/foo.dds"));
+        assert!(!is_file_reference("https://example.com/logo.png"));
+        assert!(!is_file_reference("1.0.0.0"));
+        assert!(!is_file_reference(".xml"));
+        assert!(!is_file_reference("Some text"));
+    }
+
+    #[test]
+    fn dlc_reference_names() {
+        let text = r#"<dependency>pdlc_claasPack</dependency>
+            <combination xmlFilename="$pdlcdir$kubotaPack/vehicles/tractor.xml"/>
+            <combination xmlFilename="$pdlcdir$claasPack/vehicles/header.xml"/>
+            <note>mypdlc_notThis</note>"#;
+        assert_eq!(
+            dlc_references(text).into_iter().collect::<Vec<_>>(),
+            vec!["pdlc_claasPack", "pdlc_kubotaPack"]
+        );
+        assert!(dlc_references("$pdlcdir$/broken.xml").is_empty());
+    }
+
+    #[test]
+    fn dlc_document_references() {
+        let doc = roxmltree::Document::parse(
+            r#"<modDesc>
+                <description><en>Works great with pdlc_claasPack</en></description>
+                <dependencies><dependency>pdlc_kubotaPack</dependency></dependencies>
+                <storeItems>
+                    <storeItem xmlFilename="$pdlcdir$vermeerPack/vehicles/baler.xml"/>
+                </storeItems>
+            </modDesc>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            document_dlc_references(&doc)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec!["pdlc_kubotaPack", "pdlc_vermeerPack"]
+        );
+    }
+
+    #[test]
+    fn missing_light_files() {
+        let doc = roxmltree::Document::parse(
+            r#"<vehicle>
+                <lights>
+                    <sharedLight filename="$data/shared/assets/lights/frontLight01.xml" />
+                    <sharedLight filename="lights/workLight.xml" />
+                    <sharedLight filename="Lights/Missing.xml" />
+                </lights>
+                <beaconLights>
+                    <beaconLight filename="beacon/beacon.i3d" />
+                </beaconLights>
+                <base filename="vehicle.i3d" />
+            </vehicle>"#,
+        )
+        .unwrap();
+        let files = vec![file("lights/workLight.xml"), file("vehicle.i3d")];
+        let index = FileIndex::new(&files);
+
+        let missing: Vec<&str> = light_references(&doc)
+            .filter(|reference| !index.contains(reference))
+            .collect();
+        assert_eq!(missing, vec!["Lights/Missing.xml", "beacon/beacon.i3d"]);
+    }
+
+    #[test]
+    fn case_mismatches() {
+        let files = vec![
+            file("textures/foo.dds"),
+            file("vehicle/tractor.xml"),
+            file("vehicle/Model.i3d"),
+            file("store_icon.dds"),
+        ];
+        let index = FileIndex::new(&files);
+
+        assert_eq!(index.case_mismatch("", "textures/foo.dds"), None);
+        assert_eq!(
+            index.case_mismatch("", "Textures/foo.dds"),
+            Some("textures/foo.dds")
+        );
+        assert_eq!(
+            index.case_mismatch("vehicle", "model.i3d"),
+            Some("vehicle/Model.i3d")
+        );
+        assert_eq!(index.case_mismatch("vehicle", "Model.i3d"), None);
+        assert_eq!(index.case_mismatch("", "store_icon.png"), None);
+        assert_eq!(
+            index.case_mismatch("", "Store_Icon.png"),
+            Some("store_icon.dds")
+        );
+        assert_eq!(index.case_mismatch("", "missing.dds"), None);
+    }
+}
@@ -0,0 +1,264 @@
+//! Script API usage
+//!
+//! Script mods change the game by hooking into it - adding specializations,
+//! console commands and event listeners, or replacing base game functions.
+//! This pass reads every lua file and lists those touchpoints, so admins can
+//! judge how intrusive a script mod is. It is a text scan, not a lua parser:
+//! calls built at runtime (or hidden in strings) are not found. The scan
+//! shares its file reads with the malware check.
+use crate::shared::structs::ScriptApiSummary;
+use std::collections::BTreeSet;
+
+/// Touchpoints found so far, deduplicated and sorted
+#[derive(Default)]
+pub(crate) struct ScriptApiScan {
+    /// `Utils.appendedFunction` targets
+    appended_functions: BTreeSet<String>,
+    /// `addConsoleCommand` names
+    console_commands: BTreeSet<String>,
+    /// mod and specialization event listeners
+    event_listeners: BTreeSet<String>,
+    /// `Utils.overwrittenFunction` targets
+    overwritten_functions: BTreeSet<String>,
+    /// `Utils.prependedFunction` targets
+    prepended_functions: BTreeSet<String>,
+    /// `addSpecialization` names
+    specializations: BTreeSet<String>,
+}
+
+impl ScriptApiScan {
+    /// Add the touchpoints of one lua file
+    pub(crate) fn add_script(&mut self, content: &str) {
+        let code = strip_comments(content);
+
+        for (call, list) in [
+            ("Utils.appendedFunction", &mut self.appended_functions),
+            ("addConsoleCommand", &mut self.console_commands),
+            ("addModEventListener", &mut self.event_listeners),
+            ("Utils.overwrittenFunction", &mut self.overwritten_functions),
+            ("Utils.prependedFunction", &mut self.prepended_functions),
+            ("addSpecialization", &mut self.specializations),
+        ] {
+            list.extend(call_arguments(&code, call, 0));
+        }
+        // specialization events are the second argument
+        self.event_listeners
+            .extend(call_arguments(&code, "registerEventListener", 1));
+    }
+
+    /// Sorted lists for the mod record
+    pub(crate) fn into_summary(self, script_files: usize) -> ScriptApiSummary {
+        ScriptApiSummary {
+            appended_functions: self.appended_functions.into_iter().collect(),
+            console_commands: self.console_commands.into_iter().collect(),
+            event_listeners: self.event_listeners.into_iter().collect(),
+            overwritten_functions: self.overwritten_functions.into_iter().collect(),
+            prepended_functions: self.prepended_functions.into_iter().collect(),
+            script_files,
+            specializations: self.specializations.into_iter().collect(),
+        }
+    }
+}
+
+/// Level of a long bracket (`[[` is 0, `[==[` is 2) opening the text
+fn long_bracket_level(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix('[')?;
+    let level = rest.len() - rest.trim_start_matches('=').len();
+    rest[level..].starts_with('[').then_some(level)
+}
+
+/// Length of the long string or comment body at the start of the text,
+/// including the closing bracket (the whole text if it is never closed)
+fn long_bracket_len(text: &str, level: usize) -> usize {
+    let close = format!("]{}]", "=".repeat(level));
+    let open = level + 2;
+    text[open..]
+        .find(&close)
+        .map_or(text.len(), |end| open + end + close.len())
+}
+
+/// Length of the quoted string at the start of the text, including the
+/// quotes
+///
+/// Escaped quotes are skipped, and an unfinished string ends at the line
+/// break, as lua reports it there.
+fn quoted_len(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (offset, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\n' => return offset,
+            c if c == quote => return offset + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Lua source with the comments removed
+///
+/// A small lexer rather than a line split, so `--` inside a quoted or long
+/// string is kept and block comments (`--[[ ]]`, `--[==[ ]==]`) are dropped
+/// whole. Line comments keep their line break.
+fn strip_comments(content: &str) -> String {
+    let mut code = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(comment) = rest.strip_prefix("--") {
+            let length = match long_bracket_level(comment) {
+                Some(level) => long_bracket_len(comment, level),
+                None => comment.find('\n').unwrap_or(comment.len()),
+            };
+            code.push(' ');
+            rest = &comment[length..];
+            continue;
+        }
+        let length = match long_bracket_level(rest) {
+            Some(level) => long_bracket_len(rest, level),
+            None if c == '"' || c == '\'' => quoted_len(rest, c),
+            None => c.len_utf8(),
+        };
+        code.push_str(&rest[..length]);
+        rest = &rest[length..];
+    }
+    code
+}
+
+/// Character can be part of a lua name (`Vehicle.onLoad`, `self:update`)
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == ':'
+}
+
+/// Literal value of a call argument - a quoted string or a plain name
+///
+/// Anything else (expressions, table constructors) returns `None`
+fn argument_value(argument: &str) -> Option<String> {
+    let argument = argument.trim();
+    let value = match argument.chars().next()? {
+        quote @ ('"' | '\'') => argument[1..].strip_suffix(quote)?,
+        _ if argument.chars().all(is_name_char) => argument,
+        _ => return None,
+    };
+    (!value.is_empty()).then(|| value.to_owned())
+}
+
+/// Literal values of one argument (zero based) of every call to a function
+/// in lua code
+///
+/// Calls must be followed by an opening parenthesis, and the name must not
+/// be the tail of a longer name (`myaddConsoleCommand`). Method and field
+/// calls (`g_specializationManager:addSpecialization`) are found.
+fn call_arguments(code: &str, call: &str, argument: usize) -> Vec<String> {
+    let mut found = vec![];
+
+    for (index, _) in code.match_indices(call) {
+        let before = code[..index].chars().next_back();
+        if before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+            continue;
+        }
+        let Some(arguments) = code[index + call.len()..].trim_start().strip_prefix('(') else {
+            continue;
+        };
+
+        // split on top level commas, up to the closing parenthesis
+        let mut depth = 0_usize;
+        let mut quote: Option<char> = None;
+        let mut values: Vec<&str> = vec![];
+        let mut start = 0;
+        for (offset, c) in arguments.char_indices() {
+            match (quote, c) {
+                (Some(open), c) if c == open => quote = None,
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '(' | '{' | '[') => depth += 1,
+                (None, ')' | '}' | ']') if depth > 0 => depth -= 1,
+                (None, ')') => {
+                    values.push(&arguments[start..offset]);
+                    break;
+                }
+                (None, ',') if depth == 0 => {
+                    values.push(&arguments[start..offset]);
+                    start = offset + 1;
+                }
+                _ => {}
+            }
+        }
+
+        found.extend(values.get(argument).and_then(|value| argument_value(value)));
+    }
+    found
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn script_api_touchpoints() {
+        let mut scan = ScriptApiScan::default();
+        scan.add_script(
+            r#"
+g_specializationManager:addSpecialization("myTool", "MyTool", g_currentModDirectory .. "MyTool.lua")
+addModEventListener(MyMod)
+addConsoleCommand("gsMyModReset", "Reset the mod", "consoleReset", self)
+-- addConsoleCommand("gsCommented", "Not added", "consoleNone", self)
+--[[
+addConsoleCommand("gsBlocked", "Not added", "consoleNone", self)
+]]
+addConsoleCommand("gsMyMod--Debug", "Dashes in a string", "consoleDebug", self)
+myaddConsoleCommand("gsNotReal", "", "", self)
+
+function MyTool.registerEventListeners(vehicleType)
+    SpecializationUtil.registerEventListener(vehicleType, "onLoad", MyTool)
+    SpecializationUtil.registerEventListener(vehicleType, 'onUpdate', MyTool)
+end
+
+Vehicle.load = Utils.overwrittenFunction(Vehicle.load, MyMod.load)
+Mission00.loadMission00Finished = Utils.appendedFunction(Mission00.loadMission00Finished, MyMod.loaded)
+FSBaseMission.delete = Utils.prependedFunction(FSBaseMission.delete, function(self) MyMod.delete(self, ",") end)
+Vehicle.load = Utils.overwrittenFunction(Vehicle.load, MyMod.loadAgain)
+"#,
+        );
+        let summary = scan.into_summary(1);
+
+        assert_eq!(summary.specializations, vec!["myTool"]);
+        assert_eq!(
+            summary.console_commands,
+            vec!["gsMyMod--Debug", "gsMyModReset"]
+        );
+        assert_eq!(summary.event_listeners, vec!["MyMod", "onLoad", "onUpdate"]);
+        assert_eq!(summary.overwritten_functions, vec!["Vehicle.load"]);
+        assert_eq!(
+            summary.appended_functions,
+            vec!["Mission00.loadMission00Finished"]
+        );
+        assert_eq!(summary.prepended_functions, vec!["FSBaseMission.delete"]);
+        assert_eq!(summary.hooked_functions(), 3);
+    }
+
+    #[test]
+    fn script_api_arguments() {
+        assert_eq!(
+            call_arguments("addModEventListener ( Foo )", "addModEventListener", 0),
+            vec!["Foo"]
+        );
+        assert_eq!(
+            call_arguments("addConsoleCommand(name, ...)", "addConsoleCommand", 0),
+            vec!["name"]
+        );
+        assert!(call_arguments("addConsoleCommand(\"a\" .. b)", "addConsoleCommand", 0).is_empty());
+        assert!(call_arguments("local addConsoleCommand = nil", "addConsoleCommand", 0).is_empty());
+    }
+
+    #[test]
+    fn script_comments_stripped() {
+        assert_eq!(strip_comments("a = 1 -- one\nb = 2"), "a = 1  \nb = 2");
+        assert_eq!(strip_comments("a --[[ x\ny ]] b"), "a   b");
+        assert_eq!(strip_comments("a --[==[ ]] ]==] b"), "a   b");
+        assert_eq!(strip_comments("s = \"--\" -- c"), "s = \"--\"  ");
+        assert_eq!(strip_comments("s = 'it\\'s --' c"), "s = 'it\\'s --' c");
+        assert_eq!(strip_comments("s = [[--]] c"), "s = [[--]] c");
+        assert_eq!(strip_comments("t[1] = x --[[ open"), "t[1] = x  ");
+    }
+}
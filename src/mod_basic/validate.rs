@@ -0,0 +1,299 @@
+//! modDesc.xml structure checks
+//!
+//! The basic parser is forgiving, a missing `<version>` or a broken
+//! `<multiplayer>` only shows as a default value or a single issue. This
+//! pass lists each structural problem with its position in the file, so a
+//! mod author can find and fix it.
+use crate::shared::structs::{ModDescViolation, ModDescViolationKind};
+
+/// Elements every modDesc.xml needs, directly under `<modDesc>`
+const REQUIRED_ELEMENTS: [&str; 5] = ["author", "version", "title", "description", "iconFilename"];
+
+/// Elements that hold translated text, either directly or in language children
+const L10N_ELEMENTS: [&str; 2] = ["title", "description"];
+
+/// Collects violations for a single document
+struct Validator<'a, 'input> {
+    /// document being checked
+    doc: &'a roxmltree::Document<'input>,
+    /// violations found so far
+    violations: Vec<ModDescViolation>,
+}
+
+impl<'a, 'input> Validator<'a, 'input> {
+    /// Record a violation at a node
+    fn add(
+        &mut self,
+        node: roxmltree::Node,
+        kind: ModDescViolationKind,
+        path: String,
+        message: String,
+    ) {
+        let position = self.doc.text_pos_at(node.range().start);
+        self.violations.push(ModDescViolation {
+            kind,
+            path,
+            line: position.row,
+            column: position.col,
+            message,
+        });
+    }
+
+    /// Check a required attribute exists, returning its value
+    fn required_attribute(
+        &mut self,
+        node: roxmltree::Node<'a, 'input>,
+        name: &str,
+    ) -> Option<&'a str> {
+        let value = node.attribute(name);
+        if value.is_none() {
+            self.add(
+                node,
+                ModDescViolationKind::MissingAttribute,
+                format!("{}/@{name}", node_path(node)),
+                format!("<{}> needs a {name} attribute", node.tag_name().name()),
+            );
+        }
+        value
+    }
+
+    /// Check an element has text or child elements
+    fn not_empty(&mut self, node: roxmltree::Node) -> bool {
+        if node_text(node).is_empty() && !node.children().any(|n| n.is_element()) {
+            self.add(
+                node,
+                ModDescViolationKind::EmptyElement,
+                node_path(node),
+                format!("<{}> is empty", node.tag_name().name()),
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Check the required elements under `<modDesc>`
+    fn check_required(&mut self, root: roxmltree::Node) {
+        for name in REQUIRED_ELEMENTS {
+            let Some(node) = root.children().find(|n| n.has_tag_name(name)) else {
+                self.add(
+                    root,
+                    ModDescViolationKind::MissingElement,
+                    format!("modDesc/{name}"),
+                    format!("<modDesc> needs a <{name}> element"),
+                );
+                continue;
+            };
+
+            if !self.not_empty(node) {
+                continue;
+            }
+
+            if L10N_ELEMENTS.contains(&name) {
+                for language in node.children().filter(roxmltree::Node::is_element) {
+                    self.not_empty(language);
+                }
+            }
+
+            if name == "version" && !is_valid_version(node_text(node)) {
+                self.add(
+                    node,
+                    ModDescViolationKind::InvalidValue,
+                    node_path(node),
+                    format!(
+                        "<version> should be numbers separated by dots (like 1.0.0.0), found \"{}\"",
+                        node_text(node)
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Check `<multiplayer supported="true|false">`
+    fn check_multiplayer(&mut self, root: roxmltree::Node<'a, 'input>) {
+        let Some(node) = root.children().find(|n| n.has_tag_name("multiplayer")) else {
+            return;
+        };
+        let Some(value) = self.required_attribute(node, "supported") else {
+            return;
+        };
+        if value != "true" && value != "false" {
+            self.add(
+                node,
+                ModDescViolationKind::InvalidValue,
+                format!("{}/@supported", node_path(node)),
+                format!("<multiplayer> supported should be true or false, found \"{value}\""),
+            );
+        }
+    }
+}
+
+/// Check the structure of a parsed modDesc.xml
+///
+/// Violations are sorted by position in the file
+pub(crate) fn validate_mod_desc(mod_desc: &roxmltree::Document) -> Vec<ModDescViolation> {
+    let mut validator = Validator {
+        doc: mod_desc,
+        violations: vec![],
+    };
+    let root = mod_desc.root_element();
+
+    if !root.has_tag_name("modDesc") {
+        validator.add(
+            root,
+            ModDescViolationKind::WrongRoot,
+            root.tag_name().name().to_owned(),
+            format!(
+                "root element should be <modDesc>, found <{}>",
+                root.tag_name().name()
+            ),
+        );
+        return validator.violations;
+    }
+
+    if let Some(value) = validator.required_attribute(root, "descVersion") {
+        if value.parse::<u32>().is_err() {
+            validator.add(
+                root,
+                ModDescViolationKind::InvalidValue,
+                String::from("modDesc/@descVersion"),
+                format!("<modDesc> descVersion should be a number, found \"{value}\""),
+            );
+        }
+    }
+
+    validator.check_required(root);
+    validator.check_multiplayer(root);
+
+    for node in root.descendants().filter(|n| n.has_tag_name("storeItem")) {
+        validator.required_attribute(node, "xmlFilename");
+    }
+    for node in root.descendants().filter(|n| n.has_tag_name("dependency")) {
+        validator.not_empty(node);
+    }
+
+    validator
+        .violations
+        .sort_by_key(|violation| (violation.line, violation.column));
+    validator.violations
+}
+
+/// Trimmed text of an element
+fn node_text<'a>(node: roxmltree::Node<'a, '_>) -> &'a str {
+    node.text().unwrap_or("").trim()
+}
+
+/// Slash separated element path from the root to a node
+fn node_path(node: roxmltree::Node) -> String {
+    let mut names: Vec<&str> = node
+        .ancestors()
+        .filter(roxmltree::Node::is_element)
+        .map(|n| n.tag_name().name())
+        .collect();
+    names.reverse();
+    names.join("/")
+}
+
+/// Version is dot separated numbers
+fn is_valid_version(version: &str) -> bool {
+    version
+        .split('.')
+        .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn violations(xml: &str) -> Vec<(ModDescViolationKind, String, u32)> {
+        let doc = roxmltree::Document::parse(xml).unwrap();
+        validate_mod_desc(&doc)
+            .into_iter()
+            .map(|n| (n.kind, n.path, n.line))
+            .collect()
+    }
+
+    #[test]
+    fn valid_mod_desc() {
+        let xml = r#"<modDesc descVersion="92">
+            <author>Me</author>
+            <version>1.0.0.0</version>
+            <title><en>Title</en></title>
+            <description><en>Words</en></description>
+            <iconFilename>icon.dds</iconFilename>
+            <multiplayer supported="true"/>
+            <storeItems><storeItem xmlFilename="item.xml"/></storeItems>
+        </modDesc>"#;
+
+        assert_eq!(violations(xml), vec![]);
+    }
+
+    #[test]
+    fn wrong_root() {
+        assert_eq!(
+            violations("<vehicle/>"),
+            vec![(ModDescViolationKind::WrongRoot, String::from("vehicle"), 1)]
+        );
+    }
+
+    #[test]
+    fn broken_mod_desc() {
+        let xml = r#"<modDesc descVersion="new">
+            <author>Me</author>
+            <version></version>
+            <title><en></en></title>
+            <description>Words</description>
+            <multiplayer supported="yes"/>
+            <storeItems><storeItem/></storeItems>
+            <dependencies><dependency/></dependencies>
+        </modDesc>"#;
+
+        assert_eq!(
+            violations(xml),
+            vec![
+                (
+                    ModDescViolationKind::InvalidValue,
+                    String::from("modDesc/@descVersion"),
+                    1
+                ),
+                (
+                    ModDescViolationKind::MissingElement,
+                    String::from("modDesc/iconFilename"),
+                    1
+                ),
+                (
+                    ModDescViolationKind::EmptyElement,
+                    String::from("modDesc/version"),
+                    3
+                ),
+                (
+                    ModDescViolationKind::EmptyElement,
+                    String::from("modDesc/title/en"),
+                    4
+                ),
+                (
+                    ModDescViolationKind::InvalidValue,
+                    String::from("modDesc/multiplayer/@supported"),
+                    6
+                ),
+                (
+                    ModDescViolationKind::MissingAttribute,
+                    String::from("modDesc/storeItems/storeItem/@xmlFilename"),
+                    7
+                ),
+                (
+                    ModDescViolationKind::EmptyElement,
+                    String::from("modDesc/dependencies/dependency"),
+                    8
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn version_format() {
+        assert!(is_valid_version("1.0.0.0"));
+        assert!(is_valid_version("12"));
+        assert!(!is_valid_version("1.0b"));
+        assert!(!is_valid_version("1..0"));
+    }
+}
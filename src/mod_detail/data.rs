@@ -0,0 +1,281 @@
+//! Base game data for store items
+
+/// Basegame supplied brand keys, FS22 & FS25 (including DLC brands)
+///
+/// Used to spot store items that reference a brand the game does not
+/// know, which then show in the shop under LIZARD. Sorted, upper case.
+pub const BG_BRANDS: [&str; 157] = [
+    "ABI",
+    "AEBI",
+    "AGCO",
+    "AGRIO",
+    "AGRISEM",
+    "AGROMASZ",
+    "AGROSTROJ",
+    "ALBUTT",
+    "ALLIANCE",
+    "AMAZONE",
+    "AMITYTECH",
+    "ANDERSONGROUP",
+    "ANNABURGER",
+    "ANTONIOCARRARO",
+    "APV",
+    "ARCUSIN",
+    "BEDNAR",
+    "BERGMANN",
+    "BERTHOUD",
+    "BOECKMANN",
+    "BOGBALLE",
+    "BOMECH",
+    "BOURGOULT",
+    "BRANTNER",
+    "BREDAL",
+    "BRIRI",
+    "BUCHER",
+    "CASEIH",
+    "CAT",
+    "CHALLENGER",
+    "CLAAS",
+    "CONTINENTAL",
+    "DALBO",
+    "DAMCON",
+    "DEUTZFAHR",
+    "DEWULF",
+    "DUEVELSDORF",
+    "EINBOECK",
+    "ELHO",
+    "ELMERS",
+    "ERO",
+    "FAMAROL",
+    "FARESIN",
+    "FARMET",
+    "FARMTECH",
+    "FENDT",
+    "FLIEGL",
+    "FRANQUET",
+    "GESSNER",
+    "GOEWEIL",
+    "GOLDHOFER",
+    "GORENC",
+    "GREATPLAINS",
+    "GRIMME",
+    "HARDI",
+    "HAUER",
+    "HAWE",
+    "HEIZOMAT",
+    "HOLARAS",
+    "HOLMER",
+    "HORSCH",
+    "HUSQVARNA",
+    "IMPEX",
+    "INTERNATIONAL",
+    "IPONA",
+    "JCB",
+    "JENZ",
+    "JOHNDEERE",
+    "JOSKIN",
+    "KAWECO",
+    "KEMPER",
+    "KESLA",
+    "KINZE",
+    "KNOCHE",
+    "KOECKERLING",
+    "KOMATSU",
+    "KOTTE",
+    "KRAMPE",
+    "KRONE",
+    "KRPAN",
+    "KUBOTA",
+    "KUHN",
+    "KVERNELAND",
+    "LELY",
+    "LEMKEN",
+    "LINDNER",
+    "LIZARD",
+    "LODEKING",
+    "MACDON",
+    "MAGSI",
+    "MAHINDRA",
+    "MANITOU",
+    "MASSEYFERGUSON",
+    "MAZZOTTI",
+    "MCCORMACK",
+    "MCCORMICK",
+    "MERLO",
+    "METALTECH",
+    "MICHIELTRAILER",
+    "MITAS",
+    "NARDI",
+    "NEWHOLLAND",
+    "NOKIAN",
+    "NOVAG",
+    "OXBO",
+    "PFANZELT",
+    "PLANET",
+    "POETTINGER",
+    "PONSSE",
+    "PRINOTH",
+    "PRONAR",
+    "QUICKE",
+    "RABE",
+    "RIEDLER",
+    "RISUTEC",
+    "ROPA",
+    "ROTTNE",
+    "SALEK",
+    "SAMASZ",
+    "SAMPOROSENLEW",
+    "SAMSONAGRO",
+    "SCHAEFFER",
+    "SCHOUTEN",
+    "SCHUITEMAKER",
+    "SCHWARZMUELLER",
+    "SENNEBOGEN",
+    "SILOKING",
+    "SIP",
+    "STARA",
+    "STEYR",
+    "STIHL",
+    "STRAUTMANN",
+    "SUER",
+    "TATOMA",
+    "TENWINKEL",
+    "THUNDERCREEK",
+    "TMCCANCELA",
+    "TREFFLER",
+    "TRELLEBORG",
+    "UMOTOYS",
+    "UNIA",
+    "VAEDERSTAD",
+    "VALTRA",
+    "VERMEER",
+    "VERVAET",
+    "VICON",
+    "VOGELNOOT",
+    "VOLVO",
+    "WACKERNEUSON",
+    "WALKABOUT",
+    "WEIDEMANN",
+    "WELGER",
+    "WESTTECH",
+    "WIEDENMANN",
+    "WILSON",
+    "ZETOR",
+    "ZIEGLER",
+];
+
+/// Check if a brand key is supplied by the base game
+pub fn is_base_game_brand(brand_key: &str) -> bool {
+    BG_BRANDS
+        .binary_search(&brand_key.to_uppercase().as_str())
+        .is_ok()
+}
+
+/// Base game store categories, placed in the shop tree
+///
+/// Lower case category key, top level shop tab (see
+/// [`crate::shared::l10n::SHOP_TOP_LEVELS`]) and English name. Sorted by key.
+pub const SHOP_CATEGORIES: [(&str, &str, &str); 86] = [
+    ("animalpens", "placeables", "Animal Pens"),
+    ("animaltransport", "tools", "Animal Transport"),
+    ("augerwagons", "tools", "Auger Wagons"),
+    ("baleloaders", "tools", "Bale Loaders"),
+    ("balers", "tools", "Balers"),
+    ("balewrappers", "tools", "Bale Wrappers"),
+    ("beehives", "placeables", "Beehives"),
+    ("beetharvesting", "vehicles", "Beet Harvesting"),
+    ("bigbags", "objects", "Big Bags"),
+    ("cars", "vehicles", "Cars"),
+    ("cornheaders", "tools", "Corn Headers"),
+    ("cottonharvesting", "vehicles", "Cotton Harvesting"),
+    ("cultivators", "tools", "Cultivators"),
+    ("cutters", "tools", "Headers"),
+    ("cuttertrailers", "tools", "Header Trailers"),
+    ("decoration", "placeables", "Decoration"),
+    ("discharrows", "tools", "Disc Harrows"),
+    ("dollys", "tools", "Dollies"),
+    ("farmhouses", "placeables", "Farmhouses"),
+    ("fences", "placeables", "Fences"),
+    ("fertilizerspreaders", "tools", "Fertilizer Spreaders"),
+    (
+        "forageharvestercutters",
+        "tools",
+        "Forage Harvester Headers",
+    ),
+    ("forageharvesters", "vehicles", "Forage Harvesters"),
+    ("foragewagons", "tools", "Forage Wagons"),
+    ("forestryexcavators", "vehicles", "Forestry Excavators"),
+    ("forestryforwarders", "vehicles", "Forwarders"),
+    ("forestryharvesters", "vehicles", "Forestry Harvesters"),
+    ("forklifts", "vehicles", "Forklifts"),
+    ("frontloaders", "tools", "Front Loaders"),
+    ("frontloadertools", "tools", "Front Loader Tools"),
+    ("frontloadervehicles", "vehicles", "Front Loader Vehicles"),
+    ("grapeharvesting", "vehicles", "Grape Harvesting"),
+    ("greenhouses", "placeables", "Greenhouses"),
+    ("handtools", "objects", "Hand Tools"),
+    ("harvesters", "vehicles", "Harvesters"),
+    ("leveler", "tools", "Levelers"),
+    ("lights", "placeables", "Lights"),
+    ("loaderwagons", "tools", "Loader Wagons"),
+    ("lowloaders", "tools", "Low Loaders"),
+    ("manurespreaders", "tools", "Manure Spreaders"),
+    ("misc", "tools", "Miscellaneous"),
+    ("mixerwagons", "tools", "Mixer Wagons"),
+    ("mowers", "tools", "Mowers"),
+    ("mowervehicles", "vehicles", "Mower Vehicles"),
+    ("mulchers", "tools", "Mulchers"),
+    ("objectmisc", "objects", "Miscellaneous Objects"),
+    ("oliveharvesting", "vehicles", "Olive Harvesting"),
+    ("pallets", "objects", "Pallets"),
+    ("placeablemisc", "placeables", "Miscellaneous Placeables"),
+    ("planters", "tools", "Planters"),
+    ("plows", "tools", "Plows"),
+    ("potatoharvesting", "vehicles", "Potato Harvesting"),
+    ("powerharrows", "tools", "Power Harrows"),
+    ("productionpoints", "placeables", "Production Points"),
+    ("riceharvesting", "vehicles", "Rice Harvesting"),
+    ("rollers", "tools", "Rollers"),
+    ("seeders", "tools", "Seeders"),
+    ("sellingpoints", "placeables", "Selling Points"),
+    ("sheds", "placeables", "Sheds"),
+    ("silocompaction", "tools", "Silo Compaction"),
+    ("silos", "placeables", "Silos"),
+    ("skidsteertools", "tools", "Skid Steer Tools"),
+    ("skidsteervehicles", "vehicles", "Skid Steers"),
+    ("slurrytanks", "tools", "Slurry Tanks"),
+    ("spaders", "tools", "Spaders"),
+    ("sprayers", "tools", "Sprayers"),
+    ("stonepickers", "tools", "Stone Pickers"),
+    ("stumpcutters", "tools", "Stump Cutters"),
+    ("subsoilers", "tools", "Subsoilers"),
+    ("sugarcaneharvesting", "vehicles", "Sugarcane Harvesting"),
+    ("tedders", "tools", "Tedders"),
+    ("teleloadertools", "tools", "Telehandler Tools"),
+    ("teleloadervehicles", "vehicles", "Telehandlers"),
+    ("tractorsl", "vehicles", "Large Tractors"),
+    ("tractorsm", "vehicles", "Medium Tractors"),
+    ("tractorss", "vehicles", "Small Tractors"),
+    ("trailers", "tools", "Trailers"),
+    ("trucks", "vehicles", "Trucks"),
+    ("vegetableharvesting", "vehicles", "Vegetable Harvesting"),
+    ("weeders", "tools", "Weeders"),
+    ("weights", "tools", "Weights"),
+    ("wheelloadertools", "tools", "Wheel Loader Tools"),
+    ("wheelloadervehicles", "vehicles", "Wheel Loaders"),
+    ("winches", "tools", "Winches"),
+    ("windrowers", "tools", "Windrowers"),
+    ("woodtransport", "tools", "Wood Transport"),
+];
+
+/// Find a store category in the shop tree, case is ignored
+///
+/// Only the first of a space separated list of categories is used.
+/// Returns the lower case key, top level shop tab and English name.
+pub fn shop_category(category: &str) -> Option<(&'static str, &'static str, &'static str)> {
+    let key = category.split_whitespace().next()?.to_lowercase();
+    SHOP_CATEGORIES
+        .binary_search_by(|(this_key, _, _)| this_key.cmp(&key.as_str()))
+        .ok()
+        .map(|index| SHOP_CATEGORIES[index])
+}
@@ -0,0 +1,137 @@
+//! Parse animal food groups and mixtures (TMR, pig food, etc.)
+use crate::mod_detail::structs::{
+    AnimalFoodGroup, AnimalFoodIngredient, AnimalFoodMixture, ModDetailAnimalFood,
+};
+
+/// Parse an animalFood.xml file
+///
+/// # Sample output:
+/// ```json
+/// {
+///    "animals": {
+///        "COW": [
+///            {
+///                "eatWeight": 1.0,
+///                "fillTypes": [ "forage" ],
+///                "productionWeight": 1.0,
+///                "title": "$l10n_fillType_forage"
+///            }
+///        ]
+///    },
+///    "mixtures": [
+///        {
+///            "animalType": "COW",
+///            "fillType": "forage",
+///            "ingredients": [
+///                { "fillTypes": [ "silage" ], "weight": 0.5 },
+///                { "fillTypes": [ "drygrass_windrow", "grass_windrow" ], "weight": 0.25 }
+///            ]
+///        }
+///    ]
+/// }
+/// ```
+pub fn food_parse(xml_tree: &roxmltree::Document, animal_food: &mut ModDetailAnimalFood) {
+    for animal in xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("animal") && n.has_attribute("animalType"))
+    {
+        let animal_type = animal.attribute("animalType").unwrap_or("").to_owned();
+        let food_groups = animal_food.animals.entry(animal_type).or_default();
+
+        for group in animal.children().filter(|n| n.has_tag_name("foodGroup")) {
+            food_groups.push(AnimalFoodGroup {
+                eat_weight: float_attribute(&group, "eatWeight"),
+                fill_types: fill_type_list(group.attribute("fillTypes")),
+                production_weight: float_attribute(&group, "productionWeight"),
+                title: group.attribute("title").unwrap_or("--").to_owned(),
+            });
+        }
+    }
+
+    for mixture in xml_tree.descendants().filter(|n| n.has_tag_name("mixture")) {
+        let Some(fill_type) = mixture.attribute("fillType") else {
+            continue;
+        };
+
+        animal_food.mixtures.push(AnimalFoodMixture {
+            animal_type: mixture.attribute("animalType").map(str::to_owned),
+            fill_type: fill_type.to_lowercase(),
+            ingredients: mixture
+                .children()
+                .filter(|n| n.has_tag_name("ingredient"))
+                .map(|n| AnimalFoodIngredient {
+                    fill_types: fill_type_list(n.attribute("fillTypes")),
+                    weight: float_attribute(&n, "weight"),
+                })
+                .collect(),
+        });
+    }
+}
+
+/// Split a space separated fill type attribute into a lowercase list
+fn fill_type_list(attribute: Option<&str>) -> Vec<String> {
+    attribute
+        .unwrap_or("")
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Get a float attribute, defaulting to 1.0
+fn float_attribute(node: &roxmltree::Node, key: &str) -> f32 {
+    node.attribute(key)
+        .and_then(|n| n.parse::<f32>().ok())
+        .unwrap_or(1_f32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use assert_json_diff::assert_json_include;
+    use serde_json::json;
+
+    #[test]
+    fn cow_food_and_mixture() {
+        /* cSpell: disable */
+        let minimum_xml = r#"<animalFood>
+            <animals>
+                <animal animalType="COW" consumptionType="PARALLEL">
+                    <foodGroup title="$l10n_fillType_forage" productionWeight="1.0" eatWeight="1.0" fillTypes="FORAGE" />
+                    <foodGroup title="$l10n_fillType_grass" productionWeight="0.5" eatWeight="0.5" fillTypes="GRASS_WINDROW DRYGRASS_WINDROW" />
+                </animal>
+            </animals>
+            <mixtures>
+                <mixture fillType="FORAGE" animalType="COW">
+                    <ingredient weight="0.5" fillTypes="SILAGE" />
+                    <ingredient weight="0.5" fillTypes="STRAW HAY" />
+                </mixture>
+            </mixtures>
+            </animalFood>"#;
+        /* cSpell: enable */
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut animal_food = ModDetailAnimalFood::default();
+
+        food_parse(&minimum_doc, &mut animal_food);
+
+        let actual = json!(animal_food);
+        let expected = json!({
+            "animals": {
+                "COW": [
+                    { "eatWeight": 1.0, "fillTypes": [ "forage" ], "productionWeight": 1.0 },
+                    { "eatWeight": 0.5, "fillTypes": [ "grass_windrow", "drygrass_windrow" ], "productionWeight": 0.5 }
+                ]
+            },
+            "mixtures": [
+                {
+                    "animalType": "COW",
+                    "fillType": "forage",
+                    "ingredients": [
+                        { "fillTypes": [ "silage" ], "weight": 0.5 },
+                        { "fillTypes": [ "straw", "hay" ], "weight": 0.5 }
+                    ]
+                }
+            ]
+        });
+        assert_json_include!(actual : actual, expected : expected);
+    }
+}
@@ -1,10 +1,26 @@
 //! Parse mod storeItems, l10n additions and brands
-use crate::mod_detail::structs::{ModDetail, ModDetailError};
-use crate::shared::files::{AbstractFileHandle, AbstractFolder, AbstractZipFile, FileDefinition};
-use crate::shared::{convert_mod_icon, normalize_image_file};
+use crate::mod_basic::{read_actions, read_key_binds};
+use crate::mod_detail::structs::{
+    ModDetail, ModDetailAnimalFood, ModDetailBind, ModDetailDiagnostics, ModDetailError,
+    ModDetailItemCount, ModDetailItemTiming, ModDetailObject, ModDetailOther, ModDetailPlace,
+    ModDetailPlan, ModDetailPlanCost, ModDetailVehicle,
+};
+use crate::shared::errors::FsModParserError;
+use crate::shared::files::{
+    external_references, AbstractFileHandle, AbstractFolder, AbstractZipFile, FileDefinition,
+};
+use crate::shared::l10n::{base_action_label, input_label, LANGUAGES};
+use crate::shared::xml::tolerant_xml;
+use crate::shared::{convert_mod_icon, normalize_image_file, ImageFile};
 use crate::ModParserOptions;
+use std::collections::{BTreeMap, HashSet};
+use std::panic::AssertUnwindSafe;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
+mod data;
+pub mod food;
+pub mod other;
 pub mod places;
 pub mod structs;
 pub mod vehicles;
@@ -13,49 +29,99 @@ pub mod vehicles;
 ///
 /// - store items
 /// - l10n additions
-/// - brand additions
+/// - brand additions and brand usage
+/// - animal food groups and mixtures
 ///
 /// This returns (optionally) a JSON object that looks like:
 /// ```json
 /// {
+///     "actionsMissingL10n" : [],
+///     "animalFood" : null,
+///     "bindsReadable" : {
+///         "ACTION_NAME" : {
+///             "category" : "VEHICLE",
+///             "inputs" : ["Left Shift + B"],
+///             "titles" : { "en" : "Action Title" }
+///         }
+///     },
 ///     "brands" : [],
+///     "brandUsage" : { "BRAND_KEY" : 1 },
+///     "diagnostics" : null,
 ///     "l10n" : {
 ///         "langCode" : {
 ///             "key" : "Translated String"
 ///         }
 ///     },
 ///     "issues": [],
+///     "itemOrder": [],
+///     "others": [],
 ///     "placeables": [],
+///     "placeTotals": {
+///         "incomePerHour": 0,
+///         "productionCostPerHour": 0.0,
+///         "productions": 0,
+///         "siloCapacity": 0
+///     },
+///     "sharedI3d": {},
+///     "unknownBrandItems": [],
 ///     "vehicles": [],
 /// }
 /// ```
 ///
-/// See also [`crate::mod_detail::places::place_parse`] and [`crate::mod_detail::vehicles::vehicle_parse`]
+/// See also [`crate::mod_detail::places::place_parse`], [`crate::mod_detail::vehicles::vehicle_parse`],
+/// and [`crate::mod_detail::other::other_parse`]
 pub fn parser<P: AsRef<Path>>(full_path: P) -> ModDetail {
     parser_with_options(full_path, &ModParserOptions::default())
 }
 
 /// Parse mod detail with options
 pub fn parser_with_options<P: AsRef<Path>>(full_path: P, options: &ModParserOptions) -> ModDetail {
-    let is_folder = full_path.as_ref().is_dir();
-
-    let mut abstract_file: Box<dyn AbstractFileHandle> = if is_folder {
-        if let Ok(archive) = AbstractFolder::new(full_path) {
-            Box::new(archive)
-        } else {
-            return ModDetail::fast_fail(ModDetailError::FileReadFail);
-        }
-    } else if let Ok(archive) = AbstractZipFile::new(full_path) {
-        Box::new(archive)
-    } else {
+    let Some(abstract_file) = open_mod_file(full_path, options) else {
         return ModDetail::fast_fail(ModDetailError::FileReadFail);
     };
+    parse_mod_desc(abstract_file, options)
+}
 
+/// [`crate::mod_detail::parser`], returning an error when the input can't be read
+///
+/// # Errors
+///
+/// Returns a [`FsModParserError`] when the path is missing, can't be read, or
+/// is not a readable zip file or folder
+pub fn try_parser<P: AsRef<Path>>(full_path: P) -> Result<ModDetail, FsModParserError> {
+    try_parser_with_options(full_path, &ModParserOptions::default())
+}
+
+/// [`crate::mod_detail::parser_with_options`], returning an error when the input can't be read
+///
+/// # Errors
+///
+/// Returns a [`FsModParserError`] when the path is missing, can't be read, or
+/// is not a readable zip file or folder
+pub fn try_parser_with_options<P: AsRef<Path>>(
+    full_path: P,
+    options: &ModParserOptions,
+) -> Result<ModDetail, FsModParserError> {
+    let path = full_path.as_ref();
+    FsModParserError::check_path(path)?;
+
+    let Some(abstract_file) = open_mod_file(path, options) else {
+        return Err(FsModParserError::Unreadable(path.to_path_buf()));
+    };
+    Ok(parse_mod_desc(abstract_file, options))
+}
+
+/// Read modDesc.xml from an open mod, then parse the detail
+fn parse_mod_desc(
+    mut abstract_file: Box<dyn AbstractFileHandle>,
+    options: &ModParserOptions,
+) -> ModDetail {
     let abstract_file_list = abstract_file.list();
 
-    let Ok(mod_desc_content) = abstract_file.as_text("modDesc.xml") else {
+    let Ok(mod_desc_bytes) = abstract_file.as_bin("modDesc.xml") else {
         return ModDetail::fast_fail(ModDetailError::NotModModDesc);
     };
+    let (mod_desc_content, _) = tolerant_xml(mod_desc_bytes);
 
     let Ok(mod_desc_doc) = roxmltree::Document::parse(&mod_desc_content) else {
         return ModDetail::fast_fail(ModDetailError::NotModModDesc);
@@ -64,6 +130,102 @@ pub fn parser_with_options<P: AsRef<Path>>(full_path: P, options: &ModParserOpti
     parse_open_file(abstract_file, &mod_desc_doc, &abstract_file_list, options)
 }
 
+/// Open a mod zip file or folder with the configured read limits
+fn open_mod_file<P: AsRef<Path>>(
+    full_path: P,
+    options: &ModParserOptions,
+) -> Option<Box<dyn AbstractFileHandle>> {
+    let mut abstract_file: Box<dyn AbstractFileHandle> = if full_path.as_ref().is_dir() {
+        Box::new(AbstractFolder::new(full_path).ok()?)
+    } else {
+        Box::new(AbstractZipFile::new(full_path).ok()?)
+    };
+    abstract_file.set_limits(options.read_limits);
+    abstract_file.set_case_fallback(true);
+    Some(abstract_file)
+}
+
+/// Plan a detail parse, default options
+///
+/// See [`plan_with_options`]
+#[must_use]
+pub fn plan<P: AsRef<Path>>(full_path: P) -> ModDetailPlan {
+    plan_with_options(full_path, &ModParserOptions::default())
+}
+
+/// Plan a detail parse without doing it
+///
+/// Only modDesc.xml and the file list are read, so this is cheap even
+/// for a large pack. Store items are counted (and checked to exist),
+/// along with the icons [`parser_with_options`] would convert, letting
+/// a caller decide if the full parse is worth the wait.
+///
+/// ```json
+/// {
+///     "brands": 0,
+///     "cost": "LIGHT",
+///     "icons": 0,
+///     "issues": [],
+///     "missingStoreItems": 0,
+///     "storeItems": 0,
+///     "storeItemBytes": 0
+/// }
+/// ```
+#[must_use]
+pub fn plan_with_options<P: AsRef<Path>>(
+    full_path: P,
+    options: &ModParserOptions,
+) -> ModDetailPlan {
+    let Some(mut abstract_file) = open_mod_file(full_path, options) else {
+        return ModDetailPlan::fast_fail(ModDetailError::FileReadFail);
+    };
+    let abstract_file_list = abstract_file.list();
+
+    let Ok(mod_desc_bytes) = abstract_file.as_bin("modDesc.xml") else {
+        return ModDetailPlan::fast_fail(ModDetailError::NotModModDesc);
+    };
+    let (mod_desc_content, _) = tolerant_xml(mod_desc_bytes);
+    let Ok(mod_desc_doc) = roxmltree::Document::parse(&mod_desc_content) else {
+        return ModDetailPlan::fast_fail(ModDetailError::NotModModDesc);
+    };
+
+    let mut mod_plan = ModDetailPlan::default();
+
+    for file_name in mod_desc_doc
+        .descendants()
+        .filter(|n| n.has_tag_name("storeItem"))
+        .filter_map(|n| n.attribute("xmlFilename"))
+        .map(|n| n.replace('\\', "/"))
+    {
+        mod_plan.store_items += 1;
+        if let Some(file) = abstract_file_list.iter().find(|n| n.name == file_name) {
+            mod_plan.store_item_bytes += file.size;
+        } else {
+            mod_plan.missing_store_items += 1;
+            mod_plan.issues.insert(ModDetailError::StoreItemMissing);
+        }
+    }
+
+    let brand_images: Vec<ImageFile> = mod_desc_doc
+        .descendants()
+        .filter(|n| n.has_tag_name("brand") && n.parent().is_some_and(|p| p.has_tag_name("brands")))
+        .map(|n| normalize_image_file(n.attribute("image")))
+        .collect();
+
+    mod_plan.brands = brand_images.len();
+
+    if !options.skip_detail_icons {
+        mod_plan.icons = mod_plan.store_items - mod_plan.missing_store_items
+            + brand_images
+                .iter()
+                .filter(|n| n.local_file.is_some())
+                .count();
+    }
+
+    mod_plan.cost = ModDetailPlanCost::classify(mod_plan.store_items, mod_plan.icons);
+    mod_plan
+}
+
 /// Parse mod details with an open [`AbstractFileHandle`]
 #[must_use]
 pub fn parse_open_file(
@@ -74,61 +236,312 @@ pub fn parse_open_file(
 ) -> ModDetail {
     let mut mod_detail = ModDetail::default();
 
-    do_languages(
+    let all_l10n_keys = do_languages(
         &mut mod_detail,
         &mut abstract_file,
         mod_desc_doc,
         abstract_file_list,
+        options,
     );
     do_brands(&mut mod_detail, &mut abstract_file, mod_desc_doc, options);
+    do_animal_food(&mut mod_detail, &mut abstract_file, abstract_file_list);
+    do_action_l10n(&mut mod_detail, mod_desc_doc, &all_l10n_keys);
+    do_binds_readable(&mut mod_detail, mod_desc_doc, options);
 
-    for store_item in mod_desc_doc
+    let item_files: Vec<&str> = mod_desc_doc
         .descendants()
         .filter(|n| n.has_tag_name("storeItem"))
-    {
-        if let Some(file_name) = store_item.attribute("xmlFilename") {
-            let Ok(file_content) = abstract_file.as_text(&file_name.to_owned().replace('\\', "/"))
-            else {
-                mod_detail.add_issue(ModDetailError::StoreItemMissing);
-                continue;
-            };
-            let Ok(file_tree) = roxmltree::Document::parse(&file_content) else {
-                mod_detail.add_issue(ModDetailError::StoreItemBroken);
-                continue;
-            };
+        .filter_map(|n| n.attribute("xmlFilename"))
+        .collect();
 
-            if file_tree.root_element().has_tag_name("vehicle") {
-                mod_detail.vehicles.insert(
-                    file_name.to_owned(),
-                    vehicles::vehicle_parse(&file_tree, &mut abstract_file, options),
-                );
-            } else if file_tree.root_element().has_tag_name("placeable") {
-                mod_detail.placeables.insert(
-                    file_name.to_owned(),
-                    places::place_parse(&file_tree, &mut abstract_file, options),
-                );
+    let (parsed_items, worker_limit_hit, worker_fallbacks) = match abstract_file.source_path() {
+        Some(source) if options.detail_threads > 1 && item_files.len() > 1 => {
+            parse_store_items_threaded(&item_files, &source, abstract_file.is_folder(), options)
+        }
+        _ => (
+            item_files
+                .iter()
+                .map(|file_name| parse_store_item_isolated(file_name, &mut abstract_file, options))
+                .collect(),
+            false,
+            BTreeMap::new(),
+        ),
+    };
+
+    let mut i3d_users: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut timings: Vec<(&str, Duration)> = vec![];
+    let mut panicked_items: Vec<String> = vec![];
+
+    for (file_name, (parsed_item, elapsed)) in item_files.into_iter().zip(parsed_items) {
+        timings.push((file_name, elapsed));
+        let store_item = match parsed_item {
+            Ok((store_item, external)) => {
+                do_external_references(&mut mod_detail, file_name, external);
+                store_item
             }
+            Err(e) => {
+                if e == ModDetailError::StoreItemPanic {
+                    panicked_items.push(file_name.to_owned());
+                }
+                mod_detail.add_issue(e);
+                continue;
+            }
+        };
+
+        if let Some(i3d_file) = store_item.i3d_file() {
+            i3d_users
+                .entry(i3d_file.clone())
+                .or_default()
+                .push(file_name.to_owned());
+        }
 
-            for found_item in &mod_detail.vehicles {
-                if let Some(value) = found_item.1.sorting.brand.clone() {
+        match store_item {
+            StoreItem::Vehicle(this_vehicle) => {
+                if let Some(value) = this_vehicle.sorting.brand.clone() {
                     mod_detail.item_brands.insert(value);
                 }
-                if let Some(value) = found_item.1.sorting.category.clone() {
+                if let Some(value) = this_vehicle.sorting.category.clone() {
                     mod_detail.item_categories.insert(value);
                 }
+                mod_detail
+                    .vehicles
+                    .insert(file_name.to_owned(), *this_vehicle);
             }
-
-            for found_item in &mod_detail.placeables {
-                if let Some(value) = found_item.1.sorting.category.clone() {
+            StoreItem::Place(this_place) => {
+                if let Some(value) = this_place.sorting.category.clone() {
                     mod_detail.item_categories.insert(value);
                 }
+                mod_detail
+                    .placeables
+                    .insert(file_name.to_owned(), *this_place);
+            }
+            StoreItem::Other(this_other) => {
+                if let Some(value) = this_other.category.clone() {
+                    mod_detail.item_categories.insert(value);
+                }
+                mod_detail.others.insert(file_name.to_owned(), *this_other);
             }
         }
+        mod_detail.item_order.push(file_name.to_owned());
     }
 
+    if options.detail_diagnostics {
+        mod_detail.diagnostics = Some(item_diagnostics(timings, panicked_items));
+    }
+
+    do_item_count(&mut mod_detail, mod_desc_doc);
+
+    vehicles::vehicle_resolve_combos(&mut mod_detail.vehicles);
+    do_brand_usage(&mut mod_detail);
+    do_place_totals(&mut mod_detail);
+
+    mod_detail.shared_i3d = i3d_users
+        .into_iter()
+        .filter(|(_, items)| items.len() > 1)
+        .collect();
+
+    if worker_limit_hit || abstract_file.limit_exceeded() {
+        mod_detail.add_issue(ModDetailError::FileReadLimit);
+    }
+    mod_detail.case_fallbacks = abstract_file.case_fallbacks();
+    mod_detail.case_fallbacks.extend(worker_fallbacks);
     mod_detail
 }
 
+/// Record store item references that point outside of the mod
+fn do_external_references(mod_detail: &mut ModDetail, file_name: &str, external: Vec<String>) {
+    if !external.is_empty() {
+        mod_detail.add_issue(ModDetailError::StoreItemExternalReference);
+        mod_detail
+            .external_references
+            .insert(file_name.to_owned(), external);
+    }
+}
+
+/// Compare the declared storeItem count with the items parsed
+fn do_item_count(mod_detail: &mut ModDetail, mod_desc_doc: &roxmltree::Document) {
+    mod_detail.item_count = ModDetailItemCount {
+        declared: mod_desc_doc
+            .descendants()
+            .filter(|n| n.has_tag_name("storeItem"))
+            .count(),
+        parsed: mod_detail.item_order.len(),
+    };
+    if mod_detail.item_count.declared != mod_detail.item_count.parsed {
+        mod_detail.add_issue(ModDetailError::StoreItemCountMismatch);
+    }
+}
+
+/// A parsed store item, by type
+enum StoreItem {
+    /// vehicle item
+    Vehicle(Box<ModDetailVehicle>),
+    /// placeable item
+    Place(Box<ModDetailPlace>),
+    /// any other item
+    Other(Box<ModDetailOther>),
+}
+
+impl StoreItem {
+    /// i3d asset referenced by the item
+    fn i3d_file(&self) -> Option<&String> {
+        match self {
+            StoreItem::Vehicle(item) => item.i3d_file.as_ref(),
+            StoreItem::Place(item) => item.i3d_file.as_ref(),
+            StoreItem::Other(item) => item.i3d_file.as_ref(),
+        }
+    }
+}
+
+/// Number of store items listed in [`ModDetailDiagnostics::slowest_items`]
+const SLOWEST_ITEMS: usize = 10;
+
+/// Build the diagnostics block from per item timings
+fn item_diagnostics(
+    mut timings: Vec<(&str, Duration)>,
+    panicked_items: Vec<String>,
+) -> ModDetailDiagnostics {
+    let total: Duration = timings.iter().map(|(_, elapsed)| *elapsed).sum();
+    let items_parsed = timings.len();
+
+    timings.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    timings.truncate(SLOWEST_ITEMS);
+
+    ModDetailDiagnostics {
+        items_parsed,
+        panicked_items,
+        slowest_items: timings
+            .into_iter()
+            .map(|(file, elapsed)| ModDetailItemTiming {
+                file: file.to_owned(),
+                micros: u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX),
+            })
+            .collect(),
+        total_micros: u64::try_from(total.as_micros()).unwrap_or(u64::MAX),
+    }
+}
+
+/// Store item parse result (with external references) and time taken
+type ItemResult = (Result<(StoreItem, Vec<String>), ModDetailError>, Duration);
+
+/// Parse a single store item, timed
+///
+/// A panic while parsing is caught and reported as
+/// [`ModDetailError::StoreItemPanic`], so one bad item doesn't abort the rest
+fn parse_store_item_isolated(
+    file_name: &str,
+    abstract_file: &mut Box<dyn AbstractFileHandle>,
+    options: &ModParserOptions,
+) -> ItemResult {
+    let start_time = Instant::now();
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        parse_store_item(file_name, abstract_file, options)
+    }))
+    .unwrap_or(Err(ModDetailError::StoreItemPanic));
+
+    (result, start_time.elapsed())
+}
+
+/// Read and parse a single store item XML
+///
+/// Also returns references in the XML that point outside of the mod, if
+/// [`ModParserOptions::external_references`] is set
+fn parse_store_item(
+    file_name: &str,
+    abstract_file: &mut Box<dyn AbstractFileHandle>,
+    options: &ModParserOptions,
+) -> Result<(StoreItem, Vec<String>), ModDetailError> {
+    let Ok(file_content) = abstract_file.as_text(&file_name.replace('\\', "/")) else {
+        return Err(ModDetailError::StoreItemMissing);
+    };
+    let Ok(file_tree) = roxmltree::Document::parse(&file_content) else {
+        return Err(ModDetailError::StoreItemBroken);
+    };
+
+    let i3d_file = xml_extract_i3d_file(&file_tree);
+    let external = if options.external_references {
+        external_references(&file_tree, "")
+    } else {
+        vec![]
+    };
+
+    let store_item = if file_tree.root_element().has_tag_name("vehicle") {
+        let mut this_vehicle = vehicles::vehicle_parse(&file_tree, abstract_file, options);
+        this_vehicle.i3d_file = i3d_file;
+        StoreItem::Vehicle(Box::new(this_vehicle))
+    } else if file_tree.root_element().has_tag_name("placeable") {
+        let mut this_place = places::place_parse(&file_tree, abstract_file, options);
+        this_place.i3d_file = i3d_file;
+        StoreItem::Place(Box::new(this_place))
+    } else {
+        let mut this_other = other::other_parse(&file_tree, abstract_file, options);
+        this_other.i3d_file = i3d_file;
+        StoreItem::Other(Box::new(this_other))
+    };
+    Ok((store_item, external))
+}
+
+/// Parse store items on worker threads, each with its own file handle
+///
+/// Results are in the same order as `item_files`. Also returns if any
+/// worker hit the read limits, and the files the workers only found by
+/// ignoring case.
+fn parse_store_items_threaded(
+    item_files: &[&str],
+    source: &Path,
+    is_folder: bool,
+    options: &ModParserOptions,
+) -> (Vec<ItemResult>, bool, BTreeMap<String, String>) {
+    let chunk_size = item_files.len().div_ceil(options.detail_threads);
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = item_files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let worker = scope.spawn(move || {
+                    let mut abstract_file: Box<dyn AbstractFileHandle> = if is_folder {
+                        Box::new(AbstractFolder::new(source).ok()?)
+                    } else {
+                        Box::new(AbstractZipFile::new(source).ok()?)
+                    };
+                    abstract_file.set_limits(options.read_limits);
+                    abstract_file.set_case_fallback(true);
+
+                    let results: Vec<ItemResult> = chunk
+                        .iter()
+                        .map(|file_name| {
+                            parse_store_item_isolated(file_name, &mut abstract_file, options)
+                        })
+                        .collect();
+                    Some((
+                        results,
+                        abstract_file.limit_exceeded(),
+                        abstract_file.case_fallbacks(),
+                    ))
+                });
+                (chunk.len(), worker)
+            })
+            .collect();
+
+        let mut parsed_items = Vec::with_capacity(item_files.len());
+        let mut limit_hit = false;
+        let mut case_fallbacks = BTreeMap::new();
+
+        for (chunk_length, worker) in workers {
+            if let Ok(Some((results, worker_limit_hit, worker_fallbacks))) = worker.join() {
+                parsed_items.extend(results);
+                limit_hit |= worker_limit_hit;
+                case_fallbacks.extend(worker_fallbacks);
+            } else {
+                parsed_items.extend(
+                    (0..chunk_length).map(|_| (Err(ModDetailError::FileReadFail), Duration::ZERO)),
+                );
+            }
+        }
+        (parsed_items, limit_hit, case_fallbacks)
+    })
+}
+
 /// Parse added brands
 fn do_brands(
     mod_detail: &mut ModDetail,
@@ -168,6 +581,50 @@ fn do_brands(
     }
 }
 
+/// Count vehicle brands and flag brands nobody supplies
+///
+/// A brand KEY that is neither base game nor added in `<brands>` makes
+/// the item show under LIZARD in the shop. Run after [`do_brands`] and
+/// the store items are parsed.
+fn do_brand_usage(mod_detail: &mut ModDetail) {
+    for (item_key, vehicle) in &mod_detail.vehicles {
+        let Some(brand_key) = &vehicle.sorting.brand else {
+            continue;
+        };
+        let brand_key = brand_key.to_uppercase();
+
+        if !data::is_base_game_brand(&brand_key) && !mod_detail.brands.contains_key(&brand_key) {
+            mod_detail.unknown_brand_items.push(item_key.clone());
+        }
+        *mod_detail.brand_usage.entry(brand_key).or_default() += 1;
+    }
+
+    if !mod_detail.unknown_brand_items.is_empty() {
+        mod_detail.unknown_brand_items.sort();
+        mod_detail.add_issue(ModDetailError::BrandUnknown);
+    }
+}
+
+/// Sum income, production costs and silo capacity over all placeables
+fn do_place_totals(mod_detail: &mut ModDetail) {
+    let totals = &mut mod_detail.place_totals;
+
+    for place in mod_detail.placeables.values() {
+        totals.income_per_hour = totals
+            .income_per_hour
+            .saturating_add(place.sorting.income_per_hour);
+        totals.silo_capacity = totals
+            .silo_capacity
+            .saturating_add(place.storage.silo_capacity);
+        totals.productions += place.productions.len();
+        totals.production_cost_per_hour += place
+            .productions
+            .iter()
+            .map(|production| production.cost_per_hour)
+            .sum::<f32>();
+    }
+}
+
 /// Parse added L10N keys and strings
 ///
 /// Covers :
@@ -182,18 +639,23 @@ fn do_brands(
 ///  *OR*
 /// `<e k="key" v="value"/>`
 ///
+/// Returns the (lower case) keys found in *any* language, ignoring the
+/// language filter in [`ModParserOptions::languages`]
 fn do_languages(
     mod_detail: &mut ModDetail,
     file_handle: &mut Box<dyn AbstractFileHandle>,
     mod_desc_doc: &roxmltree::Document,
     file_list: &[FileDefinition],
-) {
+    options: &ModParserOptions,
+) -> HashSet<String> {
+    let mut all_keys: HashSet<String> = HashSet::new();
+
     // <l10n filenamePrefix="languages/l10n" />
     //   *OR*
     // <l10n><text name="key"><en>value</en></text></l10n>
 
     let Some(lang_key) = mod_desc_doc.descendants().find(|n| n.has_tag_name("l10n")) else {
-        return;
+        return all_keys;
     };
 
     if lang_key.has_children() {
@@ -202,10 +664,15 @@ fn do_languages(
                 continue;
             };
             lang_entry.children().for_each(|n| {
-                if n.tag_name().name() != "" {
-                    if let Some(l10n_value) = n.text() {
-                        mod_detail.add_lang(n.tag_name().name(), l10n_key, l10n_value);
-                    }
+                if n.tag_name().name() == "" {
+                    return;
+                }
+                let Some(l10n_value) = n.text() else {
+                    return;
+                };
+                all_keys.insert(l10n_key.to_lowercase());
+                if options.wants_language(n.tag_name().name()) {
+                    mod_detail.add_lang(n.tag_name().name(), l10n_key, l10n_value);
                 }
             });
         }
@@ -213,14 +680,15 @@ fn do_languages(
 
     if let Some(prefix) = lang_key.attribute("filenamePrefix") {
         for file_to_scan in file_list.iter().filter(|n| n.name.starts_with(prefix)) {
+            let lang_code =
+                &file_to_scan.name[file_to_scan.name.len() - 6..file_to_scan.name.len() - 4];
+            let wanted = options.wants_language(lang_code);
             let Ok(l10n_contents) = file_handle.as_text(&file_to_scan.name) else {
                 continue;
             };
             let Ok(l10n_tree) = roxmltree::Document::parse(&l10n_contents) else {
                 continue;
             };
-            let lang_code =
-                &file_to_scan.name[file_to_scan.name.len() - 6..file_to_scan.name.len() - 4];
 
             //<text name="key" text="value" /> style
             for entry in l10n_tree.descendants().filter(|n| n.has_tag_name("text")) {
@@ -230,7 +698,10 @@ fn do_languages(
                 let Some(l10n_value) = entry.attribute("text") else {
                     continue;
                 };
-                mod_detail.add_lang(lang_code, l10n_key, l10n_value);
+                all_keys.insert(l10n_key.to_lowercase());
+                if wanted {
+                    mod_detail.add_lang(lang_code, l10n_key, l10n_value);
+                }
             }
 
             // <e k="key" v="value"/> style
@@ -241,10 +712,124 @@ fn do_languages(
                 let Some(l10n_value) = entry.attribute("v") else {
                     continue;
                 };
-                mod_detail.add_lang(lang_code, l10n_key, l10n_value);
+                all_keys.insert(l10n_key.to_lowercase());
+                if wanted {
+                    mod_detail.add_lang(lang_code, l10n_key, l10n_value);
+                }
             }
         }
     }
+    all_keys
+}
+
+/// Check that each declared action has an `input_` l10n entry
+///
+/// Actions without one show as blank lines in the in-game input help.
+/// `all_keys` is the key set returned by [`do_languages`], so the check
+/// is not affected by the language filter.
+fn do_action_l10n(
+    mod_detail: &mut ModDetail,
+    mod_desc_doc: &roxmltree::Document,
+    all_keys: &HashSet<String>,
+) {
+    for action in mod_desc_doc
+        .descendants()
+        .filter(|n| n.has_tag_name("action"))
+        .filter_map(|n| n.attribute("name"))
+    {
+        if !all_keys.contains(&format!("input_{action}").to_lowercase()) {
+            mod_detail.actions_missing_l10n.push(action.to_owned());
+        }
+    }
+
+    if !mod_detail.actions_missing_l10n.is_empty() {
+        mod_detail.actions_missing_l10n.sort();
+        mod_detail.add_issue(ModDetailError::ActionMissingL10N);
+    }
+}
+
+/// Resolve key binds to translated action titles and readable key combos
+///
+/// Titles come from the `input_` l10n entries of the mod, then
+/// [`base_action_label`] for base game actions. Run after [`do_languages`].
+fn do_binds_readable(
+    mod_detail: &mut ModDetail,
+    mod_desc_doc: &roxmltree::Document,
+    options: &ModParserOptions,
+) {
+    let actions = read_actions(mod_desc_doc);
+
+    for (action, inputs) in read_key_binds(mod_desc_doc) {
+        let l10n_key = format!("input_{action}").to_lowercase();
+        let mut titles: BTreeMap<String, String> = mod_detail
+            .l10n
+            .iter()
+            .filter_map(|(lang, keys)| {
+                keys.get(&l10n_key)
+                    .map(|title| (lang.clone(), title.clone()))
+            })
+            .collect();
+
+        for lang in LANGUAGES.iter().filter(|lang| options.wants_language(lang)) {
+            if let Some(label) = base_action_label(&action, lang) {
+                titles
+                    .entry((*lang).to_owned())
+                    .or_insert_with(|| label.to_owned());
+            }
+        }
+
+        mod_detail.binds_readable.insert(
+            action.clone(),
+            ModDetailBind {
+                category: actions
+                    .get(&action)
+                    .cloned()
+                    .unwrap_or_else(|| String::from("ALL")),
+                inputs: inputs.iter().map(|input| input_label(input)).collect(),
+                titles,
+            },
+        );
+    }
+}
+
+/// Parse any animalFood.xml files in the mod
+fn do_animal_food(
+    mod_detail: &mut ModDetail,
+    file_handle: &mut Box<dyn AbstractFileHandle>,
+    file_list: &[FileDefinition],
+) {
+    for file_to_scan in file_list
+        .iter()
+        .filter(|n| n.name.to_lowercase().ends_with("animalfood.xml"))
+    {
+        let Ok(food_contents) = file_handle.as_text(&file_to_scan.name) else {
+            continue;
+        };
+        let Ok(food_tree) = roxmltree::Document::parse(&food_contents) else {
+            continue;
+        };
+        if !food_tree.root_element().has_tag_name("animalFood") {
+            continue;
+        }
+
+        let animal_food = mod_detail
+            .animal_food
+            .get_or_insert_with(ModDetailAnimalFood::default);
+        food::food_parse(&food_tree, animal_food);
+    }
+}
+
+/// Read object dimensions from the first `size` element
+fn xml_extract_object_size(xml_tree: &roxmltree::Document, object: &mut ModDetailObject) {
+    let Some(size) = xml_tree.descendants().find(|n| n.has_tag_name("size")) else {
+        return;
+    };
+    let dimension = |key: &str| size.attribute(key).and_then(|n| n.parse::<f32>().ok());
+
+    object.diameter = dimension("diameter");
+    object.height = dimension("height");
+    object.length = dimension("length");
+    object.width = dimension("width");
 }
 
 /// Extract an XML text element as a `u32` `Option`
@@ -256,6 +841,22 @@ fn xml_extract_text_as_opt_u32(xml_tree: &roxmltree::Document, key: &str) -> Opt
         .and_then(|n| n.parse::<u32>().ok())
 }
 
+/// Extract the i3d asset a store item references
+///
+/// First `<filename>` ending in `.i3d`, with path separators normalized
+fn xml_extract_i3d_file(xml_tree: &roxmltree::Document) -> Option<String> {
+    xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("filename"))
+        .filter_map(|n| n.text())
+        .map(|n| n.trim().replace('\\', "/"))
+        .find(|n| {
+            Path::new(n)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("i3d"))
+        })
+}
+
 /// Extract an XML text element as a `String` `Option`
 fn xml_extract_text_as_opt_string(xml_tree: &roxmltree::Document, key: &str) -> Option<String> {
     xml_tree
@@ -273,10 +874,71 @@ fn default_float_parse(value: &str, default: f32) -> f32 {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::mod_detail::structs::ModDetailProduction;
     use crate::shared::files::AbstractNull;
     use assert_json_diff::assert_json_eq;
     use serde_json::json;
 
+    /// File handle that panics on any read
+    struct PanicFile;
+
+    #[expect(unused_variables)]
+    impl AbstractFileHandle for PanicFile {
+        fn as_text(&mut self, needle: &str) -> Result<String, std::io::Error> {
+            panic!("pathological read")
+        }
+        fn as_bin(&mut self, needle: &str) -> Result<Vec<u8>, std::io::Error> {
+            panic!("pathological read")
+        }
+        fn is_folder(&self) -> bool {
+            false
+        }
+        fn list(&mut self) -> Vec<FileDefinition> {
+            vec![]
+        }
+        fn exists(&mut self, needle: &str) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn store_item_panic_isolated() {
+        let mod_desc = roxmltree::Document::parse(
+            r#"<modDesc><storeItems>
+                <storeItem xmlFilename="xml/one.xml"/>
+                <storeItem xmlFilename="xml/two.xml"/>
+            </storeItems></modDesc>"#,
+        )
+        .unwrap();
+        let options = ModParserOptions {
+            detail_diagnostics: true,
+            ..Default::default()
+        };
+
+        let mod_detail = parse_open_file(Box::new(PanicFile), &mod_desc, &[], &options);
+
+        assert_eq!(
+            mod_detail.issues,
+            std::collections::HashSet::from([
+                ModDetailError::StoreItemCountMismatch,
+                ModDetailError::StoreItemPanic
+            ])
+        );
+        assert_eq!(
+            mod_detail.item_count,
+            ModDetailItemCount {
+                declared: 2,
+                parsed: 0
+            }
+        );
+        let diagnostics = mod_detail.diagnostics.unwrap();
+        assert_eq!(diagnostics.items_parsed, 2);
+        assert_eq!(
+            diagnostics.panicked_items,
+            vec![String::from("xml/one.xml"), String::from("xml/two.xml")]
+        );
+    }
+
     #[test]
     fn embedded_l10n_entries() {
         /* cSpell: disable */
@@ -297,6 +959,7 @@ mod test {
             &mut file_handle,
             &minimum_doc,
             &empty_file_list,
+            &ModParserOptions::default(),
         );
         let actual = json!(mod_detail.l10n);
         let expected = json!({
@@ -320,4 +983,234 @@ mod test {
         // assert_eq!(actual.to_string(), expected.to_string());
         assert_json_eq!(actual, expected);
     }
+
+    #[test]
+    fn filtered_l10n_entries() {
+        /* cSpell: disable */
+        let minimum_xml = r#"<modDesc>
+            <l10n>
+                <text name="fillType_sand"> <en>Sand</en> <de>Sand</de> <fr>Sable</fr> </text>
+            </l10n>
+            </modDesc>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+        let empty_file_list: Vec<FileDefinition> = vec![];
+        let mut mod_detail = ModDetail::default();
+        let options = ModParserOptions {
+            languages: Some(vec![String::from("fr")]),
+            ..Default::default()
+        };
+
+        do_languages(
+            &mut mod_detail,
+            &mut file_handle,
+            &minimum_doc,
+            &empty_file_list,
+            &options,
+        );
+        let actual = json!(mod_detail.l10n);
+        let expected = json!({
+            "en": { "filltype_sand": "Sand" },
+            "fr": { "filltype_sand": "Sable" }
+        });
+        /* cSpell: enable */
+        assert_json_eq!(actual, expected);
+    }
+
+    #[test]
+    fn action_missing_l10n() {
+        let minimum_xml = r#"<modDesc>
+            <actions>
+                <action name="GOOD_ACTION" />
+                <action name="BAD_ACTION" />
+            </actions>
+            <l10n>
+                <text name="input_GOOD_ACTION"> <de>Gute Aktion</de> </text>
+            </l10n>
+            </modDesc>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+        let empty_file_list: Vec<FileDefinition> = vec![];
+        let mut mod_detail = ModDetail::default();
+        let options = ModParserOptions {
+            languages: Some(vec![String::from("fr")]),
+            ..Default::default()
+        };
+
+        let all_keys = do_languages(
+            &mut mod_detail,
+            &mut file_handle,
+            &minimum_doc,
+            &empty_file_list,
+            &options,
+        );
+        do_action_l10n(&mut mod_detail, &minimum_doc, &all_keys);
+
+        assert_eq!(mod_detail.actions_missing_l10n, vec!["BAD_ACTION"]);
+        assert!(mod_detail
+            .issues
+            .contains(&ModDetailError::ActionMissingL10N));
+    }
+
+    #[test]
+    fn readable_binds() {
+        let minimum_xml = r#"<modDesc>
+            <actions>
+                <action name="GOOD_ACTION" category="VEHICLE" />
+            </actions>
+            <inputBinding>
+                <actionBinding action="GOOD_ACTION">
+                    <binding device="KB_MOUSE_DEFAULT" input="KEY_lshift KEY_b" />
+                    <binding device="0_XINPUT_GAMEPAD" input="BUTTON_1" />
+                </actionBinding>
+                <actionBinding action="TOGGLE_LIGHTS">
+                    <binding device="KB_MOUSE_DEFAULT" input="KEY_l" />
+                </actionBinding>
+            </inputBinding>
+            <l10n>
+                <text name="input_GOOD_ACTION"> <en>Good Action</en> <de>Gute Aktion</de> </text>
+            </l10n>
+            </modDesc>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+        let empty_file_list: Vec<FileDefinition> = vec![];
+        let mut mod_detail = ModDetail::default();
+        let options = ModParserOptions::default();
+
+        do_languages(
+            &mut mod_detail,
+            &mut file_handle,
+            &minimum_doc,
+            &empty_file_list,
+            &options,
+        );
+        do_binds_readable(&mut mod_detail, &minimum_doc, &options);
+
+        let good = &mod_detail.binds_readable["GOOD_ACTION"];
+        assert_eq!(good.category, "VEHICLE");
+        assert_eq!(good.inputs, vec!["Left Shift + B"]);
+        assert_eq!(good.titles.len(), 2);
+        assert_eq!(good.titles["de"], "Gute Aktion");
+
+        let lights = &mod_detail.binds_readable["TOGGLE_LIGHTS"];
+        assert_eq!(lights.category, "ALL");
+        assert_eq!(lights.inputs, vec!["L"]);
+        assert_eq!(lights.titles["en"], "Lights");
+        assert_eq!(lights.titles["fr"], "Phares");
+    }
+
+    #[test]
+    fn store_item_i3d_file() {
+        let minimum_xml = r#"<vehicle>
+            <storeData><filename>not_an_asset.xml</filename></storeData>
+            <base><filename>vehicles\tractor\Tractor.I3D</filename></base>
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+
+        assert_eq!(
+            xml_extract_i3d_file(&minimum_doc),
+            Some(String::from("vehicles/tractor/Tractor.I3D"))
+        );
+
+        let minimum_doc = roxmltree::Document::parse("<vehicle />").unwrap();
+        assert_eq!(xml_extract_i3d_file(&minimum_doc), None);
+    }
+
+    #[test]
+    fn brand_usage_unknown_brand() {
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+        let options = ModParserOptions {
+            skip_detail_icons: true,
+            ..Default::default()
+        };
+        let mut mod_detail = ModDetail::default();
+        mod_detail.add_brand("MODBRAND", Some("Mod Brand"));
+
+        for (key, brand) in [
+            ("base.xml", "johnDeere"),
+            ("added.xml", "MODBRAND"),
+            ("typo.xml", "JOHNDEER"),
+            ("also_base.xml", "JOHNDEERE"),
+        ] {
+            let minimum_xml =
+                format!("<vehicle><storeData><brand>{brand}</brand></storeData></vehicle>");
+            let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+            let this_vehicle = vehicles::vehicle_parse(&minimum_doc, &mut file_handle, &options);
+            mod_detail.vehicles.insert(key.to_owned(), this_vehicle);
+        }
+
+        do_brand_usage(&mut mod_detail);
+
+        assert_eq!(mod_detail.unknown_brand_items, vec!["typo.xml"]);
+        assert!(mod_detail.issues.contains(&ModDetailError::BrandUnknown));
+        assert_json_eq!(
+            json!(mod_detail.brand_usage),
+            json!({ "JOHNDEER": 1, "JOHNDEERE": 2, "MODBRAND": 1 })
+        );
+        assert!(data::BG_BRANDS.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(data::is_base_game_brand("goldhofer"));
+        assert!(data::is_base_game_brand("SAMSONAGRO"));
+    }
+
+    #[test]
+    fn shop_categories() {
+        use crate::mod_detail::structs::ModDetailShopCategory;
+
+        assert!(data::SHOP_CATEGORIES
+            .windows(2)
+            .all(|pair| pair[0].0 < pair[1].0));
+
+        let tractors = ModDetailShopCategory::from_category("tractorsL").unwrap();
+        assert_eq!(tractors.sub_category, "tractorsl");
+        assert_eq!(tractors.sub_category_name, "Large Tractors");
+        assert_eq!(tractors.top_level, "vehicles");
+
+        // translations cover exactly the base game categories
+        let german = crate::shared::l10n::bundle("de");
+        assert!(german
+            .shop_categories
+            .keys()
+            .eq(data::SHOP_CATEGORIES.iter().map(|(key, _, _)| key)));
+        assert_eq!(german.shop_top_levels.get("vehicles"), Some(&"Fahrzeuge"));
+
+        let harvesters = ModDetailShopCategory::from_category("harvesters forageHarvesters");
+        assert_eq!(harvesters.map(|c| c.sub_category), Some("harvesters"));
+        assert_eq!(
+            ModDetailShopCategory::from_category("handTools").map(|c| c.top_level),
+            Some("objects")
+        );
+        assert!(ModDetailShopCategory::from_category("myModCategory").is_none());
+        assert!(ModDetailShopCategory::from_category("").is_none());
+    }
+
+    #[test]
+    fn placeable_totals() {
+        let mut mod_detail = ModDetail::default();
+
+        let mut solar = ModDetailPlace::default();
+        solar.sorting.income_per_hour = 40;
+        mod_detail
+            .placeables
+            .insert(String::from("solar.xml"), solar);
+
+        let mut factory = ModDetailPlace::default();
+        factory.storage.silo_capacity = 50000;
+        factory.productions = vec![ModDetailProduction::new(), ModDetailProduction::new()];
+        factory.productions[0].cost_per_hour = 2.5;
+        mod_detail
+            .placeables
+            .insert(String::from("factory.xml"), factory);
+
+        do_place_totals(&mut mod_detail);
+
+        assert_json_eq!(
+            json!(mod_detail.place_totals),
+            json!({
+                "incomePerHour": 40,
+                "productionCostPerHour": 3.5,
+                "productions": 2,
+                "siloCapacity": 50000
+            })
+        );
+    }
 }
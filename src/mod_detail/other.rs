@@ -0,0 +1,143 @@
+//! Parse other store items (hand tools, objects, etc.)
+use super::{xml_extract_object_size, xml_extract_text_as_opt_string, xml_extract_text_as_opt_u32};
+use crate::mod_detail::structs::{ModDetailObject, ModDetailOther, ModDetailShopCategory};
+use crate::shared::files::AbstractFileHandle;
+use crate::shared::{convert_mod_icon, extract_and_normalize_image};
+use crate::ModParserOptions;
+
+/// Parse a store item that is not a vehicle or placeable
+///
+/// # Sample output:
+/// ```json
+/// {
+///    "category": "handTools",
+///    "iconBase": null,
+///    "iconFile": null,
+///    "iconOrig": "store_chainsaw.png",
+///    "masterType": "handTool",
+///    "name": "Chainsaw",
+///    "object": null,
+///    "price": 800
+/// }
+/// ```
+pub fn other_parse(
+    xml_tree: &roxmltree::Document,
+    file_handle: &mut Box<dyn AbstractFileHandle>,
+    options: &ModParserOptions,
+) -> ModDetailOther {
+    let mut this_item = ModDetailOther::new(xml_tree.root_element().tag_name().name());
+
+    this_item.category = xml_extract_text_as_opt_string(xml_tree, "category");
+    this_item.shop_category = this_item
+        .category
+        .as_deref()
+        .and_then(ModDetailShopCategory::from_category);
+    this_item.name = xml_extract_text_as_opt_string(xml_tree, "name");
+    this_item.price = xml_extract_text_as_opt_u32(xml_tree, "price").unwrap_or(0);
+
+    if xml_tree.root_element().has_tag_name("bale") {
+        this_item.object = Some(other_parse_bale(xml_tree));
+    }
+
+    if !options.skip_detail_icons {
+        let image_entry = extract_and_normalize_image(xml_tree, "image");
+
+        image_entry.original.clone_into(&mut this_item.icon_orig);
+
+        if let Some(filename) = image_entry.base_game {
+            this_item.icon_base = Some(filename);
+        } else if let Some(filename) = image_entry.local_file {
+            if let Ok(file_content) = file_handle.as_bin(&filename) {
+                this_item.icon_file = convert_mod_icon(file_content);
+            }
+        }
+    }
+
+    this_item
+}
+
+/// Parse bale capacity, fill types and size
+fn other_parse_bale(xml_tree: &roxmltree::Document) -> ModDetailObject {
+    let mut object = ModDetailObject::default();
+
+    for fill_type in xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("fillType") && n.has_attribute("name"))
+    {
+        if let Some(name) = fill_type.attribute("name") {
+            object.fill_types.push(name.to_lowercase());
+        }
+        if let Some(capacity) = fill_type
+            .attribute("capacity")
+            .and_then(|n| n.parse::<u32>().ok())
+        {
+            object.capacity = std::cmp::max(object.capacity, capacity);
+        }
+    }
+
+    object.fill_types.sort();
+    object.fill_types.dedup();
+
+    xml_extract_object_size(xml_tree, &mut object);
+    object
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::shared::files::AbstractNull;
+    use assert_json_diff::assert_json_include;
+    use serde_json::json;
+
+    #[test]
+    fn hand_tool_item() {
+        let minimum_xml = r#"<handTool><storeData>
+            <name>Chainsaw</name>
+            <price>800</price>
+            <category>handTools</category>
+            </storeData></handTool>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+
+        let this_item = other_parse(&minimum_doc, &mut file_handle, &ModParserOptions::default());
+
+        let actual = json!(this_item);
+        let expected = json!({
+            "category": "handTools",
+            "masterType": "handTool",
+            "name": "Chainsaw",
+            "price": 800
+        });
+        assert_json_include!(actual : actual, expected : expected);
+    }
+
+    #[test]
+    fn round_bale_item() {
+        let minimum_xml = r#"<bale type="roundbale">
+            <storeData><name>Round Bale</name><price>200</price></storeData>
+            <size diameter="1.5" width="1.25" isRoundbale="true" />
+            <fillTypes>
+                <fillType name="GRASS_WINDROW" capacity="4000" />
+                <fillType name="SILAGE" capacity="4500" />
+            </fillTypes>
+            </bale>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+
+        let this_item = other_parse(&minimum_doc, &mut file_handle, &ModParserOptions::default());
+
+        let actual = json!(this_item);
+        let expected = json!({
+            "masterType": "bale",
+            "object": {
+                "capacity": 4500,
+                "diameter": 1.5,
+                "fillTypes": [ "grass_windrow", "silage" ],
+                "height": null,
+                "length": null,
+                "width": 1.25
+            }
+        });
+        assert_json_include!(actual : actual, expected : expected);
+    }
+}
@@ -1,13 +1,14 @@
 //! Parse placeables and productions
-use super::{xml_extract_text_as_opt_string, xml_extract_text_as_opt_u32};
+use super::{default_float_parse, xml_extract_text_as_opt_string, xml_extract_text_as_opt_u32};
 use crate::mod_detail::structs::{
-    ModDetailPlace, ModDetailProduction, ProductionBoost, ProductionIngredient,
-    ProductionIngredients, VehicleCapability,
+    ModDetailPlace, ModDetailPlacePlacement, ModDetailPlacePowerKey, ModDetailProduction,
+    ModDetailShopCategory, ProductionBoost, ProductionIngredient, ProductionIngredients,
+    VehicleCapability,
 };
 use crate::shared::files::AbstractFileHandle;
 use crate::shared::{convert_mod_icon, extract_and_normalize_image};
 use crate::ModParserOptions;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// Parse a placeable
 ///
@@ -27,6 +28,13 @@ use std::collections::HashMap;
 ///    "iconBase": null,
 ///    "iconFile": null,
 ///    "masterType": "placeable",
+///    "power": {
+///        "exists": false,
+///        "incomePerHour": 0,
+///        "incomeCurve": [],
+///        "kind": null,
+///        "storageCapacity": null
+///    },
 ///    "productions": [
 ///    {
 ///        "boosts": [],
@@ -72,6 +80,8 @@ pub fn place_parse(
     place_parse_sorting(xml_tree, &mut this_place);
     place_parse_storage(xml_tree, &mut this_place);
     place_parse_animals(xml_tree, &mut this_place);
+    place_parse_power(xml_tree, &mut this_place);
+    place_parse_placement(xml_tree, &mut this_place);
 
     for production in xml_tree
         .descendants()
@@ -104,7 +114,7 @@ fn place_parse_production(xml_node: &roxmltree::Node) -> ModDetailProduction {
     let mut this_production = ModDetailProduction::default();
 
     // let single_input:ProductionRecipe = vec![];
-    let mut mix_inputs: HashMap<String, ProductionIngredients> = HashMap::new();
+    let mut mix_inputs: BTreeMap<String, ProductionIngredients> = BTreeMap::new();
 
     if let Some(name) = xml_node.attribute("name") {
         name.clone_into(&mut this_production.name);
@@ -298,10 +308,120 @@ fn place_parse_animals(xml_tree: &roxmltree::Document, this_place: &mut ModDetai
     }
 }
 
+/// Parse power generators (solar, wind, generic generators)
+///
+/// Income scaling is read from `<incomeFactors><key time="12" factor="1.0"/>`,
+/// battery storage from a `capacity` attribute on `<battery>` or
+/// `<energyStorage>`.
+fn place_parse_power(xml_tree: &roxmltree::Document, this_place: &mut ModDetailPlace) {
+    let type_name = this_place
+        .sorting
+        .type_name
+        .as_deref()
+        .unwrap_or("")
+        .to_lowercase();
+    let has_tag = |tags: &[&str]| {
+        xml_tree
+            .descendants()
+            .any(|n| tags.contains(&n.tag_name().name()))
+    };
+
+    let kind = if type_name.contains("solar") || has_tag(&["solarPanel", "solarPanels"]) {
+        "solar"
+    } else if type_name.contains("wind") || has_tag(&["windTurbine", "windTurbines"]) {
+        "wind"
+    } else if type_name.contains("generator") || has_tag(&["generator", "powerPlant"]) {
+        "generator"
+    } else {
+        return;
+    };
+
+    this_place.power.exists = true;
+    this_place.power.kind = Some(kind.to_owned());
+    this_place.power.income_per_hour = this_place.sorting.income_per_hour;
+
+    if let Some(curve) = xml_tree
+        .descendants()
+        .find(|n| n.has_tag_name("incomeFactors"))
+    {
+        this_place.power.income_curve = curve
+            .children()
+            .filter(|n| n.has_tag_name("key"))
+            .map(|n| ModDetailPlacePowerKey {
+                hour: default_float_parse(n.attribute("time").unwrap_or("0"), 0.0),
+                factor: default_float_parse(n.attribute("factor").unwrap_or("1"), 1.0),
+            })
+            .collect();
+        this_place
+            .power
+            .income_curve
+            .sort_by(|a, b| a.hour.total_cmp(&b.hour));
+    }
+
+    this_place.power.storage_capacity = xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("battery") || n.has_tag_name("energyStorage"))
+        .find_map(|n| n.attribute("capacity"))
+        .and_then(|capacity| capacity.parse::<u32>().ok());
+}
+
+/// Parse placement constraints (`<placement>`, `<leveling>`, `<clearAreas>`)
+///
+/// Left as `None` when the placeable defines none of them
+fn place_parse_placement(xml_tree: &roxmltree::Document, this_place: &mut ModDetailPlace) {
+    let count = |tag: &str| {
+        u32::try_from(
+            xml_tree
+                .descendants()
+                .filter(|n| n.has_tag_name(tag))
+                .count(),
+        )
+        .unwrap_or(u32::MAX)
+    };
+    let leveling = xml_tree.descendants().find(|n| n.has_tag_name("leveling"));
+
+    let mut placement = ModDetailPlacePlacement {
+        clear_areas: count("clearArea"),
+        daily_upkeep: xml_extract_text_as_opt_u32(xml_tree, "dailyUpkeep").unwrap_or(0),
+        ground_types: vec![],
+        level_areas: count("levelArea"),
+        max_slope: leveling
+            .and_then(|n| n.attribute("maxSlope"))
+            .and_then(|value| value.parse::<f32>().ok()),
+        paint_areas: count("paintArea"),
+        require_leveling: leveling.and_then(|n| n.attribute("requireLeveling")) == Some("true"),
+        test_areas: count("testArea"),
+    };
+
+    if leveling.is_none()
+        && placement.clear_areas == 0
+        && placement.test_areas == 0
+        && placement.paint_areas == 0
+    {
+        return;
+    }
+
+    placement.ground_types = xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("levelArea") || n.has_tag_name("paintArea"))
+        .filter_map(|n| n.attribute("groundType"))
+        .map(str::to_owned)
+        .collect();
+    placement.ground_types.sort();
+    placement.ground_types.dedup();
+
+    this_place.placement = Some(placement);
+}
+
 /// Parse placeable sorting data
 fn place_parse_sorting(xml_tree: &roxmltree::Document, this_place: &mut ModDetailPlace) {
     this_place.parent_item = xml_tree.descendants().find(|n|n.has_tag_name("parentFile")).and_then(|n|n.attribute("xmlFilename")).map(std::string::ToString::to_string);
     this_place.sorting.category = xml_extract_text_as_opt_string(xml_tree, "category");
+    this_place.sorting.shop_category = this_place
+        .sorting
+        .category
+        .as_deref()
+        .and_then(ModDetailShopCategory::from_category);
     this_place.sorting.income_per_hour =
         xml_extract_text_as_opt_u32(xml_tree, "incomePerHour").unwrap_or(0);
     this_place.sorting.name = xml_extract_text_as_opt_string(xml_tree, "name");
@@ -383,6 +503,104 @@ mod test {
         assert_json_include!(actual : actual, expected : expected);
     }
 
+    #[test]
+    fn placeable_power_solar() {
+        let minimum_xml = r#"<placeable type="solarPanelsBattery">
+            <storeData><name>Solar Farm</name></storeData>
+            <incomePerHour>40</incomePerHour>
+            <incomeFactors>
+                <key time="18" factor="0.25" />
+                <key time="6" factor="0.25" />
+                <key time="12" factor="1.0" />
+            </incomeFactors>
+            <battery capacity="500" />
+            </placeable>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_place = ModDetailPlace::default();
+
+        place_parse_sorting(&minimum_doc, &mut this_place);
+        place_parse_power(&minimum_doc, &mut this_place);
+
+        let actual = json!(this_place.power);
+        let expected = json!({
+            "exists": true,
+            "incomePerHour": 40,
+            "incomeCurve": [
+                { "hour": 6.0, "factor": 0.25 },
+                { "hour": 12.0, "factor": 1.0 },
+                { "hour": 18.0, "factor": 0.25 }
+            ],
+            "kind": "solar",
+            "storageCapacity": 500
+        });
+        assert_json_include!(actual : actual, expected : expected);
+    }
+
+    #[test]
+    fn placeable_placement() {
+        let minimum_xml = r#"<placeable type="silo">
+            <storeData><dailyUpkeep>25</dailyUpkeep></storeData>
+            <placement>
+                <testAreas>
+                    <testArea startNode="testArea1Start" endNode="testArea1End" />
+                </testAreas>
+            </placement>
+            <leveling requireLeveling="true" maxSmoothDistance="10" maxSlope="75">
+                <levelAreas>
+                    <levelArea startNode="l1s" widthNode="l1w" heightNode="l1h" groundType="asphalt" />
+                    <levelArea startNode="l2s" widthNode="l2w" heightNode="l2h" groundType="gravel" />
+                </levelAreas>
+                <paintAreas>
+                    <paintArea startNode="p1s" widthNode="p1w" heightNode="p1h" groundType="asphalt" />
+                </paintAreas>
+            </leveling>
+            <clearAreas>
+                <clearArea startNode="c1s" widthNode="c1w" heightNode="c1h" />
+            </clearAreas>
+            </placeable>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_place = ModDetailPlace::default();
+
+        place_parse_placement(&minimum_doc, &mut this_place);
+
+        let placement = this_place.placement.unwrap();
+        assert!(placement.flattens_terrain());
+        assert_eq!(
+            placement,
+            ModDetailPlacePlacement {
+                clear_areas: 1,
+                daily_upkeep: 25,
+                ground_types: vec![String::from("asphalt"), String::from("gravel")],
+                level_areas: 2,
+                max_slope: Some(75.0),
+                paint_areas: 1,
+                require_leveling: true,
+                test_areas: 1,
+            }
+        );
+
+        let decoration = roxmltree::Document::parse(r#"<placeable type="simple"/>"#).unwrap();
+        let mut this_place = ModDetailPlace::default();
+        place_parse_placement(&decoration, &mut this_place);
+        assert!(this_place.placement.is_none());
+    }
+
+    #[test]
+    fn placeable_power_none() {
+        let minimum_xml = r#"<placeable type="silo">
+            <incomePerHour>10</incomePerHour>
+            </placeable>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_place = ModDetailPlace::default();
+
+        place_parse_sorting(&minimum_doc, &mut this_place);
+        place_parse_power(&minimum_doc, &mut this_place);
+
+        assert!(!this_place.power.exists);
+        assert_eq!(this_place.power.kind, None);
+        assert_eq!(this_place.power.income_per_hour, 0);
+    }
+
     #[test]
     fn placeable_animal_cows() {
         let minimum_xml = r#"<placeable>
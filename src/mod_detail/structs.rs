@@ -1,9 +1,12 @@
 //! Mod Detail data structures
+use crate::mod_detail::data::shop_category;
+use crate::shared::deserialize_unit_variant;
+use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Detail errors
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Clone)]
 pub enum ModDetailError {
     /// Could not read file
     FileReadFail,
@@ -15,6 +18,18 @@ pub enum ModDetailError {
     StoreItemMissing,
     /// Bad storeItem XML
     StoreItemBroken,
+    /// Declared action has no `input_` l10n entry
+    ActionMissingL10N,
+    /// A file read was refused by the configured read limits
+    FileReadLimit,
+    /// Vehicle brand is neither a base game brand nor added by the mod
+    BrandUnknown,
+    /// Store item parse panicked, the item was skipped
+    StoreItemPanic,
+    /// Fewer store items parsed than modDesc.xml declares
+    StoreItemCountMismatch,
+    /// Store item references files outside of the mod
+    StoreItemExternalReference,
 }
 
 impl Serialize for ModDetailError {
@@ -40,26 +55,108 @@ impl Serialize for ModDetailError {
             ModDetailError::StoreItemBroken => {
                 serializer.serialize_unit_variant("ModDetailError", 4, "DETAIL_ERROR_PARSE_ITEM")
             }
+            ModDetailError::ActionMissingL10N => serializer.serialize_unit_variant(
+                "ModDetailError",
+                5,
+                "DETAIL_ERROR_MISSING_ACTION_L10N",
+            ),
+            ModDetailError::FileReadLimit => {
+                serializer.serialize_unit_variant("ModDetailError", 6, "DETAIL_ERROR_READ_LIMIT")
+            }
+            ModDetailError::BrandUnknown => {
+                serializer.serialize_unit_variant("ModDetailError", 7, "DETAIL_ERROR_UNKNOWN_BRAND")
+            }
+            ModDetailError::StoreItemPanic => {
+                serializer.serialize_unit_variant("ModDetailError", 8, "DETAIL_ERROR_ITEM_PANIC")
+            }
+            ModDetailError::StoreItemCountMismatch => serializer.serialize_unit_variant(
+                "ModDetailError",
+                9,
+                "DETAIL_ERROR_ITEM_COUNT_MISMATCH",
+            ),
+            ModDetailError::StoreItemExternalReference => serializer.serialize_unit_variant(
+                "ModDetailError",
+                10,
+                "DETAIL_ERROR_EXTERNAL_REFERENCE",
+            ),
         }
     }
 }
 
+impl<'de> Deserialize<'de> for ModDetailError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                ModDetailError::FileReadFail,
+                ModDetailError::NotModModDesc,
+                ModDetailError::BrandMissingIcon,
+                ModDetailError::StoreItemMissing,
+                ModDetailError::StoreItemBroken,
+                ModDetailError::ActionMissingL10N,
+                ModDetailError::FileReadLimit,
+                ModDetailError::BrandUnknown,
+                ModDetailError::StoreItemPanic,
+                ModDetailError::StoreItemCountMismatch,
+                ModDetailError::StoreItemExternalReference,
+            ],
+        )
+    }
+}
+
 /// Mod Detail Data
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetail {
+    /// declared actions with no l10n entry in any language
+    pub actions_missing_l10n: Vec<String>,
+    /// animal food groups and mixtures (if the mod ships an animalFood.xml)
+    pub animal_food: Option<ModDetailAnimalFood>,
     /// list of brands
     pub brands: BrandDefinition,
+    /// number of vehicles using each brand KEY
+    pub brand_usage: BTreeMap<String, u32>,
+    /// files only found by ignoring case, requested name to the name found, see
+    /// [`crate::shared::files::AbstractFileHandle::set_case_fallback`]
+    ///
+    /// Moved to the [`crate::shared::errors::ModError::FileErrorNameCase`]
+    /// issue context when parsed as part of a mod record
+    pub case_fallbacks: BTreeMap<String, String>,
+    /// store item parse timing (if requested)
+    pub diagnostics: Option<ModDetailDiagnostics>,
+    /// store item files with references outside of the mod (if requested), see
+    /// [`crate::shared::files::is_external_reference`]
+    ///
+    /// Moved to [`crate::shared::structs::ModFile::external_references`]
+    /// when parsed as part of a mod record
+    pub external_references: BTreeMap<String, Vec<String>>,
     /// list of detected issues
     pub issues: HashSet<ModDetailError>,
+    /// key binds with translated action titles and readable inputs, by action name
+    pub binds_readable: BTreeMap<String, ModDetailBind>,
     /// Item brands
     pub item_brands: HashSet<String>,
     /// Item categories
     pub item_categories: HashSet<String>,
+    /// storeItem count declared in modDesc.xml, and the number parsed
+    pub item_count: ModDetailItemCount,
+    /// storeItem keys, in the order declared in modDesc.xml (in-game shop order)
+    pub item_order: Vec<String>,
     /// l10n languages, keys, and strings
     pub l10n: LanguageDefinition,
+    /// other store items (hand tools, objects, etc.)
+    pub others: HashMap<String, ModDetailOther>,
     /// placables
     pub placeables: HashMap<String, ModDetailPlace>,
+    /// income, production cost and storage summed over all placeables
+    pub place_totals: ModDetailPlaceTotals,
+    /// i3d assets used by more than one store item, mapped to those items
+    pub shared_i3d: BTreeMap<String, Vec<String>>,
+    /// vehicles using a brand KEY unknown to the base game and this mod
+    pub unknown_brand_items: Vec<String>,
     /// vehicles
     pub vehicles: HashMap<String, ModDetailVehicle>,
 }
@@ -69,12 +166,25 @@ impl ModDetail {
     #[must_use]
     pub fn new() -> Self {
         ModDetail {
+            actions_missing_l10n: vec![],
+            animal_food: None,
             brands: HashMap::new(),
+            brand_usage: BTreeMap::new(),
+            case_fallbacks: BTreeMap::new(),
+            diagnostics: None,
+            external_references: BTreeMap::new(),
             issues: HashSet::new(),
+            binds_readable: BTreeMap::new(),
             item_brands: HashSet::new(),
             item_categories: HashSet::new(),
+            item_count: ModDetailItemCount::default(),
+            item_order: vec![],
             l10n: HashMap::new(),
+            others: HashMap::new(),
             placeables: HashMap::new(),
+            place_totals: ModDetailPlaceTotals::default(),
+            shared_i3d: BTreeMap::new(),
+            unknown_brand_items: vec![],
             vehicles: HashMap::new(),
         }
     }
@@ -138,11 +248,48 @@ impl std::fmt::Display for ModDetail {
     }
 }
 
+/// Declared and parsed store item counts
+///
+/// A difference usually means a packaging error, like a wrong path or
+/// a case mismatch in an `xmlFilename`
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailItemCount {
+    /// `<storeItem>` entries in modDesc.xml
+    pub declared: usize,
+    /// store items parsed (vehicles, placeables and others)
+    pub parsed: usize,
+}
+
+/// Store item parse timing and failures, for triage of slow packs
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailDiagnostics {
+    /// store items attempted
+    pub items_parsed: usize,
+    /// store items whose parse panicked
+    pub panicked_items: Vec<String>,
+    /// slowest store items, slowest first
+    pub slowest_items: Vec<ModDetailItemTiming>,
+    /// time spent on all store items, in microseconds
+    pub total_micros: u64,
+}
+
+/// Time taken to parse one store item
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailItemTiming {
+    /// store item file name
+    pub file: String,
+    /// parse time in microseconds
+    pub micros: u64,
+}
+
 /// Nested language definition langCode => [key, string]
 type LanguageDefinition = HashMap<String, HashMap<String, String>>;
 
 /// Added brand
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailBrand {
     /// name of the brand (human readable)
@@ -172,11 +319,75 @@ impl Default for ModDetailBrand {
     }
 }
 
+/// Store category placed in the in-game shop tree
+///
+/// See [`crate::mod_detail::data::SHOP_CATEGORIES`]. Translated category and
+/// tab names are in [`crate::shared::l10n::bundle`], by key.
+#[derive(serde::Serialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailShopCategory {
+    /// sub category key (the base game category, lower case)
+    pub sub_category: &'static str,
+    /// sub category name (English)
+    pub sub_category_name: &'static str,
+    /// top level shop tab key, see [`crate::shared::l10n::SHOP_TOP_LEVELS`]
+    pub top_level: &'static str,
+}
+
+impl ModDetailShopCategory {
+    /// Place a raw store category in the shop tree
+    ///
+    /// Returns `None` for categories the base game does not have
+    #[must_use]
+    pub fn from_category(category: &str) -> Option<Self> {
+        let (sub_category, top_level, sub_category_name) = shop_category(category)?;
+        Some(ModDetailShopCategory {
+            sub_category,
+            sub_category_name,
+            top_level,
+        })
+    }
+}
+
+/// Names come from [`crate::mod_detail::data::SHOP_CATEGORIES`], only the
+/// sub category key is read
+impl<'de> Deserialize<'de> for ModDetailShopCategory {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// Stored shop category
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct StoredCategory {
+            /// sub category key
+            sub_category: String,
+        }
+
+        let stored = StoredCategory::deserialize(deserializer)?;
+        ModDetailShopCategory::from_category(&stored.sub_category).ok_or_else(|| {
+            serde::de::Error::custom(format!("unknown shop category `{}`", stored.sub_category))
+        })
+    }
+}
+
+/// Key bind in a readable form
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailBind {
+    /// action category (`ALL` when not set)
+    pub category: String,
+    /// default keyboard inputs as key combos (e.g. "Left Shift + B")
+    pub inputs: Vec<String>,
+    /// action title by language code, from the mod l10n or the base game
+    pub titles: BTreeMap<String, String>,
+}
+
 /// Brand definition mapping Brand Key -> Brand Record
 type BrandDefinition = HashMap<String, ModDetailBrand>;
 
 /// Vehicle sorting data
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailVehicleSorting {
     /// brand KEY
@@ -185,14 +396,25 @@ pub struct ModDetailVehicleSorting {
     pub category: Option<String>,
     /// list of combos (local or basegame)
     pub combos: Vec<String>,
+    /// combos resolved against the store items of this mod
+    pub combo_items: Vec<ModDetailVehicleCombo>,
+    /// decade of the vehicle year (e.g. 1970), for vintage filtering
+    pub decade: Option<u32>,
     /// name of vehicle
     pub name: Option<String>,
+    /// category placed in the shop tree (base game categories only)
+    pub shop_category: Option<ModDetailShopCategory>,
     /// type name
     pub type_name: Option<String>,
     /// type description
     pub type_description: Option<String>,
     /// year of vehicle (non-standard)
+    ///
+    /// From `<year>`, an alternative tag or `year` attribute, or a model
+    /// year in the vehicle name
     pub year: Option<u32>,
+    /// how the year was found, `None` when there is no year
+    pub year_confidence: Option<ModDetailYearConfidence>,
 }
 
 impl ModDetailVehicleSorting {
@@ -202,15 +424,66 @@ impl ModDetailVehicleSorting {
             brand: None,
             category: None,
             combos: vec![],
+            combo_items: vec![],
+            decade: None,
             name: None,
+            shop_category: None,
             type_name: None,
             type_description: None,
             year: None,
+            year_confidence: None,
         }
     }
 }
 
+/// Source of an inferred vehicle year
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum ModDetailYearConfidence {
+    /// a `<year>` element
+    High,
+    /// an alternative element (`<modelYear>`, etc.) or a `year` attribute
+    Medium,
+    /// guessed from a 4 digit number in the vehicle name
+    Low,
+}
+
+impl Serialize for ModDetailYearConfidence {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            ModDetailYearConfidence::High => {
+                serializer.serialize_unit_variant("ModDetailYearConfidence", 0, "HIGH")
+            }
+            ModDetailYearConfidence::Medium => {
+                serializer.serialize_unit_variant("ModDetailYearConfidence", 1, "MEDIUM")
+            }
+            ModDetailYearConfidence::Low => {
+                serializer.serialize_unit_variant("ModDetailYearConfidence", 2, "LOW")
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ModDetailYearConfidence {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                ModDetailYearConfidence::High,
+                ModDetailYearConfidence::Medium,
+                ModDetailYearConfidence::Low,
+            ],
+        )
+    }
+}
+
 /// Vehicle Capability
+#[derive(Clone)]
 pub enum VehicleCapability {
     /// Has option
     Yes,
@@ -219,19 +492,31 @@ pub enum VehicleCapability {
 }
 
 /// Vehicle flags
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailVehicleFlags {
+    /// has more than one attacher joint configuration (e.g. hitch heights)
+    pub attacher_configs: VehicleCapability,
+    /// has an autoload script (`universalAutoload`, `aPalletAutoLoader`, ...)
+    pub autoload: VehicleCapability,
     /// has beacon lights
     pub beacons: VehicleCapability,
     /// has paint options
     pub color: VehicleCapability,
+    /// can hold loose objects in place with dynamic mount attachers
+    pub dynamic_mount: VehicleCapability,
     /// can be entered by player
     pub enterable: VehicleCapability,
+    /// has license plates
+    pub license_plates: VehicleCapability,
     /// has real lights
     pub lights: VehicleCapability,
     /// is motorized
     pub motorized: VehicleCapability,
+    /// has passenger seats, or hooks for passenger script mods
+    pub passenger_seats: VehicleCapability,
+    /// has warning signs, or warning sign configurations
+    pub warning_signs: VehicleCapability,
     /// has wheel options
     pub wheels: VehicleCapability,
 }
@@ -240,11 +525,17 @@ impl ModDetailVehicleFlags {
     /// Create new vehicle flag sub-record
     fn new() -> Self {
         ModDetailVehicleFlags {
+            attacher_configs: VehicleCapability::No,
+            autoload: VehicleCapability::No,
             beacons: VehicleCapability::No,
             color: VehicleCapability::No,
+            dynamic_mount: VehicleCapability::No,
             enterable: VehicleCapability::No,
+            license_plates: VehicleCapability::No,
             lights: VehicleCapability::No,
             motorized: VehicleCapability::No,
+            passenger_seats: VehicleCapability::No,
+            warning_signs: VehicleCapability::No,
             wheels: VehicleCapability::No,
         }
     }
@@ -262,8 +553,21 @@ impl Serialize for VehicleCapability {
     }
 }
 
+impl<'de> Deserialize<'de> for VehicleCapability {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if bool::deserialize(deserializer)? {
+            Ok(VehicleCapability::Yes)
+        } else {
+            Ok(VehicleCapability::No)
+        }
+    }
+}
+
 /// Vehicle engine sub-record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailVehicleEngine {
     /// fuel type
@@ -286,7 +590,7 @@ impl ModDetailVehicleEngine {
 }
 
 /// Vehicle spray variant
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailSprayType {
     /// fill types supported
@@ -296,7 +600,7 @@ pub struct ModDetailSprayType {
 }
 
 /// Vehicle fill and spray sub-record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailVehicleFillSpray {
     /// fill categories for storage
@@ -307,6 +611,8 @@ pub struct ModDetailVehicleFillSpray {
     pub fill_type: Vec<String>,
     /// list of spray variants
     pub spray_types: Vec<ModDetailSprayType>,
+    /// separate tanks of the largest fill configuration (e.g. seed and fertilizer)
+    pub tanks: Vec<ModDetailVehicleTank>,
 }
 
 impl ModDetailVehicleFillSpray {
@@ -317,12 +623,232 @@ impl ModDetailVehicleFillSpray {
             fill_level: 0,
             fill_type: vec![],
             spray_types: vec![],
+            tanks: vec![],
+        }
+    }
+}
+
+/// Single fill unit (tank) of a vehicle
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailVehicleTank {
+    /// tank capacity
+    pub capacity: u32,
+    /// fill categories for this tank
+    pub fill_cat: Vec<String>,
+    /// fill types for this tank
+    pub fill_type: Vec<String>,
+}
+
+/// Combination item of a vehicle
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailVehicleCombo {
+    /// xml path as listed in `<combination>`
+    pub file: String,
+    /// path points into the base game (`$data/...`)
+    pub base_game: bool,
+    /// store item key in this mod, if the combo is local and was found
+    pub item_key: Option<String>,
+    /// brand KEY of the combo item
+    pub brand: Option<String>,
+    /// category of the combo item
+    pub category: Option<String>,
+    /// name of the combo item
+    pub name: Option<String>,
+}
+
+/// Other storeItem record (hand tools, objects, etc.)
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailOther {
+    /// store category
+    pub category: Option<String>,
+    /// i3d asset referenced by the item (`<filename>`)
+    pub i3d_file: Option<String>,
+    /// path to base game icon
+    pub icon_base: Option<String>,
+    /// base64 webp icon, if loaded
+    pub icon_file: Option<String>,
+    /// original icon path
+    pub icon_orig: Option<String>,
+    /// master type (root element of the item XML)
+    pub master_type: String,
+    /// item name
+    pub name: Option<String>,
+    /// bale data (for bales)
+    pub object: Option<ModDetailObject>,
+    /// item price
+    pub price: u32,
+    /// category placed in the shop tree (base game categories only)
+    pub shop_category: Option<ModDetailShopCategory>,
+}
+
+impl ModDetailOther {
+    #[must_use]
+    /// Create new other item record
+    pub fn new(master_type: &str) -> Self {
+        ModDetailOther {
+            category: None,
+            i3d_file: None,
+            icon_base: None,
+            icon_file: None,
+            icon_orig: None,
+            master_type: master_type.to_owned(),
+            name: None,
+            object: None,
+            price: 0,
+            shop_category: None,
+        }
+    }
+}
+
+/// Bale and pallet object data
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailObject {
+    /// capacity in liters
+    pub capacity: u32,
+    /// diameter in meters (round bales)
+    pub diameter: Option<f32>,
+    /// accepted fill types
+    pub fill_types: Vec<String>,
+    /// height in meters
+    pub height: Option<f32>,
+    /// length in meters
+    pub length: Option<f32>,
+    /// width in meters
+    pub width: Option<f32>,
+}
+
+/// Vehicle connections sub-record (PTO and hoses)
+///
+/// Connection types are `pto`, `hydraulic`, `electric`, `air`, `isobus`,
+/// or the lowercase hose type for anything else
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailVehicleConnections {
+    /// connections this vehicle needs from the vehicle it is attached to
+    pub requires: Vec<String>,
+    /// connections this vehicle offers to attached implements
+    pub provides: Vec<String>,
+}
+
+/// Vehicle configuration choices sub-record
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailVehicleConfigurations {
+    /// color and material choices, in the order of the XML
+    pub colors: Vec<ModDetailVehicleColor>,
+    /// wheel and tire choices, in the order of the XML
+    pub wheels: Vec<ModDetailVehicleWheel>,
+}
+
+/// Single color or material configuration choice
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailVehicleColor {
+    /// configuration set, the tag name without `Configuration`
+    /// (e.g. `baseMaterial`, `rimColor`)
+    pub kind: String,
+    /// display name (may be an l10n key)
+    pub name: Option<String>,
+    /// color as written, RGB(A) values or a shared color name
+    pub color: Option<String>,
+    /// linear RGB values (0-1), when the color is written as numbers
+    pub rgb: Option<[f32; 3]>,
+    /// sRGB hex code (`#rrggbb`) for swatches, when the color is written as numbers
+    pub hex: Option<String>,
+    /// material template or material id, if set
+    pub material: Option<String>,
+    /// extra price of the choice
+    pub price: Option<u32>,
+}
+
+/// Single wheel configuration choice
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailVehicleWheel {
+    /// display name (may be an l10n key)
+    pub name: Option<String>,
+    /// tire brand KEY, from the configuration or the shared tire path
+    pub brand: Option<String>,
+    /// tire model, the folder of the shared tire file (e.g. `contiCrossTracHS3`)
+    pub tire_model: Option<String>,
+    /// physics tire type (e.g. `mud`, `street`, `crawler`)
+    pub tire_type: Option<String>,
+    /// extra price of the choice
+    pub price: Option<u32>,
+}
+
+/// Vehicle wear, dirt and upkeep sub-record
+///
+/// Wear and dirt hold the numeric attributes of the `<wearable>` and
+/// `<washable>` tags as written, e.g. `wearDuration` and `dirtDuration`
+/// (minutes of work to fully wear or dirty), `workMultiplier` and
+/// `fieldMultiplier`
+///
+/// There are no repair or repaint price fields. The base game works both
+/// out in script from the vehicle price and its damage or wear, the vehicle
+/// XML has no factor for them. Factors a mod script reads from `<wearable>`
+/// (e.g. `repairPriceFactor`) are kept in `wear` like any other attribute.
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailVehicleMaintenance {
+    /// daily upkeep cost (`storeData` dailyUpkeep)
+    pub daily_upkeep: Option<u32>,
+    /// `<washable>` parameters
+    pub dirt: BTreeMap<String, f32>,
+    /// `<wearable>` parameters
+    pub wear: BTreeMap<String, f32>,
+}
+
+/// Vehicle power requirements sub-record
+///
+/// Power is in horse power, like the `neededPower` store spec. PTO power
+/// in the vehicle XML is in kilowatts and is converted
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailVehiclePower {
+    /// hydraulic hoses the vehicle needs connected
+    pub hydraulic_hoses: u32,
+    /// draft force of ground working tools, in kN (`powerConsumer` maxForce)
+    pub max_force: Option<f32>,
+    /// `neededPower` store spec
+    pub needed_power: Option<u32>,
+    /// PTO power needed at full load (`neededMaxPtoPower`)
+    pub pto_power_max: Option<u32>,
+    /// PTO power needed when idle (`neededMinPtoPower`)
+    pub pto_power_min: Option<u32>,
+    /// PTO shaft speed
+    pub pto_rpm: Option<u32>,
+}
+
+/// Vehicle work width section sub-record
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailVehicleSections {
+    /// number of switchable sections
+    pub count: u32,
+    /// has variable work width (section control ready)
+    pub section_control: bool,
+    /// section widths in meters, left to right (where defined)
+    pub widths: Vec<f32>,
+}
+
+impl ModDetailVehicleSections {
+    /// create new vehicle sections sub-record
+    fn new() -> Self {
+        ModDetailVehicleSections {
+            count: 0,
+            section_control: false,
+            widths: vec![],
         }
     }
 }
 
 /// Vehicle spec sub-record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailVehicleSpecs {
     /// vehicle functions
@@ -331,12 +857,16 @@ pub struct ModDetailVehicleSpecs {
     pub joint_accepts: Vec<String>,
     /// this vehicle needs to connect to these type of joints
     pub joint_requires: Vec<String>,
+    /// loader tool mounting standards (euro, sms, skidSteer, telehandler)
+    pub loader_mounts: Vec<String>,
     /// vehicle name
     pub name: String,
     /// vehicle price
     pub price: u32,
     /// list of included specs
     pub specs: HashMap<String, u32>,
+    /// number of tension belts (largest configuration)
+    pub tension_belts: u32,
     /// vehicle weight
     pub weight: u32,
 }
@@ -348,34 +878,54 @@ impl ModDetailVehicleSpecs {
             functions: vec![],
             joint_accepts: vec![],
             joint_requires: vec![],
+            loader_mounts: vec![],
             name: String::new(),
             price: 0,
             specs: HashMap::new(),
+            tension_belts: 0,
             weight: 0,
         }
     }
 }
 
 /// Vehicle storeItem record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailVehicle {
+    /// configuration choices (colors and materials)
+    pub configurations: ModDetailVehicleConfigurations,
+    /// required and offered PTO / hose connections
+    pub connections: ModDetailVehicleConnections,
     /// fills and sprays
     pub fill_spray: ModDetailVehicleFillSpray,
     /// feature flags
     pub flags: ModDetailVehicleFlags,
+    /// i3d asset referenced by the item (`<filename>`)
+    pub i3d_file: Option<String>,
     /// path to base game icon
     pub icon_base: Option<String>,
     /// base64 webp icon, if loaded
     pub icon_file: Option<String>,
     /// original icon path
     pub icon_orig: Option<String>,
+    /// wear, dirt and upkeep parameters, if any
+    pub maintenance: Option<ModDetailVehicleMaintenance>,
     /// master type (vehicle)
     pub master_type: String,
     /// motor information
     pub motor: ModDetailVehicleEngine,
+    /// pallet data (for pallets)
+    pub object: Option<ModDetailObject>,
     /// File is a sub of a different item
     pub parent_item: Option<String>,
+    /// interior and polish detail counts (dashboards, mirrors, ...), if any
+    ///
+    /// `motorStartDuration` is in milliseconds, everything else is a count
+    pub polish_indicators: Option<BTreeMap<String, u32>>,
+    /// power, PTO and hydraulic needs of implements, if any
+    pub power_requirements: Option<ModDetailVehiclePower>,
+    /// work width sections
+    pub sections: ModDetailVehicleSections,
     /// sorting information
     pub sorting: ModDetailVehicleSorting,
     /// vehicle specs
@@ -387,14 +937,22 @@ impl ModDetailVehicle {
     /// Create new vehicle record
     pub fn new() -> Self {
         ModDetailVehicle {
+            configurations: ModDetailVehicleConfigurations::default(),
+            connections: ModDetailVehicleConnections::default(),
             fill_spray: ModDetailVehicleFillSpray::new(),
             flags: ModDetailVehicleFlags::new(),
+            i3d_file: None,
             icon_base: None,
             icon_file: None,
             icon_orig: None,
+            maintenance: None,
             master_type: String::from("vehicle"),
             parent_item: None,
             motor: ModDetailVehicleEngine::new(),
+            object: None,
+            polish_indicators: None,
+            power_requirements: None,
+            sections: ModDetailVehicleSections::new(),
             sorting: ModDetailVehicleSorting::new(),
             specs: ModDetailVehicleSpecs::new(),
         }
@@ -407,8 +965,34 @@ impl Default for ModDetailVehicle {
     }
 }
 
-/// motor value definition (hp, kph, or mph)
-#[derive(serde::Serialize)]
+/// Which unit tables are built for motors, see [`MotorEntry`]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotorUnits {
+    /// Horse power, kilowatts, kph and mph
+    #[default]
+    Both,
+    /// Kilowatts and kph
+    Metric,
+    /// Horse power and mph
+    Imperial,
+}
+
+impl MotorUnits {
+    /// Metric tables (kW, kph) are built
+    #[must_use]
+    pub fn metric(self) -> bool {
+        self != MotorUnits::Imperial
+    }
+
+    /// Imperial tables (hp, mph) are built
+    #[must_use]
+    pub fn imperial(self) -> bool {
+        self != MotorUnits::Metric
+    }
+}
+
+/// motor value definition (hp, kW, kph, or mph)
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MotorValue {
     /// RPM value
@@ -432,20 +1016,44 @@ impl MotorValue {
     }
 }
 
+/// raw motor torque curve point
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MotorTorquePoint {
+    /// RPM (`rpm`, or `normRpm` times the motor max RPM)
+    pub rpm: f32,
+    /// normalized torque, before [`MotorEntry::torque_scale`]
+    pub torque: f32,
+}
+
 /// motor definition
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MotorEntry {
     /// name of motor
     pub name: String,
-    /// list of rpm->hp values
+    /// transmission axle ratio
+    pub axle_ratio: Option<f32>,
+    /// forward gear ratios, for transmissions that list them
+    pub gear_ratios: Vec<f32>,
+    /// list of rpm->hp values (see [`MotorUnits`])
     pub horse_power: Vec<MotorValue>,
+    /// list of rpm->kW values (see [`MotorUnits`])
+    pub kilowatts: Vec<MotorValue>,
+    /// motor max RPM
+    pub max_rpm: f32,
     /// maximum stated speed (from author)
     pub max_speed: u32,
-    /// list of rpm->kph values
+    /// transmission minimum forward gear ratio, if set directly
+    pub min_forward_gear_ratio: Option<f32>,
+    /// list of rpm->kph values (see [`MotorUnits`])
     pub speed_kph: Vec<MotorValue>,
-    /// list of rpm->mph values
+    /// list of rpm->mph values (see [`MotorUnits`])
     pub speed_mph: Vec<MotorValue>,
+    /// raw torque curve, the source of the hp and speed values
+    pub torque_curve: Vec<MotorTorquePoint>,
+    /// motor torque scale
+    pub torque_scale: f32,
 }
 
 impl MotorEntry {
@@ -454,16 +1062,23 @@ impl MotorEntry {
     pub fn new(name: String, max_speed: u32) -> Self {
         MotorEntry {
             name,
+            axle_ratio: None,
+            gear_ratios: vec![],
             horse_power: vec![],
+            kilowatts: vec![],
+            max_rpm: 1800.0,
             max_speed,
+            min_forward_gear_ratio: None,
             speed_kph: vec![],
             speed_mph: vec![],
+            torque_curve: vec![],
+            torque_scale: 1.0,
         }
     }
 }
 
 /// placable sorting information sub-record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailPlaceSorting {
     /// category
@@ -478,6 +1093,8 @@ pub struct ModDetailPlaceSorting {
     pub name: Option<String>,
     /// price
     pub price: u32,
+    /// category placed in the shop tree (base game categories only)
+    pub shop_category: Option<ModDetailShopCategory>,
     /// type name
     pub type_name: Option<String>,
 }
@@ -492,13 +1109,14 @@ impl ModDetailPlaceSorting {
             income_per_hour: 0,
             name: None,
             price: 0,
+            shop_category: None,
             type_name: None,
         }
     }
 }
 
 /// placable husbandry sub-record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailPlaceAnimals {
     /// is a beehive
@@ -529,8 +1147,91 @@ impl ModDetailPlaceAnimals {
     }
 }
 
+/// placable placement and terraforming sub-record
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailPlacePlacement {
+    /// areas cleared of foliage and objects when placed (`<clearArea>`)
+    pub clear_areas: u32,
+    /// daily upkeep cost
+    pub daily_upkeep: u32,
+    /// ground types painted under the placeable (level and paint areas)
+    pub ground_types: Vec<String>,
+    /// terrain areas flattened when placed (`<levelArea>`)
+    pub level_areas: u32,
+    /// steepest slope the leveling will smooth to, in degrees
+    pub max_slope: Option<f32>,
+    /// terrain areas painted when placed (`<paintArea>`)
+    pub paint_areas: u32,
+    /// terrain must be leveled to place (`requireLeveling`)
+    pub require_leveling: bool,
+    /// areas that must be free of objects to place (`<testArea>`)
+    pub test_areas: u32,
+}
+
+impl ModDetailPlacePlacement {
+    /// Placing the item flattens terrain
+    #[must_use]
+    pub fn flattens_terrain(&self) -> bool {
+        self.require_leveling && self.level_areas > 0
+    }
+}
+
+/// placable power generation sub-record (solar, wind, generators)
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailPlacePower {
+    /// is a power generator
+    pub exists: bool,
+    /// base income per hour
+    pub income_per_hour: u32,
+    /// income factor by time of day, empty if the income is constant
+    pub income_curve: Vec<ModDetailPlacePowerKey>,
+    /// kind of generator (`solar`, `wind` or `generator`)
+    pub kind: Option<String>,
+    /// battery storage capacity (modded placeables only)
+    pub storage_capacity: Option<u32>,
+}
+
+impl ModDetailPlacePower {
+    /// create new placeable power sub-record
+    fn new() -> Self {
+        ModDetailPlacePower {
+            exists: false,
+            income_per_hour: 0,
+            income_curve: vec![],
+            kind: None,
+            storage_capacity: None,
+        }
+    }
+}
+
+/// Point on a power generator income curve
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailPlacePowerKey {
+    /// hour of the day (0-24)
+    pub hour: f32,
+    /// income factor at that hour
+    pub factor: f32,
+}
+
+/// Totals across every placeable in a mod
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailPlaceTotals {
+    /// income generated per hour
+    pub income_per_hour: u32,
+    /// running cost per hour of every production
+    pub production_cost_per_hour: f32,
+    /// number of productions
+    pub productions: usize,
+    /// silo capacity
+    pub silo_capacity: u32,
+}
+
 /// placable storage sub-record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailPlaceStorage {
     /// number of objects for object storage types
@@ -558,13 +1259,59 @@ impl ModDetailPlaceStorage {
     }
 }
 
+/// Animal food data
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailAnimalFood {
+    /// food groups, by animal type
+    pub animals: HashMap<String, Vec<AnimalFoodGroup>>,
+    /// mixed feeds (TMR, pig food, etc.)
+    pub mixtures: Vec<AnimalFoodMixture>,
+}
+
+/// Animal food group
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimalFoodGroup {
+    /// share of food eaten from this group
+    pub eat_weight: f32,
+    /// fill types in the group
+    pub fill_types: Vec<String>,
+    /// effect of this group on production
+    pub production_weight: f32,
+    /// group title
+    pub title: String,
+}
+
+/// Mixed animal feed recipe
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimalFoodMixture {
+    /// animal type the mixture is for
+    pub animal_type: Option<String>,
+    /// fill type produced
+    pub fill_type: String,
+    /// ingredients of the mixture
+    pub ingredients: Vec<AnimalFoodIngredient>,
+}
+
+/// Mixed animal feed ingredient
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimalFoodIngredient {
+    /// fill types that satisfy this ingredient
+    pub fill_types: Vec<String>,
+    /// share of the mixture (0-1)
+    pub weight: f32,
+}
+
 /// Production ingredient list
 pub type ProductionIngredients = Vec<ProductionIngredient>;
 /// Production recipe (list of list of ingredients - ingredients in nested level are "OR", ingredient list in top level is "AND")
 pub type ProductionRecipe = Vec<ProductionIngredients>;
 
 /// Production ingredient
-#[derive(serde::Serialize, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ProductionIngredient {
     /// quantity for ingredient
@@ -581,7 +1328,7 @@ impl ProductionIngredient {
 }
 
 /// production boost type
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ProductionBoost {
     /// quantity for boots
@@ -604,7 +1351,7 @@ impl ProductionBoost {
 }
 
 /// Placeable production record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailProduction {
     /// list of boosts
@@ -646,11 +1393,13 @@ impl Default for ModDetailProduction {
 }
 
 /// Placable record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDetailPlace {
     /// beehive and husbandry
     pub animals: ModDetailPlaceAnimals,
+    /// i3d asset referenced by the item (`<filename>`)
+    pub i3d_file: Option<String>,
     /// path to base game icon
     pub icon_base: Option<String>,
     /// base64 webp icon, if loaded
@@ -661,6 +1410,10 @@ pub struct ModDetailPlace {
     pub master_type: String,
     /// File is a sub of a different item
     pub parent_item: Option<String>,
+    /// placement constraints and terraforming (if any are defined)
+    pub placement: Option<ModDetailPlacePlacement>,
+    /// power generation
+    pub power: ModDetailPlacePower,
     /// production list
     pub productions: Vec<ModDetailProduction>,
     /// show in store
@@ -677,11 +1430,14 @@ impl ModDetailPlace {
     pub fn new() -> Self {
         ModDetailPlace {
             animals: ModDetailPlaceAnimals::new(),
+            i3d_file: None,
             icon_base: None,
             icon_file: None,
             icon_orig: None,
             master_type: String::from("placeable"),
             parent_item: None,
+            placement: None,
+            power: ModDetailPlacePower::new(),
             productions: vec![],
             show_in_store: true,
             sorting: ModDetailPlaceSorting::new(),
@@ -695,3 +1451,140 @@ impl Default for ModDetailPlace {
         Self::new()
     }
 }
+
+/// Expected cost of a full detail parse
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum ModDetailPlanCost {
+    /// a handful of store items, finishes quickly
+    Light,
+    /// noticeable wait
+    Moderate,
+    /// large pack, long parse
+    Heavy,
+}
+
+impl ModDetailPlanCost {
+    /// Work units (store items plus weighted icons) below which a parse is light
+    const LIGHT_BELOW: usize = 50;
+    /// Work units below which a parse is moderate
+    const MODERATE_BELOW: usize = 250;
+    /// Icon conversion costs roughly this many store item parses
+    const ICON_WEIGHT: usize = 4;
+
+    /// Classify a parse by the number of store items and icons
+    #[must_use]
+    pub fn classify(store_items: usize, icons: usize) -> Self {
+        let work = store_items + icons * Self::ICON_WEIGHT;
+
+        if work < Self::LIGHT_BELOW {
+            ModDetailPlanCost::Light
+        } else if work < Self::MODERATE_BELOW {
+            ModDetailPlanCost::Moderate
+        } else {
+            ModDetailPlanCost::Heavy
+        }
+    }
+}
+
+impl Serialize for ModDetailPlanCost {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            ModDetailPlanCost::Light => {
+                serializer.serialize_unit_variant("ModDetailPlanCost", 0, "LIGHT")
+            }
+            ModDetailPlanCost::Moderate => {
+                serializer.serialize_unit_variant("ModDetailPlanCost", 1, "MODERATE")
+            }
+            ModDetailPlanCost::Heavy => {
+                serializer.serialize_unit_variant("ModDetailPlanCost", 2, "HEAVY")
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ModDetailPlanCost {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                ModDetailPlanCost::Light,
+                ModDetailPlanCost::Moderate,
+                ModDetailPlanCost::Heavy,
+            ],
+        )
+    }
+}
+
+/// Work a detail parse would do, read from modDesc.xml only
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDetailPlan {
+    /// added brands
+    pub brands: usize,
+    /// expected cost of the parse
+    pub cost: ModDetailPlanCost,
+    /// icons that would be converted (upper bound, store items may use base game icons)
+    pub icons: usize,
+    /// list of detected issues
+    pub issues: HashSet<ModDetailError>,
+    /// declared store items missing from the mod
+    pub missing_store_items: usize,
+    /// declared store items
+    pub store_items: usize,
+    /// uncompressed size of the store item XML files
+    pub store_item_bytes: u64,
+}
+
+impl ModDetailPlan {
+    /// Create new empty plan
+    #[must_use]
+    pub fn new() -> Self {
+        ModDetailPlan {
+            brands: 0,
+            cost: ModDetailPlanCost::Light,
+            icons: 0,
+            issues: HashSet::new(),
+            missing_store_items: 0,
+            store_items: 0,
+            store_item_bytes: 0,
+        }
+    }
+
+    /// Create new plan with a single error condition
+    #[must_use]
+    pub fn fast_fail(e: ModDetailError) -> Self {
+        let mut record = ModDetailPlan::default();
+        record.issues.insert(e);
+        record
+    }
+
+    /// Output as pretty-print JSON
+    #[must_use]
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or(String::from("{}"))
+    }
+
+    /// Output as JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Default for ModDetailPlan {
+    fn default() -> Self {
+        ModDetailPlan::new()
+    }
+}
+
+impl std::fmt::Display for ModDetailPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&serde_json::to_string(&self).unwrap_or(String::from("{}")))
+    }
+}
@@ -1,11 +1,19 @@
 //! Parse vehicles
-use super::{default_float_parse, xml_extract_text_as_opt_string, xml_extract_text_as_opt_u32};
+use super::{
+    default_float_parse, xml_extract_object_size, xml_extract_text_as_opt_string,
+    xml_extract_text_as_opt_u32,
+};
 use crate::mod_detail::structs::{
-    ModDetailSprayType, ModDetailVehicle, MotorEntry, MotorValue, VehicleCapability,
+    ModDetailObject, ModDetailShopCategory, ModDetailSprayType, ModDetailVehicle,
+    ModDetailVehicleColor, ModDetailVehicleCombo, ModDetailVehicleMaintenance,
+    ModDetailVehiclePower, ModDetailVehicleTank, ModDetailVehicleWheel, ModDetailYearConfidence,
+    MotorEntry, MotorTorquePoint, MotorUnits, MotorValue, VehicleCapability,
 };
 use crate::shared::files::AbstractFileHandle;
 use crate::shared::{convert_mod_icon, extract_and_normalize_image};
 use crate::ModParserOptions;
+use chrono::{Datelike, Utc};
+use std::collections::{BTreeMap, HashMap};
 use std::f32::consts::PI;
 
 /// Parse a vehicle
@@ -13,6 +21,32 @@ use std::f32::consts::PI;
 /// # Sample Output
 /// ```json
 ///{
+///    "configurations": {
+///        "colors": [
+///            {
+///                "kind": "baseMaterial",
+///                "name": null,
+///                "color": "0.0284 0.1441 0.0284 1",
+///                "rgb": [ 0.0284, 0.1441, 0.0284 ],
+///                "hex": "#2f6a2f",
+///                "material": null,
+///                "price": null
+///            }
+///        ],
+///        "wheels": [
+///            {
+///                "name": "$l10n_configuration_valueDefault",
+///                "brand": "MITAS",
+///                "tireModel": "AC85",
+///                "tireType": null,
+///                "price": 0
+///            }
+///        ]
+///    },
+///    "connections": {
+///        "requires": [ "electric", "hydraulic", "pto" ],
+///        "provides": []
+///    },
 ///    "fillSpray": {
 ///        "fillCat": [],
 ///        "fillLevel": 11433,
@@ -22,6 +56,10 @@ use std::f32::consts::PI;
 ///                "fills": [ "fertilizer" ],
 ///                "width": null
 ///            }
+///        ],
+///        "tanks": [
+///            { "capacity": 6433, "fillCat": [], "fillType": [ "seeds" ] },
+///            { "capacity": 5000, "fillCat": [], "fillType": [ "liquidfertilizer" ] }
 ///        ]
 ///    },
 ///    "flags": {
@@ -32,6 +70,7 @@ use std::f32::consts::PI;
 ///        "motorized": false,
 ///        "wheels": true
 ///    },
+///    "i3dFile": "vehicles/1775NT/1775NT.i3d",
 ///    "iconBase": null,
 ///    "iconFile": null,
 ///    "masterType": "vehicle",
@@ -40,14 +79,23 @@ use std::f32::consts::PI;
 ///        "transmissionType": null,
 ///        "motors": []
 ///    },
+///    "object": null,
+///    "sections": {
+///        "count": 2,
+///        "sectionControl": true,
+///        "widths": [ 6.0, 6.0 ]
+///    },
 ///    "sorting": {
 ///        "brand": "JOHNDEERE",
 ///        "category": "planters",
 ///        "combos": [],
+///        "comboItems": [],
+///        "decade": 2020,
 ///        "name": "1775NT 2022",
 ///        "typeName": "fertilizingSowingMachine",
 ///        "typeDescription": "$l10n_typeDesc_sowingMachine",
-///        "year": null
+///        "year": 2022,
+///        "yearConfidence": "LOW"
 ///    },
 ///    "specs": {
 ///        "functions": [
@@ -56,6 +104,7 @@ use std::f32::consts::PI;
 ///        ],
 ///        "jointAccepts": [ "trailer", "trailerLow" ],
 ///        "jointRequires": [ "implement" ],
+///        "loaderMounts": [],
 ///        "name": "1775NT 2022",
 ///        "price": 362878,
 ///        "specs": {
@@ -77,7 +126,18 @@ pub fn vehicle_parse(
     vehicle_parse_flags(xml_tree, &mut this_vehicle);
     vehicle_parse_specs(xml_tree, &mut this_vehicle);
     vehicle_parse_fills(xml_tree, &mut this_vehicle);
-    vehicle_parse_motor(xml_tree, &mut this_vehicle);
+    vehicle_parse_motor(xml_tree, &mut this_vehicle, options.motor_units);
+    vehicle_parse_sections(xml_tree, &mut this_vehicle);
+    vehicle_parse_connections(xml_tree, &mut this_vehicle);
+    vehicle_parse_power(xml_tree, &mut this_vehicle);
+    vehicle_parse_maintenance(xml_tree, &mut this_vehicle);
+    vehicle_parse_polish(xml_tree, &mut this_vehicle);
+    vehicle_parse_configurations(xml_tree, &mut this_vehicle);
+    vehicle_parse_wheels(xml_tree, &mut this_vehicle);
+
+    if xml_tree.root_element().attribute("type") == Some("pallet") {
+        vehicle_parse_pallet(xml_tree, &mut this_vehicle);
+    }
 
     if !options.skip_detail_icons {
         let image_entry = extract_and_normalize_image(xml_tree, "image");
@@ -122,11 +182,77 @@ impl TorqueEntry {
     }
 }
 
+/// Transient transmission ratios, as listed in the vehicle XML
+#[derive(Default)]
+struct TransmissionRatios {
+    /// axle ratio
+    pub axle: Option<f32>,
+    /// forward gear ratios
+    pub forward_gears: Vec<f32>,
+    /// minimum forward gear ratio
+    pub min_forward: Option<f32>,
+}
+
+impl TransmissionRatios {
+    /// Read the ratios of a transmission node
+    fn new(node: &roxmltree::Node) -> Self {
+        let ratio = |n: &roxmltree::Node, key: &str| n.attribute(key).and_then(|v| v.parse().ok());
+        TransmissionRatios {
+            axle: ratio(node, "axleRatio"),
+            forward_gears: node
+                .children()
+                .filter(|n| n.has_tag_name("forwardGear"))
+                .filter_map(|n| ratio(&n, "gearRatio"))
+                .collect(),
+            min_forward: ratio(node, "minForwardGearRatio"),
+        }
+    }
+}
+
+/// Lowest forward gear ratio of a transmission, including the axle ratio
+///
+/// Uses `minForwardGearRatio` when set, otherwise the lowest ratio of the
+/// forward gears (from `gearRatio`, or derived from `maxSpeed`)
+fn min_forward_ratio(transmission: &roxmltree::Node, motor_rpm: f32) -> f32 {
+    let axel_ratio = transmission
+        .attribute("axleRatio")
+        .map_or(1_f32, |n| n.parse::<f32>().unwrap_or(1_f32));
+
+    if let Some(fwd_gear_ratio) = transmission.attribute("minForwardGearRatio") {
+        return axel_ratio * default_float_parse(fwd_gear_ratio, 1_f32);
+    }
+
+    let mut min_ratio = f32::MAX;
+    for forward_gear in transmission
+        .children()
+        .filter(|n| n.has_tag_name("forwardGear"))
+    {
+        if let Some(known_ratio) = forward_gear.attribute("gearRatio") {
+            min_ratio = f32::min(
+                min_ratio,
+                axel_ratio * default_float_parse(known_ratio, 1_f32),
+            );
+        } else if let Some(known_max) = forward_gear.attribute("maxSpeed") {
+            min_ratio = f32::min(
+                min_ratio,
+                axel_ratio
+                    * (motor_rpm * PI / (default_float_parse(known_max, 1_f32) / 3.6_f32 * 30_f32)),
+            );
+        }
+    }
+    min_ratio
+}
+
 /// Parse motor configurations
-fn vehicle_parse_motor(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDetailVehicle) {
+fn vehicle_parse_motor(
+    xml_tree: &roxmltree::Document,
+    this_vehicle: &mut ModDetailVehicle,
+    units: MotorUnits,
+) {
     let mut torque_entries: Vec<TorqueEntry> = vec![];
     let mut motor_rpm = 1800_f32;
     let mut transmission_name = "";
+    let mut ratios = TransmissionRatios::default();
     let mut min_fwd_gear_and_axel_ratio = f32::MAX;
 
     for motor_config in xml_tree
@@ -167,8 +293,7 @@ fn vehicle_parse_motor(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDet
             .children()
             .find(|n| n.has_tag_name("transmission"))
         {
-            // Invalidate the old ratio
-            min_fwd_gear_and_axel_ratio = f32::MAX;
+            ratios = TransmissionRatios::new(&new_transmission);
 
             // New name found, overwrite "last"
             if let Some(trans_name) = new_transmission.attribute("name") {
@@ -178,35 +303,7 @@ fn vehicle_parse_motor(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDet
                 }
             }
 
-            let axel_ratio = new_transmission
-                .attribute("axleRatio")
-                .map_or(1_f32, |n| n.parse::<f32>().unwrap_or(1_f32));
-
-            if let Some(fwd_gear_ratio) = new_transmission.attribute("minForwardGearRatio") {
-                // found minForwardGearRatio, can calculate `min_fwd_gear_and_axel_ratio`
-                min_fwd_gear_and_axel_ratio =
-                    axel_ratio * default_float_parse(fwd_gear_ratio, 1_f32);
-            } else {
-                // we have to calculate the ratio
-                for forward_gear in new_transmission
-                    .children()
-                    .filter(|n| n.has_tag_name("forwardGear"))
-                {
-                    if let Some(known_ratio) = forward_gear.attribute("gearRatio") {
-                        min_fwd_gear_and_axel_ratio = f32::min(
-                            min_fwd_gear_and_axel_ratio,
-                            axel_ratio * default_float_parse(known_ratio, 1_f32),
-                        );
-                    } else if let Some(known_max) = forward_gear.attribute("maxSpeed") {
-                        min_fwd_gear_and_axel_ratio = f32::min(
-                            min_fwd_gear_and_axel_ratio,
-                            axel_ratio
-                                * (motor_rpm * PI
-                                    / (default_float_parse(known_max, 1_f32) / 3.6_f32 * 30_f32)),
-                        );
-                    }
-                }
-            }
+            min_fwd_gear_and_axel_ratio = min_forward_ratio(&new_transmission, motor_rpm);
         }
         // end new transmission
 
@@ -227,13 +324,19 @@ fn vehicle_parse_motor(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDet
             full_name.push_str(motor_hp_name);
         }
 
-        this_vehicle.motor.motors.push(vehicle_build_motor(
+        let mut motor_record = vehicle_build_motor(
             full_name,
             defined_max_speed,
             &torque_entries,
             min_fwd_gear_and_axel_ratio,
             motor_scale,
-        ));
+            units,
+        );
+        motor_record.axle_ratio = ratios.axle;
+        motor_record.gear_ratios.clone_from(&ratios.forward_gears);
+        motor_record.max_rpm = motor_rpm;
+        motor_record.min_forward_gear_ratio = ratios.min_forward;
+        this_vehicle.motor.motors.push(motor_record);
     } // end motor_config
 
     this_vehicle.motor.fuel_type = xml_tree
@@ -251,22 +354,35 @@ fn vehicle_build_motor(
     torque_entries: &Vec<TorqueEntry>,
     min_fwd_gear_and_axel_ratio: f32,
     motor_scale: f32,
+    units: MotorUnits,
 ) -> MotorEntry {
     let mut motor_record = MotorEntry::new(full_name, defined_max_speed);
+    motor_record.torque_scale = motor_scale;
 
     for torque_entry in torque_entries {
-        motor_record.horse_power.push(MotorValue::new(
-            torque_entry.rpm,
-            motor_scale * (1.359_621_6 * PI * torque_entry.rpm * torque_entry.torque) / 30.0,
-        ));
-        motor_record.speed_kph.push(MotorValue::new(
-            torque_entry.rpm,
-            3.6 * ((torque_entry.rpm * PI) / (30.0 * min_fwd_gear_and_axel_ratio)),
-        ));
-        motor_record.speed_mph.push(MotorValue::new(
-            torque_entry.rpm,
-            3.6 * ((torque_entry.rpm * PI) / (30.0 * min_fwd_gear_and_axel_ratio) * 0.621_371),
-        ));
+        let kilowatts = motor_scale * (PI * torque_entry.rpm * torque_entry.torque) / 30.0;
+        let kph = 3.6 * ((torque_entry.rpm * PI) / (30.0 * min_fwd_gear_and_axel_ratio));
+
+        if units.imperial() {
+            motor_record
+                .horse_power
+                .push(MotorValue::new(torque_entry.rpm, kilowatts * 1.359_621_6));
+            motor_record
+                .speed_mph
+                .push(MotorValue::new(torque_entry.rpm, kph * 0.621_371));
+        }
+        if units.metric() {
+            motor_record
+                .kilowatts
+                .push(MotorValue::new(torque_entry.rpm, kilowatts));
+            motor_record
+                .speed_kph
+                .push(MotorValue::new(torque_entry.rpm, kph));
+        }
+        motor_record.torque_curve.push(MotorTorquePoint {
+            rpm: torque_entry.rpm,
+            torque: torque_entry.torque,
+        });
     }
 
     motor_record
@@ -281,6 +397,7 @@ fn vehicle_parse_fills(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDet
         .filter(|n| n.has_tag_name("fillUnitConfiguration"))
     {
         capacity.clear();
+        let mut tanks: Vec<ModDetailVehicleTank> = vec![];
 
         for fill_unit in fill_config.descendants().filter(|n| {
             n.has_tag_name("fillUnit")
@@ -294,18 +411,29 @@ fn vehicle_parse_fills(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDet
 
             capacity.push(fill_unit.attribute("capacity"));
 
+            let mut this_tank = ModDetailVehicleTank {
+                capacity: fill_unit
+                    .attribute("capacity")
+                    .and_then(|n| n.parse::<u32>().ok())
+                    .unwrap_or(0),
+                ..Default::default()
+            };
+
             if let Some(cats) = fill_unit.attribute("fillTypeCategories") {
+                this_tank.fill_cat = cats.split(' ').map(str::to_lowercase).collect();
                 this_vehicle
                     .fill_spray
                     .fill_cat
-                    .extend(cats.split(' ').map(|n| n.to_lowercase().clone()));
+                    .extend(this_tank.fill_cat.iter().cloned());
             }
             if let Some(cats) = fill_unit.attribute("fillTypes") {
+                this_tank.fill_type = cats.split(' ').map(str::to_lowercase).collect();
                 this_vehicle
                     .fill_spray
                     .fill_type
-                    .extend(cats.split(' ').map(|n| n.to_lowercase().clone()));
+                    .extend(this_tank.fill_type.iter().cloned());
             }
+            tanks.push(this_tank);
 
             let this_capacity = capacity
                 .clone()
@@ -314,6 +442,9 @@ fn vehicle_parse_fills(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDet
                 .flat_map(str::parse::<u32>)
                 .sum();
 
+            if this_capacity > total_capacity || this_vehicle.fill_spray.tanks.is_empty() {
+                this_vehicle.fill_spray.tanks.clone_from(&tanks);
+            }
             total_capacity = std::cmp::max(total_capacity, this_capacity);
         }
     }
@@ -355,12 +486,20 @@ fn vehicle_parse_sorting(xml_tree: &roxmltree::Document, this_vehicle: &mut ModD
     this_vehicle.sorting.name = xml_extract_text_as_opt_string(xml_tree, "name");
     this_vehicle.sorting.brand = xml_extract_text_as_opt_string(xml_tree, "brand");
     this_vehicle.sorting.category = xml_extract_text_as_opt_string(xml_tree, "category");
+    this_vehicle.sorting.shop_category = this_vehicle
+        .sorting
+        .category
+        .as_deref()
+        .and_then(ModDetailShopCategory::from_category);
     this_vehicle.sorting.type_description = xml_extract_text_as_opt_string(xml_tree, "typeDesc");
     this_vehicle.sorting.type_name = xml_tree
         .root_element()
         .attribute("type")
         .map(std::string::ToString::to_string);
-    this_vehicle.sorting.year = xml_extract_text_as_opt_u32(xml_tree, "year");
+    let year = vehicle_parse_year(xml_tree, this_vehicle.sorting.name.as_deref());
+    this_vehicle.sorting.year = year.map(|(year, _)| year);
+    this_vehicle.sorting.year_confidence = year.map(|(_, confidence)| confidence);
+    this_vehicle.sorting.decade = this_vehicle.sorting.year.map(|year| year / 10 * 10);
 
     this_vehicle.sorting.combos = xml_tree
         .descendants()
@@ -370,8 +509,125 @@ fn vehicle_parse_sorting(xml_tree: &roxmltree::Document, this_vehicle: &mut ModD
         .collect();
 }
 
+/// Resolve vehicle combos against the other vehicles of the mod
+///
+/// Local paths are matched to store item keys ignoring case and slash
+/// direction. Base game paths (`$data/...`) are flagged, but not resolved.
+pub(crate) fn vehicle_resolve_combos(vehicles: &mut HashMap<String, ModDetailVehicle>) {
+    let normalize = |path: &str| {
+        path.replace('\\', "/")
+            .trim_start_matches("./")
+            .to_lowercase()
+    };
+
+    let known_items: HashMap<String, ModDetailVehicleCombo> = vehicles
+        .iter()
+        .map(|(key, vehicle)| {
+            (
+                normalize(key),
+                ModDetailVehicleCombo {
+                    item_key: Some(key.clone()),
+                    brand: vehicle.sorting.brand.clone(),
+                    category: vehicle.sorting.category.clone(),
+                    name: vehicle.sorting.name.clone(),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+
+    for vehicle in vehicles.values_mut() {
+        vehicle.sorting.combo_items = vehicle
+            .sorting
+            .combos
+            .iter()
+            .map(|file| {
+                let base_game = file.starts_with("$data");
+                let found = if base_game {
+                    None
+                } else {
+                    known_items.get(&normalize(file)).cloned()
+                };
+                ModDetailVehicleCombo {
+                    file: file.clone(),
+                    base_game,
+                    ..found.unwrap_or_default()
+                }
+            })
+            .collect();
+    }
+}
+
+/// Alternative elements some authors use for the vehicle year
+const VEHICLE_YEAR_TAGS: [&str; 4] = [
+    "modelYear",
+    "productionYear",
+    "yearOfManufacture",
+    "buildYear",
+];
+
+/// Find the vehicle year, checking fallback sources in order
+///
+/// `<year>` element ([`ModDetailYearConfidence::High`]), then an alternative
+/// element or a `year` attribute on `storeData` or `motorized` (`Medium`),
+/// then a 4 digit model year ending the name or in parentheses, e.g.
+/// "1775NT 2022" or "Series 60 (1955)" (`Low`). Years past next year are
+/// ignored.
+fn vehicle_parse_year(
+    xml_tree: &roxmltree::Document,
+    name: Option<&str>,
+) -> Option<(u32, ModDetailYearConfidence)> {
+    let next_year = u32::try_from(Utc::now().year() + 1).unwrap_or(u32::MAX);
+    let plausible = |year: &u32| (1900..=next_year).contains(year);
+    let parse_year = |text: &str| text.trim().parse::<u32>().ok().filter(plausible);
+
+    xml_extract_text_as_opt_u32(xml_tree, "year")
+        .filter(plausible)
+        .map(|year| (year, ModDetailYearConfidence::High))
+        .or_else(|| {
+            xml_tree
+                .descendants()
+                .filter(|n| VEHICLE_YEAR_TAGS.iter().any(|tag| n.has_tag_name(*tag)))
+                .filter_map(|n| n.text())
+                .chain(
+                    xml_tree
+                        .descendants()
+                        .filter(|n| n.has_tag_name("storeData") || n.has_tag_name("motorized"))
+                        .filter_map(|n| n.attribute("year")),
+                )
+                .find_map(parse_year)
+                .map(|year| (year, ModDetailYearConfidence::Medium))
+        })
+        .or_else(|| {
+            let name = name.filter(|n| !n.starts_with("$l10n"))?.trim();
+            name.split('(')
+                .filter_map(|part| part.split_once(')').map(|(inside, _)| inside))
+                .chain(name.rsplit(char::is_whitespace).next())
+                .filter(|n| n.len() == 4 && n.chars().all(|c| c.is_ascii_digit()))
+                .filter_map(parse_year)
+                .next_back()
+                .map(|year| (year, ModDetailYearConfidence::Low))
+        })
+}
+
 /// Parse vehicle flags
+///
+/// Also counts tension belts, see [`count_tension_belts`]
 fn vehicle_parse_flags(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDetailVehicle) {
+    if xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("attacherJointConfiguration"))
+        .count()
+        > 1
+    {
+        this_vehicle.flags.attacher_configs = VehicleCapability::Yes;
+    }
+    if xml_tree
+        .descendants()
+        .any(|n| is_autoload_tag(n.tag_name().name()))
+    {
+        this_vehicle.flags.autoload = VehicleCapability::Yes;
+    }
     if xml_tree
         .descendants()
         .any(|n| n.has_tag_name("beaconLights"))
@@ -384,15 +640,39 @@ fn vehicle_parse_flags(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDet
     {
         this_vehicle.flags.color = VehicleCapability::Yes;
     }
+    if xml_tree
+        .descendants()
+        .any(|n| n.has_tag_name("dynamicMountAttacher"))
+    {
+        this_vehicle.flags.dynamic_mount = VehicleCapability::Yes;
+    }
     if xml_tree.descendants().any(|n| n.has_tag_name("enterable")) {
         this_vehicle.flags.enterable = VehicleCapability::Yes;
     }
+    if xml_tree
+        .descendants()
+        .any(|n| n.has_tag_name("licensePlates"))
+    {
+        this_vehicle.flags.license_plates = VehicleCapability::Yes;
+    }
     if xml_tree.descendants().any(|n| n.has_tag_name("realLights")) {
         this_vehicle.flags.lights = VehicleCapability::Yes;
     }
     if xml_tree.descendants().any(|n| n.has_tag_name("motorized")) {
         this_vehicle.flags.motorized = VehicleCapability::Yes;
     }
+    if xml_tree
+        .descendants()
+        .any(|n| is_passenger_tag(n.tag_name().name()))
+    {
+        this_vehicle.flags.passenger_seats = VehicleCapability::Yes;
+    }
+    if xml_tree
+        .descendants()
+        .any(|n| is_warning_sign_tag(n.tag_name().name()))
+    {
+        this_vehicle.flags.warning_signs = VehicleCapability::Yes;
+    }
     if xml_tree
         .descendants()
         .filter(|n| n.has_tag_name("wheelConfiguration"))
@@ -401,6 +681,248 @@ fn vehicle_parse_flags(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDet
     {
         this_vehicle.flags.wheels = VehicleCapability::Yes;
     }
+    this_vehicle.specs.tension_belts = count_tension_belts(xml_tree);
+}
+
+/// Check for warning sign tags
+///
+/// Covers `warningSigns` and `warningSignConfigurations`
+fn is_warning_sign_tag(tag_name: &str) -> bool {
+    tag_name.to_ascii_lowercase().contains("warningsign")
+}
+
+/// Check for autoload script tags
+///
+/// Covers `autoLoad`, and the hooks used by autoload script mods
+/// (`universalAutoload`, `aPalletAutoLoader`, ...)
+fn is_autoload_tag(tag_name: &str) -> bool {
+    tag_name.to_ascii_lowercase().contains("autoload")
+}
+
+/// Count tension belts
+///
+/// Belts are grouped by their parent, each tension belt configuration has
+/// its own group. The largest group is the most belts the vehicle can have
+fn count_tension_belts(xml_tree: &roxmltree::Document) -> u32 {
+    let mut groups: HashMap<roxmltree::NodeId, u32> = HashMap::new();
+
+    for belt in xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("tensionBelt"))
+    {
+        if let Some(parent) = belt.parent_element() {
+            *groups.entry(parent.id()).or_default() += 1;
+        }
+    }
+    groups.into_values().max().unwrap_or(0)
+}
+
+/// Check for passenger seat tags
+///
+/// Covers the game's own `passengerSeat` (in `passengerSeats`), and the
+/// `passenger` tag used by passenger script mods
+fn is_passenger_tag(tag_name: &str) -> bool {
+    matches!(tag_name, "passenger" | "passengerSeat")
+}
+
+/// Polish indicators counted as entries of a list, as (list, entry)
+///
+/// The list name is used as the key
+const POLISH_LISTS: [(&str, &str); 4] = [
+    ("animations", "animation"),
+    ("dashboards", "dashboard"),
+    ("mirrors", "mirror"),
+    ("wipers", "wiper"),
+];
+
+/// Parse color and material configurations
+///
+/// Covers `<baseMaterialConfiguration>`, `<rimColorConfiguration>` and the
+/// other `*ColorConfiguration` / `*MaterialConfiguration` choices, and the
+/// older `<color>` entries of a `*ColorConfigurations` list
+fn vehicle_parse_configurations(
+    xml_tree: &roxmltree::Document,
+    this_vehicle: &mut ModDetailVehicle,
+) {
+    for node in xml_tree.descendants().filter(roxmltree::Node::is_element) {
+        let tag_name = node.tag_name().name();
+        let kind = if tag_name == "color" {
+            node.parent_element()
+                .map(|n| n.tag_name().name())
+                .and_then(|n| n.strip_suffix("Configurations"))
+                .filter(|n| n.ends_with("Color"))
+        } else {
+            tag_name
+                .strip_suffix("Configuration")
+                .filter(|n| n.ends_with("Color") || n.ends_with("Material"))
+        };
+        let Some(kind) = kind else {
+            continue;
+        };
+
+        let color = node.attribute("color").map(str::trim);
+        let rgb = color.and_then(parse_linear_rgb);
+
+        this_vehicle
+            .configurations
+            .colors
+            .push(ModDetailVehicleColor {
+                kind: kind.to_owned(),
+                name: node.attribute("name").map(str::to_owned),
+                color: color.map(str::to_owned),
+                rgb,
+                hex: rgb.map(srgb_hex),
+                material: node
+                    .attribute("materialTemplateName")
+                    .or_else(|| node.attribute("material"))
+                    .or_else(|| node.attribute("materialId"))
+                    .map(str::to_owned),
+                price: node.attribute("price").and_then(|n| n.parse::<u32>().ok()),
+            });
+    }
+}
+
+/// Parse wheel configurations
+///
+/// Brand and model come from the `brand` attribute, or the path of a shared
+/// tire file (`$data/shared/wheels/tires/<brand>/<model>/...`). Crawler
+/// setups without a physics tire type are `crawler`.
+fn vehicle_parse_wheels(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDetailVehicle) {
+    for config in xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("wheelConfiguration"))
+    {
+        let tire_path: Option<Vec<&str>> = config
+            .descendants()
+            .filter(|n| n.has_tag_name("wheel"))
+            .filter_map(|n| n.attribute("filename"))
+            .find_map(|n| n.split_once("wheels/tires/"))
+            .map(|(_, path)| path.split('/').collect());
+        let tire_path = tire_path.unwrap_or_default();
+
+        this_vehicle
+            .configurations
+            .wheels
+            .push(ModDetailVehicleWheel {
+                name: config.attribute("name").map(str::to_owned),
+                brand: config
+                    .attribute("brand")
+                    .map(str::to_owned)
+                    .or_else(|| (tire_path.len() > 2).then(|| tire_path[0].to_uppercase())),
+                tire_model: (tire_path.len() > 2).then(|| tire_path[1].to_owned()),
+                tire_type: config
+                    .descendants()
+                    .filter(|n| n.has_tag_name("physics"))
+                    .find_map(|n| n.attribute("tireType"))
+                    .map(str::to_owned)
+                    .or_else(|| {
+                        config
+                            .descendants()
+                            .any(|n| n.has_tag_name("crawler"))
+                            .then(|| String::from("crawler"))
+                    }),
+                price: config
+                    .attribute("price")
+                    .and_then(|n| n.parse::<u32>().ok()),
+            });
+    }
+}
+
+/// Read an `"r g b"` or `"r g b a"` linear color
+fn parse_linear_rgb(color: &str) -> Option<[f32; 3]> {
+    let values: Vec<f32> = color
+        .split_whitespace()
+        .map(str::parse::<f32>)
+        .collect::<Result<_, _>>()
+        .ok()?;
+
+    match values[..] {
+        [red, green, blue] | [red, green, blue, _] => Some([red, green, blue]),
+        _ => None,
+    }
+}
+
+/// Hex code of a linear color, converted to sRGB
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn srgb_hex(rgb: [f32; 3]) -> String {
+    let channel = |linear: f32| {
+        let linear = linear.clamp(0.0, 1.0);
+        let srgb = if linear <= 0.003_130_8 {
+            linear * 12.92
+        } else {
+            1.055 * linear.powf(1.0 / 2.4) - 0.055
+        };
+        (srgb * 255.0).round() as u8
+    };
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(rgb[0]),
+        channel(rgb[1]),
+        channel(rgb[2])
+    )
+}
+
+/// Parse interior and polish details
+///
+/// Counts dashboards, mirrors, wipers, animations, sounds with indoor
+/// tuning, inside cameras and character IK targets (hands on the wheel,
+/// feet on the pedals), and the motor start duration. Only non-zero values
+/// are kept
+fn vehicle_parse_polish(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDetailVehicle) {
+    let mut indicators: BTreeMap<String, u32> = BTreeMap::new();
+    let mut add = |key: &str, value: usize| {
+        if value > 0 {
+            indicators.insert(key.to_owned(), u32::try_from(value).unwrap_or(u32::MAX));
+        }
+    };
+
+    for (list, entry) in POLISH_LISTS {
+        add(
+            list,
+            xml_tree
+                .descendants()
+                .filter(|n| {
+                    n.has_tag_name(entry) && n.parent().is_some_and(|p| p.has_tag_name(list))
+                })
+                .count(),
+        );
+    }
+    add(
+        "indoorSounds",
+        xml_tree
+            .descendants()
+            .filter(|n| n.has_tag_name("indoorAttributes"))
+            .count(),
+    );
+    add(
+        "indoorCameras",
+        xml_tree
+            .descendants()
+            .filter(|n| n.has_tag_name("camera") && n.attribute("isInside") == Some("true"))
+            .count(),
+    );
+    add(
+        "characterTargets",
+        xml_tree
+            .descendants()
+            .filter(|n| n.has_tag_name("target") && n.has_attribute("ikChain"))
+            .count(),
+    );
+
+    if let Some(duration) = xml_tree
+        .descendants()
+        .find(|n| n.has_tag_name("motorStartDuration"))
+        .and_then(|n| n.text())
+        .and_then(|n| n.trim().parse::<f32>().ok())
+    {
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let millis = (duration * 1000.0).round() as usize;
+        add("motorStartDuration", millis);
+    }
+
+    if !indicators.is_empty() {
+        this_vehicle.polish_indicators = Some(indicators);
+    }
 }
 
 /// Parse vehicle specs
@@ -468,6 +990,206 @@ fn vehicle_parse_specs(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDet
 
     this_vehicle.specs.joint_requires.sort();
     this_vehicle.specs.joint_requires.dedup();
+
+    this_vehicle.specs.loader_mounts = this_vehicle
+        .specs
+        .joint_accepts
+        .iter()
+        .chain(this_vehicle.specs.joint_requires.iter())
+        .filter_map(|n| loader_mount_standard(n))
+        .map(str::to_owned)
+        .collect();
+
+    this_vehicle.specs.loader_mounts.sort();
+    this_vehicle.specs.loader_mounts.dedup();
+}
+
+/// Parse pallet capacity, fill types and size (after fills are parsed)
+fn vehicle_parse_pallet(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDetailVehicle) {
+    let mut object = ModDetailObject {
+        capacity: this_vehicle.fill_spray.fill_level,
+        fill_types: this_vehicle.fill_spray.fill_type.clone(),
+        ..Default::default()
+    };
+    xml_extract_object_size(xml_tree, &mut object);
+    this_vehicle.object = Some(object);
+}
+
+/// Parse variable work width sections
+fn vehicle_parse_sections(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDetailVehicle) {
+    let Some(work_width) = xml_tree
+        .descendants()
+        .find(|n| n.has_tag_name("variableWorkWidth"))
+    else {
+        return;
+    };
+
+    let sections = &mut this_vehicle.sections;
+
+    for side in ["leftParts", "centerParts", "rightParts"] {
+        let Some(side_node) = work_width.children().find(|n| n.has_tag_name(side)) else {
+            continue;
+        };
+        for part in side_node.children().filter(|n| n.has_tag_name("part")) {
+            sections.count += 1;
+            if let Some(width) = part
+                .attribute("width")
+                .or(part.attribute("maxWidth"))
+                .and_then(|n| n.parse::<f32>().ok())
+            {
+                sections.widths.push(width);
+            }
+        }
+    }
+
+    sections.section_control = sections.count > 0;
+}
+
+/// Parse power take-offs and connection hoses
+///
+/// PTO `<input>` and hose `<hose>` entries are needed from the towing
+/// vehicle, PTO `<output>` and hose `<target>` entries are offered
+fn vehicle_parse_connections(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDetailVehicle) {
+    let connections = &mut this_vehicle.connections;
+
+    if let Some(pto) = xml_tree
+        .descendants()
+        .find(|n| n.has_tag_name("powerTakeOffs"))
+    {
+        if pto.children().any(|n| n.has_tag_name("input")) {
+            connections.requires.push(String::from("pto"));
+        }
+        if pto.children().any(|n| n.has_tag_name("output")) {
+            connections.provides.push(String::from("pto"));
+        }
+    }
+
+    if let Some(hoses) = xml_tree
+        .descendants()
+        .find(|n| n.has_tag_name("connectionHoses"))
+    {
+        for hose in hoses.descendants() {
+            let list = match hose.tag_name().name() {
+                "hose" => &mut connections.requires,
+                "target" => &mut connections.provides,
+                _ => continue,
+            };
+            if let Some(hose_type) = hose.attribute("type") {
+                list.push(connection_type(hose_type));
+            }
+        }
+    }
+
+    for list in [&mut connections.requires, &mut connections.provides] {
+        list.sort();
+        list.dedup();
+    }
+}
+
+/// kW to (metric) horse power
+const KW_TO_HP: f32 = 1.359_62;
+
+/// Parse power, PTO and hydraulic requirements (after specs are parsed)
+///
+/// Vehicles with several power consumers (configurations) report the
+/// largest need. Left as `None` when nothing is required
+fn vehicle_parse_power(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDetailVehicle) {
+    let mut power = ModDetailVehiclePower {
+        needed_power: this_vehicle.specs.specs.get("neededPower").copied(),
+        ..Default::default()
+    };
+
+    let largest = |current: Option<u32>, value: Option<f32>| match (current, value) {
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        (_, Some(value)) if value > 0.0 => Some(current.unwrap_or(0).max(value.round() as u32)),
+        _ => current,
+    };
+
+    for consumer in xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("powerConsumer"))
+    {
+        let value = |key: &str| consumer.attribute(key).and_then(|n| n.parse::<f32>().ok());
+
+        power.pto_rpm = largest(power.pto_rpm, value("ptoRpm"));
+        power.pto_power_min = largest(
+            power.pto_power_min,
+            value("neededMinPtoPower").map(|n| n * KW_TO_HP),
+        );
+        power.pto_power_max = largest(
+            power.pto_power_max,
+            value("neededMaxPtoPower").map(|n| n * KW_TO_HP),
+        );
+        if let Some(force) = value("maxForce").filter(|n| *n > 0.0) {
+            power.max_force = Some(power.max_force.unwrap_or(0.0).max(force));
+        }
+    }
+
+    power.hydraulic_hoses = xml_tree
+        .descendants()
+        .filter(|n| n.has_tag_name("connectionHoses"))
+        .flat_map(|n| n.descendants().filter(|n| n.has_tag_name("hose")))
+        .filter(|n| {
+            n.attribute("type")
+                .is_some_and(|n| connection_type(n) == "hydraulic")
+        })
+        .count()
+        .try_into()
+        .unwrap_or(u32::MAX);
+
+    if power != ModDetailVehiclePower::default() {
+        this_vehicle.power_requirements = Some(power);
+    }
+}
+
+/// Parse wear, dirt and upkeep parameters
+///
+/// Left as `None` when the vehicle has none of them
+fn vehicle_parse_maintenance(xml_tree: &roxmltree::Document, this_vehicle: &mut ModDetailVehicle) {
+    let numeric_attributes = |tag: &str| -> BTreeMap<String, f32> {
+        xml_tree
+            .descendants()
+            .find(|n| n.has_tag_name(tag))
+            .map(|node| {
+                node.attributes()
+                    .filter_map(|n| Some((n.name().to_owned(), n.value().parse::<f32>().ok()?)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let maintenance = ModDetailVehicleMaintenance {
+        daily_upkeep: xml_extract_text_as_opt_u32(xml_tree, "dailyUpkeep"),
+        dirt: numeric_attributes("washable"),
+        wear: numeric_attributes("wearable"),
+    };
+
+    if maintenance != ModDetailVehicleMaintenance::default() {
+        this_vehicle.maintenance = Some(maintenance);
+    }
+}
+
+/// Group a connection hose type (e.g. `hydraulicIn`, `airDoubleRed`)
+fn connection_type(hose_type: &str) -> String {
+    let hose_type = hose_type.to_lowercase();
+    if hose_type.starts_with("hydraulic") {
+        String::from("hydraulic")
+    } else if hose_type.starts_with("air") {
+        String::from("air")
+    } else {
+        hose_type
+    }
+}
+
+/// Tool mounting standard for a loader joint type
+fn loader_mount_standard(joint_type: &str) -> Option<&'static str> {
+    match joint_type.to_lowercase().as_str() {
+        "frontloader" => Some("euro"),
+        "wheelloader" => Some("sms"),
+        "skidsteer" => Some("skidSteer"),
+        "telehandler" => Some("telehandler"),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -529,6 +1251,174 @@ mod test {
         assert_json_include!(actual : actual, expected : expected);
     }
 
+    #[test]
+    fn vehicle_color_configurations() {
+        let minimum_xml = r#"<vehicle>
+            <baseMaterialConfigurations>
+                <baseMaterialConfiguration color="0.0284 0.1441 0.0284 1" materialTemplateName="calibratedPaint" />
+                <baseMaterialConfiguration name="$l10n_configuration_valueRed" color="SHARED_RED" price="500" />
+            </baseMaterialConfigurations>
+            <rimColorConfigurations>
+                <rimColorConfiguration color="1 1 0" />
+            </rimColorConfigurations>
+            <designColorConfigurations price="1000">
+                <color color="0 0 0 1" name="Black" />
+            </designColorConfigurations>
+            <wheelConfigurations><wheelConfiguration /></wheelConfigurations>
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_configurations(&minimum_doc, &mut this_vehicle);
+        let colors = &this_vehicle.configurations.colors;
+
+        assert_eq!(colors.len(), 4);
+        assert_eq!(
+            colors[0],
+            ModDetailVehicleColor {
+                kind: String::from("baseMaterial"),
+                name: None,
+                color: Some(String::from("0.0284 0.1441 0.0284 1")),
+                rgb: Some([0.0284, 0.1441, 0.0284]),
+                hex: Some(String::from("#2f6a2f")),
+                material: Some(String::from("calibratedPaint")),
+                price: None,
+            }
+        );
+        assert_eq!(colors[1].color.as_deref(), Some("SHARED_RED"));
+        assert_eq!(colors[1].rgb, None);
+        assert_eq!(colors[1].price, Some(500));
+        assert_eq!(colors[2].kind, "rimColor");
+        assert_eq!(colors[2].hex.as_deref(), Some("#ffff00"));
+        assert_eq!(colors[3].kind, "designColor");
+        assert_eq!(colors[3].name.as_deref(), Some("Black"));
+        assert_eq!(colors[3].hex.as_deref(), Some("#000000"));
+    }
+
+    #[test]
+    fn vehicle_wheel_configurations() {
+        let minimum_xml = r#"<vehicle><wheelConfigurations>
+            <wheelConfiguration name="$l10n_configuration_valueDefault" price="0">
+                <wheels><wheel filename="$data/shared/wheels/tires/continental/contiCrossTracHS3/385_65R22_5.xml">
+                    <physics repr="wheelFrontLeft" />
+                </wheel></wheels>
+            </wheelConfiguration>
+            <wheelConfiguration name="Narrow" brand="TRELLEBORG" price="1500">
+                <wheels><wheel filename="$data/shared/wheels/tires/mitas/AC85/320_85R38.xml">
+                    <physics tireType="mud" />
+                </wheel></wheels>
+            </wheelConfiguration>
+            <wheelConfiguration name="EU" saveId="CRAWLER01">
+                <wheels><wheel><physics repr="crawlerFrontLeft" /></wheel></wheels>
+                <crawlers><crawler filename="$data/shared/crawlers/series8RXCrawler_EU_front.xml" /></crawlers>
+            </wheelConfiguration>
+        </wheelConfigurations></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_wheels(&minimum_doc, &mut this_vehicle);
+        let wheels = &this_vehicle.configurations.wheels;
+
+        assert_eq!(wheels.len(), 3);
+        assert_eq!(
+            wheels[0],
+            ModDetailVehicleWheel {
+                name: Some(String::from("$l10n_configuration_valueDefault")),
+                brand: Some(String::from("CONTINENTAL")),
+                tire_model: Some(String::from("contiCrossTracHS3")),
+                tire_type: None,
+                price: Some(0),
+            }
+        );
+        assert_eq!(wheels[1].brand.as_deref(), Some("TRELLEBORG"));
+        assert_eq!(wheels[1].tire_model.as_deref(), Some("AC85"));
+        assert_eq!(wheels[1].tire_type.as_deref(), Some("mud"));
+        assert_eq!(wheels[1].price, Some(1500));
+        assert_eq!(wheels[2].brand, None);
+        assert_eq!(wheels[2].tire_type.as_deref(), Some("crawler"));
+    }
+
+    #[test]
+    fn vehicle_year_fallbacks() {
+        let minimum_xml = r#"<vehicle><storeData>
+                <name>Classic 5000</name>
+                <year>1968</year>
+            </storeData></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_sorting(&minimum_doc, &mut this_vehicle);
+        assert_eq!(this_vehicle.sorting.year, Some(1968));
+        assert_eq!(this_vehicle.sorting.decade, Some(1960));
+        assert_eq!(
+            this_vehicle.sorting.year_confidence,
+            Some(ModDetailYearConfidence::High)
+        );
+
+        let minimum_xml = r#"<vehicle><storeData><name>Tractor</name></storeData>
+                <motorized year="1985" /></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_sorting(&minimum_doc, &mut this_vehicle);
+        assert_eq!(this_vehicle.sorting.year, Some(1985));
+        assert_eq!(this_vehicle.sorting.decade, Some(1980));
+        assert_eq!(
+            this_vehicle.sorting.year_confidence,
+            Some(ModDetailYearConfidence::Medium)
+        );
+
+        let minimum_xml = r#"<vehicle><storeData><name>Tractor</name>
+                <specs><modelYear>1974</modelYear></specs></storeData></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_sorting(&minimum_doc, &mut this_vehicle);
+        assert_eq!(this_vehicle.sorting.year, Some(1974));
+        assert_eq!(
+            this_vehicle.sorting.year_confidence,
+            Some(ModDetailYearConfidence::Medium)
+        );
+
+        let minimum_xml = r#"<vehicle><storeData><name>1775NT 2022</name></storeData></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_sorting(&minimum_doc, &mut this_vehicle);
+        assert_eq!(this_vehicle.sorting.year, Some(2022));
+        assert_eq!(this_vehicle.sorting.decade, Some(2020));
+        assert_eq!(
+            this_vehicle.sorting.year_confidence,
+            Some(ModDetailYearConfidence::Low)
+        );
+
+        let minimum_xml =
+            r#"<vehicle><storeData><name>Series 60 (1955)</name></storeData></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_sorting(&minimum_doc, &mut this_vehicle);
+        assert_eq!(this_vehicle.sorting.year, Some(1955));
+        assert_eq!(this_vehicle.sorting.decade, Some(1950));
+
+        for name in [
+            "Combine 9999",
+            "Hauler 2099",
+            "Model 2010 Edition",
+            "MX1955 Loader",
+        ] {
+            let minimum_xml =
+                format!("<vehicle><storeData><name>{name}</name></storeData></vehicle>");
+            let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+            let mut this_vehicle = ModDetailVehicle::default();
+
+            vehicle_parse_sorting(&minimum_doc, &mut this_vehicle);
+            assert_eq!(this_vehicle.sorting.year, None, "{name}");
+            assert_eq!(this_vehicle.sorting.decade, None);
+            assert_eq!(this_vehicle.sorting.year_confidence, None);
+        }
+    }
+
     #[test]
     fn vehicle_fill_unit() {
         let minimum_xml = r#"
@@ -556,7 +1446,43 @@ mod test {
                 "fillCat": [],
                 "fillLevel": 15000,
                 "fillType": ["fertilizer", "lime", "seeds"],
-                "sprayTypes": []
+                "sprayTypes": [],
+                "tanks": [
+                    { "capacity": 15000, "fillCat": [], "fillType": ["fertilizer", "lime"] }
+                ]
+        });
+        assert_json_include!(actual : actual, expected : expected);
+    }
+
+    #[test]
+    fn vehicle_fill_unit_multi_tank() {
+        let minimum_xml = r#"
+        <fillUnitConfigurations>
+            <fillUnitConfiguration>
+                <fillUnits>
+                    <fillUnit fillTypes="seeds" capacity="5000"></fillUnit>
+                    <fillUnit fillTypes="fertilizer liquidFertilizer" capacity="3000"></fillUnit>
+                </fillUnits>
+            </fillUnitConfiguration>
+            <fillUnitConfiguration>
+                <fillUnits>
+                    <fillUnit fillTypes="seeds" capacity="6000"></fillUnit>
+                </fillUnits>
+            </fillUnitConfiguration>
+        </fillUnitConfigurations>"#;
+
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_fills(&minimum_doc, &mut this_vehicle);
+
+        let actual = json!(this_vehicle.fill_spray);
+        let expected = json!({
+                "fillLevel": 8000,
+                "tanks": [
+                    { "capacity": 5000, "fillCat": [], "fillType": ["seeds"] },
+                    { "capacity": 3000, "fillCat": [], "fillType": ["fertilizer", "liquidfertilizer"] }
+                ]
         });
         assert_json_include!(actual : actual, expected : expected);
     }
@@ -609,7 +1535,7 @@ mod test {
         let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
         let mut this_vehicle = ModDetailVehicle::default();
 
-        vehicle_parse_motor(&minimum_doc, &mut this_vehicle);
+        vehicle_parse_motor(&minimum_doc, &mut this_vehicle, MotorUnits::Both);
 
         let actual = json!(this_vehicle.motor);
         let expected = json!({
@@ -682,7 +1608,7 @@ mod test {
         let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
         let mut this_vehicle = ModDetailVehicle::default();
 
-        vehicle_parse_motor(&minimum_doc, &mut this_vehicle);
+        vehicle_parse_motor(&minimum_doc, &mut this_vehicle, MotorUnits::Both);
 
         let actual = json!(this_vehicle.motor);
         let expected = json!({
@@ -720,5 +1646,437 @@ mod test {
             "transmissionType": "$l10n_info_transmission_manual"
         });
         assert_json_include!(actual : actual, expected : expected);
+
+        let motor = &this_vehicle.motor.motors[0];
+        assert_eq!(motor.axle_ratio, Some(25.0));
+        assert_eq!(
+            motor.gear_ratios,
+            vec![4.784, 2.423, 1.443, 1.000, 0.826, 0.643]
+        );
+        assert_eq!(motor.max_rpm, 6000.0);
+        assert_eq!(motor.min_forward_gear_ratio, None);
+        assert_eq!(motor.torque_scale, 0.6);
+        assert_eq!(motor.torque_curve.len(), 6);
+        assert_eq!(
+            motor.torque_curve[3],
+            MotorTorquePoint {
+                rpm: 4560.0,
+                torque: 0.75
+            }
+        );
+
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_motor(&minimum_doc, &mut this_vehicle, MotorUnits::Metric);
+        let motor = &this_vehicle.motor.motors[0];
+        assert!(motor.horse_power.is_empty());
+        assert!(motor.speed_mph.is_empty());
+        assert_eq!(motor.kilowatts.len(), 6);
+        assert_eq!(motor.kilowatts[2].value, 219);
+        assert_eq!(motor.speed_kph[2].value, 82);
+    }
+
+    #[test]
+    fn vehicle_passenger_seats() {
+        let minimum_xml = r#"<vehicle><enterable>
+            <passengerSeats><passengerSeat node="seat01" /></passengerSeats>
+        </enterable></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_flags(&minimum_doc, &mut this_vehicle);
+        assert!(matches!(
+            this_vehicle.flags.passenger_seats,
+            VehicleCapability::Yes
+        ));
+
+        let minimum_xml = r#"<vehicle><passengers>
+            <passenger node="seat01" />
+        </passengers></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_flags(&minimum_doc, &mut this_vehicle);
+        assert!(matches!(
+            this_vehicle.flags.passenger_seats,
+            VehicleCapability::Yes
+        ));
+
+        let minimum_xml = r#"<vehicle><enterable>
+            <passengerDoor node="door01" />
+        </enterable></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_flags(&minimum_doc, &mut this_vehicle);
+        assert!(matches!(
+            this_vehicle.flags.passenger_seats,
+            VehicleCapability::No
+        ));
+
+        let minimum_doc = roxmltree::Document::parse("<vehicle><enterable /></vehicle>").unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_flags(&minimum_doc, &mut this_vehicle);
+        assert!(matches!(
+            this_vehicle.flags.passenger_seats,
+            VehicleCapability::No
+        ));
+    }
+
+    #[test]
+    fn vehicle_trailer_capabilities() {
+        let minimum_xml = r#"<vehicle>
+            <dynamicMountAttacher node="mountTrigger" />
+            <aPalletAutoLoader><trigger node="loadArea" /></aPalletAutoLoader>
+            <tensionBelts>
+                <tensionBeltsConfigurations>
+                    <tensionBeltsConfiguration>
+                        <tensionBelts>
+                            <tensionBelt startNode="a" />
+                            <tensionBelt startNode="b" />
+                        </tensionBelts>
+                    </tensionBeltsConfiguration>
+                    <tensionBeltsConfiguration>
+                        <tensionBelts>
+                            <tensionBelt startNode="a" />
+                            <tensionBelt startNode="b" />
+                            <tensionBelt startNode="c" />
+                        </tensionBelts>
+                    </tensionBeltsConfiguration>
+                </tensionBeltsConfigurations>
+            </tensionBelts>
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_flags(&minimum_doc, &mut this_vehicle);
+
+        assert!(matches!(
+            this_vehicle.flags.autoload,
+            VehicleCapability::Yes
+        ));
+        assert!(matches!(
+            this_vehicle.flags.dynamic_mount,
+            VehicleCapability::Yes
+        ));
+        assert_eq!(this_vehicle.specs.tension_belts, 3);
+
+        let minimum_doc = roxmltree::Document::parse("<vehicle><enterable /></vehicle>").unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_flags(&minimum_doc, &mut this_vehicle);
+
+        assert!(matches!(this_vehicle.flags.autoload, VehicleCapability::No));
+        assert!(matches!(
+            this_vehicle.flags.dynamic_mount,
+            VehicleCapability::No
+        ));
+        assert_eq!(this_vehicle.specs.tension_belts, 0);
+    }
+
+    #[test]
+    fn vehicle_road_extras() {
+        let minimum_xml = r#"<vehicle>
+            <licensePlates><licensePlate node="plateBack" position="BACK" /></licensePlates>
+            <warningSignConfigurations>
+                <warningSignConfiguration name="$l10n_configuration_valueNo" />
+                <warningSignConfiguration name="$l10n_configuration_valueYes" />
+            </warningSignConfigurations>
+            <attacherJoints>
+                <attacherJointConfigurations>
+                    <attacherJointConfiguration name="low" />
+                    <attacherJointConfiguration name="high" />
+                </attacherJointConfigurations>
+            </attacherJoints>
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_flags(&minimum_doc, &mut this_vehicle);
+
+        assert!(matches!(
+            this_vehicle.flags.license_plates,
+            VehicleCapability::Yes
+        ));
+        assert!(matches!(
+            this_vehicle.flags.warning_signs,
+            VehicleCapability::Yes
+        ));
+        assert!(matches!(
+            this_vehicle.flags.attacher_configs,
+            VehicleCapability::Yes
+        ));
+
+        let minimum_xml = r#"<vehicle><attacherJoints>
+            <attacherJointConfigurations><attacherJointConfiguration /></attacherJointConfigurations>
+        </attacherJoints></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_flags(&minimum_doc, &mut this_vehicle);
+
+        assert!(matches!(
+            this_vehicle.flags.license_plates,
+            VehicleCapability::No
+        ));
+        assert!(matches!(
+            this_vehicle.flags.warning_signs,
+            VehicleCapability::No
+        ));
+        assert!(matches!(
+            this_vehicle.flags.attacher_configs,
+            VehicleCapability::No
+        ));
+    }
+
+    #[test]
+    fn vehicle_power_requirements() {
+        let minimum_xml = r#"<vehicle>
+            <storeData><specs><neededPower>120</neededPower></specs></storeData>
+            <powerConsumer ptoRpm="540" neededMinPtoPower="15" neededMaxPtoPower="60" maxForce="25" />
+            <connectionHoses>
+                <hose inputAttacherJointIndices="1" type="hydraulicIn" />
+                <hose inputAttacherJointIndices="1" type="hydraulicOut" />
+                <hose inputAttacherJointIndices="1" type="electric" />
+                <target attacherJointIndices="1" type="hydraulicIn" />
+            </connectionHoses>
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_specs(&minimum_doc, &mut this_vehicle);
+        vehicle_parse_power(&minimum_doc, &mut this_vehicle);
+
+        assert_eq!(
+            this_vehicle.power_requirements,
+            Some(ModDetailVehiclePower {
+                hydraulic_hoses: 2,
+                max_force: Some(25.0),
+                needed_power: Some(120),
+                pto_power_max: Some(82),
+                pto_power_min: Some(20),
+                pto_rpm: Some(540),
+            })
+        );
+
+        let minimum_doc = roxmltree::Document::parse("<vehicle><enterable /></vehicle>").unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_specs(&minimum_doc, &mut this_vehicle);
+        vehicle_parse_power(&minimum_doc, &mut this_vehicle);
+        assert_eq!(this_vehicle.power_requirements, None);
+    }
+
+    #[test]
+    fn vehicle_maintenance() {
+        let minimum_xml = r#"<vehicle>
+            <storeData><dailyUpkeep>45</dailyUpkeep></storeData>
+            <wearable wearDuration="600" workMultiplier="5" fieldMultiplier="2" repairPriceFactor="0.5" />
+            <washable dirtDuration="90" washDuration="1" workMultiplier="4" node="body" />
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_maintenance(&minimum_doc, &mut this_vehicle);
+
+        let Some(maintenance) = this_vehicle.maintenance else {
+            panic!("maintenance missing");
+        };
+        assert_eq!(maintenance.daily_upkeep, Some(45));
+        assert_eq!(maintenance.wear.get("wearDuration"), Some(&600.0));
+        assert_eq!(maintenance.wear.get("fieldMultiplier"), Some(&2.0));
+        assert_eq!(maintenance.wear.get("repairPriceFactor"), Some(&0.5));
+        assert_eq!(maintenance.dirt.get("dirtDuration"), Some(&90.0));
+        assert_eq!(maintenance.dirt.len(), 3);
+
+        let minimum_doc = roxmltree::Document::parse("<vehicle><enterable /></vehicle>").unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_maintenance(&minimum_doc, &mut this_vehicle);
+        assert_eq!(this_vehicle.maintenance, None);
+    }
+
+    #[test]
+    fn vehicle_polish_indicators() {
+        let minimum_xml = r#"<vehicle>
+            <motorized><motorStartDuration>1.5</motorStartDuration></motorized>
+            <enterable>
+                <cameras>
+                    <camera node="outdoorCamera" isInside="false" />
+                    <camera node="indoorCamera" isInside="true" />
+                </cameras>
+                <characterNode node="playerSkin">
+                    <target ikChain="rightFoot" targetNode="throttlePedal" />
+                    <target ikChain="leftArm" targetNode="steeringWheel" />
+                </characterNode>
+                <mirrors><mirror node="mirrorLeft" /><mirror node="mirrorRight" /></mirrors>
+            </enterable>
+            <dashboard><dashboards><dashboard displayType="ROT" /></dashboards></dashboard>
+            <motorized><sounds><motorStart template="DEFAULT"><indoorAttributes volume="0.8" /></motorStart></sounds></motorized>
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_polish(&minimum_doc, &mut this_vehicle);
+        assert_eq!(
+            this_vehicle.polish_indicators,
+            Some(BTreeMap::from([
+                (String::from("characterTargets"), 2),
+                (String::from("dashboards"), 1),
+                (String::from("indoorCameras"), 1),
+                (String::from("indoorSounds"), 1),
+                (String::from("mirrors"), 2),
+                (String::from("motorStartDuration"), 1500),
+            ]))
+        );
+
+        let minimum_doc = roxmltree::Document::parse("<vehicle><wheels /></vehicle>").unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+        vehicle_parse_polish(&minimum_doc, &mut this_vehicle);
+        assert_eq!(this_vehicle.polish_indicators, None);
+    }
+
+    #[test]
+    fn vehicle_loader_mounts() {
+        let minimum_xml = r#"
+        <vehicle>
+            <attacherJoints>
+                <attacherJoint jointType="frontloader" />
+                <attacherJoint jointType="implement" />
+            </attacherJoints>
+            <attacherJointConfigurations>
+                <attacherJointConfiguration>
+                    <attacherJoint jointType="skidSteer" />
+                </attacherJointConfiguration>
+            </attacherJointConfigurations>
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_specs(&minimum_doc, &mut this_vehicle);
+
+        assert_eq!(this_vehicle.specs.loader_mounts, vec!["euro", "skidSteer"]);
+    }
+
+    #[test]
+    fn vehicle_work_width_sections() {
+        let minimum_xml = r#"
+        <vehicle><variableWorkWidth>
+            <leftParts>
+                <part node="sectionLeft2" width="3" />
+                <part node="sectionLeft1" width="3" />
+            </leftParts>
+            <rightParts>
+                <part node="sectionRight1" width="3" />
+                <part node="sectionRight2" />
+            </rightParts>
+        </variableWorkWidth></vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_sections(&minimum_doc, &mut this_vehicle);
+
+        let actual = json!(this_vehicle.sections);
+        let expected = json!({
+            "count": 4,
+            "sectionControl": true,
+            "widths": [ 3.0, 3.0, 3.0 ]
+        });
+        assert_json_include!(actual : actual, expected : expected);
+    }
+
+    #[test]
+    fn vehicle_pallet_object() {
+        let minimum_xml = r#"
+        <vehicle type="pallet">
+            <base><size width="1.25" length="1.25" height="1.5" /></base>
+            <fillUnit><fillUnitConfigurations><fillUnitConfiguration><fillUnits>
+                <fillUnit fillTypes="SEEDS" capacity="1000" />
+            </fillUnits></fillUnitConfiguration></fillUnitConfigurations></fillUnit>
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+        let options = ModParserOptions {
+            skip_detail_icons: true,
+            ..Default::default()
+        };
+
+        let this_vehicle = vehicle_parse(&minimum_doc, &mut file_handle, &options);
+
+        let actual = json!(this_vehicle.object);
+        let expected = json!({
+            "capacity": 1000,
+            "diameter": null,
+            "fillTypes": [ "seeds" ],
+            "height": 1.5,
+            "length": 1.25,
+            "width": 1.25
+        });
+        assert_json_include!(actual : actual, expected : expected);
+    }
+
+    #[test]
+    fn vehicle_combo_resolution() {
+        let mut file_handle: Box<dyn AbstractFileHandle> = Box::new(AbstractNull::new().unwrap());
+        let options = ModParserOptions {
+            skip_detail_icons: true,
+            ..Default::default()
+        };
+        let tractor_xml = r#"<vehicle type="tractor"><storeData>
+            <name>Big Tractor</name><brand>LIZARD</brand><category>tractorsL</category>
+            <combination xmlFilename="xml\Seeder.xml" />
+            <combination xmlFilename="$data/vehicles/krampe/dolly10L/dolly10L.xml" />
+            <combination xmlFilename="missing.xml" />
+            </storeData></vehicle>"#;
+        let seeder_xml = r#"<vehicle type="sowingMachine"><storeData>
+            <name>Small Seeder</name><brand>LIZARD</brand><category>seeders</category>
+            </storeData></vehicle>"#;
+
+        let mut vehicles = HashMap::new();
+        for (key, xml) in [("tractor.xml", tractor_xml), ("xml/seeder.xml", seeder_xml)] {
+            let doc = roxmltree::Document::parse(xml).unwrap();
+            let this_vehicle = vehicle_parse(&doc, &mut file_handle, &options);
+            vehicles.insert(key.to_owned(), this_vehicle);
+        }
+
+        vehicle_resolve_combos(&mut vehicles);
+
+        let actual = json!(vehicles["tractor.xml"].sorting.combo_items);
+        let expected = json!([
+            {
+                "file": "xml\\Seeder.xml",
+                "baseGame": false,
+                "itemKey": "xml/seeder.xml",
+                "brand": "LIZARD",
+                "category": "seeders",
+                "name": "Small Seeder"
+            },
+            {
+                "file": "$data/vehicles/krampe/dolly10L/dolly10L.xml",
+                "baseGame": true,
+                "itemKey": null,
+                "name": null
+            },
+            {
+                "file": "missing.xml",
+                "baseGame": false,
+                "itemKey": null,
+                "name": null
+            }
+        ]);
+        assert_json_include!(actual : actual, expected : expected);
+        assert!(vehicles["xml/seeder.xml"].sorting.combo_items.is_empty());
+    }
+
+    #[test]
+    fn vehicle_connections() {
+        let minimum_xml = r#"<vehicle>
+            <powerTakeOffs><input inputNode="ptoInput" /></powerTakeOffs>
+            <connectionHoses>
+                <hose inputAttacherJointIndices="1" type="hydraulicIn" />
+                <hose inputAttacherJointIndices="1" type="hydraulicOut" />
+                <hose inputAttacherJointIndices="1" type="electric" />
+                <hose inputAttacherJointIndices="1" type="airDoubleRed" />
+                <target attacherJointIndices="1" type="ISOBUS" />
+            </connectionHoses>
+        </vehicle>"#;
+        let minimum_doc = roxmltree::Document::parse(&minimum_xml).unwrap();
+        let mut this_vehicle = ModDetailVehicle::default();
+
+        vehicle_parse_connections(&minimum_doc, &mut this_vehicle);
+
+        let actual = json!(this_vehicle.connections);
+        let expected = json!({
+            "requires": [ "air", "electric", "hydraulic", "pto" ],
+            "provides": [ "isobus" ]
+        });
+        assert_json_include!(actual : actual, expected : expected);
     }
 }
@@ -0,0 +1,365 @@
+//! Run the parser in a separate worker process
+//!
+//! Intended for services that parse untrusted uploads. The worker is the
+//! `parse_file` binary shipped with this crate, which prints the
+//! [`crate::shared::structs::ModRecord`] as JSON on stdout. A runaway worker
+//! (decompression bomb, pathological XML) is killed on timeout, and can
+//! be held to an address space limit on unix.
+use crate::maps::structs::GrowthPeriodMode;
+use crate::mod_detail::structs::MotorUnits;
+use crate::shared::files::FolderHashMode;
+use crate::ModParserOptions;
+use std::{
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Interval between checks on the worker process
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Sandboxed worker settings
+pub struct SandboxOptions {
+    /// Path to the `parse_file` worker binary
+    pub worker: PathBuf,
+    /// Wall clock time allowed for the worker
+    pub timeout: Duration,
+    /// Address space limit for the worker in bytes (unix only)
+    pub memory_limit: Option<u64>,
+    /// Maximum bytes of JSON accepted from the worker
+    pub max_output: u64,
+}
+
+impl SandboxOptions {
+    /// Create sandbox settings for a worker binary
+    ///
+    /// Defaults to a 60 second timeout, a 2GB memory limit, and
+    /// 64MB of output
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(worker: P) -> Self {
+        SandboxOptions {
+            worker: worker.as_ref().to_path_buf(),
+            timeout: Duration::from_secs(60),
+            memory_limit: Some(2048 * 0x0010_0000),
+            max_output: 64 * 0x0010_0000,
+        }
+    }
+}
+
+/// Sandboxed parse failures
+#[derive(Debug)]
+pub enum SandboxError {
+    /// Worker could not be started
+    Spawn(std::io::Error),
+    /// Worker did not finish in the allowed time, and was killed
+    Timeout,
+    /// Worker exited unsuccessfully (exit code, if any)
+    Crashed(Option<i32>),
+    /// Worker produced more output than allowed
+    OutputTooLarge,
+    /// Worker output was not valid JSON
+    BadOutput,
+}
+
+impl std::fmt::Display for SandboxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SandboxError::Spawn(e) => write!(f, "could not start worker: {e}"),
+            SandboxError::Timeout => f.write_str("worker timed out"),
+            SandboxError::Crashed(Some(code)) => write!(f, "worker exited with code {code}"),
+            SandboxError::Crashed(None) => f.write_str("worker was terminated"),
+            SandboxError::OutputTooLarge => f.write_str("worker output too large"),
+            SandboxError::BadOutput => f.write_str("worker output was not valid JSON"),
+        }
+    }
+}
+
+impl std::error::Error for SandboxError {}
+
+/// Parse a mod in a separate worker process
+///
+/// Returns the JSON representation of the [`crate::shared::structs::ModRecord`].
+/// The `modhub_hashes`, `metrics` and `badge_rules` options are not passed
+/// to the worker.
+///
+/// # Errors
+///
+/// Returns a [`SandboxError`] if the worker fails to start, times out, crashes,
+/// or returns unusable output
+pub fn parse_mod_sandboxed<P: AsRef<Path>>(
+    full_path: P,
+    options: &ModParserOptions,
+    sandbox: &SandboxOptions,
+) -> Result<String, SandboxError> {
+    let mut command = Command::new(&sandbox.worker);
+
+    command
+        .args(worker_args(options))
+        .arg(full_path.as_ref())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    #[cfg(unix)]
+    if let Some(limit) = sandbox.memory_limit {
+        limit_memory(&mut command, limit);
+    }
+
+    let mut child = command.spawn().map_err(SandboxError::Spawn)?;
+
+    let Some(mut stdout) = child.stdout.take() else {
+        let _ = child.kill();
+        return Err(SandboxError::BadOutput);
+    };
+
+    let max_output = sandbox.max_output;
+    let reader = thread::spawn(move || {
+        let mut buffer: Vec<u8> = vec![];
+        (&mut stdout)
+            .take(max_output + 1)
+            .read_to_end(&mut buffer)?;
+        // keep the pipe open so an oversized worker finishes rather than
+        // dying on a broken pipe
+        io::copy(&mut stdout, &mut io::sink())?;
+        Ok::<_, io::Error>(buffer)
+    });
+
+    let start_time = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start_time.elapsed() > sandbox.timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(SandboxError::Timeout);
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                let _ = child.kill();
+                return Err(SandboxError::Spawn(e));
+            }
+        }
+    };
+
+    let buffer = reader.join().ok().and_then(Result::ok);
+
+    if buffer
+        .as_ref()
+        .is_some_and(|buffer| buffer.len() as u64 > max_output)
+    {
+        return Err(SandboxError::OutputTooLarge);
+    }
+
+    if !status.success() {
+        return Err(SandboxError::Crashed(status.code()));
+    }
+
+    let Some(buffer) = buffer else {
+        return Err(SandboxError::BadOutput);
+    };
+
+    let output = String::from_utf8(buffer).map_err(|_| SandboxError::BadOutput)?;
+
+    if serde_json::from_str::<serde_json::Value>(&output).is_err() {
+        return Err(SandboxError::BadOutput);
+    }
+
+    Ok(output)
+}
+
+/// Command line flags for the worker from parser options
+///
+/// Every option is named here, so a new one has to be forwarded (or listed
+/// as skipped) before this builds. `modhub_hashes` (too long for a command
+/// line), `metrics` and `badge_rules` (closures and shared state) stay in
+/// this process. Also the options key of [`crate::cache::ParseCache`].
+#[expect(clippy::too_many_lines)]
+pub(crate) fn worker_args(options: &ModParserOptions) -> Vec<String> {
+    let &ModParserOptions {
+        include_save_game,
+        include_mod_detail,
+        skip_detail_icons,
+        skip_mod_icons,
+        include_disabled,
+        ref languages,
+        time_limit,
+        ref raw_mod_desc_tags,
+        read_limits,
+        detail_threads,
+        detail_diagnostics,
+        growth_periods,
+        texture_report,
+        full_map_overview,
+        reference_case,
+        external_references,
+        store_item_dlcs,
+        script_api,
+        folder_hash,
+        save_game_statistics,
+        motor_units,
+        map_image_size,
+        map_farmlands,
+        map_density_check,
+        gallery_images,
+        weight_images,
+        ref allowed_extra_files,
+        slim_output,
+        modhub_hashes: _,
+        metrics: _,
+        badge_rules: _,
+    } = options;
+
+    let mut args = vec![String::from("--compact")];
+
+    if include_mod_detail {
+        args.push(String::from("--detail"));
+    }
+    if include_save_game {
+        args.push(String::from("--savegame"));
+    }
+    if save_game_statistics {
+        args.push(String::from("--statistics"));
+    }
+    if !skip_detail_icons {
+        args.push(String::from("--detail-icons"));
+    }
+    if detail_diagnostics {
+        args.push(String::from("--diagnostics"));
+    }
+    if skip_mod_icons {
+        args.push(String::from("--no-icons"));
+    }
+    if include_disabled {
+        args.push(String::from("--disabled"));
+    }
+    if texture_report {
+        args.push(String::from("--textures"));
+    }
+    if reference_case {
+        args.push(String::from("--case-check"));
+    }
+    if external_references {
+        args.push(String::from("--external"));
+    }
+    if store_item_dlcs {
+        args.push(String::from("--dlcs"));
+    }
+    if script_api {
+        args.push(String::from("--scripts"));
+    }
+    if full_map_overview {
+        args.push(String::from("--full-overview"));
+    }
+    if map_farmlands {
+        args.push(String::from("--farmlands"));
+    }
+    if map_density_check {
+        args.push(String::from("--density"));
+    }
+    if slim_output {
+        args.push(String::from("--slim"));
+    }
+    if weight_images {
+        args.push(String::from("--weight-maps"));
+    }
+    if let Some(size) = map_image_size {
+        args.push(format!("--map-size={size}"));
+    }
+    if gallery_images > 0 {
+        args.push(format!("--gallery={gallery_images}"));
+    }
+    if let Some(limit) = time_limit {
+        args.push(format!("--time-limit={}", limit.as_millis()));
+    }
+    if let Some(limit) = read_limits.per_file {
+        args.push(format!("--file-limit={limit}"));
+    }
+    if let Some(limit) = read_limits.per_mod {
+        args.push(format!("--mod-limit={limit}"));
+    }
+    if detail_threads > 1 {
+        args.push(format!("--threads={detail_threads}"));
+    }
+    match growth_periods {
+        GrowthPeriodMode::Wrap => {}
+        GrowthPeriodMode::Clamp => args.push(String::from("--growth=clamp")),
+        GrowthPeriodMode::Skip => args.push(String::from("--growth=skip")),
+    }
+    match folder_hash {
+        FolderHashMode::Off => {}
+        FolderHashMode::Listing => args.push(String::from("--folder-hash=listing")),
+        FolderHashMode::Content => args.push(String::from("--folder-hash=content")),
+    }
+    match motor_units {
+        MotorUnits::Both => {}
+        MotorUnits::Metric => args.push(String::from("--units=metric")),
+        MotorUnits::Imperial => args.push(String::from("--units=imperial")),
+    }
+    if let Some(languages) = languages {
+        args.push(format!("--languages={}", languages.join(",")));
+    }
+    if let Some(tags) = raw_mod_desc_tags {
+        args.push(format!("--raw={}", tags.join(",")));
+    }
+    if !allowed_extra_files.is_empty() {
+        args.push(format!("--allow-extra={}", allowed_extra_files.join(",")));
+    }
+    args
+}
+
+/// Apply an address space limit to the worker before it starts
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, limit: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlim_t::try_from(limit).unwrap_or(libc::RLIM_INFINITY);
+
+    // SAFETY: only async-signal-safe `setrlimit` is called between fork and exec
+    unsafe {
+        command.pre_exec(move || {
+            let rlimit = libc::rlimit {
+                rlim_cur: limit,
+                rlim_max: limit,
+            };
+            if libc::setrlimit(libc::RLIMIT_AS, std::ptr::addr_of!(rlimit)) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn worker_flags() {
+        let options = ModParserOptions {
+            include_mod_detail: true,
+            skip_detail_icons: true,
+            languages: Some(vec![String::from("de"), String::from("fr")]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            worker_args(&options),
+            vec!["--compact", "--detail", "--languages=de,fr"]
+        );
+    }
+
+    #[test]
+    fn missing_worker() {
+        let sandbox = SandboxOptions::new("./this/worker/does/not/exist");
+        let result = parse_mod_sandboxed(
+            "./tests/test_mods/PASS_Good_Simple_Mod.zip",
+            &ModParserOptions::default(),
+            &sandbox,
+        );
+
+        assert!(matches!(result, Err(SandboxError::Spawn(..))));
+    }
+}
@@ -1,13 +1,18 @@
 //! Parse save game files.
-use crate::shared::files::{AbstractFileHandle, AbstractFolder, AbstractZipFile};
+use crate::shared::deserialize_unit_variant;
+use crate::shared::errors::FsModParserError;
+use crate::shared::files::{open_nested, AbstractFileHandle, AbstractFolder, AbstractZipFile};
+use crate::shared::structs::ModRecord;
+use crate::ModParserOptions;
+use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     path::Path,
 };
 
 /// Possible parse problems with a savegame
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Clone)]
 pub enum SaveError {
     /// File is unreadable
     FileUnreadable,
@@ -66,8 +71,30 @@ impl Serialize for SaveError {
     }
 }
 
+impl<'de> Deserialize<'de> for SaveError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                SaveError::FileUnreadable,
+                SaveError::FarmsMissing,
+                SaveError::FarmsParseError,
+                SaveError::PlaceableMissing,
+                SaveError::PlaceableParseError,
+                SaveError::VehicleMissing,
+                SaveError::VehicleParseError,
+                SaveError::CareerMissing,
+                SaveError::CareerParseError,
+            ],
+        )
+    }
+}
+
 /// Data structure for a savegame mod
-#[derive(serde::Serialize, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveGameMod {
     /// Mod version from careerSavegame
@@ -77,6 +104,12 @@ pub struct SaveGameMod {
     /// List of farms mod is purchased on
     #[serde(serialize_with = "ordered_set")]
     pub farms: HashSet<usize>,
+    /// Number of saved placeables from the mod
+    pub placeables: usize,
+    /// Number of saved vehicles from the mod
+    pub vehicles: usize,
+    /// How the save uses the mod
+    pub usage: SaveGameModUsage,
 }
 
 impl SaveGameMod {
@@ -86,9 +119,87 @@ impl SaveGameMod {
             version: String::from("0"),
             title: String::from("--"),
             farms: HashSet::new(),
+            placeables: 0,
+            vehicles: 0,
+            usage: SaveGameModUsage::NoItems,
         }
     }
 }
+
+/// How a save game uses a loaded mod
+///
+/// Only the map, vehicles and placeables are attributed to mods - a mod
+/// that adds fill types, animals, or scripts the save relies on shows as
+/// [`SaveGameModUsage::NoItems`]. Telling those apart from unused mods
+/// needs the mod records, see [`SaveGameRecord::classify_with_mods`]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SaveGameModUsage {
+    /// The mod is the map of the save
+    Map,
+    /// The save has vehicles or placeables from the mod
+    Used,
+    /// The mod is loaded and nothing saved comes from it, but it may add
+    /// scripts, fill types or animals, or be needed by another mod
+    NoItems,
+    /// The mod is loaded, only adds store items, none are in the save, and
+    /// no other loaded mod needs it
+    Unused,
+}
+
+impl Serialize for SaveGameModUsage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            SaveGameModUsage::Map => {
+                serializer.serialize_unit_variant("SaveGameModUsage", 0, "MAP")
+            }
+            SaveGameModUsage::Used => {
+                serializer.serialize_unit_variant("SaveGameModUsage", 1, "USED")
+            }
+            SaveGameModUsage::NoItems => {
+                serializer.serialize_unit_variant("SaveGameModUsage", 2, "NO_ITEMS")
+            }
+            SaveGameModUsage::Unused => {
+                serializer.serialize_unit_variant("SaveGameModUsage", 3, "UNUSED")
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SaveGameModUsage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                SaveGameModUsage::Map,
+                SaveGameModUsage::Used,
+                SaveGameModUsage::NoItems,
+                SaveGameModUsage::Unused,
+            ],
+        )
+    }
+}
+/// Download manifest entry for a savegame mod
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveGameManifestEntry {
+    /// Mod short name
+    pub short_name: String,
+    /// Mod title from careerSavegame
+    pub title: String,
+    /// Mod version from careerSavegame
+    pub version: String,
+    /// Mod is official DLC (`pdlc_` prefix), not downloadable
+    pub is_dlc: bool,
+    /// Farm IDs using the mod (see [`SaveGameRecord::farms`])
+    pub used_by: Vec<usize>,
+}
+
 /// Sort and collect a `HashSet` to a javascript array
 fn ordered_set<S, K: Ord + Serialize>(value: &HashSet<K>, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -100,7 +211,7 @@ where
 }
 
 /// Data structure for a savegame farm
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveGameFarm {
     /// Name of farm
@@ -111,6 +222,12 @@ pub struct SaveGameFarm {
     pub loan: i64,
     /// Color index for farm (1-16)
     pub color: usize,
+    /// Farm statistics, statistic name to value (e.g. `plowedHectares`)
+    ///
+    /// Only included with [`ModParserOptions::save_game_statistics`]
+    pub statistics: Option<BTreeMap<String, f64>>,
+    /// Day by day finances, ready for charting
+    pub finance_history: Option<SaveGameFinanceHistory>,
 }
 
 impl SaveGameFarm {
@@ -121,12 +238,89 @@ impl SaveGameFarm {
             cash: 0_i64,
             loan: 0_i64,
             color: 1_usize,
+            statistics: None,
+            finance_history: None,
         }
     }
 }
 
+/// Finance history of a savegame farm, as parallel arrays
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveGameFinanceHistory {
+    /// Days before the save of each point, oldest first (0 is the save day)
+    pub days_ago: Vec<u32>,
+    /// Value of each finance stat (e.g. `harvestIncome`) at each point
+    pub series: BTreeMap<String, Vec<f64>>,
+}
+
+impl SaveGameFinanceHistory {
+    /// Evenly sample the history down to `points`, keeping the oldest and
+    /// the newest day
+    ///
+    /// A history already that short (or `points` of 0) is returned as is
+    #[must_use]
+    pub fn downsample(&self, points: usize) -> Self {
+        if points == 0 || self.days_ago.len() <= points {
+            return self.clone();
+        }
+        let last = self.days_ago.len() - 1;
+        let step = points.saturating_sub(1).max(1);
+        let picks: Vec<usize> = (0..points).map(|i| i * last / step).collect();
+
+        SaveGameFinanceHistory {
+            days_ago: picks.iter().map(|i| self.days_ago[*i]).collect(),
+            series: self
+                .series
+                .iter()
+                .map(|(key, values)| (key.clone(), picks.iter().map(|i| values[*i]).collect()))
+                .collect(),
+        }
+    }
+}
+
+/// Weather forecast entry of a savegame
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveGameForecast {
+    /// Weather type (e.g. `SUN`, `RAIN`)
+    pub weather: String,
+    /// Season of the weather (e.g. `SPRING`)
+    pub season: String,
+    /// In-game day the weather starts (see [`SaveGameEnvironment::day`])
+    pub start_day: u64,
+    /// Time of day the weather starts, hours:minutes
+    pub start_time: String,
+    /// Length of the weather, in minutes
+    pub duration: u64,
+}
+
+/// In-game date, time, and weather of a savegame
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveGameEnvironment {
+    /// Days since the save was started, from 1
+    pub day: u64,
+    /// Time of day, hours:minutes
+    pub time: String,
+    /// Days in each period
+    pub days_per_period: u64,
+    /// Day within the current period, from 1
+    pub day_in_period: u64,
+    /// Current period, 1 (early spring) to 12 (late winter)
+    pub period: u64,
+    /// Calendar month of the period on a northern hemisphere map (1 = January)
+    ///
+    /// Southern hemisphere maps are six months ahead
+    pub month: u64,
+    /// Current year, from 1
+    pub year: u64,
+    /// Upcoming weather, in order
+    pub forecast: Vec<SaveGameForecast>,
+}
+
 /// Data structure for a savegame
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveGameRecord {
     /// List of found errors
@@ -139,6 +333,8 @@ pub struct SaveGameRecord {
     pub map_mod: Option<String>,
     /// Map title
     pub map_title: Option<String>,
+    /// In-game date and weather, if the save has an environment.xml
+    pub environment: Option<SaveGameEnvironment>,
     /// Number of mods loaded
     pub mod_count: usize,
     /// List of mods
@@ -161,13 +357,26 @@ impl SaveGameRecord {
     }
 
     /// Add (or update) a mod with the owning farm already known
-    fn add_mod_with_farm(&mut self, mod_key: &str, farm_id: usize) -> &mut Self {
+    fn add_mod_with_farm(&mut self, mod_key: &str, farm_id: usize) -> &mut SaveGameMod {
         let this_mod = self
             .mods
             .entry(mod_key.to_owned())
             .or_insert_with(SaveGameMod::new);
         this_mod.farms.insert(farm_id);
-        self
+        this_mod
+    }
+
+    /// Set the usage of each mod from the map and item counts
+    fn classify_mods(&mut self) {
+        for (mod_key, this_mod) in &mut self.mods {
+            this_mod.usage = if self.map_mod.as_ref() == Some(mod_key) {
+                SaveGameModUsage::Map
+            } else if this_mod.vehicles > 0 || this_mod.placeables > 0 {
+                SaveGameModUsage::Used
+            } else {
+                SaveGameModUsage::NoItems
+            };
+        }
     }
 
     /// Add (or update) a mod with the details already known
@@ -200,6 +409,7 @@ impl SaveGameRecord {
             is_valid: true,
             map_mod: None,
             map_title: None,
+            environment: None,
             mod_count: 0,
             mods: HashMap::new(),
             name: None,
@@ -216,6 +426,77 @@ impl SaveGameRecord {
         record
     }
 
+    /// Build a download manifest of the mods in this save, sorted by short name
+    ///
+    /// Mods with a short name in `installed` are left out
+    #[must_use]
+    pub fn mod_manifest(&self, installed: Option<&[String]>) -> Vec<SaveGameManifestEntry> {
+        let mut manifest: Vec<SaveGameManifestEntry> = self
+            .mods
+            .iter()
+            .filter(|(short_name, _)| installed.is_none_or(|list| !list.contains(short_name)))
+            .map(|(short_name, this_mod)| {
+                let mut used_by: Vec<usize> = this_mod.farms.iter().copied().collect();
+                used_by.sort_unstable();
+
+                SaveGameManifestEntry {
+                    short_name: short_name.clone(),
+                    title: this_mod.title.clone(),
+                    version: this_mod.version.clone(),
+                    is_dlc: short_name.starts_with("pdlc_"),
+                    used_by,
+                }
+            })
+            .collect();
+
+        manifest.sort_by(|a, b| a.short_name.cmp(&b.short_name));
+        manifest
+    }
+
+    /// Mark [`SaveGameModUsage::NoItems`] mods that are certainly unused
+    ///
+    /// A mod is unused when its record has store items and no scripts, and
+    /// no other loaded mod depends on it. Mods without a record, and mods
+    /// with no store items (fill types, animals) keep their usage.
+    pub fn classify_with_mods(&mut self, mod_records: &[ModRecord]) {
+        let needed: HashSet<&str> = mod_records
+            .iter()
+            .filter(|record| self.mods.contains_key(&record.file_detail.short_name))
+            .flat_map(|record| &record.mod_desc.depend)
+            .map(|dependency| dependency.name.as_str())
+            .collect();
+
+        for record in mod_records {
+            let short_name = record.file_detail.short_name.as_str();
+            let Some(this_mod) = self.mods.get_mut(short_name) else {
+                continue;
+            };
+            if this_mod.usage == SaveGameModUsage::NoItems
+                && record.mod_desc.script_files == 0
+                && record.mod_desc.store_items > 0
+                && !needed.contains(short_name)
+            {
+                this_mod.usage = SaveGameModUsage::Unused;
+            }
+        }
+    }
+
+    /// Short names of loaded mods the save does not use, sorted
+    ///
+    /// These can be dropped from the mod list. Only set by
+    /// [`SaveGameRecord::classify_with_mods`], so empty until that is called
+    #[must_use]
+    pub fn unused_mods(&self) -> Vec<&str> {
+        let mut unused: Vec<&str> = self
+            .mods
+            .iter()
+            .filter(|(_, this_mod)| this_mod.usage == SaveGameModUsage::Unused)
+            .map(|(short_name, _)| short_name.as_str())
+            .collect();
+        unused.sort_unstable();
+        unused
+    }
+
     /// Get output as pretty-print JSON
     #[must_use]
     pub fn to_json_pretty(&self) -> String {
@@ -235,6 +516,95 @@ impl std::fmt::Display for SaveGameRecord {
     }
 }
 
+/// Why a saved vehicle will not load
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SaveGameVehicleProblem {
+    /// The mod is not in the mods folder
+    ModMissing,
+    /// The mod is installed, but no longer contains the vehicle file
+    FileMissing,
+}
+
+impl Serialize for SaveGameVehicleProblem {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            SaveGameVehicleProblem::ModMissing => {
+                serializer.serialize_unit_variant("SaveGameVehicleProblem", 0, "MOD_MISSING")
+            }
+            SaveGameVehicleProblem::FileMissing => {
+                serializer.serialize_unit_variant("SaveGameVehicleProblem", 1, "FILE_MISSING")
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SaveGameVehicleProblem {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                SaveGameVehicleProblem::ModMissing,
+                SaveGameVehicleProblem::FileMissing,
+            ],
+        )
+    }
+}
+
+/// Saved vehicle that will vanish when the save is loaded
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveGameMissingVehicle {
+    /// Vehicle id from vehicles.xml (`uniqueId` or `id`)
+    pub id: String,
+    /// Owning farm ID
+    pub farm_id: usize,
+    /// Mod short name the vehicle file belongs to
+    pub mod_name: String,
+    /// Vehicle XML file, relative to the mod root
+    pub file_name: String,
+    /// Why the vehicle will not load
+    pub problem: SaveGameVehicleProblem,
+}
+
+/// Saved vehicles checked against a mods folder
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveGameVehicleCheck {
+    /// Problems reading the save (only vehicles.xml is read)
+    #[serde(serialize_with = "ordered_set")]
+    pub error_list: HashSet<SaveError>,
+    /// Number of saved vehicles that come from mods
+    pub mod_vehicles: usize,
+    /// Vehicles that will vanish, in vehicles.xml order
+    pub missing: Vec<SaveGameMissingVehicle>,
+}
+
+impl SaveGameVehicleCheck {
+    /// Get output as pretty-print JSON
+    #[must_use]
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or(String::from("{}"))
+    }
+
+    /// Get output as JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for SaveGameVehicleCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&serde_json::to_string(&self).unwrap_or(String::from("{}")))
+    }
+}
+
 /// Parse a savegame
 ///
 /// Returned information includes:
@@ -270,40 +640,184 @@ impl std::fmt::Display for SaveGameRecord {
 /// }
 /// ```
 pub fn parser<P: AsRef<Path>>(full_path: P) -> SaveGameRecord {
-    let is_folder = full_path.as_ref().is_dir();
+    parser_with_options(full_path, &ModParserOptions::default())
+}
 
-    let abstract_file: Box<dyn AbstractFileHandle> = if is_folder {
-        if let Ok(archive) = AbstractFolder::new(full_path) {
-            Box::new(archive)
-        } else {
-            return SaveGameRecord::fast_fail(SaveError::FileUnreadable);
-        }
-    } else if let Ok(archive) = AbstractZipFile::new(full_path) {
-        Box::new(archive)
-    } else {
+/// Parse a savegame (folder or zip) with options
+///
+/// Only [`ModParserOptions::save_game_statistics`] applies to save games
+pub fn parser_with_options<P: AsRef<Path>>(
+    full_path: P,
+    options: &ModParserOptions,
+) -> SaveGameRecord {
+    let Some(abstract_file) = open_file(full_path) else {
         return SaveGameRecord::fast_fail(SaveError::FileUnreadable);
     };
 
-    parse_open_file(abstract_file)
+    parse_open_file_with_options(abstract_file, options)
+}
+
+/// [`crate::savegame::parser`], returning an error when the input can't be read
+///
+/// # Errors
+///
+/// Returns a [`FsModParserError`] when the path is missing, can't be read, or
+/// is not a readable zip file or folder
+pub fn try_parser<P: AsRef<Path>>(full_path: P) -> Result<SaveGameRecord, FsModParserError> {
+    try_parser_with_options(full_path, &ModParserOptions::default())
+}
+
+/// [`crate::savegame::parser_with_options`], returning an error when the input can't be read
+///
+/// # Errors
+///
+/// Returns a [`FsModParserError`] when the path is missing, can't be read, or
+/// is not a readable zip file or folder
+pub fn try_parser_with_options<P: AsRef<Path>>(
+    full_path: P,
+    options: &ModParserOptions,
+) -> Result<SaveGameRecord, FsModParserError> {
+    let path = full_path.as_ref();
+    FsModParserError::check_path(path)?;
+
+    let Some(abstract_file) = open_file(path) else {
+        return Err(FsModParserError::Unreadable(path.to_path_buf()));
+    };
+    Ok(parse_open_file_with_options(abstract_file, options))
+}
+
+/// Open a save game or mod, either a folder or a zip file
+fn open_file<P: AsRef<Path>>(full_path: P) -> Option<Box<dyn AbstractFileHandle>> {
+    if full_path.as_ref().is_dir() {
+        AbstractFolder::new(full_path)
+            .ok()
+            .map(|archive| Box::new(archive) as Box<dyn AbstractFileHandle>)
+    } else {
+        AbstractZipFile::new(full_path)
+            .ok()
+            .map(|archive| Box::new(archive) as Box<dyn AbstractFileHandle>)
+    }
+}
+
+/// Find saved vehicles that will vanish with the mods in a mods folder
+///
+/// Mod vehicles are stored as `$moddir$<mod>/<file>.xml`. A vehicle is
+/// reported when its mod is not in the folder (as `<mod>.zip` or an
+/// unzipped `<mod>` folder), or the installed version of the mod no longer
+/// has the file - usually after a mod update moved or renamed it.
+/// File names are matched case sensitively, as on a linux server.
+pub fn check_vehicles<P: AsRef<Path>, Q: AsRef<Path>>(
+    full_path: P,
+    mods_folder: Q,
+) -> SaveGameVehicleCheck {
+    let mut check = SaveGameVehicleCheck::default();
+
+    let Some(abstract_file) = open_file(full_path) else {
+        check.error_list.insert(SaveError::FileUnreadable);
+        return check;
+    };
+    let mut abstract_file = open_nested(abstract_file, "careerSavegame.xml");
+    let Ok(vehicles_content) = abstract_file.as_text("vehicles.xml") else {
+        check.error_list.insert(SaveError::VehicleMissing);
+        return check;
+    };
+    let Ok(vehicles_document) = roxmltree::Document::parse(&vehicles_content) else {
+        check.error_list.insert(SaveError::VehicleParseError);
+        return check;
+    };
+
+    let mut installed: HashMap<String, Option<Box<dyn AbstractFileHandle>>> = HashMap::new();
+
+    for item in vehicles_document
+        .root_element()
+        .children()
+        .filter(|n| n.has_tag_name("vehicle"))
+    {
+        let Some((mod_name, file_name)) = item
+            .attribute("filename")
+            .and_then(|n| n.strip_prefix("$moddir$"))
+            .and_then(|n| n.split_once('/'))
+        else {
+            continue;
+        };
+        check.mod_vehicles += 1;
+
+        let mod_file = installed.entry(mod_name.to_owned()).or_insert_with(|| {
+            let zip_path = mods_folder.as_ref().join(format!("{mod_name}.zip"));
+            let folder_path = mods_folder.as_ref().join(mod_name);
+            if zip_path.is_file() {
+                open_file(zip_path)
+            } else if folder_path.is_dir() {
+                open_file(folder_path)
+            } else {
+                None
+            }
+        });
+
+        let problem = match mod_file {
+            None => SaveGameVehicleProblem::ModMissing,
+            Some(handle) => {
+                if handle.exists(file_name) {
+                    continue;
+                }
+                SaveGameVehicleProblem::FileMissing
+            }
+        };
+
+        check.missing.push(SaveGameMissingVehicle {
+            id: item
+                .attribute("uniqueId")
+                .or(item.attribute("id"))
+                .unwrap_or("")
+                .to_owned(),
+            farm_id: item
+                .attribute("farmId")
+                .map_or(0, |n| n.parse::<usize>().unwrap_or(0)),
+            mod_name: mod_name.to_owned(),
+            file_name: file_name.to_owned(),
+            problem,
+        });
+    }
+
+    check
 }
 
 /// Parse a savegame from an already open [`AbstractFileHandle`]
 #[must_use]
-pub fn parse_open_file(mut abstract_file: Box<dyn AbstractFileHandle>) -> SaveGameRecord {
+pub fn parse_open_file(abstract_file: Box<dyn AbstractFileHandle>) -> SaveGameRecord {
+    parse_open_file_with_options(abstract_file, &ModParserOptions::default())
+}
+
+/// Parse a savegame from an already open [`AbstractFileHandle`] with options
+///
+/// A save zipped with its folder (`savegame1/careerSavegame.xml`) is read
+/// from that folder
+#[must_use]
+pub fn parse_open_file_with_options(
+    abstract_file: Box<dyn AbstractFileHandle>,
+    options: &ModParserOptions,
+) -> SaveGameRecord {
+    let mut abstract_file = open_nested(abstract_file, "careerSavegame.xml");
     let mut save_record = SaveGameRecord::new();
 
-    do_farms(&mut save_record, &mut abstract_file);
+    do_farms(&mut save_record, &mut abstract_file, options);
     do_placeables(&mut save_record, &mut abstract_file);
     do_vehicles(&mut save_record, &mut abstract_file);
     do_career(&mut save_record, &mut abstract_file);
+    do_environment(&mut save_record, &mut abstract_file);
 
     save_record.mod_count = save_record.mods.len();
+    save_record.classify_mods();
 
     save_record
 }
 
 /// Process farms.xml
-fn do_farms(save_record: &mut SaveGameRecord, abstract_file: &mut Box<dyn AbstractFileHandle>) {
+fn do_farms(
+    save_record: &mut SaveGameRecord,
+    abstract_file: &mut Box<dyn AbstractFileHandle>,
+    options: &ModParserOptions,
+) {
     let Ok(farms_content) = abstract_file.as_text("farms.xml") else {
         save_record.add_issue(SaveError::FarmsMissing);
         return;
@@ -348,11 +862,72 @@ fn do_farms(save_record: &mut SaveGameRecord, abstract_file: &mut Box<dyn Abstra
         farm_record.color = farm_entry
             .attribute("color")
             .map_or(0, |n| n.parse::<usize>().unwrap_or(0));
+        farm_record.finance_history = farm_finances(&farm_entry);
+
+        if options.save_game_statistics {
+            farm_record.statistics = Some(farm_statistics(&farm_entry));
+        }
 
         save_record.farms.insert(farm_id, farm_record);
     }
 }
 
+/// Numeric statistics of a farm, from the `<statistics>` block
+///
+/// Non-numeric entries are skipped
+fn farm_statistics(farm_entry: &roxmltree::Node) -> BTreeMap<String, f64> {
+    farm_entry
+        .children()
+        .find(|n| n.has_tag_name("statistics"))
+        .map(|stats| {
+            stats
+                .children()
+                .filter(roxmltree::Node::is_element)
+                .filter_map(|n| {
+                    let value = n.text()?.trim().parse::<f64>().ok()?;
+                    Some((n.tag_name().name().to_owned(), value))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Finance history of a farm, from the `<finances>` block
+///
+/// A stat missing on a day is 0
+fn farm_finances(farm_entry: &roxmltree::Node) -> Option<SaveGameFinanceHistory> {
+    let mut days: Vec<(u32, roxmltree::Node)> = farm_entry
+        .children()
+        .find(|n| n.has_tag_name("finances"))?
+        .children()
+        .filter(|n| n.has_tag_name("stats"))
+        .filter_map(|n| Some((n.attribute("day")?.parse::<u32>().ok()?, n)))
+        .collect();
+
+    if days.is_empty() {
+        return None;
+    }
+    days.sort_by_key(|(day, _)| std::cmp::Reverse(*day));
+
+    let mut history = SaveGameFinanceHistory {
+        days_ago: days.iter().map(|(day, _)| *day).collect(),
+        series: BTreeMap::new(),
+    };
+
+    for (index, (_, stats)) in days.iter().enumerate() {
+        for stat in stats.children().filter(roxmltree::Node::is_element) {
+            let Some(value) = stat.text().and_then(|n| n.trim().parse::<f64>().ok()) else {
+                continue;
+            };
+            history
+                .series
+                .entry(stat.tag_name().name().to_owned())
+                .or_insert_with(|| vec![0.0; days.len()])[index] = value;
+        }
+    }
+    Some(history)
+}
+
 /// Process placables.xml
 fn do_placeables(
     save_record: &mut SaveGameRecord,
@@ -375,8 +950,9 @@ fn do_placeables(
             .attribute("farmId")
             .map_or(0, |n| n.parse::<usize>().unwrap_or(0));
 
-        item.attribute("modName")
-            .map(|key| save_record.add_mod_with_farm(key, farm_id));
+        if let Some(key) = item.attribute("modName") {
+            save_record.add_mod_with_farm(key, farm_id).placeables += 1;
+        }
     }
 }
 
@@ -399,8 +975,9 @@ fn do_vehicles(save_record: &mut SaveGameRecord, abstract_file: &mut Box<dyn Abs
             .attribute("farmId")
             .map_or(0, |n| n.parse::<usize>().unwrap_or(0));
 
-        item.attribute("modName")
-            .map(|key| save_record.add_mod_with_farm(key, farm_id));
+        if let Some(key) = item.attribute("modName") {
+            save_record.add_mod_with_farm(key, farm_id).vehicles += 1;
+        }
     }
 }
 
@@ -475,3 +1052,72 @@ fn do_career(save_record: &mut SaveGameRecord, abstract_file: &mut Box<dyn Abstr
         }
     }
 }
+
+/// Format milliseconds since midnight as hours:minutes
+fn clock_time(milliseconds: u64) -> String {
+    let minutes = milliseconds / 60_000;
+    format!("{:02}:{:02}", (minutes / 60) % 24, minutes % 60)
+}
+
+/// Process environment.xml
+///
+/// Older and partial saves may not have one, that is not an error
+fn do_environment(
+    save_record: &mut SaveGameRecord,
+    abstract_file: &mut Box<dyn AbstractFileHandle>,
+) {
+    let Ok(environment_content) = abstract_file.as_text("environment.xml") else {
+        return;
+    };
+    let Ok(environment_document) = roxmltree::Document::parse(&environment_content) else {
+        return;
+    };
+
+    let root = environment_document.root_element();
+    let number = |tag: &str| {
+        root.children()
+            .find(|n| n.has_tag_name(tag))
+            .and_then(|n| n.text())
+            .and_then(|n| n.trim().parse::<f64>().ok())
+    };
+
+    let Some(day) = number("currentMonotonicDay").or(number("currentDay")) else {
+        return;
+    };
+
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (day, days_per_period, day_time) = (
+        day.max(1.0) as u64,
+        number("daysPerPeriod").unwrap_or(1.0).max(1.0) as u64,
+        (number("dayTime").unwrap_or(0.0).max(0.0) * 60_000.0) as u64,
+    );
+    let period = ((day - 1) / days_per_period) % 12 + 1;
+
+    let forecast = environment_document
+        .descendants()
+        .filter(|n| n.has_tag_name("forecast"))
+        .flat_map(|n| n.children().filter(|n| n.has_tag_name("instance")))
+        .map(|n| {
+            let attribute = |key: &str| n.attribute(key).unwrap_or_default();
+            let value = |key: &str| n.attribute(key).and_then(|v| v.parse::<u64>().ok());
+            SaveGameForecast {
+                weather: attribute("typeName").to_owned(),
+                season: attribute("season").to_owned(),
+                start_day: value("startDay").unwrap_or(day),
+                start_time: clock_time(value("startDayTime").unwrap_or(0)),
+                duration: value("duration").unwrap_or(0) / 60_000,
+            }
+        })
+        .collect();
+
+    save_record.environment = Some(SaveGameEnvironment {
+        day,
+        time: clock_time(day_time),
+        days_per_period,
+        day_in_period: (day - 1) % days_per_period + 1,
+        period,
+        month: (period + 1) % 12 + 1,
+        year: (day - 1) / (days_per_period * 12) + 1,
+        forecast,
+    });
+}
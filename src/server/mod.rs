@@ -0,0 +1,193 @@
+//! Dedicated server mod folder checks
+//!
+//! A dedicated server only loads zipped, multiplayer capable mods.
+//! [`scan_server_mods`] parses every mod in a server's mod folder and
+//! reports which ones will load, and why the others won't.
+use crate::mod_basic::parser_with_options;
+use crate::shared::structs::ModRecord;
+use crate::ModParserOptions;
+use serde::ser::{Serialize, Serializer};
+use std::path::Path;
+
+/// Reasons a mod is not ready for a dedicated server
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Clone, Copy)]
+pub enum ServerModProblem {
+    /// Mod is broken or not a mod (see the record issues)
+    CanNotUse,
+    /// Mod is an unzipped folder
+    Unzipped,
+    /// Mod is not flagged for multiplayer
+    NotMultiplayer,
+    /// Mod contains scripts, and scripts are not allowed
+    HasScripts,
+}
+
+impl Serialize for ServerModProblem {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            ServerModProblem::CanNotUse => {
+                serializer.serialize_unit_variant("ServerModProblem", 0, "SERVER_CAN_NOT_USE")
+            }
+            ServerModProblem::Unzipped => {
+                serializer.serialize_unit_variant("ServerModProblem", 1, "SERVER_UNZIPPED")
+            }
+            ServerModProblem::NotMultiplayer => {
+                serializer.serialize_unit_variant("ServerModProblem", 2, "SERVER_NOT_MULTIPLAYER")
+            }
+            ServerModProblem::HasScripts => {
+                serializer.serialize_unit_variant("ServerModProblem", 3, "SERVER_HAS_SCRIPTS")
+            }
+        }
+    }
+}
+
+/// Server specific rules
+pub struct ServerRules {
+    /// Allow mods that contain script files
+    pub allow_scripts: bool,
+}
+
+impl Default for ServerRules {
+    fn default() -> Self {
+        ServerRules {
+            allow_scripts: true,
+        }
+    }
+}
+
+/// Verdict for a single mod
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerModVerdict {
+    /// short name of the mod
+    pub short_name: String,
+    /// mod will load on the server
+    pub ready: bool,
+    /// reasons the mod will not load
+    pub problems: Vec<ServerModProblem>,
+    /// full parsed record
+    pub record: ModRecord,
+}
+
+impl ServerModVerdict {
+    /// Check a parsed mod against the server rules
+    #[must_use]
+    pub fn new(record: ModRecord, rules: &ServerRules) -> Self {
+        let mut problems = vec![];
+
+        if record.can_not_use {
+            problems.push(ServerModProblem::CanNotUse);
+        }
+        if record.file_detail.is_folder {
+            problems.push(ServerModProblem::Unzipped);
+        }
+        if !record.can_not_use && !record.mod_desc.multi_player {
+            problems.push(ServerModProblem::NotMultiplayer);
+        }
+        if !rules.allow_scripts && record.mod_desc.script_files > 0 {
+            problems.push(ServerModProblem::HasScripts);
+        }
+
+        ServerModVerdict {
+            short_name: record.file_detail.short_name.clone(),
+            ready: problems.is_empty(),
+            problems,
+            record,
+        }
+    }
+}
+
+/// Readiness of a dedicated server mod folder
+#[derive(serde::Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerReadinessReport {
+    /// every mod will load
+    pub all_ready: bool,
+    /// number of mods that will load
+    pub ready_count: usize,
+    /// number of mods that will not load
+    pub not_ready_count: usize,
+    /// per mod verdicts, sorted by short name
+    pub mods: Vec<ServerModVerdict>,
+}
+
+impl ServerReadinessReport {
+    /// Build a report from verdicts
+    #[must_use]
+    pub fn new(mut mods: Vec<ServerModVerdict>) -> Self {
+        mods.sort_by(|a, b| a.short_name.cmp(&b.short_name));
+        let ready_count = mods.iter().filter(|verdict| verdict.ready).count();
+
+        ServerReadinessReport {
+            all_ready: ready_count == mods.len(),
+            ready_count,
+            not_ready_count: mods.len() - ready_count,
+            mods,
+        }
+    }
+
+    /// Output as pretty-print JSON
+    #[must_use]
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or(String::from("{}"))
+    }
+
+    /// Output as JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for ServerReadinessReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&serde_json::to_string(&self).unwrap_or(String::from("{}")))
+    }
+}
+
+/// Check every mod in a dedicated server mod folder, default options
+///
+/// See [`scan_server_mods_with_options`]
+#[must_use]
+pub fn scan_server_mods<P: AsRef<Path>>(mod_folder: P) -> ServerReadinessReport {
+    scan_server_mods_with_options(
+        mod_folder,
+        &ModParserOptions {
+            skip_mod_icons: true,
+            ..Default::default()
+        },
+        &ServerRules::default(),
+    )
+}
+
+/// Check every mod in a dedicated server mod folder
+///
+/// Zip files and folders directly inside `mod_folder` are parsed, other
+/// files are ignored. An unreadable folder gives an empty report.
+#[must_use]
+pub fn scan_server_mods_with_options<P: AsRef<Path>>(
+    mod_folder: P,
+    options: &ModParserOptions,
+    rules: &ServerRules,
+) -> ServerReadinessReport {
+    let Ok(entries) = std::fs::read_dir(mod_folder) else {
+        return ServerReadinessReport::new(vec![]);
+    };
+
+    let verdicts = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir()
+                || path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+        })
+        .map(|path| ServerModVerdict::new(parser_with_options(&path, options), rules))
+        .collect();
+
+    ServerReadinessReport::new(verdicts)
+}
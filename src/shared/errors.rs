@@ -1,21 +1,100 @@
 //! Passable Error flags
+use crate::shared::deserialize_unit_variant;
+use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
+use std::path::{Path, PathBuf};
+
+/// Errors returned by the `try_` parsers, like [`crate::try_parse_mod`],
+/// and when loading a [`crate::collection::Index`]
+///
+/// The other parsers report these as issues in the returned record
+#[derive(Debug)]
+pub enum FsModParserError {
+    /// Input path does not exist
+    NotFound(PathBuf),
+    /// Input could not be read
+    Io(std::io::Error),
+    /// Input is not a readable zip file or folder
+    Unreadable(PathBuf),
+    /// Collection index file is damaged or not an index
+    InvalidIndex(PathBuf),
+}
+
+impl FsModParserError {
+    /// Check that an input path exists and can be opened
+    pub(crate) fn check_path(path: &Path) -> Result<(), FsModParserError> {
+        let meta = std::fs::metadata(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => FsModParserError::NotFound(path.to_path_buf()),
+            _ => FsModParserError::Io(e),
+        })?;
+        if meta.is_dir() {
+            std::fs::read_dir(path)?;
+        } else {
+            std::fs::File::open(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for FsModParserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FsModParserError::NotFound(path) => write!(f, "{} not found", path.display()),
+            FsModParserError::Io(e) => write!(f, "could not read input: {e}"),
+            FsModParserError::Unreadable(path) => {
+                write!(f, "{} is not a readable zip file or folder", path.display())
+            }
+            FsModParserError::InvalidIndex(path) => {
+                write!(f, "{} is not a valid collection index", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FsModParserError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FsModParserError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for FsModParserError {
+    fn from(e: std::io::Error) -> Self {
+        FsModParserError::Io(e)
+    }
+}
 
 /// Possible Detectable Mod Errors
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Clone)]
 pub enum ModError {
+    /// File references point outside of the mod
+    FileErrorExternalReference,
     /// File is not the right type for a mod
     FileErrorGarbageFile,
     /// File is probably a copy
     FileErrorLikelyCopy,
+    /// Folder is probably a mods folder, not a single mod
+    FileErrorLikelyModFolder,
     /// File is probably a save game
     FileErrorLikelySaveGame,
     /// File is probably a pack of mods
     FileErrorLikelyZipPack,
+    /// Internal paths are too long for windows in a typical mods folder
+    FileErrorLongPath,
+    /// Store items reference light or beacon files that are not in the mod
+    FileErrorMissingLight,
+    /// Files were only found by ignoring the case of their names
+    FileErrorNameCase,
     /// Filename is invalid for a mod
     FileErrorNameInvalid,
     /// Filename starts with a digit
     FileErrorNameStartsDigit,
+    /// Internal file names contain non-ASCII characters
+    FileErrorNonAsciiName,
+    /// File references differ in case from the files in the mod
+    FileErrorReferenceCase,
     /// ZIP file could not be read
     FileErrorUnreadableZip,
     /// File is an unsupported archive type
@@ -28,8 +107,20 @@ pub enum ModError {
     InfoDangerousFile,
     /// Mod is unzipped and can't be used in multiplayer
     InfoNoMultiplayerUnzipped,
+    /// Parse time budget ran out, optional stages were skipped
+    InfoTimedOut,
+    /// Map density, info layer or weight files are missing or empty
+    MapDensityFileMissing,
+    /// Map growth file has out of range periods or reversed ranges
+    MapGrowthOutOfRange,
     /// The modDesc.xml file is damaged
     ModDescDamaged,
+    /// The same default key is bound to more than one action
+    ModDescDuplicateBinding,
+    /// The mod icon is not stored in a compressed (BC / DXT) format
+    ModDescIconUncompressed,
+    /// The mod icon is not 256x256
+    ModDescIconWrongSize,
     /// The modDesc.xml file is missing
     ModDescMissing,
     /// The mod is missing an icon
@@ -46,6 +137,8 @@ pub enum ModError {
     PerformanceMissingL10N,
     /// File contains DDS files that are too big
     PerformanceOversizeDDS,
+    /// Audio files are too big in total
+    PerformanceOversizeAudio,
     /// File contains GDM files that are too big
     PerformanceOversizeGDM,
     /// File contains I3D.CACHE files that are too big
@@ -64,11 +157,63 @@ pub enum ModError {
     PerformanceQuantityPNG,
     /// File contains too many TXT files
     PerformanceQuantityTXT,
+    /// A file read was refused by the configured read limits
+    PerformanceReadLimit,
 }
 
+/// Every `ModError`, in declaration order
+pub const ALL_ERRORS: [&ModError; 45] = [
+    &ModError::FileErrorExternalReference,
+    &ModError::FileErrorGarbageFile,
+    &ModError::FileErrorLikelyCopy,
+    &ModError::FileErrorLikelyModFolder,
+    &ModError::FileErrorLikelySaveGame,
+    &ModError::FileErrorLikelyZipPack,
+    &ModError::FileErrorLongPath,
+    &ModError::FileErrorMissingLight,
+    &ModError::FileErrorNameCase,
+    &ModError::FileErrorNameInvalid,
+    &ModError::FileErrorNameStartsDigit,
+    &ModError::FileErrorNonAsciiName,
+    &ModError::FileErrorReferenceCase,
+    &ModError::FileErrorUnreadableZip,
+    &ModError::FileErrorUnsupportedArchive,
+    &ModError::InfoLikelyPiracy,
+    &ModError::InfoMaliciousCode,
+    &ModError::InfoDangerousFile,
+    &ModError::InfoNoMultiplayerUnzipped,
+    &ModError::InfoTimedOut,
+    &ModError::MapDensityFileMissing,
+    &ModError::MapGrowthOutOfRange,
+    &ModError::ModDescDamaged,
+    &ModError::ModDescDuplicateBinding,
+    &ModError::ModDescIconUncompressed,
+    &ModError::ModDescIconWrongSize,
+    &ModError::ModDescMissing,
+    &ModError::ModDescNoModIcon,
+    &ModError::ModDescNoModVersion,
+    &ModError::ModDescParseError,
+    &ModError::ModDescVersionOldOrMissing,
+    &ModError::PerformanceFileSpaces,
+    &ModError::PerformanceMissingL10N,
+    &ModError::PerformanceOversizeAudio,
+    &ModError::PerformanceOversizeDDS,
+    &ModError::PerformanceOversizeGDM,
+    &ModError::PerformanceOversizeI3D,
+    &ModError::PerformanceOversizeSHAPES,
+    &ModError::PerformanceOversizeXML,
+    &ModError::PerformanceQuantityExtra,
+    &ModError::PerformanceQuantityGRLE,
+    &ModError::PerformanceQuantityPDF,
+    &ModError::PerformanceQuantityPNG,
+    &ModError::PerformanceQuantityTXT,
+    &ModError::PerformanceReadLimit,
+];
+
 /// `ModErrors` the mean a mod is broken (won't work)
-pub const BADGE_BROKEN: [&ModError; 10] = [
+pub const BADGE_BROKEN: [&ModError; 11] = [
     &ModError::FileErrorGarbageFile,
+    &ModError::FileErrorLikelyModFolder,
     &ModError::FileErrorLikelySaveGame,
     &ModError::FileErrorLikelyZipPack,
     &ModError::FileErrorNameInvalid,
@@ -81,15 +226,25 @@ pub const BADGE_BROKEN: [&ModError; 10] = [
 ];
 
 /// `ModErrors` that should be fixed, but probably still work
-pub const BADGE_ISSUE: [&ModError; 18] = [
+pub const BADGE_ISSUE: [&ModError; 29] = [
+    &ModError::FileErrorExternalReference,
+    &ModError::FileErrorLongPath,
+    &ModError::FileErrorMissingLight,
+    &ModError::FileErrorNameCase,
+    &ModError::FileErrorNonAsciiName,
+    &ModError::FileErrorReferenceCase,
     &ModError::InfoLikelyPiracy,
     &ModError::InfoMaliciousCode,
     &ModError::InfoDangerousFile,
+    &ModError::MapGrowthOutOfRange,
+    &ModError::ModDescIconUncompressed,
+    &ModError::ModDescIconWrongSize,
     &ModError::ModDescNoModIcon,
     &ModError::ModDescNoModVersion,
     &ModError::ModDescDamaged,
     &ModError::PerformanceFileSpaces,
     &ModError::PerformanceMissingL10N,
+    &ModError::PerformanceOversizeAudio,
     &ModError::PerformanceOversizeDDS,
     &ModError::PerformanceOversizeGDM,
     &ModError::PerformanceOversizeI3D,
@@ -100,11 +255,13 @@ pub const BADGE_ISSUE: [&ModError; 18] = [
     &ModError::PerformanceQuantityPDF,
     &ModError::PerformanceQuantityPNG,
     &ModError::PerformanceQuantityTXT,
+    &ModError::PerformanceReadLimit,
 ];
 
 /// `ModErrors` that denote it's not actually a mod
-pub const BADGE_NOT_MOD: [&ModError; 6] = [
+pub const BADGE_NOT_MOD: [&ModError; 7] = [
     &ModError::FileErrorGarbageFile,
+    &ModError::FileErrorLikelyModFolder,
     &ModError::FileErrorLikelySaveGame,
     &ModError::FileErrorLikelyZipPack,
     &ModError::FileErrorUnreadableZip,
@@ -112,7 +269,311 @@ pub const BADGE_NOT_MOD: [&ModError; 6] = [
     &ModError::ModDescMissing,
 ];
 
+/// How serious a [`ModError`] is
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Clone, Copy)]
+pub enum ModErrorSeverity {
+    /// Worth knowing, nothing to fix
+    Info,
+    /// Should be fixed, mod probably still works
+    Warning,
+    /// Mod will not load correctly
+    Error,
+    /// File is not a usable mod at all
+    Fatal,
+}
+
+impl Serialize for ModErrorSeverity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            ModErrorSeverity::Info => {
+                serializer.serialize_unit_variant("ModErrorSeverity", 0, "info")
+            }
+            ModErrorSeverity::Warning => {
+                serializer.serialize_unit_variant("ModErrorSeverity", 1, "warning")
+            }
+            ModErrorSeverity::Error => {
+                serializer.serialize_unit_variant("ModErrorSeverity", 2, "error")
+            }
+            ModErrorSeverity::Fatal => {
+                serializer.serialize_unit_variant("ModErrorSeverity", 3, "fatal")
+            }
+        }
+    }
+}
+
+impl ModError {
+    /// Severity of this issue
+    #[must_use]
+    pub fn severity(&self) -> ModErrorSeverity {
+        match self {
+            ModError::FileErrorGarbageFile
+            | ModError::FileErrorLikelyModFolder
+            | ModError::FileErrorLikelySaveGame
+            | ModError::FileErrorLikelyZipPack
+            | ModError::FileErrorUnreadableZip
+            | ModError::FileErrorUnsupportedArchive
+            | ModError::ModDescMissing
+            | ModError::ModDescParseError => ModErrorSeverity::Fatal,
+            ModError::FileErrorNameInvalid
+            | ModError::FileErrorNameStartsDigit
+            | ModError::ModDescVersionOldOrMissing => ModErrorSeverity::Error,
+            ModError::FileErrorExternalReference
+            | ModError::FileErrorLikelyCopy
+            | ModError::FileErrorLongPath
+            | ModError::FileErrorMissingLight
+            | ModError::FileErrorNameCase
+            | ModError::FileErrorNonAsciiName
+            | ModError::FileErrorReferenceCase
+            | ModError::InfoLikelyPiracy
+            | ModError::InfoMaliciousCode
+            | ModError::InfoDangerousFile
+            | ModError::MapDensityFileMissing
+            | ModError::MapGrowthOutOfRange
+            | ModError::ModDescDamaged
+            | ModError::ModDescDuplicateBinding
+            | ModError::ModDescIconUncompressed
+            | ModError::ModDescIconWrongSize
+            | ModError::ModDescNoModIcon
+            | ModError::ModDescNoModVersion
+            | ModError::PerformanceReadLimit => ModErrorSeverity::Warning,
+            ModError::InfoNoMultiplayerUnzipped
+            | ModError::InfoTimedOut
+            | ModError::PerformanceFileSpaces
+            | ModError::PerformanceMissingL10N
+            | ModError::PerformanceOversizeAudio
+            | ModError::PerformanceOversizeDDS
+            | ModError::PerformanceOversizeGDM
+            | ModError::PerformanceOversizeI3D
+            | ModError::PerformanceOversizeSHAPES
+            | ModError::PerformanceOversizeXML
+            | ModError::PerformanceQuantityExtra
+            | ModError::PerformanceQuantityGRLE
+            | ModError::PerformanceQuantityPDF
+            | ModError::PerformanceQuantityPNG
+            | ModError::PerformanceQuantityTXT => ModErrorSeverity::Info,
+        }
+    }
+
+    /// Human readable description of this issue
+    ///
+    /// Supported languages are listed in [`crate::shared::l10n::LANGUAGES`],
+    /// anything else falls back to english
+    #[must_use]
+    pub fn describe(&self, lang: &str) -> &'static str {
+        match lang {
+            "de" => self.describe_de(),
+            "fr" => self.describe_fr(),
+            _ => self.describe_en(),
+        }
+    }
+
+    /// English description
+    fn describe_en(&self) -> &'static str {
+        match self {
+            ModError::FileErrorExternalReference => "Some file references point outside the mod",
+            ModError::FileErrorGarbageFile => "File is not a mod",
+            ModError::FileErrorLikelyCopy => "File looks like a copy of another mod",
+            ModError::FileErrorLikelyModFolder => {
+                "Folder looks like a mods folder, not a single mod"
+            }
+            ModError::FileErrorLikelySaveGame => "File looks like a save game",
+            ModError::FileErrorLikelyZipPack => "File looks like a pack of several mods",
+            ModError::FileErrorLongPath => "Some file paths are too long for windows",
+            ModError::FileErrorMissingLight => "Some light or beacon files are missing",
+            ModError::FileErrorNameCase => "Some files were only found by ignoring case",
+            ModError::FileErrorNameInvalid => "File name is not valid for a mod",
+            ModError::FileErrorNameStartsDigit => "File name starts with a digit",
+            ModError::FileErrorNonAsciiName => "Some file names contain non-ASCII characters",
+            ModError::FileErrorReferenceCase => "Some file references do not match the file case",
+            ModError::FileErrorUnreadableZip => "ZIP file could not be read",
+            ModError::FileErrorUnsupportedArchive => "Archive type is not supported",
+            ModError::InfoLikelyPiracy => "Mod may contain pirated content",
+            ModError::InfoMaliciousCode => "Mod may contain malicious script code",
+            ModError::InfoDangerousFile => "Mod contains potentially dangerous files",
+            ModError::InfoNoMultiplayerUnzipped => "Unzipped mods can not be used in multiplayer",
+            ModError::InfoTimedOut => "Parsing took too long, some details were skipped",
+            ModError::MapDensityFileMissing => "Map density or weight files are missing or empty",
+            ModError::MapGrowthOutOfRange => "Map growth file has out of range periods or ranges",
+            ModError::ModDescDamaged => "modDesc.xml is damaged but could be read",
+            ModError::ModDescDuplicateBinding => "The same key is bound to more than one action",
+            ModError::ModDescIconUncompressed => "Mod icon is not a compressed (DXT / BC) DDS",
+            ModError::ModDescIconWrongSize => "Mod icon is not 256x256",
+            ModError::ModDescMissing => "modDesc.xml is missing",
+            ModError::ModDescNoModIcon => "Mod icon is missing",
+            ModError::ModDescNoModVersion => "Mod version is missing",
+            ModError::ModDescParseError => "modDesc.xml could not be read",
+            ModError::ModDescVersionOldOrMissing => "modDesc.xml descVersion is old or missing",
+            ModError::PerformanceFileSpaces => "Some file names contain spaces",
+            ModError::PerformanceMissingL10N => "Title or description is not translated",
+            ModError::PerformanceOversizeAudio => "Audio files are too large in total",
+            ModError::PerformanceOversizeDDS => "Some DDS files are too large",
+            ModError::PerformanceOversizeGDM => "Some GDM files are too large",
+            ModError::PerformanceOversizeI3D => "Some I3D cache files are too large",
+            ModError::PerformanceOversizeSHAPES => "Some SHAPES files are too large",
+            ModError::PerformanceOversizeXML => "Some XML files are too large",
+            ModError::PerformanceQuantityExtra => "Mod contains too many unused files",
+            ModError::PerformanceQuantityGRLE => "Mod contains too many GRLE files",
+            ModError::PerformanceQuantityPDF => "Mod contains too many PDF files",
+            ModError::PerformanceQuantityPNG => "Mod contains too many PNG files",
+            ModError::PerformanceQuantityTXT => "Mod contains too many TXT files",
+            ModError::PerformanceReadLimit => "Some files were too large to read",
+        }
+    }
+
+    /// German description
+    fn describe_de(&self) -> &'static str {
+        match self {
+            ModError::FileErrorExternalReference => {
+                "Einige Dateiverweise zeigen auf Dateien außerhalb des Mods"
+            }
+            ModError::FileErrorGarbageFile => "Datei ist kein Mod",
+            ModError::FileErrorLikelyCopy => "Datei ist vermutlich eine Kopie eines anderen Mods",
+            ModError::FileErrorLikelyModFolder => {
+                "Ordner ist vermutlich ein Mod-Ordner, kein einzelner Mod"
+            }
+            ModError::FileErrorLikelySaveGame => "Datei ist vermutlich ein Spielstand",
+            ModError::FileErrorLikelyZipPack => "Datei ist vermutlich ein Paket mehrerer Mods",
+            ModError::FileErrorLongPath => "Einige Dateipfade sind für Windows zu lang",
+            ModError::FileErrorMissingLight => "Einige Licht- oder Rundumleuchten-Dateien fehlen",
+            ModError::FileErrorNameCase => {
+                "Einige Dateien wurden nur ohne Groß-/Kleinschreibung gefunden"
+            }
+            ModError::FileErrorNameInvalid => "Dateiname ist für einen Mod ungültig",
+            ModError::FileErrorNameStartsDigit => "Dateiname beginnt mit einer Ziffer",
+            ModError::FileErrorNonAsciiName => "Einige Dateinamen enthalten Nicht-ASCII-Zeichen",
+            ModError::FileErrorReferenceCase => {
+                "Einige Dateiverweise weichen in der Groß-/Kleinschreibung ab"
+            }
+            ModError::FileErrorUnreadableZip => "ZIP-Datei konnte nicht gelesen werden",
+            ModError::FileErrorUnsupportedArchive => "Archivformat wird nicht unterstützt",
+            ModError::InfoLikelyPiracy => "Mod enthält möglicherweise raubkopierte Inhalte",
+            ModError::InfoMaliciousCode => "Mod enthält möglicherweise schädlichen Skriptcode",
+            ModError::InfoDangerousFile => "Mod enthält möglicherweise gefährliche Dateien",
+            ModError::InfoNoMultiplayerUnzipped => {
+                "Entpackte Mods können im Mehrspieler nicht genutzt werden"
+            }
+            ModError::InfoTimedOut => {
+                "Auswertung dauerte zu lange, einige Details wurden übersprungen"
+            }
+            ModError::MapDensityFileMissing => {
+                "Dichte- oder Gewichtungsdateien der Karte fehlen oder sind leer"
+            }
+            ModError::MapGrowthOutOfRange => {
+                "Wachstumsdatei der Karte enthält ungültige Perioden oder Bereiche"
+            }
+            ModError::ModDescDamaged => "modDesc.xml ist beschädigt, konnte aber gelesen werden",
+            ModError::ModDescDuplicateBinding => "Dieselbe Taste ist mehreren Aktionen zugewiesen",
+            ModError::ModDescIconUncompressed => "Mod-Symbol ist kein komprimiertes (DXT / BC) DDS",
+            ModError::ModDescIconWrongSize => "Mod-Symbol ist nicht 256x256",
+            ModError::ModDescMissing => "modDesc.xml fehlt",
+            ModError::ModDescNoModIcon => "Mod-Symbol fehlt",
+            ModError::ModDescNoModVersion => "Mod-Version fehlt",
+            ModError::ModDescParseError => "modDesc.xml konnte nicht gelesen werden",
+            ModError::ModDescVersionOldOrMissing => "descVersion in modDesc.xml ist alt oder fehlt",
+            ModError::PerformanceFileSpaces => "Einige Dateinamen enthalten Leerzeichen",
+            ModError::PerformanceMissingL10N => "Titel oder Beschreibung sind nicht übersetzt",
+            ModError::PerformanceOversizeAudio => "Audiodateien sind insgesamt zu groß",
+            ModError::PerformanceOversizeDDS => "Einige DDS-Dateien sind zu groß",
+            ModError::PerformanceOversizeGDM => "Einige GDM-Dateien sind zu groß",
+            ModError::PerformanceOversizeI3D => "Einige I3D-Cache-Dateien sind zu groß",
+            ModError::PerformanceOversizeSHAPES => "Einige SHAPES-Dateien sind zu groß",
+            ModError::PerformanceOversizeXML => "Einige XML-Dateien sind zu groß",
+            ModError::PerformanceQuantityExtra => "Mod enthält zu viele ungenutzte Dateien",
+            ModError::PerformanceQuantityGRLE => "Mod enthält zu viele GRLE-Dateien",
+            ModError::PerformanceQuantityPDF => "Mod enthält zu viele PDF-Dateien",
+            ModError::PerformanceQuantityPNG => "Mod enthält zu viele PNG-Dateien",
+            ModError::PerformanceQuantityTXT => "Mod enthält zu viele TXT-Dateien",
+            ModError::PerformanceReadLimit => "Einige Dateien waren zu groß zum Lesen",
+        }
+    }
+
+    /// French description
+    fn describe_fr(&self) -> &'static str {
+        match self {
+            ModError::FileErrorExternalReference => {
+                "Certaines références de fichiers pointent hors du mod"
+            }
+            ModError::FileErrorGarbageFile => "Le fichier n'est pas un mod",
+            ModError::FileErrorLikelyCopy => "Le fichier semble être une copie d'un autre mod",
+            ModError::FileErrorLikelyModFolder => {
+                "Le dossier semble être un dossier de mods, pas un seul mod"
+            }
+            ModError::FileErrorLikelySaveGame => "Le fichier semble être une sauvegarde",
+            ModError::FileErrorLikelyZipPack => "Le fichier semble être un pack de plusieurs mods",
+            ModError::FileErrorLongPath => {
+                "Certains chemins de fichiers sont trop longs pour Windows"
+            }
+            ModError::FileErrorMissingLight => {
+                "Certains fichiers de feux ou de gyrophares sont manquants"
+            }
+            ModError::FileErrorNameCase => {
+                "Certains fichiers n'ont été trouvés qu'en ignorant la casse"
+            }
+            ModError::FileErrorNameInvalid => "Le nom du fichier n'est pas valide pour un mod",
+            ModError::FileErrorNameStartsDigit => "Le nom du fichier commence par un chiffre",
+            ModError::FileErrorNonAsciiName => {
+                "Certains noms de fichiers contiennent des caractères non ASCII"
+            }
+            ModError::FileErrorReferenceCase => {
+                "Certaines références de fichiers ne respectent pas la casse"
+            }
+            ModError::FileErrorUnreadableZip => "Le fichier ZIP n'a pas pu être lu",
+            ModError::FileErrorUnsupportedArchive => "Ce type d'archive n'est pas pris en charge",
+            ModError::InfoLikelyPiracy => "Le mod peut contenir du contenu piraté",
+            ModError::InfoMaliciousCode => "Le mod peut contenir du code de script malveillant",
+            ModError::InfoDangerousFile => "Le mod contient des fichiers potentiellement dangereux",
+            ModError::InfoNoMultiplayerUnzipped => {
+                "Les mods décompressés ne peuvent pas être utilisés en multijoueur"
+            }
+            ModError::InfoTimedOut => {
+                "L'analyse a pris trop de temps, certains détails ont été ignorés"
+            }
+            ModError::MapDensityFileMissing => {
+                "Des fichiers de densité ou de poids de la carte sont manquants ou vides"
+            }
+            ModError::MapGrowthOutOfRange => {
+                "Le fichier de croissance de la carte contient des périodes ou plages invalides"
+            }
+            ModError::ModDescDamaged => "modDesc.xml est endommagé mais a pu être lu",
+            ModError::ModDescDuplicateBinding => "La même touche est attribuée à plusieurs actions",
+            ModError::ModDescIconUncompressed => {
+                "L'icône du mod n'est pas un DDS compressé (DXT / BC)"
+            }
+            ModError::ModDescIconWrongSize => "L'icône du mod ne fait pas 256x256",
+            ModError::ModDescMissing => "modDesc.xml est manquant",
+            ModError::ModDescNoModIcon => "L'icône du mod est manquante",
+            ModError::ModDescNoModVersion => "La version du mod est manquante",
+            ModError::ModDescParseError => "modDesc.xml n'a pas pu être lu",
+            ModError::ModDescVersionOldOrMissing => {
+                "Le descVersion de modDesc.xml est ancien ou manquant"
+            }
+            ModError::PerformanceFileSpaces => "Certains noms de fichiers contiennent des espaces",
+            ModError::PerformanceMissingL10N => "Le titre ou la description n'est pas traduit",
+            ModError::PerformanceOversizeAudio => {
+                "Les fichiers audio sont trop volumineux au total"
+            }
+            ModError::PerformanceOversizeDDS => "Certains fichiers DDS sont trop volumineux",
+            ModError::PerformanceOversizeGDM => "Certains fichiers GDM sont trop volumineux",
+            ModError::PerformanceOversizeI3D => {
+                "Certains fichiers de cache I3D sont trop volumineux"
+            }
+            ModError::PerformanceOversizeSHAPES => "Certains fichiers SHAPES sont trop volumineux",
+            ModError::PerformanceOversizeXML => "Certains fichiers XML sont trop volumineux",
+            ModError::PerformanceQuantityExtra => "Le mod contient trop de fichiers inutilisés",
+            ModError::PerformanceQuantityGRLE => "Le mod contient trop de fichiers GRLE",
+            ModError::PerformanceQuantityPDF => "Le mod contient trop de fichiers PDF",
+            ModError::PerformanceQuantityPNG => "Le mod contient trop de fichiers PNG",
+            ModError::PerformanceQuantityTXT => "Le mod contient trop de fichiers TXT",
+            ModError::PerformanceReadLimit => "Certains fichiers étaient trop volumineux à lire",
+        }
+    }
+}
+
 impl Serialize for ModError {
+    #[expect(clippy::too_many_lines)]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -212,6 +673,60 @@ impl Serialize for ModError {
             ModError::PerformanceQuantityTXT => {
                 serializer.serialize_unit_variant("ModError", 29, "PERF_TXT_TOO_MANY")
             }
+            ModError::InfoTimedOut => {
+                serializer.serialize_unit_variant("ModError", 30, "INFO_PARSE_TIMED_OUT")
+            }
+            ModError::ModDescDuplicateBinding => {
+                serializer.serialize_unit_variant("ModError", 31, "MOD_ERROR_DUPLICATE_BIND")
+            }
+            ModError::PerformanceReadLimit => {
+                serializer.serialize_unit_variant("ModError", 32, "PERF_READ_LIMIT_EXCEEDED")
+            }
+            ModError::MapGrowthOutOfRange => {
+                serializer.serialize_unit_variant("ModError", 33, "MAP_GROWTH_OUT_OF_RANGE")
+            }
+            ModError::ModDescIconWrongSize => {
+                serializer.serialize_unit_variant("ModError", 34, "MOD_ERROR_ICON_WRONG_SIZE")
+            }
+            ModError::ModDescIconUncompressed => {
+                serializer.serialize_unit_variant("ModError", 35, "PERF_ICON_UNCOMPRESSED")
+            }
+            ModError::MapDensityFileMissing => {
+                serializer.serialize_unit_variant("ModError", 36, "MAP_DENSITY_FILE_MISSING")
+            }
+            ModError::PerformanceOversizeAudio => {
+                serializer.serialize_unit_variant("ModError", 37, "PERF_AUDIO_TOO_BIG")
+            }
+            ModError::FileErrorReferenceCase => {
+                serializer.serialize_unit_variant("ModError", 38, "FILE_ERROR_REFERENCE_CASE")
+            }
+            ModError::FileErrorExternalReference => {
+                serializer.serialize_unit_variant("ModError", 39, "FILE_ERROR_EXTERNAL_REFERENCE")
+            }
+            ModError::FileErrorLikelyModFolder => {
+                serializer.serialize_unit_variant("ModError", 40, "FILE_ERROR_LIKELY_MOD_FOLDER")
+            }
+            ModError::FileErrorNameCase => {
+                serializer.serialize_unit_variant("ModError", 41, "FILE_ERROR_NAME_CASE")
+            }
+            ModError::FileErrorLongPath => {
+                serializer.serialize_unit_variant("ModError", 42, "FILE_ERROR_LONG_PATH")
+            }
+            ModError::FileErrorNonAsciiName => {
+                serializer.serialize_unit_variant("ModError", 43, "FILE_ERROR_NON_ASCII_NAME")
+            }
+            ModError::FileErrorMissingLight => {
+                serializer.serialize_unit_variant("ModError", 44, "FILE_ERROR_MISSING_LIGHT")
+            }
         }
     }
 }
+
+impl<'de> Deserialize<'de> for ModError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(deserializer, ALL_ERRORS.into_iter().cloned())
+    }
+}
@@ -3,9 +3,10 @@
 //! This allows treating zipped mods and unzipped mods
 //! the same by the parsers
 use crate::shared::errors::ModError;
-use glob::glob;
+use glob::glob_with;
 use std::{
-    fs::{self, File},
+    collections::BTreeMap,
+    fs::File,
     io::Read,
     path::{self, Path, PathBuf},
 };
@@ -23,6 +24,258 @@ pub struct FileDefinition {
     pub is_folder: bool,
 }
 
+/// Caps on how much data a file handle will read into memory
+///
+/// `None` is unlimited. Sizes are uncompressed bytes.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadLimits {
+    /// Largest single file that will be read
+    pub per_file: Option<u64>,
+    /// Total bytes read across all files in a mod
+    pub per_mod: Option<u64>,
+}
+
+/// A read was refused by [`ReadLimits`]
+///
+/// Returned wrapped in a [`std::io::Error`], see [`is_read_limit_error`]
+#[derive(Debug)]
+pub struct ReadLimitExceeded {
+    /// File that was being read
+    pub name: String,
+    /// Bytes that were allowed for the read
+    pub limit: u64,
+}
+
+impl std::fmt::Display for ReadLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} exceeds read limit of {} bytes",
+            self.name, self.limit
+        )
+    }
+}
+
+impl std::error::Error for ReadLimitExceeded {}
+
+/// Check if a read error came from [`ReadLimits`]
+#[must_use]
+pub fn is_read_limit_error(error: &std::io::Error) -> bool {
+    matches!(error.get_ref(), Some(inner) if inner.is::<ReadLimitExceeded>())
+}
+
+/// How the contents of a folder mod are hashed, see [`folder_hash`]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FolderHashMode {
+    /// No hash
+    #[default]
+    Off,
+    /// File names, sizes and modification times
+    Listing,
+    /// File names and full contents (reads every file)
+    Content,
+}
+
+/// Merkle-style hash of the files in a folder
+///
+/// Each file gets a leaf hash of its name and either its size and
+/// modification time or its full content. The folder hash is the MD5 of the
+/// leaf hashes in name order, so any added, removed, renamed or changed
+/// file changes it. Content is streamed from disk and does not count
+/// against [`ReadLimits`].
+///
+/// Returns `None` for [`FolderHashMode::Off`]
+#[must_use]
+pub fn folder_hash(
+    folder: &Path,
+    file_list: &[FileDefinition],
+    mode: FolderHashMode,
+) -> Option<String> {
+    if mode == FolderHashMode::Off {
+        return None;
+    }
+
+    let mut files: Vec<&FileDefinition> = file_list.iter().filter(|n| !n.is_folder).collect();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut root = md5::Context::new();
+    for file in files {
+        let full_path = folder.join(&file.name);
+        let mut leaf = md5::Context::new();
+        leaf.consume(file.name.as_bytes());
+        leaf.consume([0]);
+
+        if mode == FolderHashMode::Content {
+            if let Ok(mut handle) = File::open(&full_path) {
+                let _ = std::io::copy(&mut handle, &mut leaf);
+            }
+        } else {
+            let modified = std::fs::metadata(&full_path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |time| time.as_nanos());
+            leaf.consume(file.size.to_le_bytes());
+            leaf.consume(modified.to_le_bytes());
+        }
+        root.consume(leaf.compute().0);
+    }
+
+    Some(format!("{:x}", root.compute()))
+}
+
+/// Extensions of files that XML and I3D files refer to
+pub const REFERENCE_EXTENSIONS: [&str; 10] = [
+    "dds", "gdm", "gls", "grle", "i3d", "lua", "ogg", "png", "shapes", "xml",
+];
+
+/// Value ends in a file name with one of [`REFERENCE_EXTENSIONS`]
+#[must_use]
+pub fn has_reference_extension(value: &str) -> bool {
+    value
+        .rsplit(['/', '\\'])
+        .next()
+        .and_then(|file_name| file_name.rsplit_once('.'))
+        .is_some_and(|(stem, extension)| {
+            !stem.is_empty()
+                && REFERENCE_EXTENSIONS
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(extension))
+        })
+}
+
+/// Value is a file reference that points outside of the mod
+///
+/// Absolute paths (`/foo`, `\\foo`, `C:\\foo`) and relative paths that climb
+/// out of the mod from `base_folder`. Base game (`$data`) references are
+/// not external.
+#[must_use]
+pub fn is_external_reference(base_folder: &str, value: &str) -> bool {
+    if value.starts_with('$') || !has_reference_extension(value) {
+        return false;
+    }
+
+    let bytes = value.as_bytes();
+    let is_drive_path = bytes.len() > 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'/' || bytes[2] == b'\\');
+
+    is_drive_path
+        || value.starts_with(['/', '\\'])
+        || resolve_mod_path(base_folder, value).is_none()
+}
+
+/// External file references in a document, see [`is_external_reference`]
+///
+/// Attribute values and element text are checked, results are sorted
+#[must_use]
+pub fn external_references(doc: &roxmltree::Document, base_folder: &str) -> Vec<String> {
+    let found: std::collections::BTreeSet<String> = doc
+        .descendants()
+        .filter(roxmltree::Node::is_element)
+        .flat_map(|node| {
+            node.attributes()
+                .map(|attribute| attribute.value().trim())
+                .chain(node.text().map(str::trim))
+        })
+        .filter(|value| is_external_reference(base_folder, value))
+        .map(str::to_owned)
+        .collect();
+
+    found.into_iter().collect()
+}
+
+/// Resolve a file reference against the folder it is relative to
+///
+/// Returns `None` for base game (`$data`) references, or paths that
+/// climb out of the mod
+#[must_use]
+pub fn resolve_mod_path(base_folder: &str, file_name: &str) -> Option<String> {
+    if file_name.starts_with('$') {
+        return None;
+    }
+
+    let mut parts: Vec<&str> = base_folder.split('/').filter(|n| !n.is_empty()).collect();
+    for part in file_name.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            _ => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// MD5 of a file, streamed from disk (lowercase hex)
+///
+/// Does not count against [`ReadLimits`]
+pub(crate) fn file_md5(full_path: &Path) -> Option<String> {
+    let mut reader = std::io::BufReader::with_capacity(0x0001_0000, File::open(full_path).ok()?);
+    let mut context = md5::Context::new();
+    std::io::copy(&mut reader, &mut context).ok()?;
+    Some(format!("{:x}", context.compute()))
+}
+
+/// Running total of bytes read against [`ReadLimits`]
+#[derive(Default, Debug)]
+struct ReadBudget {
+    /// configured limits
+    limits: ReadLimits,
+    /// bytes read so far
+    bytes_read: u64,
+    /// a read has been refused
+    exceeded: bool,
+}
+
+impl ReadBudget {
+    /// Read everything from a reader, refusing reads over the limits
+    fn read<R: Read>(&mut self, reader: R, name: &str) -> Result<Vec<u8>, std::io::Error> {
+        let limit = self.limits.per_file.unwrap_or(u64::MAX).min(
+            self.limits
+                .per_mod
+                .map_or(u64::MAX, |n| n.saturating_sub(self.bytes_read)),
+        );
+
+        let mut buf = vec![];
+        reader.take(limit.saturating_add(1)).read_to_end(&mut buf)?;
+        if buf.len() as u64 > limit {
+            self.exceeded = true;
+            return Err(std::io::Error::other(ReadLimitExceeded {
+                name: name.to_owned(),
+                limit,
+            }));
+        }
+        self.bytes_read += buf.len() as u64;
+        Ok(buf)
+    }
+
+    /// Read everything from a reader as UTF-8 text
+    fn read_text<R: Read>(&mut self, reader: R, name: &str) -> Result<String, std::io::Error> {
+        String::from_utf8(self.read(reader, name)?)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Files only found by ignoring case, see [`AbstractFileHandle::set_case_fallback`]
+#[derive(Default, Debug)]
+struct CaseFallback {
+    /// fall back to a case insensitive match
+    enabled: bool,
+    /// requested name to the name found
+    found: BTreeMap<String, String>,
+}
+
+impl CaseFallback {
+    /// Record a file found by ignoring case, returns the name found
+    fn record(&mut self, needle: &str, actual: String) -> String {
+        self.found.insert(needle.to_owned(), actual.clone());
+        actual
+    }
+}
+
 /// Use a folder or zip file interchangeably
 pub trait AbstractFileHandle {
     /// Check if a file exists in the zip/folder
@@ -34,6 +287,22 @@ pub trait AbstractFileHandle {
     /// List contained files
     fn list(&mut self) -> Vec<FileDefinition>;
 
+    /// List contained files with a name starting with `prefix`
+    ///
+    /// Use a folder name with a trailing slash (`xml/`) to list a folder
+    fn list_prefix(&mut self, prefix: &str) -> Vec<FileDefinition> {
+        let mut names = self.list();
+        names.retain(|n| n.name.starts_with(prefix));
+        names
+    }
+
+    /// List contained files (not folders) with an extension, case is ignored
+    fn list_extension(&mut self, extension: &str) -> Vec<FileDefinition> {
+        let mut names = self.list();
+        names.retain(|n| !n.is_folder && n.extension.eq_ignore_ascii_case(extension));
+        names
+    }
+
     /// Open a contained file as text
     ///
     /// # Errors
@@ -47,12 +316,55 @@ pub trait AbstractFileHandle {
     ///
     /// returns as error when file not found or unreadable
     fn as_bin(&mut self, needle: &str) -> Result<Vec<u8>, std::io::Error>;
+
+    /// Open the first `length` bytes of a contained file as binary
+    ///
+    /// # Errors
+    ///
+    /// returns as error when file not found or unreadable
+    fn as_bin_head(&mut self, needle: &str, length: u64) -> Result<Vec<u8>, std::io::Error> {
+        let mut contents = self.as_bin(needle)?;
+        contents.truncate(usize::try_from(length).unwrap_or(usize::MAX));
+        Ok(contents)
+    }
+
+    /// Apply read limits to [`AbstractFileHandle::as_text`] and [`AbstractFileHandle::as_bin`]
+    ///
+    /// Reads over the limit fail with [`ReadLimitExceeded`]
+    fn set_limits(&mut self, _limits: ReadLimits) {}
+
+    /// Check if any read has been refused by the read limits
+    fn limit_exceeded(&self) -> bool {
+        false
+    }
+
+    /// Path this handle was opened from, if it can be opened again
+    fn source_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// When a contained file is not found, try again ignoring case
+    ///
+    /// Windows ignores case, so a mod with `moddesc.xml` works there. With
+    /// this set [`AbstractFileHandle::exists`], [`AbstractFileHandle::as_text`]
+    /// and [`AbstractFileHandle::as_bin`] find it too, and the names are
+    /// listed by [`AbstractFileHandle::case_fallbacks`]
+    fn set_case_fallback(&mut self, _enabled: bool) {}
+
+    /// Files only found by ignoring case, requested name to the name found
+    fn case_fallbacks(&self) -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
 }
 
 /// Open a folder as an [`AbstractFileHandle`]
 pub struct AbstractFolder {
     /// [`PathBuf`] to folder
     path: PathBuf,
+    /// read limits and usage
+    budget: ReadBudget,
+    /// case insensitive lookups
+    case_fallback: CaseFallback,
 }
 
 impl AbstractFolder {
@@ -69,10 +381,16 @@ impl AbstractFolder {
             if input_path.is_absolute() {
                 Ok(AbstractFolder {
                     path: input_path.to_path_buf(),
+                    budget: ReadBudget::default(),
+                    case_fallback: CaseFallback::default(),
                 })
             } else {
                 match path::absolute(input_path) {
-                    Ok(new_path) => Ok(AbstractFolder { path: new_path }),
+                    Ok(new_path) => Ok(AbstractFolder {
+                        path: new_path,
+                        budget: ReadBudget::default(),
+                        case_fallback: CaseFallback::default(),
+                    }),
                     Err(..) => Err(ModError::FileErrorUnreadableZip),
                 }
                 // input_path.
@@ -81,23 +399,64 @@ impl AbstractFolder {
             Err(ModError::FileErrorUnreadableZip)
         }
     }
-}
-impl AbstractFileHandle for AbstractFolder {
-    fn as_text(&mut self, needle: &str) -> Result<String, std::io::Error> {
-        let search_path = Path::new(&self.path).join(needle);
-        fs::read_to_string(search_path)
-    }
-    fn as_bin(&mut self, needle: &str) -> Result<Vec<u8>, std::io::Error> {
-        let search_path = Path::new(&self.path).join(needle);
-        fs::read(search_path)
-    }
-    fn is_folder(&self) -> bool {
-        true
+
+    /// Full path of a contained file, falling back to a case insensitive
+    /// match of each part of the name
+    fn find_path(&mut self, needle: &str) -> PathBuf {
+        let search_path = self.path.join(needle);
+        if !self.case_fallback.enabled || search_path.exists() {
+            return search_path;
+        }
+
+        let mut found_path = self.path.clone();
+        for part in needle.split('/').filter(|part| !part.is_empty()) {
+            let exact = found_path.join(part);
+            if exact.exists() {
+                found_path = exact;
+                continue;
+            }
+            let Some(entry) = std::fs::read_dir(&found_path).ok().and_then(|entries| {
+                entries.filter_map(Result::ok).find(|entry| {
+                    entry
+                        .file_name()
+                        .to_string_lossy()
+                        .eq_ignore_ascii_case(part)
+                })
+            }) else {
+                return search_path;
+            };
+            found_path = entry.path();
+        }
+
+        if let Some(actual) = pathdiff::diff_paths(&found_path, &self.path) {
+            self.case_fallback
+                .record(needle, actual.to_string_lossy().replace('\\', "/"));
+        }
+        found_path
     }
-    fn list(&mut self) -> Vec<FileDefinition> {
+
+    /// Files below a sub folder matching a glob pattern, named relative to
+    /// the mod folder
+    ///
+    /// Only that sub folder is walked, not the whole mod
+    fn list_matching(
+        &self,
+        sub_folder: &str,
+        pattern: &str,
+        case_sensitive: bool,
+    ) -> Vec<FileDefinition> {
         let mut names: Vec<FileDefinition> = vec![];
-        let search_path = self.path.clone().join("**/*").to_string_lossy().to_string();
-        let Ok(glob_entries) = glob(&search_path) else {
+        let search_path = self
+            .path
+            .join(sub_folder)
+            .join(pattern)
+            .to_string_lossy()
+            .to_string();
+        let options = glob::MatchOptions {
+            case_sensitive,
+            ..glob::MatchOptions::new()
+        };
+        let Ok(glob_entries) = glob_with(&search_path, options) else {
             return names;
         };
 
@@ -129,10 +488,55 @@ impl AbstractFileHandle for AbstractFolder {
 
         names
     }
+}
+impl AbstractFileHandle for AbstractFolder {
+    fn as_text(&mut self, needle: &str) -> Result<String, std::io::Error> {
+        let search_path = self.find_path(needle);
+        self.budget.read_text(File::open(search_path)?, needle)
+    }
+    fn as_bin(&mut self, needle: &str) -> Result<Vec<u8>, std::io::Error> {
+        let search_path = self.find_path(needle);
+        self.budget.read(File::open(search_path)?, needle)
+    }
+    fn as_bin_head(&mut self, needle: &str, length: u64) -> Result<Vec<u8>, std::io::Error> {
+        let search_path = self.find_path(needle);
+        let file = File::open(search_path)?;
+        self.budget.read(file.take(length), needle)
+    }
+    fn is_folder(&self) -> bool {
+        true
+    }
+    fn set_limits(&mut self, limits: ReadLimits) {
+        self.budget.limits = limits;
+    }
+    fn limit_exceeded(&self) -> bool {
+        self.budget.exceeded
+    }
+    fn source_path(&self) -> Option<PathBuf> {
+        Some(self.path.clone())
+    }
+    fn set_case_fallback(&mut self, enabled: bool) {
+        self.case_fallback.enabled = enabled;
+    }
+    fn case_fallbacks(&self) -> BTreeMap<String, String> {
+        self.case_fallback.found.clone()
+    }
+    fn list(&mut self) -> Vec<FileDefinition> {
+        self.list_matching("", "**/*", true)
+    }
+    fn list_prefix(&mut self, prefix: &str) -> Vec<FileDefinition> {
+        let folder = prefix.rsplit_once('/').map_or("", |(folder, _)| folder);
+        let mut names = self.list_matching(folder, "**/*", true);
+        names.retain(|n| n.name.starts_with(prefix));
+        names
+    }
+    fn list_extension(&mut self, extension: &str) -> Vec<FileDefinition> {
+        let mut names = self.list_matching("", &format!("**/*.{extension}"), false);
+        names.retain(|n| !n.is_folder);
+        names
+    }
     fn exists(&mut self, needle: &str) -> bool {
-        let search_path = Path::new(&self.path).join(needle);
-
-        search_path.exists()
+        self.find_path(needle).exists()
     }
 }
 
@@ -140,6 +544,14 @@ impl AbstractFileHandle for AbstractFolder {
 pub struct AbstractZipFile {
     /// archive file (opened)
     archive: zip::ZipArchive<File>,
+    /// [`PathBuf`] to archive file
+    path: PathBuf,
+    /// read limits and usage
+    budget: ReadBudget,
+    /// contained files, read from the archive on first use
+    listing: Option<Vec<FileDefinition>>,
+    /// case insensitive lookups
+    case_fallback: CaseFallback,
 }
 impl AbstractZipFile {
     /// Create a new [`AbstractFileHandle`] record from a zip file [`std::path::Path`]
@@ -152,64 +564,239 @@ impl AbstractZipFile {
         let path = file_path.as_ref();
         match std::fs::File::open(path) {
             Ok(file) => match zip::ZipArchive::new(file) {
-                Ok(archive) => Ok(AbstractZipFile { archive }),
+                Ok(archive) => Ok(AbstractZipFile {
+                    archive,
+                    path: path.to_path_buf(),
+                    budget: ReadBudget::default(),
+                    listing: None,
+                    case_fallback: CaseFallback::default(),
+                }),
                 Err(..) => Err(ModError::FileErrorUnreadableZip),
             },
             Err(..) => Err(ModError::FileErrorUnreadableZip),
         }
     }
+
+    /// Name of a contained file, falling back to a case insensitive match
+    fn find_name(&mut self, needle: &str) -> String {
+        if !self.case_fallback.enabled || self.archive.index_for_name(needle).is_some() {
+            return needle.to_owned();
+        }
+        let actual = self
+            .listing()
+            .iter()
+            .find(|n| !n.is_folder && n.name.eq_ignore_ascii_case(needle))
+            .map(|n| n.name.clone());
+        match actual {
+            Some(actual) => self.case_fallback.record(needle, actual),
+            None => needle.to_owned(),
+        }
+    }
+
+    /// Contained files, read from the archive on first use and cached
+    ///
+    /// Walking the central directory of a large pack for every listing
+    /// adds up, the archive does not change while it is open
+    fn listing(&mut self) -> &[FileDefinition] {
+        let archive = &mut self.archive;
+        self.listing.get_or_insert_with(|| {
+            let mut names: Vec<FileDefinition> = vec![];
+            for i in 0..archive.len() {
+                let Ok(file) = archive.by_index(i) else {
+                    continue;
+                };
+                let name = file
+                    .mangled_name()
+                    .to_string_lossy()
+                    .into_owned()
+                    .replace('\\', "/");
+
+                let extension = match Path::new(&name).extension() {
+                    Some(ext) => ext.to_string_lossy().to_ascii_lowercase(),
+                    None => String::new(),
+                };
+
+                names.push(FileDefinition {
+                    extension,
+                    name,
+                    size: if file.is_dir() { 0 } else { file.size() },
+                    is_folder: file.is_dir(),
+                });
+            }
+            names
+        })
+    }
 }
 impl AbstractFileHandle for AbstractZipFile {
     fn as_bin(&mut self, needle: &str) -> Result<Vec<u8>, std::io::Error> {
-        let mut file = self.archive.by_name(needle)?;
-        let mut buf = vec![];
-        file.read_to_end(&mut buf)?;
-        Ok(buf.clone())
+        let name = self.find_name(needle);
+        let file = self.archive.by_name(&name)?;
+        self.budget.read(file, needle)
+    }
+
+    fn as_bin_head(&mut self, needle: &str, length: u64) -> Result<Vec<u8>, std::io::Error> {
+        let name = self.find_name(needle);
+        let file = self.archive.by_name(&name)?;
+        self.budget.read(file.take(length), needle)
     }
 
     fn as_text(&mut self, needle: &str) -> Result<String, std::io::Error> {
-        let mut file = self.archive.by_name(needle)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        Ok(contents)
+        let name = self.find_name(needle);
+        let file = self.archive.by_name(&name)?;
+        self.budget.read_text(file, needle)
     }
     fn is_folder(&self) -> bool {
         false
     }
+    fn set_limits(&mut self, limits: ReadLimits) {
+        self.budget.limits = limits;
+    }
+    fn limit_exceeded(&self) -> bool {
+        self.budget.exceeded
+    }
+    fn source_path(&self) -> Option<PathBuf> {
+        Some(self.path.clone())
+    }
+    fn set_case_fallback(&mut self, enabled: bool) {
+        self.case_fallback.enabled = enabled;
+    }
+    fn case_fallbacks(&self) -> BTreeMap<String, String> {
+        self.case_fallback.found.clone()
+    }
     fn list(&mut self) -> Vec<FileDefinition> {
-        let mut names: Vec<FileDefinition> = vec![];
-        for i in 0..self.archive.len() {
-            let Ok(file) = self.archive.by_index(i) else {
-                continue;
-            };
-            let name = file
-                .mangled_name()
-                .to_string_lossy()
-                .into_owned()
-                .replace('\\', "/");
-
-            let extension = match Path::new(&name).extension() {
-                Some(ext) => ext.to_string_lossy().to_ascii_lowercase(),
-                None => String::new(),
-            };
-
-            names.push(FileDefinition {
-                extension,
-                name,
-                size: if file.is_dir() { 0 } else { file.size() },
-                is_folder: file.is_dir(),
-            });
-        }
-        names
+        self.listing().to_vec()
+    }
+    fn list_prefix(&mut self, prefix: &str) -> Vec<FileDefinition> {
+        self.listing()
+            .iter()
+            .filter(|n| n.name.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+    fn list_extension(&mut self, extension: &str) -> Vec<FileDefinition> {
+        self.listing()
+            .iter()
+            .filter(|n| !n.is_folder && n.extension.eq_ignore_ascii_case(extension))
+            .cloned()
+            .collect()
     }
     fn exists(&mut self, needle: &str) -> bool {
-        match self.archive.by_name(needle) {
+        let name = self.find_name(needle);
+        match self.archive.by_name(&name) {
             Ok(..) => true,
             Err(..) => false,
         }
     }
 }
 
+/// Find the folder holding `needle` one level down, when it is not at the root
+///
+/// Save games are often zipped with their folder (`savegame1/careerSavegame.xml`)
+pub fn nested_folder(handle: &mut dyn AbstractFileHandle, needle: &str) -> Option<String> {
+    if handle.exists(needle) {
+        return None;
+    }
+    handle.list().into_iter().find_map(|file| {
+        file.name
+            .strip_suffix(needle)
+            .and_then(|folder| folder.strip_suffix('/'))
+            .filter(|folder| !folder.is_empty() && !folder.contains('/'))
+            .map(str::to_owned)
+    })
+}
+
+/// Open `needle`'s folder as the root when it is nested one level down,
+/// see [`nested_folder`]
+#[must_use]
+pub fn open_nested(
+    mut handle: Box<dyn AbstractFileHandle>,
+    needle: &str,
+) -> Box<dyn AbstractFileHandle> {
+    match nested_folder(handle.as_mut(), needle) {
+        Some(folder) => Box::new(AbstractSubFolder::new(handle, &folder)),
+        None => handle,
+    }
+}
+
+/// Open a folder inside another [`AbstractFileHandle`] as an [`AbstractFileHandle`]
+pub struct AbstractSubFolder {
+    /// containing file handle
+    inner: Box<dyn AbstractFileHandle>,
+    /// folder name, with a trailing slash
+    prefix: String,
+}
+
+impl AbstractSubFolder {
+    /// Create a new [`AbstractFileHandle`] record from a folder of another handle
+    #[must_use]
+    pub fn new(inner: Box<dyn AbstractFileHandle>, folder: &str) -> AbstractSubFolder {
+        AbstractSubFolder {
+            inner,
+            prefix: format!("{}/", folder.trim_end_matches('/')),
+        }
+    }
+
+    /// Name of a contained file in the containing handle
+    fn full_name(&self, needle: &str) -> String {
+        format!("{}{needle}", self.prefix)
+    }
+}
+impl AbstractFileHandle for AbstractSubFolder {
+    fn as_text(&mut self, needle: &str) -> Result<String, std::io::Error> {
+        let name = self.full_name(needle);
+        self.inner.as_text(&name)
+    }
+    fn as_bin(&mut self, needle: &str) -> Result<Vec<u8>, std::io::Error> {
+        let name = self.full_name(needle);
+        self.inner.as_bin(&name)
+    }
+    fn as_bin_head(&mut self, needle: &str, length: u64) -> Result<Vec<u8>, std::io::Error> {
+        let name = self.full_name(needle);
+        self.inner.as_bin_head(&name, length)
+    }
+    fn is_folder(&self) -> bool {
+        self.inner.is_folder()
+    }
+    fn set_limits(&mut self, limits: ReadLimits) {
+        self.inner.set_limits(limits);
+    }
+    fn limit_exceeded(&self) -> bool {
+        self.inner.limit_exceeded()
+    }
+    fn set_case_fallback(&mut self, enabled: bool) {
+        self.inner.set_case_fallback(enabled);
+    }
+    fn case_fallbacks(&self) -> BTreeMap<String, String> {
+        self.inner
+            .case_fallbacks()
+            .into_iter()
+            .filter_map(|(needle, actual)| {
+                Some((
+                    needle.strip_prefix(&self.prefix)?.to_owned(),
+                    actual.get(self.prefix.len()..)?.to_owned(),
+                ))
+            })
+            .collect()
+    }
+    fn list(&mut self) -> Vec<FileDefinition> {
+        self.inner
+            .list_prefix(&self.prefix)
+            .into_iter()
+            .filter_map(|file| {
+                let name = file.name.strip_prefix(&self.prefix)?;
+                (!name.is_empty()).then(|| FileDefinition {
+                    name: name.to_owned(),
+                    ..file.clone()
+                })
+            })
+            .collect()
+    }
+    fn exists(&mut self, needle: &str) -> bool {
+        let name = self.full_name(needle);
+        self.inner.exists(&name)
+    }
+}
+
 /// Open nothing as an [`AbstractFileHandle`]
 #[cfg(test)]
 pub struct AbstractNull {}
@@ -269,6 +856,156 @@ mod tests {
         assert!(file_handle.as_text("foo.txt").is_err());
     }
 
+    #[test]
+    fn nested_save_folder() {
+        let mut file_handle: Box<dyn AbstractFileHandle> =
+            Box::new(AbstractZipFile::new("./tests/test_mods/SAVEGAME_Nested.zip").unwrap());
+        assert_eq!(
+            nested_folder(file_handle.as_mut(), "careerSavegame.xml"),
+            Some(String::from("savegame1"))
+        );
+
+        let mut file_handle = open_nested(file_handle, "careerSavegame.xml");
+        assert!(file_handle.exists("careerSavegame.xml"));
+        assert!(file_handle.as_text("farms.xml").is_ok());
+        let mut names: Vec<String> = file_handle.list().into_iter().map(|n| n.name).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "careerSavegame.xml",
+                "farms.xml",
+                "placeables.xml",
+                "vehicles.xml"
+            ]
+        );
+
+        let mut file_handle: Box<dyn AbstractFileHandle> =
+            Box::new(AbstractZipFile::new("./tests/test_mods/SAVEGAME_Good.zip").unwrap());
+        assert_eq!(
+            nested_folder(file_handle.as_mut(), "careerSavegame.xml"),
+            None
+        );
+    }
+
+    #[test]
+    fn read_limits() {
+        let mut file_handle =
+            AbstractFolder::new("./tests/test_mods/PASS_Good_Simple_Mod").unwrap();
+        let mod_desc_size = file_handle.as_bin("modDesc.xml").unwrap().len() as u64;
+        assert!(!file_handle.limit_exceeded());
+
+        file_handle.set_limits(ReadLimits {
+            per_file: Some(mod_desc_size - 1),
+            per_mod: None,
+        });
+        let error = file_handle.as_text("modDesc.xml").unwrap_err();
+        assert!(is_read_limit_error(&error));
+        assert!(file_handle.limit_exceeded());
+
+        let mut file_handle =
+            AbstractFolder::new("./tests/test_mods/PASS_Good_Simple_Mod").unwrap();
+        file_handle.set_limits(ReadLimits {
+            per_file: None,
+            per_mod: Some(mod_desc_size * 2 - 1),
+        });
+        assert!(file_handle.as_bin("modDesc.xml").is_ok());
+        assert!(file_handle.as_bin("modDesc.xml").is_err());
+        assert!(file_handle.limit_exceeded());
+    }
+
+    #[test]
+    fn folder_hashes() {
+        let folder = Path::new("./tests/test_mods/PASS_Good_Simple_Mod");
+        let file_list = AbstractFolder::new(folder).unwrap().list();
+
+        assert_eq!(folder_hash(folder, &file_list, FolderHashMode::Off), None);
+
+        let listing = folder_hash(folder, &file_list, FolderHashMode::Listing).unwrap();
+        let content = folder_hash(folder, &file_list, FolderHashMode::Content).unwrap();
+        assert_eq!(listing.len(), 32);
+        assert_ne!(listing, content);
+        assert_eq!(
+            folder_hash(folder, &file_list, FolderHashMode::Listing),
+            Some(listing)
+        );
+
+        let mut changed = file_list.clone();
+        changed[0].size += 1;
+        assert_ne!(
+            folder_hash(folder, &changed, FolderHashMode::Listing),
+            folder_hash(folder, &file_list, FolderHashMode::Listing)
+        );
+        assert_eq!(
+            folder_hash(folder, &changed, FolderHashMode::Content),
+            Some(content)
+        );
+    }
+
+    #[test]
+    fn external_reference_values() {
+        assert!(is_external_reference("", "../shared/model.i3d"));
+        assert!(is_external_reference(
+            "",
+            "C:\\Users\\me\\textures\\foo.dds"
+        ));
+        assert!(is_external_reference("", "d:/mods/foo.xml"));
+        assert!(is_external_reference("", "/home/me/foo.png"));
+        assert!(is_external_reference("vehicle", "../../foo.dds"));
+        assert!(!is_external_reference("vehicle", "../textures/foo.dds"));
+        assert!(!is_external_reference("", "textures/foo.dds"));
+        assert!(!is_external_reference("", "$data/shared/foo.dds"));
+        assert!(!is_external_reference("", "../not_a_file"));
+
+        let doc = roxmltree::Document::parse(
+            r#"<vehicle><base><filename>../other.i3d</filename></base>
+            <image src="C:\work\store.png"/><image src="store.png"/>
+            <copy src="../other.i3d"/></vehicle>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            external_references(&doc, ""),
+            vec![
+                String::from("../other.i3d"),
+                String::from("C:\\work\\store.png")
+            ]
+        );
+    }
+
+    #[test]
+    fn filtered_listing() {
+        let mut file_handle = AbstractZipFile::new("./tests/test_mods/DETAIL_Samples.zip").unwrap();
+        let names = |list: Vec<FileDefinition>| -> Vec<String> {
+            let mut names: Vec<String> = list.into_iter().map(|n| n.name).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(
+            names(file_handle.list_prefix("languages/")),
+            vec!["languages/l10n_de.xml", "languages/l10n_en.xml"]
+        );
+        assert_eq!(
+            names(file_handle.list_prefix("xml/production")),
+            vec!["xml/production-deep.xml", "xml/production-simple.xml"]
+        );
+        assert_eq!(file_handle.list_extension("XML").len(), 10);
+        assert_eq!(file_handle.list_extension("dds").len(), 3);
+        assert_eq!(file_handle.list().len(), 13);
+
+        let mut file_handle =
+            AbstractFolder::new("./tests/test_mods/PASS_Good_Simple_Mod").unwrap();
+        assert_eq!(
+            names(file_handle.list_prefix("mod")),
+            vec!["modDesc.xml", "modIcon.dds"]
+        );
+        assert_eq!(
+            names(file_handle.list_extension("DDS")),
+            vec!["modIcon.dds"]
+        );
+        assert!(file_handle.list_prefix("xml/").is_empty());
+    }
+
     #[test]
     fn invalid_path() {
         let file_handle = AbstractFolder::new("./foo/bar/foo");
@@ -0,0 +1,502 @@
+//! Translated text for parser concepts
+//!
+//! Issue descriptions and badge labels in every supported language, so a
+//! front end can show them without keeping its own translations. Use
+//! [`bundle`] to fetch everything for one language at once.
+use crate::mod_detail::structs::ModDetailShopCategory;
+use crate::shared::errors::{ModError, ALL_ERRORS};
+use std::collections::BTreeMap;
+
+/// Supported language codes, english is the fallback
+pub const LANGUAGES: [&str; 3] = ["en", "de", "fr"];
+
+/// Badge names, as returned by [`crate::shared::structs::ModBadges::names`]
+pub const BADGE_NAMES: [&str; 8] = [
+    "broken", "folder", "malware", "noMP", "notmod", "pconly", "problem", "savegame",
+];
+
+/// Top level shop tabs, as used by
+/// [`crate::mod_detail::structs::ModDetailShopCategory`]
+pub const SHOP_TOP_LEVELS: [&str; 4] = ["objects", "placeables", "tools", "vehicles"];
+
+/// German and french shop category names, by lower case category key
+///
+/// Sorted by key, the english names are in
+/// [`crate::mod_detail::data::SHOP_CATEGORIES`]
+const SHOP_CATEGORY_NAMES: [(&str, &str, &str); 86] = [
+    ("animalpens", "Tierhaltung", "Enclos à animaux"),
+    ("animaltransport", "Tiertransport", "Transport d'animaux"),
+    ("augerwagons", "Überladewagen", "Transbordeurs"),
+    ("baleloaders", "Ballenlader", "Ramasseuses de balles"),
+    ("balers", "Ballenpressen", "Presses à balles"),
+    ("balewrappers", "Ballenwickler", "Enrubanneuses"),
+    ("beehives", "Bienenstöcke", "Ruches"),
+    ("beetharvesting", "Rübenernte", "Récolte de betteraves"),
+    ("bigbags", "Big Bags", "Big bags"),
+    ("cars", "Autos", "Voitures"),
+    ("cornheaders", "Maisschneidwerke", "Cueilleurs à maïs"),
+    ("cottonharvesting", "Baumwollernte", "Récolte de coton"),
+    ("cultivators", "Grubber", "Cultivateurs"),
+    ("cutters", "Schneidwerke", "Coupes"),
+    ("cuttertrailers", "Schneidwerkswagen", "Chariots de coupe"),
+    ("decoration", "Dekoration", "Décoration"),
+    ("discharrows", "Scheibeneggen", "Déchaumeurs à disques"),
+    ("dollys", "Dollys", "Dollys"),
+    ("farmhouses", "Bauernhäuser", "Fermes"),
+    ("fences", "Zäune", "Clôtures"),
+    (
+        "fertilizerspreaders",
+        "Düngerstreuer",
+        "Épandeurs d'engrais",
+    ),
+    (
+        "forageharvestercutters",
+        "Feldhäcksler-Vorsätze",
+        "Becs d'ensileuse",
+    ),
+    ("forageharvesters", "Feldhäcksler", "Ensileuses"),
+    ("foragewagons", "Ladewagen", "Autochargeuses"),
+    ("forestryexcavators", "Forstbagger", "Pelles forestières"),
+    ("forestryforwarders", "Rückezüge", "Porteurs forestiers"),
+    (
+        "forestryharvesters",
+        "Forst-Harvester",
+        "Abatteuses forestières",
+    ),
+    ("forklifts", "Gabelstapler", "Chariots élévateurs"),
+    ("frontloaders", "Frontlader", "Chargeurs frontaux"),
+    (
+        "frontloadertools",
+        "Frontlader-Werkzeuge",
+        "Outils de chargeur frontal",
+    ),
+    (
+        "frontloadervehicles",
+        "Frontlader-Fahrzeuge",
+        "Véhicules à chargeur frontal",
+    ),
+    ("grapeharvesting", "Traubenernte", "Récolte de raisin"),
+    ("greenhouses", "Gewächshäuser", "Serres"),
+    ("handtools", "Handwerkzeuge", "Outils à main"),
+    ("harvesters", "Mähdrescher", "Moissonneuses-batteuses"),
+    ("leveler", "Planierschilde", "Lames niveleuses"),
+    ("lights", "Beleuchtung", "Éclairage"),
+    ("loaderwagons", "Verladewagen", "Wagons de chargement"),
+    ("lowloaders", "Tieflader", "Porte-engins"),
+    ("manurespreaders", "Miststreuer", "Épandeurs de fumier"),
+    ("misc", "Sonstiges", "Divers"),
+    ("mixerwagons", "Futtermischwagen", "Mélangeuses"),
+    ("mowers", "Mähwerke", "Faucheuses"),
+    ("mowervehicles", "Mähfahrzeuge", "Véhicules de fauche"),
+    ("mulchers", "Mulcher", "Broyeurs"),
+    ("objectmisc", "Sonstige Objekte", "Objets divers"),
+    ("oliveharvesting", "Olivenernte", "Récolte d'olives"),
+    ("pallets", "Paletten", "Palettes"),
+    (
+        "placeablemisc",
+        "Sonstige platzierbare Objekte",
+        "Objets plaçables divers",
+    ),
+    ("planters", "Einzelkornsämaschinen", "Semoirs monograines"),
+    ("plows", "Pflüge", "Charrues"),
+    (
+        "potatoharvesting",
+        "Kartoffelernte",
+        "Récolte de pommes de terre",
+    ),
+    ("powerharrows", "Kreiseleggen", "Herses rotatives"),
+    (
+        "productionpoints",
+        "Produktionsstätten",
+        "Sites de production",
+    ),
+    ("riceharvesting", "Reisernte", "Récolte de riz"),
+    ("rollers", "Walzen", "Rouleaux"),
+    ("seeders", "Sämaschinen", "Semoirs"),
+    ("sellingpoints", "Verkaufsstellen", "Points de vente"),
+    ("sheds", "Hallen", "Hangars"),
+    (
+        "silocompaction",
+        "Silageverdichtung",
+        "Compactage d'ensilage",
+    ),
+    ("silos", "Silos", "Silos"),
+    (
+        "skidsteertools",
+        "Kompaktlader-Werkzeuge",
+        "Outils de chargeuse compacte",
+    ),
+    ("skidsteervehicles", "Kompaktlader", "Chargeuses compactes"),
+    ("slurrytanks", "Güllefässer", "Tonnes à lisier"),
+    ("spaders", "Spatenmaschinen", "Bêcheuses"),
+    ("sprayers", "Feldspritzen", "Pulvérisateurs"),
+    ("stonepickers", "Steinsammler", "Ramasseuses de pierres"),
+    ("stumpcutters", "Stubbenfräsen", "Rogneuses de souches"),
+    ("subsoilers", "Tiefenlockerer", "Décompacteurs"),
+    (
+        "sugarcaneharvesting",
+        "Zuckerrohrernte",
+        "Récolte de canne à sucre",
+    ),
+    ("tedders", "Kreiselheuer", "Faneuses"),
+    (
+        "teleloadertools",
+        "Teleskoplader-Werkzeuge",
+        "Outils de chargeur télescopique",
+    ),
+    (
+        "teleloadervehicles",
+        "Teleskoplader",
+        "Chargeurs télescopiques",
+    ),
+    ("tractorsl", "Große Traktoren", "Gros tracteurs"),
+    ("tractorsm", "Mittlere Traktoren", "Tracteurs moyens"),
+    ("tractorss", "Kleine Traktoren", "Petits tracteurs"),
+    ("trailers", "Anhänger", "Remorques"),
+    ("trucks", "Lkw", "Camions"),
+    ("vegetableharvesting", "Gemüseernte", "Récolte de légumes"),
+    ("weeders", "Hackstriegel", "Désherbeuses"),
+    ("weights", "Gewichte", "Masses"),
+    (
+        "wheelloadertools",
+        "Radlader-Werkzeuge",
+        "Outils de chargeuse sur pneus",
+    ),
+    ("wheelloadervehicles", "Radlader", "Chargeuses sur pneus"),
+    ("winches", "Seilwinden", "Treuils"),
+    ("windrowers", "Schwader", "Andaineurs"),
+    ("woodtransport", "Holztransport", "Transport de bois"),
+];
+
+/// Resolve a language code to a supported one (falls back to english)
+#[must_use]
+pub fn resolve_language(lang: &str) -> &'static str {
+    LANGUAGES
+        .iter()
+        .find(|code| code.eq_ignore_ascii_case(lang))
+        .copied()
+        .unwrap_or("en")
+}
+
+/// Human readable label for a badge name
+///
+/// Returns `None` for unknown badge names
+#[must_use]
+pub fn badge_label(badge: &str, lang: &str) -> Option<&'static str> {
+    let label = match (badge, resolve_language(lang)) {
+        ("broken", "de") => "Defekt",
+        ("broken", "fr") => "Défectueux",
+        ("broken", _) => "Broken",
+        ("folder", "de") => "Ordner",
+        ("folder", "fr") => "Dossier",
+        ("folder", _) => "Folder",
+        ("malware", "de") => "Schadsoftware",
+        ("malware", "fr") => "Logiciel malveillant",
+        ("malware", _) => "Malware",
+        ("noMP", "de") => "Kein Mehrspieler",
+        ("noMP", "fr") => "Pas de multijoueur",
+        ("noMP", _) => "No Multiplayer",
+        ("notmod", "de") => "Kein Mod",
+        ("notmod", "fr") => "Pas un mod",
+        ("notmod", _) => "Not a Mod",
+        ("pconly", "de") => "Nur PC",
+        ("pconly", "fr") => "PC uniquement",
+        ("pconly", _) => "PC Only",
+        ("problem", "fr") => "Problème",
+        ("problem", _) => "Problem",
+        ("savegame", "de") => "Spielstand",
+        ("savegame", "fr") => "Sauvegarde",
+        ("savegame", _) => "Save Game",
+        _ => return None,
+    };
+    Some(label)
+}
+
+/// Shop tab name for a top level shop key
+///
+/// Returns `None` for unknown keys
+#[must_use]
+pub fn shop_top_level_label(top_level: &str, lang: &str) -> Option<&'static str> {
+    let label = match (top_level, resolve_language(lang)) {
+        ("objects", "de") => "Objekte",
+        ("objects", "fr") => "Objets",
+        ("objects", _) => "Objects",
+        ("placeables", "de") => "Platzierbare Objekte",
+        ("placeables", "fr") => "Objets plaçables",
+        ("placeables", _) => "Placeables",
+        ("tools", "de") => "Geräte",
+        ("tools", "fr") => "Outils",
+        ("tools", _) => "Tools",
+        ("vehicles", "de") => "Fahrzeuge",
+        ("vehicles", "fr") => "Véhicules",
+        ("vehicles", _) => "Vehicles",
+        _ => return None,
+    };
+    Some(label)
+}
+
+/// Title of a base game input action (for mods binding base game actions)
+///
+/// Returns `None` for actions not in the table
+#[must_use]
+pub fn base_action_label(action: &str, lang: &str) -> Option<&'static str> {
+    let label = match (action, resolve_language(lang)) {
+        ("ACTIVATE_OBJECT", "de") => "Objekt aktivieren",
+        ("ACTIVATE_OBJECT", "fr") => "Activer l'objet",
+        ("ACTIVATE_OBJECT", _) => "Activate Object",
+        ("ATTACH", "de") => "Ankoppeln / Abkoppeln",
+        ("ATTACH", "fr") => "Atteler / Dételer",
+        ("ATTACH", _) => "Attach / Detach",
+        ("IMPLEMENT_EXTRA", "de") => "Gerät ein / aus",
+        ("IMPLEMENT_EXTRA", "fr") => "Allumer / éteindre l'outil",
+        ("IMPLEMENT_EXTRA", _) => "Turn Implement On / Off",
+        ("IMPLEMENT_EXTRA2", "de") => "Gerät ausklappen / einklappen",
+        ("IMPLEMENT_EXTRA2", "fr") => "Déplier / replier l'outil",
+        ("IMPLEMENT_EXTRA2", _) => "Fold / Unfold Implement",
+        ("LOWER_IMPLEMENT", "de") => "Gerät heben / senken",
+        ("LOWER_IMPLEMENT", "fr") => "Lever / baisser l'outil",
+        ("LOWER_IMPLEMENT", _) => "Lower / Raise Implement",
+        ("TOGGLE_BEACON_LIGHTS", "de") => "Rundumleuchten",
+        ("TOGGLE_BEACON_LIGHTS", "fr") => "Gyrophares",
+        ("TOGGLE_BEACON_LIGHTS", _) => "Beacon Lights",
+        ("TOGGLE_LIGHTS", "de") => "Licht",
+        ("TOGGLE_LIGHTS", "fr") => "Phares",
+        ("TOGGLE_LIGHTS", _) => "Lights",
+        ("TOGGLE_TIPSIDE", "de") => "Kipprichtung wechseln",
+        ("TOGGLE_TIPSIDE", "fr") => "Changer le côté de déchargement",
+        ("TOGGLE_TIPSIDE", _) => "Toggle Tip Side",
+        _ => return None,
+    };
+    Some(label)
+}
+
+/// Readable name of a single input code (`KEY_lshift` is "Left Shift")
+fn input_code_label(code: &str) -> String {
+    let named = match code {
+        "KEY_lshift" => "Left Shift",
+        "KEY_rshift" => "Right Shift",
+        "KEY_lctrl" => "Left Ctrl",
+        "KEY_rctrl" => "Right Ctrl",
+        "KEY_lalt" => "Left Alt",
+        "KEY_ralt" => "Right Alt",
+        "KEY_space" => "Space",
+        "KEY_return" => "Enter",
+        "KEY_tab" => "Tab",
+        "KEY_backspace" => "Backspace",
+        "KEY_esc" | "KEY_escape" => "Esc",
+        "KEY_delete" => "Delete",
+        "KEY_insert" => "Insert",
+        "KEY_home" => "Home",
+        "KEY_end" => "End",
+        "KEY_pageup" => "Page Up",
+        "KEY_pagedown" => "Page Down",
+        "KEY_up" => "Up Arrow",
+        "KEY_down" => "Down Arrow",
+        "KEY_left" => "Left Arrow",
+        "KEY_right" => "Right Arrow",
+        "MOUSE_BUTTON_LEFT" => "Left Mouse Button",
+        "MOUSE_BUTTON_RIGHT" => "Right Mouse Button",
+        "MOUSE_BUTTON_MIDDLE" => "Middle Mouse Button",
+        "MOUSE_BUTTON_WHEEL_UP" => "Mouse Wheel Up",
+        "MOUSE_BUTTON_WHEEL_DOWN" => "Mouse Wheel Down",
+        _ => "",
+    };
+    if !named.is_empty() {
+        return named.to_owned();
+    }
+
+    match code.strip_prefix("KEY_") {
+        Some(key) if key.starts_with("KP_") => format!("Numpad {}", &key[3..]),
+        Some(key) => {
+            let mut chars = key.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        }
+        None => code.to_owned(),
+    }
+}
+
+/// Readable key combo for a raw input (`KEY_lshift KEY_b` is "Left Shift + B")
+///
+/// Unknown codes are passed through
+#[must_use]
+pub fn input_label(input: &str) -> String {
+    input
+        .split_whitespace()
+        .map(input_code_label)
+        .collect::<Vec<String>>()
+        .join(" + ")
+}
+
+/// Shop category name for a store category, placed as
+/// [`ModDetailShopCategory::from_category`] does
+///
+/// Returns `None` for categories the base game does not have
+#[must_use]
+pub fn shop_category_label(category: &str, lang: &str) -> Option<&'static str> {
+    let category = ModDetailShopCategory::from_category(category)?;
+    let names = SHOP_CATEGORY_NAMES
+        .binary_search_by(|(key, _, _)| key.cmp(&category.sub_category))
+        .ok()
+        .map(|index| SHOP_CATEGORY_NAMES[index]);
+    let label = match (names, resolve_language(lang)) {
+        (Some((_, german, _)), "de") => german,
+        (Some((_, _, french)), "fr") => french,
+        _ => category.sub_category_name,
+    };
+    Some(label)
+}
+
+/// All translated strings for a single language
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct L10nBundle {
+    /// language code of the strings
+    pub language: &'static str,
+    /// badge name to label
+    pub badges: BTreeMap<&'static str, &'static str>,
+    /// issue code (as serialized, e.g. `PERF_GRLE_TOO_MANY`) to description
+    pub issues: BTreeMap<String, &'static str>,
+    /// shop category key (lower case) to category name
+    pub shop_categories: BTreeMap<&'static str, &'static str>,
+    /// top level shop key to tab name
+    pub shop_top_levels: BTreeMap<&'static str, &'static str>,
+}
+
+impl L10nBundle {
+    /// Output as pretty-print JSON
+    #[must_use]
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or(String::from("{}"))
+    }
+
+    /// Output as JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for L10nBundle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&serde_json::to_string(&self).unwrap_or(String::from("{}")))
+    }
+}
+
+/// Serialized code of an issue
+fn issue_code(issue: &ModError) -> String {
+    match serde_json::to_value(issue) {
+        Ok(serde_json::Value::String(code)) => code,
+        _ => format!("{issue:?}"),
+    }
+}
+
+/// Fetch every issue description and badge label for a language
+///
+/// Unsupported languages get the english bundle, check
+/// [`L10nBundle::language`]
+#[must_use]
+pub fn bundle(lang: &str) -> L10nBundle {
+    let language = resolve_language(lang);
+
+    L10nBundle {
+        language,
+        badges: BADGE_NAMES
+            .iter()
+            .filter_map(|name| badge_label(name, language).map(|label| (*name, label)))
+            .collect(),
+        issues: ALL_ERRORS
+            .iter()
+            .map(|issue| (issue_code(issue), issue.describe(language)))
+            .collect(),
+        shop_categories: SHOP_CATEGORY_NAMES
+            .iter()
+            .filter_map(|(key, _, _)| shop_category_label(key, language).map(|label| (*key, label)))
+            .collect(),
+        shop_top_levels: SHOP_TOP_LEVELS
+            .iter()
+            .filter_map(|key| shop_top_level_label(key, language).map(|label| (*key, label)))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn bundles_are_complete() {
+        for lang in LANGUAGES {
+            let strings = bundle(lang);
+            assert_eq!(strings.language, lang);
+            assert_eq!(strings.badges.len(), BADGE_NAMES.len());
+            assert_eq!(strings.issues.len(), ALL_ERRORS.len());
+            assert_eq!(strings.shop_categories.len(), SHOP_CATEGORY_NAMES.len());
+            assert_eq!(strings.shop_top_levels.len(), SHOP_TOP_LEVELS.len());
+            assert!(strings.issues.values().all(|text| !text.is_empty()));
+        }
+
+        let unique: HashSet<&ModError> = ALL_ERRORS.into_iter().collect();
+        assert_eq!(unique.len(), ALL_ERRORS.len());
+    }
+
+    #[test]
+    fn readable_inputs() {
+        assert_eq!(input_label("KEY_lshift KEY_b"), "Left Shift + B");
+        assert_eq!(input_label("KEY_lctrl KEY_KP_5"), "Left Ctrl + Numpad 5");
+        assert_eq!(input_label("KEY_f10"), "F10");
+        assert_eq!(input_label("MOUSE_BUTTON_LEFT"), "Left Mouse Button");
+        assert_eq!(input_label("AXIS_X"), "AXIS_X");
+
+        assert_eq!(base_action_label("TOGGLE_LIGHTS", "de"), Some("Licht"));
+        assert_eq!(base_action_label("TOGGLE_LIGHTS", "xx"), Some("Lights"));
+        assert_eq!(base_action_label("MOD_ACTION", "en"), None);
+    }
+
+    #[test]
+    fn shop_category_names() {
+        assert_eq!(
+            shop_category_label("tractorsL", "de"),
+            Some("Große Traktoren")
+        );
+        assert_eq!(
+            shop_category_label("tractorsL", "fr"),
+            Some("Gros tracteurs")
+        );
+        assert_eq!(
+            shop_category_label("tractorsL", "xx"),
+            Some("Large Tractors")
+        );
+        assert_eq!(shop_category_label("myModCategory", "de"), None);
+    }
+
+    #[test]
+    fn translated_strings() {
+        let french = bundle("FR");
+        assert_eq!(french.language, "fr");
+        assert_eq!(
+            french.issues.get("PERF_GRLE_TOO_MANY"),
+            Some(&"Le mod contient trop de fichiers GRLE")
+        );
+        assert_eq!(french.badges.get("noMP"), Some(&"Pas de multijoueur"));
+
+        assert_eq!(bundle("xx").language, "en");
+        assert_eq!(badge_label("savegame", "de"), Some("Spielstand"));
+        assert_eq!(badge_label("savegame", "xx"), Some("Save Game"));
+        assert_eq!(badge_label("unknown", "en"), None);
+
+        let badges = crate::shared::structs::ModBadges {
+            broken: false,
+            folder: true,
+            malware: false,
+            no_mp: true,
+            notmod: false,
+            pconly: false,
+            problem: false,
+            savegame: false,
+        };
+        assert_eq!(badges.labels("de"), vec!["Ordner", "Kein Mehrspieler"]);
+    }
+}
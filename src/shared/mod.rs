@@ -1,13 +1,20 @@
 //! Shared data
+use crate::maps::farmlands::FarmlandLayer;
+#[cfg(feature = "images")]
 use base64::{engine::general_purpose, Engine as _};
-use image::{imageops::FilterType, DynamicImage};
-use image_dds::ddsfile;
+#[cfg(feature = "images")]
+use image::{imageops::FilterType, DynamicImage, Pixel};
+#[cfg(feature = "images")]
 use std::io::Cursor;
+use structs::ModIconInfo;
+#[cfg(feature = "images")]
 use webp::{Encoder, WebPMemory};
 
 pub mod errors;
 pub mod files;
+pub mod l10n;
 pub mod structs;
+pub mod xml;
 
 /// Image tag information
 #[cfg_attr(test, derive(Debug, PartialEq, Eq, PartialOrd, Ord))]
@@ -88,6 +95,63 @@ pub fn normalize_image_file(file_node: Option<&str>) -> ImageFile {
     ImageFile::fail()
 }
 
+/// Compare two dot separated version strings (e.g. `1.0.0.0`)
+///
+/// Parts are compared as numbers, missing parts count as zero, and a
+/// part that isn't a number counts as zero.
+#[must_use]
+pub fn compare_versions(left: &str, right: &str) -> std::cmp::Ordering {
+    let parts = |version: &str| -> Vec<u32> {
+        version
+            .trim()
+            .split('.')
+            .map(|part| part.trim().parse::<u32>().unwrap_or(0))
+            .collect()
+    };
+    let (left, right) = (parts(left), parts(right));
+
+    (0..left.len().max(right.len()))
+        .map(|i| left.get(i).unwrap_or(&0).cmp(right.get(i).unwrap_or(&0)))
+        .find(|order| order.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// Deserialize a unit variant from the name its `Serialize` impl writes
+///
+/// `variants` lists every variant of the enum, so names are only kept in
+/// the `Serialize` impl
+///
+/// # Errors
+///
+/// Fails when the input is not a string, or matches no variant
+pub(crate) fn deserialize_unit_variant<'de, D, T>(
+    deserializer: D,
+    variants: impl IntoIterator<Item = T>,
+) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Serialize,
+{
+    let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+    variants
+        .into_iter()
+        .find(|variant| serde_json::to_value(variant).is_ok_and(|value| value == name.as_str()))
+        .ok_or_else(|| serde::de::Error::custom(format!("unknown variant `{name}`")))
+}
+
+/// Normalize an input string so key order and spacing don't matter
+pub(crate) fn normalize_input(input: &str) -> String {
+    let mut keys: Vec<&str> = input.split_whitespace().collect();
+    keys.sort_unstable();
+    keys.join(" ")
+}
+
+/// Check if two action categories can be active at the same time
+pub(crate) fn categories_overlap(left: &str, right: &str) -> bool {
+    left.split_whitespace()
+        .any(|l| l == "ALL" || right.split_whitespace().any(|r| r == "ALL" || r == l))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -143,6 +207,33 @@ mod test {
 
         assert_eq!(response, expected);
     }
+
+    #[test]
+    fn map_image_layouts() {
+        assert_eq!(map_image_layout(0), (MAP_IMAGE_SCALE, MAP_IMAGE_CROP));
+        assert_eq!(map_image_layout(512), (MAP_IMAGE_SCALE, MAP_IMAGE_CROP));
+        assert_eq!(map_image_layout(1024), (2048, [512, 512, 1024, 1024]));
+        assert_eq!(map_image_layout(8), (128, [32, 32, 64, 64]));
+        assert_eq!(map_image_layout(u32::MAX), (8192, [2048, 2048, 4096, 4096]));
+    }
+
+    #[test]
+    fn map_image_crops() {
+        assert_eq!(map_image_crop(1024, 1024), MAP_IMAGE_CROP);
+        assert_eq!(map_image_crop(1024, 512), [256, 128, 512, 256]);
+        assert_eq!(map_image_crop(2048, 2048), [512, 512, 1024, 1024]);
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        use std::cmp::Ordering;
+
+        assert_eq!(compare_versions("1.0.0.0", "1.0.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0", "1.0.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0.10", "1.0.0.9"), Ordering::Greater);
+        assert_eq!(compare_versions("1.2.0.0", "1.10.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("2", "1.9.9.9"), Ordering::Greater);
+    }
 }
 
 /// Load the mod icon, and convert to webp
@@ -151,6 +242,7 @@ mod test {
 /// with an `<image src="...">` tag.
 ///
 /// Supports DDS BC1-BC7 in one pass, in-memory
+#[cfg(feature = "images")]
 #[must_use]
 pub fn convert_mod_icon(bin_file: Vec<u8>) -> Option<String> {
     let input_vector: Cursor<Vec<u8>> = Cursor::new(bin_file);
@@ -164,6 +256,177 @@ pub fn convert_mod_icon(bin_file: Vec<u8>) -> Option<String> {
     Some(format!("data:image/webp;base64, {b64}"))
 }
 
+/// Gallery images are scaled down to fit this size
+pub const GALLERY_IMAGE_SIZE: u32 = 512;
+
+/// Load a gallery image (DDS or PNG), scale it down, and convert to webp
+///
+/// Images larger than [`GALLERY_IMAGE_SIZE`] are scaled to fit, keeping
+/// the aspect ratio
+#[cfg(feature = "images")]
+#[must_use]
+pub fn convert_gallery_image(bin_file: Vec<u8>) -> Option<String> {
+    let original_image = if bin_file.starts_with(b"DDS ") {
+        let dds = ddsfile::Dds::read(Cursor::new(bin_file)).ok()?;
+        DynamicImage::ImageRgba8(image_dds::image_from_dds(&dds, 0).ok()?)
+    } else {
+        image::load_from_memory_with_format(&bin_file, image::ImageFormat::Png).ok()?
+    };
+    if original_image.width() > GALLERY_IMAGE_SIZE || original_image.height() > GALLERY_IMAGE_SIZE {
+        return encode_webp(&original_image.resize(
+            GALLERY_IMAGE_SIZE,
+            GALLERY_IMAGE_SIZE,
+            FilterType::Triangle,
+        ));
+    }
+    encode_webp(&original_image)
+}
+
+/// Load a gallery image (DDS or PNG), scale it down, and convert to webp
+///
+/// Built without the `images` feature, always `None`
+#[cfg(not(feature = "images"))]
+#[must_use]
+pub fn convert_gallery_image(_bin_file: Vec<u8>) -> Option<String> {
+    None
+}
+
+/// Load the mod icon, and convert to webp
+///
+/// Built without the `images` feature, always `None`
+#[cfg(not(feature = "images"))]
+#[must_use]
+pub fn convert_mod_icon(_bin_file: Vec<u8>) -> Option<String> {
+    None
+}
+
+/// Bytes in a DDS header, including the magic number and the DX10 extension
+///
+/// Enough for [`analyze_dds`], the pixel data is not needed
+pub const DDS_HEADER_SIZE: u64 = 148;
+
+/// Read the header of a DDS file
+///
+/// Returns `None` if the file is not a DDS
+#[must_use]
+pub fn analyze_dds(bin_file: &[u8]) -> Option<ModIconInfo> {
+    let dds = ddsfile::Dds::read(bin_file).ok()?;
+
+    let format = match (&dds.header10, dds.get_d3d_format()) {
+        (None, Some(d3d_format)) => format!("{d3d_format:?}"),
+        _ => dds
+            .get_dxgi_format()
+            .map_or(String::from("Unknown"), |dxgi_format| {
+                format!("{dxgi_format:?}")
+            }),
+    };
+    let (width, height) = (dds.get_width(), dds.get_height());
+
+    Some(ModIconInfo {
+        compressed: format.starts_with("BC") || format.starts_with("DXT"),
+        format,
+        height,
+        mip_levels: dds.get_num_mipmap_levels(),
+        power_of_two: width.is_power_of_two() && height.is_power_of_two(),
+        width,
+    })
+}
+
+/// Map overview images are scaled to fit this size before cropping
+pub const MAP_IMAGE_SCALE: u32 = 1024;
+
+/// Crop rectangle (x, y, width, height) of a square scaled map overview
+///
+/// The overview covers twice the map size, the playable area is the center.
+/// Other shapes are cropped with [`map_image_crop`].
+pub const MAP_IMAGE_CROP: [u32; 4] = [256, 256, 512, 512];
+
+/// Crop rectangle (x, y, width, height) of the playable area of a scaled
+/// map overview - the center half in each direction
+#[must_use]
+pub fn map_image_crop(width: u32, height: u32) -> [u32; 4] {
+    [width / 4, height / 4, width / 2, height / 2]
+}
+
+/// Allowed sizes of the cropped map image, in pixels
+pub const MAP_IMAGE_SIZES: std::ops::RangeInclusive<u32> = 64..=4096;
+
+/// Scale and crop rectangle for a cropped map image of `size` pixels
+///
+/// A size of 0 uses the default layout ([`MAP_IMAGE_SCALE`], [`MAP_IMAGE_CROP`]),
+/// other sizes are clamped to [`MAP_IMAGE_SIZES`].
+/// The crop assumes a square overview, the conversion crops the real
+/// scaled image with [`map_image_crop`].
+#[must_use]
+pub fn map_image_layout(size: u32) -> (u32, [u32; 4]) {
+    if size == 0 {
+        return (MAP_IMAGE_SCALE, MAP_IMAGE_CROP);
+    }
+    let size = size.clamp(*MAP_IMAGE_SIZES.start(), *MAP_IMAGE_SIZES.end());
+    (size * 2, [size / 2, size / 2, size, size])
+}
+
+/// Map image settings, see [`convert_map_overview_with_options`]
+#[derive(Default)]
+pub struct MapImageOptions<'a> {
+    /// include the scaled, uncropped image
+    pub include_full: bool,
+    /// size of the cropped image in pixels, see [`map_image_layout`]
+    pub size: u32,
+    /// farmland borders to draw on the playable area
+    pub farmlands: Option<&'a FarmlandLayer>,
+}
+
+/// Map overview images, see [`convert_map_overview`]
+pub struct MapOverviewImages {
+    /// center of the overview (the playable area) - base64 webp
+    pub cropped: String,
+    /// crop rectangle (x, y, width, height) of the cropped image
+    pub crop: [u32; 4],
+    /// full overview, if requested - base64 webp
+    pub full: Option<String>,
+    /// width of the scaled full overview
+    pub width: u32,
+    /// height of the scaled full overview
+    pub height: u32,
+}
+
+/// Encode an image as a base64 webp, for an `<image src="...">` tag
+#[cfg(feature = "images")]
+fn encode_webp(image: &DynamicImage) -> Option<String> {
+    let encoder: Encoder = Encoder::from_image(image).ok()?;
+    let webp: WebPMemory = encoder.encode(75_f32);
+    let b64 = general_purpose::STANDARD.encode(webp.as_ref());
+
+    Some(format!("data:image/webp;base64, {b64}"))
+}
+
+/// Border color for farmland edges (RGBA)
+#[cfg(feature = "images")]
+const FARMLAND_BORDER: [u8; 4] = [255, 255, 255, 192];
+
+/// Draw farmland borders on the playable area of a scaled map overview
+#[cfg(feature = "images")]
+fn draw_farmland_borders(
+    image: &DynamicImage,
+    crop: [u32; 4],
+    farmlands: &FarmlandLayer,
+) -> DynamicImage {
+    let [x, y, width, height] = crop;
+    let mut canvas = image.to_rgba8();
+
+    for pixel_y in 0..height {
+        for pixel_x in 0..width {
+            if farmlands.is_border(pixel_x, pixel_y, width, height) {
+                if let Some(pixel) = canvas.get_pixel_mut_checked(x + pixel_x, y + pixel_y) {
+                    pixel.blend(&image::Rgba(FARMLAND_BORDER));
+                }
+            }
+        }
+    }
+    DynamicImage::ImageRgba8(canvas)
+}
+
 /// Load the map image resize, crop, and convert to webp
 ///
 /// Returns the webp as a base64 string suitable for use
@@ -172,16 +435,71 @@ pub fn convert_mod_icon(bin_file: Vec<u8>) -> Option<String> {
 /// Supports DDS BC1-BC7 in one pass, in-memory
 #[must_use]
 pub fn convert_map_image(bin_file: Vec<u8>) -> Option<String> {
+    convert_map_overview(bin_file, false).map(|images| images.cropped)
+}
+
+/// Load the map image, resize, and convert to webp
+///
+/// The overview is scaled to fit [`MAP_IMAGE_SCALE`], then cropped to the
+/// playable area (see [`map_image_crop`]). The scaled, uncropped image is included when
+/// `include_full` is set.
+#[must_use]
+pub fn convert_map_overview(bin_file: Vec<u8>, include_full: bool) -> Option<MapOverviewImages> {
+    convert_map_overview_with_options(
+        bin_file,
+        &MapImageOptions {
+            include_full,
+            ..Default::default()
+        },
+    )
+}
+
+/// Load the map image, resize, and convert to webp, with size and overlay
+/// settings
+///
+/// The overview is scaled and cropped per [`map_image_layout`], and
+/// farmland borders are drawn on it when a layer is given
+#[cfg(feature = "images")]
+#[must_use]
+pub fn convert_map_overview_with_options(
+    bin_file: Vec<u8>,
+    options: &MapImageOptions,
+) -> Option<MapOverviewImages> {
+    let (scale, _) = map_image_layout(options.size);
     let input_vector = Cursor::new(bin_file);
     let dds = ddsfile::Dds::read(input_vector).ok()?;
     let original_image = image_dds::image_from_dds(&dds, 0).ok()?;
-    let unscaled_image = DynamicImage::ImageRgba8(original_image);
-    let cropped_image = unscaled_image
-        .resize(1024, 1024, FilterType::Nearest)
-        .crop(256, 256, 512, 512);
-    let encoder: Encoder = Encoder::from_image(&cropped_image).ok()?;
-    let webp: WebPMemory = encoder.encode(75_f32);
-    let b64 = general_purpose::STANDARD.encode(webp.as_ref());
+    let mut scaled_image =
+        DynamicImage::ImageRgba8(original_image).resize(scale, scale, FilterType::Nearest);
+    let crop = map_image_crop(scaled_image.width(), scaled_image.height());
+    if let Some(farmlands) = options.farmlands {
+        scaled_image = draw_farmland_borders(&scaled_image, crop, farmlands);
+    }
+    let [x, y, width, height] = crop;
+    let cropped_image = scaled_image.crop_imm(x, y, width, height);
 
-    Some(format!("data:image/webp;base64, {b64}"))
+    Some(MapOverviewImages {
+        cropped: encode_webp(&cropped_image)?,
+        crop,
+        full: if options.include_full {
+            encode_webp(&scaled_image)
+        } else {
+            None
+        },
+        width: scaled_image.width(),
+        height: scaled_image.height(),
+    })
+}
+
+/// Load the map image, resize, and convert to webp, with size and overlay
+/// settings
+///
+/// Built without the `images` feature, always `None`
+#[cfg(not(feature = "images"))]
+#[must_use]
+pub fn convert_map_overview_with_options(
+    _bin_file: Vec<u8>,
+    _options: &MapImageOptions,
+) -> Option<MapOverviewImages> {
+    None
 }
@@ -1,17 +1,23 @@
 //! Structs used to collect data for JSON export
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     path::Path,
+    sync::Arc,
 };
 
-use crate::maps::structs::{CropList, CropWeatherType};
+use crate::maps::structs::{CropCalendar, CropList, CropListDiff, CropWeatherType, MapOverview};
 use crate::mod_detail::structs::ModDetail;
 use crate::savegame::SaveGameRecord;
-use crate::shared::errors::{ModError, BADGE_BROKEN, BADGE_ISSUE, BADGE_NOT_MOD};
+use crate::shared::errors::{ModError, ModErrorSeverity, BADGE_BROKEN, BADGE_ISSUE, BADGE_NOT_MOD};
+use crate::shared::l10n::badge_label;
+use crate::shared::{
+    categories_overlap, compare_versions, deserialize_unit_variant, normalize_input,
+};
+use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 
 /// Translatable modDesc entries
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModDescL10N {
     /// Translation strings for the mod title
@@ -20,8 +26,16 @@ pub struct ModDescL10N {
     pub description: HashMap<String, String>,
 }
 
+impl ModDescL10N {
+    /// Keep only the language codes matching a predicate
+    pub fn retain_languages<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        self.title.retain(|code, _| keep(code));
+        self.description.retain(|code, _| keep(code));
+    }
+}
+
 /// Master mod record
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModRecord {
     /// List of active badges
@@ -30,12 +44,21 @@ pub struct ModRecord {
     pub can_not_use: bool,
     /// Current collection for mod (not set)
     pub current_collection: String,
+    /// Active custom badges, by name (sorted), see [`BadgeRule`]
+    pub custom_badges: Vec<String>,
     /// Detail icons processed flag
     pub detail_icon_loaded: bool,
     /// File details
     pub file_detail: ModFile,
+    /// Bundled configuration for third party frameworks, with the matching
+    /// files, by framework
+    pub framework_configs: BTreeMap<ModFramework, Vec<String>>,
     /// Errors or issues found
     pub issues: HashSet<ModError>,
+    /// Context for issues raised more than once (file names, counts), by issue
+    ///
+    /// Every issue listed here is also in `issues`
+    pub issue_context: BTreeMap<ModError, Vec<String>>,
     /// storeItems found (if processed)
     pub include_detail: Option<ModDetail>,
     /// save game record (if processed)
@@ -46,8 +69,27 @@ pub struct ModRecord {
     pub md5_sum: Option<String>,
     /// modDesc.xml fields
     pub mod_desc: ModDesc,
+    /// Patterns that can cause multiplayer sync problems, see [`MpRiskKind`]
+    pub mp_risk_factors: Vec<MpRiskFactor>,
+    /// Sum of the [`ModRecord::mp_risk_factors`] weights (0 is no known risk)
+    pub mp_risk_score: u32,
+    /// File references that only match a file with different case (if requested)
+    pub reference_case: Option<Vec<ReferenceCaseMismatch>>,
+    /// Official DLCs the mod needs (`pdlc_` short names), from the modDesc
+    /// and (if requested) store item files, see
+    /// [`crate::ModParserOptions::store_item_dlcs`]
+    pub required_dlcs: Vec<String>,
+    /// Game API touchpoints of the lua scripts (script mods only), with
+    /// [`crate::ModParserOptions::script_api`]
+    pub script_api: Option<ScriptApiSummary>,
+    /// Likely origin of the mod
+    pub source: ModSource,
+    /// DDS texture audit (if requested)
+    pub texture_report: Option<TextureReport>,
     /// Mod UUID from full path and filename (MD5)
     pub uuid: String,
+    /// modDesc.xml structure problems, in document order
+    pub validation: Vec<ModDescViolation>,
 }
 
 impl ModRecord {
@@ -60,9 +102,12 @@ impl ModRecord {
             badge_array: ModBadges::new(),
             can_not_use: false,
             current_collection: String::new(),
+            custom_badges: vec![],
             detail_icon_loaded: false,
             file_detail: ModFile::new(full_path, is_folder),
+            framework_configs: BTreeMap::new(),
             issues: HashSet::new(),
+            issue_context: BTreeMap::new(),
             include_detail: None,
             include_save_game: None,
             l10n: ModDescL10N {
@@ -71,9 +116,28 @@ impl ModRecord {
             },
             md5_sum: None,
             mod_desc: ModDesc::new(),
+            mp_risk_factors: vec![],
+            mp_risk_score: 0,
+            reference_case: None,
+            required_dlcs: vec![],
+            script_api: None,
+            source: ModSource::Unknown,
+            texture_report: None,
             uuid: format!("{:?}", md5::compute(full_path.to_str().unwrap_or(""))),
+            validation: vec![],
         }
     }
+    /// record a multiplayer risk factor, and add its weight to the score
+    pub fn add_mp_risk<S: Into<String>>(&mut self, kind: MpRiskKind, detail: S) -> &mut Self {
+        let weight = kind.weight();
+        self.mp_risk_score += weight;
+        self.mp_risk_factors.push(MpRiskFactor {
+            kind,
+            detail: detail.into(),
+            weight,
+        });
+        self
+    }
     /// raise an fatal error on the mod
     pub fn add_fatal(&mut self, issue: ModError) -> &mut Self {
         self.can_not_use = true;
@@ -85,6 +149,17 @@ impl ModRecord {
         self.issues.insert(issue);
         self
     }
+    /// raise an error on the mod, with a context entry (file name, count)
+    ///
+    /// Repeated context entries for the same issue are only listed once
+    pub fn add_issue_context<S: Into<String>>(&mut self, issue: ModError, context: S) -> &mut Self {
+        let context = context.into();
+        let entries = self.issue_context.entry(issue.clone()).or_default();
+        if !entries.contains(&context) {
+            entries.push(context);
+        }
+        self.add_issue(issue)
+    }
     /// update the badge array from other data
     pub fn update_badges(&mut self) -> &mut Self {
         self.badge_array.notmod = BADGE_NOT_MOD.iter().any(|x| self.issues.contains(x));
@@ -106,6 +181,21 @@ impl ModRecord {
         }
         self
     }
+    /// set the custom badges from a list of rules
+    ///
+    /// Rules see the record with its built in badges already set, so call
+    /// after [`ModRecord::update_badges`]
+    pub fn apply_badge_rules(&mut self, rules: &[BadgeRule]) -> &mut Self {
+        let mut names: Vec<String> = rules
+            .iter()
+            .filter(|rule| rule.matches(self))
+            .map(|rule| rule.name.clone())
+            .collect();
+        names.sort();
+        names.dedup();
+        self.custom_badges = names;
+        self
+    }
     /// Output as pretty-print JSON
     #[must_use]
     pub fn to_json_pretty(&self) -> String {
@@ -117,6 +207,152 @@ impl ModRecord {
     pub fn to_json(&self) -> String {
         self.to_string()
     }
+
+    /// Issues with severity and a human readable message (sorted)
+    #[must_use]
+    pub fn issue_details(&self, lang: &str) -> Vec<ModIssueDetail<'_>> {
+        let mut issues: Vec<&ModError> = self.issues.iter().collect();
+        issues.sort();
+
+        issues
+            .into_iter()
+            .map(|issue| ModIssueDetail {
+                code: issue,
+                severity: issue.severity(),
+                message: issue.describe(lang),
+            })
+            .collect()
+    }
+
+    /// Output as JSON, with an added `issueDetails` list
+    ///
+    /// See [`ModRecord::issue_details`]
+    #[must_use]
+    pub fn to_json_verbose(&self, lang: &str, pretty: bool) -> String {
+        let Ok(mut value) = serde_json::to_value(self) else {
+            return String::from("{}");
+        };
+        if let (Some(map), Ok(details)) = (
+            value.as_object_mut(),
+            serde_json::to_value(self.issue_details(lang)),
+        ) {
+            map.insert(String::from("issueDetails"), details);
+        }
+        let output = if pretty {
+            serde_json::to_string_pretty(&value)
+        } else {
+            serde_json::to_string(&value)
+        };
+        output.unwrap_or(String::from("{}"))
+    }
+
+    /// Compact summary of the record, for list views
+    ///
+    /// Omits images, file lists, l10n blobs and included detail/save records
+    #[must_use]
+    pub fn to_summary(&self) -> ModSummary<'_> {
+        let mut issues: Vec<&ModError> = self.issues.iter().collect();
+        issues.sort();
+
+        ModSummary {
+            author: &self.mod_desc.author,
+            badge_array: &self.badge_array,
+            can_not_use: self.can_not_use,
+            custom_badges: &self.custom_badges,
+            depend: &self.mod_desc.depend,
+            depend_versions: &self.mod_desc.depend_versions,
+            file_date: &self.file_detail.file_date,
+            file_size: self.file_detail.file_size,
+            full_path: &self.file_detail.full_path,
+            is_disabled: self.file_detail.is_disabled,
+            is_folder: self.file_detail.is_folder,
+            issues,
+            multi_player: self.mod_desc.multi_player,
+            script_files: self.mod_desc.script_files,
+            short_name: &self.file_detail.short_name,
+            store_items: self.mod_desc.store_items,
+            title: self.title(),
+            uuid: &self.uuid,
+            version: &self.mod_desc.version,
+        }
+    }
+
+    /// Drop the heavy fields, keeping the shape of the record
+    ///
+    /// Clears base64 images (mod icon, map images, gallery), the listed
+    /// files (I3D, images, audio and extra files), l10n descriptions and
+    /// included detail/save records. See [`crate::ModParserOptions::slim_output`]
+    pub fn slim(&mut self) -> &mut Self {
+        self.mod_desc.icon_image = None;
+        self.mod_desc.map_image = None;
+        self.mod_desc.gallery.clear();
+        if let Some(overview) = &mut self.mod_desc.map_overview {
+            overview.full_image = None;
+        }
+
+        self.file_detail.audio_files.clear();
+        self.file_detail.extra_files.clear();
+        self.file_detail.i3d_files.clear();
+        self.file_detail.images = ModImages::default();
+
+        self.l10n.description.clear();
+        self.include_detail = None;
+        self.include_save_game = None;
+        self
+    }
+
+    /// Changes from this record to a newer parse of the same mod
+    ///
+    /// Files are compared using the lists the record keeps (I3D, images,
+    /// audio and extra files) - scripts and XML files are not listed
+    #[must_use]
+    pub fn diff(&self, other: &ModRecord) -> ModDiff {
+        let old_files = self.file_detail.listed_files();
+        let new_files = other.file_detail.listed_files();
+        let sorted_issues = |left: &HashSet<ModError>, right: &HashSet<ModError>| {
+            let mut issues: Vec<ModError> = left.difference(right).cloned().collect();
+            issues.sort();
+            issues
+        };
+        let delta = |old: u64, new: u64| {
+            i64::try_from(new).unwrap_or(i64::MAX) - i64::try_from(old).unwrap_or(i64::MAX)
+        };
+
+        ModDiff {
+            files_added: new_files
+                .difference(&old_files)
+                .map(|n| (*n).to_owned())
+                .collect(),
+            files_removed: old_files
+                .difference(&new_files)
+                .map(|n| (*n).to_owned())
+                .collect(),
+            issues_added: sorted_issues(&other.issues, &self.issues),
+            issues_removed: sorted_issues(&self.issues, &other.issues),
+            new_version: other.mod_desc.version.clone(),
+            old_version: self.mod_desc.version.clone(),
+            size_delta: delta(self.file_detail.file_size, other.file_detail.file_size),
+            store_items_delta: delta(
+                self.mod_desc.store_items as u64,
+                other.mod_desc.store_items as u64,
+            ),
+            upgrade: compare_versions(&other.mod_desc.version, &self.mod_desc.version)
+                == std::cmp::Ordering::Greater,
+        }
+    }
+
+    /// Mod title (english, or first available language)
+    #[must_use]
+    pub fn title(&self) -> &str {
+        if let Some(title) = self.l10n.title.get("en") {
+            return title;
+        }
+        let mut keys: Vec<&String> = self.l10n.title.keys().collect();
+        keys.sort();
+        keys.first()
+            .and_then(|key| self.l10n.title.get(*key))
+            .map_or("", String::as_str)
+    }
 }
 impl std::fmt::Display for ModRecord {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -124,16 +360,164 @@ impl std::fmt::Display for ModRecord {
     }
 }
 
+/// A single issue with its severity and message, for verbose output
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModIssueDetail<'a> {
+    /// issue code
+    pub code: &'a ModError,
+    /// issue severity
+    pub severity: ModErrorSeverity,
+    /// human readable message
+    pub message: &'static str,
+}
+
+/// Compact view of a [`ModRecord`], for list views
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+#[expect(clippy::struct_excessive_bools)]
+pub struct ModSummary<'a> {
+    /// Mod Author
+    pub author: &'a str,
+    /// List of active badges
+    pub badge_array: &'a ModBadges,
+    /// Mod not usable flag
+    pub can_not_use: bool,
+    /// Active custom badges
+    pub custom_badges: &'a [String],
+    /// Mods this mod depends on (shortNames)
+    pub depend: &'a [ModDependency],
+    /// Required version by shortName, for dependencies that give one
+    pub depend_versions: &'a BTreeMap<String, String>,
+    /// mod file date
+    pub file_date: &'a str,
+    /// mod size (packed zip or folder contents)
+    pub file_size: u64,
+    /// full path to file
+    pub full_path: &'a str,
+    /// disabled flag (renamed to e.g. `.zip.disabled`)
+    pub is_disabled: bool,
+    /// folder flag (is this a folder?)
+    pub is_folder: bool,
+    /// Errors or issues found (sorted)
+    pub issues: Vec<&'a ModError>,
+    /// multi-player capable
+    pub multi_player: bool,
+    /// Script file count
+    pub script_files: u32,
+    /// short name of mod
+    pub short_name: &'a str,
+    /// Store Item count
+    pub store_items: usize,
+    /// Mod title (english, or first available language)
+    pub title: &'a str,
+    /// Mod UUID from full path and filename (MD5)
+    pub uuid: &'a str,
+    /// mod version
+    pub version: &'a str,
+}
+
+impl ModSummary<'_> {
+    /// Output as pretty-print JSON
+    #[must_use]
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or(String::from("{}"))
+    }
+
+    /// Output as JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+impl std::fmt::Display for ModSummary<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&serde_json::to_string(&self).unwrap_or(String::from("{}")))
+    }
+}
+
+/// Changes between two parses of the same mod, see [`ModRecord::diff`]
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDiff {
+    /// files in the newer parse only
+    pub files_added: Vec<String>,
+    /// files in the older parse only
+    pub files_removed: Vec<String>,
+    /// issues in the newer parse only (sorted)
+    pub issues_added: Vec<ModError>,
+    /// issues in the older parse only (sorted)
+    pub issues_removed: Vec<ModError>,
+    /// version of the newer parse
+    pub new_version: String,
+    /// version of the older parse
+    pub old_version: String,
+    /// change in mod size, in bytes (newer minus older)
+    pub size_delta: i64,
+    /// change in store item count (newer minus older)
+    pub store_items_delta: i64,
+    /// the newer version number is higher, see [`compare_versions`]
+    pub upgrade: bool,
+}
+
+impl ModDiff {
+    /// No differences found
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files_added.is_empty()
+            && self.files_removed.is_empty()
+            && self.issues_added.is_empty()
+            && self.issues_removed.is_empty()
+            && self.new_version == self.old_version
+            && self.size_delta == 0
+            && self.store_items_delta == 0
+    }
+
+    /// Output as pretty-print JSON
+    #[must_use]
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or(String::from("{}"))
+    }
+
+    /// Output as JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+impl std::fmt::Display for ModDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&serde_json::to_string(&self).unwrap_or(String::from("{}")))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn test_mod_record_summary() {
+        let mut record = ModRecord::new("FS22_Example.zip", false);
+        record.add_issue(ModError::ModDescNoModIcon);
+        record.mod_desc.icon_image = Some("x".repeat(4096));
+        record.update_badges();
+
+        let summary = record.to_summary();
+        assert_eq!(summary.short_name, "FS22_Example");
+        assert_eq!(summary.title, "--");
+
+        let json = summary.to_json();
+        assert!(json.len() < 1024);
+        assert!(json.contains("MOD_ERROR_NO_MOD_ICON"));
+        assert!(!json.contains("iconImage"));
+    }
+
     #[test]
     fn test_blank_mod_record_json() {
         let record = ModRecord::new("foo.txt", false);
 
         let byte_length = record.to_json().len() as i32;
-        let byte_expected: i32 = 861;
+        let byte_expected: i32 = 1391;
         let byte_margin = 20;
         assert!(
             (byte_length - byte_expected).abs() < byte_margin,
@@ -145,35 +529,307 @@ mod test {
             (byte_length - byte_expected).abs()
         );
     }
+
+    #[test]
+    fn mod_record_diff() {
+        let mut old = ModRecord::new("FS22_Example.zip", false);
+        old.mod_desc.version = String::from("1.0.0.0");
+        old.file_detail.file_size = 1000;
+        old.file_detail.i3d_files = vec![String::from("old.i3d"), String::from("shared.i3d")];
+        old.add_issue(ModError::ModDescNoModIcon);
+
+        let mut new = ModRecord::new("FS22_Example.zip", false);
+        new.mod_desc.version = String::from("1.1.0.0");
+        new.mod_desc.store_items = 2;
+        new.file_detail.file_size = 800;
+        new.file_detail.i3d_files = vec![String::from("new.i3d"), String::from("shared.i3d")];
+        new.add_issue(ModError::PerformanceQuantityGRLE);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.files_added, vec!["new.i3d"]);
+        assert_eq!(diff.files_removed, vec!["old.i3d"]);
+        assert_eq!(diff.issues_added, vec![ModError::PerformanceQuantityGRLE]);
+        assert_eq!(diff.issues_removed, vec![ModError::ModDescNoModIcon]);
+        assert_eq!(diff.size_delta, -200);
+        assert_eq!(diff.store_items_delta, 2);
+        assert!(diff.upgrade);
+        assert!(!diff.is_empty());
+        assert!(!new.diff(&old).upgrade);
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn issue_context_entries() {
+        let mut record = ModRecord::new("foo.txt", false);
+        record.add_issue_context(ModError::PerformanceOversizeDDS, "a.dds");
+        record.add_issue_context(ModError::PerformanceOversizeDDS, "b.dds");
+        record.add_issue_context(ModError::PerformanceOversizeDDS, "a.dds");
+
+        assert_eq!(record.issues.len(), 1);
+        assert_eq!(
+            record.issue_context[&ModError::PerformanceOversizeDDS],
+            vec!["a.dds", "b.dds"]
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&record.to_json()).unwrap();
+        assert_eq!(
+            json["issueContext"],
+            serde_json::json!({ "PERF_DDS_TOO_BIG": ["a.dds", "b.dds"] })
+        );
+    }
+
+    #[test]
+    fn verbose_issue_details() {
+        let mut record = ModRecord::new("foo.txt", false);
+        record.add_issue(ModError::PerformanceQuantityGRLE);
+        record.add_issue(ModError::FileErrorGarbageFile);
+
+        let details = record.issue_details("en");
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].code, &ModError::FileErrorGarbageFile);
+        assert_eq!(details[0].severity, ModErrorSeverity::Fatal);
+        assert_eq!(details[1].severity, ModErrorSeverity::Info);
+        assert_eq!(details[1].message, "Mod contains too many GRLE files");
+        assert_eq!(
+            ModError::PerformanceQuantityGRLE.describe("de"),
+            "Mod enthält zu viele GRLE-Dateien"
+        );
+        assert_eq!(
+            ModError::PerformanceQuantityGRLE.describe("xx"),
+            details[1].message
+        );
+
+        let verbose: serde_json::Value =
+            serde_json::from_str(&record.to_json_verbose("en", false)).unwrap();
+        assert_eq!(
+            verbose["issueDetails"][1],
+            serde_json::json!({
+                "code": "PERF_GRLE_TOO_MANY",
+                "severity": "info",
+                "message": "Mod contains too many GRLE files"
+            })
+        );
+    }
 }
 
-/// ModDesc.xml specific fields from a mod
-#[derive(serde::Serialize)]
+/// Likely origin of a mod
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum ModSource {
+    /// Not enough information
+    Unknown,
+    /// Looks like an untouched `ModHub` download
+    LikelyModHub,
+    /// File hash is in the supplied `ModHub` list
+    ModHub,
+    /// File hash is not in the supplied `ModHub` list
+    ThirdParty,
+}
+
+impl Serialize for ModSource {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            ModSource::Unknown => serializer.serialize_unit_variant("ModSource", 0, "unknown"),
+            ModSource::LikelyModHub => {
+                serializer.serialize_unit_variant("ModSource", 1, "likelyModHub")
+            }
+            ModSource::ModHub => serializer.serialize_unit_variant("ModSource", 2, "modHub"),
+            ModSource::ThirdParty => {
+                serializer.serialize_unit_variant("ModSource", 3, "thirdParty")
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ModSource {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                ModSource::Unknown,
+                ModSource::LikelyModHub,
+                ModSource::ModHub,
+                ModSource::ThirdParty,
+            ],
+        )
+    }
+}
+
+/// Third party mod framework a mod can ship configuration for
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub enum ModFramework {
+    /// `AutoDrive` route network
+    AutoDrive,
+    /// `Courseplay` courses
+    Courseplay,
+    /// `GlobalCompany` configuration
+    GlobalCompany,
+}
+
+impl Serialize for ModFramework {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            ModFramework::AutoDrive => {
+                serializer.serialize_unit_variant("ModFramework", 0, "autoDrive")
+            }
+            ModFramework::Courseplay => {
+                serializer.serialize_unit_variant("ModFramework", 1, "courseplay")
+            }
+            ModFramework::GlobalCompany => {
+                serializer.serialize_unit_variant("ModFramework", 2, "globalCompany")
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ModFramework {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                ModFramework::AutoDrive,
+                ModFramework::Courseplay,
+                ModFramework::GlobalCompany,
+            ],
+        )
+    }
+}
+
+/// Pattern that can cause multiplayer sync problems
+///
+/// These are heuristics to help triage mods for a server, not errors
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub enum MpRiskKind {
+    /// GUI, HUD or dialog scripts, which only run on the client
+    ClientScripts,
+    /// Many I3D files, all loaded on every client
+    DenseI3d,
+    /// Many store items, all synced to every client
+    ManyStoreItems,
+    /// Large total audio size
+    OversizeAudio,
+}
+
+impl MpRiskKind {
+    /// Weight of the pattern in [`ModRecord::mp_risk_score`]
+    #[must_use]
+    pub fn weight(self) -> u32 {
+        match self {
+            MpRiskKind::ClientScripts => 40,
+            MpRiskKind::DenseI3d | MpRiskKind::ManyStoreItems => 20,
+            MpRiskKind::OversizeAudio => 10,
+        }
+    }
+}
+
+impl Serialize for MpRiskKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            MpRiskKind::ClientScripts => {
+                serializer.serialize_unit_variant("MpRiskKind", 0, "clientScripts")
+            }
+            MpRiskKind::DenseI3d => serializer.serialize_unit_variant("MpRiskKind", 1, "denseI3d"),
+            MpRiskKind::ManyStoreItems => {
+                serializer.serialize_unit_variant("MpRiskKind", 2, "manyStoreItems")
+            }
+            MpRiskKind::OversizeAudio => {
+                serializer.serialize_unit_variant("MpRiskKind", 3, "oversizeAudio")
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MpRiskKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                MpRiskKind::ClientScripts,
+                MpRiskKind::DenseI3d,
+                MpRiskKind::ManyStoreItems,
+                MpRiskKind::OversizeAudio,
+            ],
+        )
+    }
+}
+
+/// Multiplayer risk factor found in a mod
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
+pub struct MpRiskFactor {
+    /// pattern found
+    pub kind: MpRiskKind,
+    /// what was found (file names or counts)
+    pub detail: String,
+    /// weight added to the score
+    pub weight: u32,
+}
+
+/// ModDesc.xml specific fields from a mod
+///
+/// The derived (de)serializers are inherent functions (`remote = "Self"`),
+/// wrapped by the trait impls so dependency versions can be restored
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase", remote = "Self")]
 #[expect(clippy::struct_excessive_bools)]
 pub struct ModDesc {
     /// Keyboard actions
     pub actions: HashMap<String, String>,
     /// Keyboard bindings
     pub binds: HashMap<String, Vec<String>>,
-    /// Mod Author
+    /// Mod Author, as written
     pub author: String,
+    /// Author names split from [`ModDesc::author`] (trimmed, deduplicated, no urls)
+    pub authors: Vec<String>,
+    /// urls found in [`ModDesc::author`]
+    pub author_urls: Vec<String>,
     /// Script file count
     pub script_files: u32,
     /// Store Item count
     pub store_items: usize,
-    /// Crop details (for maps)
+    /// Crop details (for maps and crop addons)
     pub crop_info: CropList,
+    /// Crop calendar by month, aligned to the map hemisphere (for maps)
+    pub crop_calendar: Vec<CropCalendar>,
+    /// Crop calendar differences from the base game (maps with a custom growth file)
+    pub crop_diff: Option<CropListDiff>,
     /// Map Weather (for maps)
     pub crop_weather: Option<CropWeatherType>,
-    /// Mods this mod depends on (shortNames)
-    pub depend: Vec<String>,
+    /// Mods this mod depends on (shortNames, with any required version)
+    pub depend: Vec<ModDependency>,
+    /// Required version by shortName, for dependencies that give one
+    pub depend_versions: BTreeMap<String, String>,
     /// descVersion
     pub desc_version: u32,
+    /// gallery images, if requested - base64 webp, see
+    /// [`crate::ModParserOptions::gallery_images`]
+    pub gallery: Vec<String>,
     /// icon file name
     pub icon_file_name: Option<String>,
+    /// icon DDS header details (if the icon could be read)
+    pub icon_info: Option<ModIconInfo>,
     /// icon image, if processed and loaded - base64 webp
     pub icon_image: Option<String>,
+    /// Website and support links, keyed by modDesc tag name (see
+    /// [`crate::mod_basic::LINK_TAGS`])
+    pub links: BTreeMap<String, String>,
     /// map config file (for maps)
     pub map_config_file: Option<String>,
     /// map has a custom environment
@@ -182,36 +838,115 @@ pub struct ModDesc {
     pub map_custom_crop: bool,
     /// map has a custom growth file
     pub map_custom_grow: bool,
+    /// density, info layer and weight maps referenced by the map that are missing or empty
+    pub map_density_missing: Vec<String>,
+    /// problems found in the custom growth file (out of range periods, reversed ranges)
+    pub map_growth_warnings: Vec<String>,
     /// map is in the southern hemisphere
     pub map_is_south: bool,
     /// map image, if processed and loaded - base64 webp
     pub map_image: Option<String>,
+    /// map overview layout and full image (for maps)
+    pub map_overview: Option<MapOverview>,
     /// multi-player capable
     pub multi_player: bool,
+    /// Raw modDesc subtrees requested by tag name, see
+    /// [`crate::ModParserOptions::raw_mod_desc_tags`]
+    pub raw: BTreeMap<String, Vec<ModDescRawNode>>,
     /// mod version
     pub version: String,
 }
 
 impl ModDesc {
+    /// Key binds grouped by action category, as in the in-game input menu
+    ///
+    /// Maps category to action name to default keyboard inputs
+    #[must_use]
+    pub fn binds_by_category(&self) -> BTreeMap<String, BTreeMap<String, Vec<String>>> {
+        let mut grouped: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+
+        for (action, inputs) in &self.binds {
+            let category = self
+                .actions
+                .get(action)
+                .cloned()
+                .unwrap_or_else(|| String::from("ALL"));
+            grouped
+                .entry(category)
+                .or_default()
+                .insert(action.clone(), inputs.clone());
+        }
+        grouped
+    }
+
+    /// Default keyboard inputs bound to more than one action in this mod
+    ///
+    /// Maps normalized input to the (sorted) actions using it, only where
+    /// the action categories overlap
+    #[must_use]
+    pub fn duplicate_binds(&self) -> BTreeMap<String, Vec<String>> {
+        let mut by_input: BTreeMap<String, Vec<(&str, &str)>> = BTreeMap::new();
+
+        for (action, inputs) in &self.binds {
+            let category = self.actions.get(action).map_or("ALL", String::as_str);
+            for input in inputs {
+                by_input
+                    .entry(normalize_input(input))
+                    .or_default()
+                    .push((action, category));
+            }
+        }
+
+        by_input
+            .into_iter()
+            .filter_map(|(input, entries)| {
+                let mut actions: Vec<String> = entries
+                    .iter()
+                    .filter(|this| {
+                        entries
+                            .iter()
+                            .any(|other| other.0 != this.0 && categories_overlap(this.1, other.1))
+                    })
+                    .map(|n| n.0.to_owned())
+                    .collect();
+                actions.sort();
+                actions.dedup();
+                (!actions.is_empty()).then_some((input, actions))
+            })
+            .collect()
+    }
+
     /// Create an empty moddesc record
     fn new() -> ModDesc {
         ModDesc {
             actions: HashMap::new(),
             author: "--".to_owned(),
+            authors: vec![],
+            author_urls: vec![],
             binds: HashMap::new(),
             crop_info: CropList::new(),
+            crop_calendar: vec![],
+            crop_diff: None,
             crop_weather: None,
             depend: vec![],
+            depend_versions: BTreeMap::new(),
             desc_version: 0,
+            gallery: vec![],
             icon_file_name: None,
+            icon_info: None,
             icon_image: None,
+            links: BTreeMap::new(),
             map_config_file: None,
             map_custom_env: false,
             map_custom_crop: false,
             map_custom_grow: false,
+            map_density_missing: vec![],
+            map_growth_warnings: vec![],
             map_is_south: false,
             map_image: None,
+            map_overview: None,
             multi_player: false,
+            raw: BTreeMap::new(),
             script_files: 0,
             store_items: 0,
             version: "--".to_owned(),
@@ -219,8 +954,310 @@ impl ModDesc {
     }
 }
 
+impl Serialize for ModDesc {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ModDesc::serialize(self, serializer)
+    }
+}
+
+/// Dependencies are stored as names, their minimum versions are restored
+/// from [`ModDesc::depend_versions`]
+impl<'de> Deserialize<'de> for ModDesc {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut mod_desc = ModDesc::deserialize(deserializer)?;
+        for dependency in &mut mod_desc.depend {
+            dependency.min_version = mod_desc.depend_versions.get(&dependency.name).cloned();
+        }
+        Ok(mod_desc)
+    }
+}
+
+/// A mod this mod depends on
+///
+/// Serializes as just the short name, the JSON `depend` list is a list
+/// of strings. Required versions are serialized in
+/// [`ModDesc::depend_versions`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModDependency {
+    /// short name of the required mod
+    pub name: String,
+    /// lowest version of the required mod that will do, if given
+    pub min_version: Option<String>,
+}
+
+impl ModDependency {
+    /// Create a dependency with no version requirement
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        ModDependency {
+            name: name.to_owned(),
+            min_version: None,
+        }
+    }
+
+    /// Check if a version of the required mod is new enough
+    ///
+    /// Always true when no minimum version is given
+    #[must_use]
+    pub fn is_met_by(&self, version: &str) -> bool {
+        self.min_version
+            .as_ref()
+            .is_none_or(|min_version| compare_versions(version, min_version).is_ge())
+    }
+}
+
+impl Serialize for ModDependency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.name)
+    }
+}
+
+/// Only the name is serialized, the minimum version is restored by
+/// [`ModDesc`]
+impl<'de> Deserialize<'de> for ModDependency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ModDependency::new(&String::deserialize(deserializer)?))
+    }
+}
+
+/// Mod icon DDS header details
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModIconInfo {
+    /// block compressed (BC / DXT) format
+    pub compressed: bool,
+    /// pixel format (e.g. `DXT1`, `BC7_UNorm`)
+    pub format: String,
+    /// height in pixels
+    pub height: u32,
+    /// number of mip levels (1 is no mips)
+    pub mip_levels: u32,
+    /// width and height are both powers of two
+    pub power_of_two: bool,
+    /// width in pixels
+    pub width: u32,
+}
+
+/// Textures at or over this size (either side) are flagged as large
+pub const LARGE_TEXTURE_SIZE: u32 = 4096;
+
+/// DDS texture audit across every texture in a mod
+///
+/// Only the DDS header of each texture is read
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TextureReport {
+    /// number of DDS files checked
+    pub checked: usize,
+    /// textures at or over [`LARGE_TEXTURE_SIZE`]
+    pub large: Vec<String>,
+    /// textures with a single mip level
+    pub no_mipmaps: Vec<String>,
+    /// textures with a width or height that is not a power of two
+    pub not_power_of_two: Vec<String>,
+    /// files that could not be read as DDS
+    pub unreadable: Vec<String>,
+}
+
+impl TextureReport {
+    /// Record the header details of a single texture
+    pub fn add(&mut self, file_name: &str, info: Option<ModIconInfo>) {
+        self.checked += 1;
+        let Some(info) = info else {
+            self.unreadable.push(file_name.to_owned());
+            return;
+        };
+
+        if info.width >= LARGE_TEXTURE_SIZE || info.height >= LARGE_TEXTURE_SIZE {
+            self.large.push(file_name.to_owned());
+        }
+        if info.mip_levels <= 1 {
+            self.no_mipmaps.push(file_name.to_owned());
+        }
+        if !info.power_of_two {
+            self.not_power_of_two.push(file_name.to_owned());
+        }
+    }
+
+    /// No texture problems found
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.large.is_empty()
+            && self.no_mipmaps.is_empty()
+            && self.not_power_of_two.is_empty()
+            && self.unreadable.is_empty()
+    }
+}
+
+/// Game API touchpoints used by the lua scripts of a mod
+///
+/// Each list is sorted and free of duplicates. Hooked functions are named
+/// as written in the script, e.g. `Vehicle.onLoad`
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptApiSummary {
+    /// base game functions extended with `Utils.appendedFunction`
+    pub appended_functions: Vec<String>,
+    /// console commands added with `addConsoleCommand`
+    pub console_commands: Vec<String>,
+    /// mod event listeners (`addModEventListener`) and specialization
+    /// events (`SpecializationUtil.registerEventListener`)
+    pub event_listeners: Vec<String>,
+    /// base game functions replaced with `Utils.overwrittenFunction`
+    pub overwritten_functions: Vec<String>,
+    /// base game functions extended with `Utils.prependedFunction`
+    pub prepended_functions: Vec<String>,
+    /// number of lua files read
+    pub script_files: usize,
+    /// specializations added with `addSpecialization`
+    pub specializations: Vec<String>,
+}
+
+impl ScriptApiSummary {
+    /// Number of base game functions the scripts hook into
+    #[must_use]
+    pub fn hooked_functions(&self) -> usize {
+        self.appended_functions.len()
+            + self.overwritten_functions.len()
+            + self.prepended_functions.len()
+    }
+}
+
+/// JSON conversion of a raw modDesc XML element
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDescRawNode {
+    /// element tag name
+    pub tag: String,
+    /// element attributes
+    pub attributes: BTreeMap<String, String>,
+    /// trimmed text content, if any
+    pub text: Option<String>,
+    /// child elements, in document order
+    pub children: Vec<ModDescRawNode>,
+}
+
+impl ModDescRawNode {
+    /// Convert an XML element and everything below it
+    pub(crate) fn from_node(node: &roxmltree::Node) -> ModDescRawNode {
+        ModDescRawNode {
+            tag: node.tag_name().name().to_owned(),
+            attributes: node
+                .attributes()
+                .map(|attr| (attr.name().to_owned(), attr.value().to_owned()))
+                .collect(),
+            text: node
+                .text()
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(str::to_owned),
+            children: node
+                .children()
+                .filter(roxmltree::Node::is_element)
+                .map(|child| ModDescRawNode::from_node(&child))
+                .collect(),
+        }
+    }
+}
+
+/// Kinds of modDesc.xml structure problems
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Clone, Copy)]
+pub enum ModDescViolationKind {
+    /// root element is not `<modDesc>`
+    WrongRoot,
+    /// required element is missing
+    MissingElement,
+    /// element has no content
+    EmptyElement,
+    /// required attribute is missing
+    MissingAttribute,
+    /// element or attribute value is not valid
+    InvalidValue,
+}
+
+impl Serialize for ModDescViolationKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            ModDescViolationKind::WrongRoot => {
+                serializer.serialize_unit_variant("ModDescViolationKind", 0, "MODDESC_WRONG_ROOT")
+            }
+            ModDescViolationKind::MissingElement => serializer.serialize_unit_variant(
+                "ModDescViolationKind",
+                1,
+                "MODDESC_MISSING_ELEMENT",
+            ),
+            ModDescViolationKind::EmptyElement => serializer.serialize_unit_variant(
+                "ModDescViolationKind",
+                2,
+                "MODDESC_EMPTY_ELEMENT",
+            ),
+            ModDescViolationKind::MissingAttribute => serializer.serialize_unit_variant(
+                "ModDescViolationKind",
+                3,
+                "MODDESC_MISSING_ATTRIBUTE",
+            ),
+            ModDescViolationKind::InvalidValue => serializer.serialize_unit_variant(
+                "ModDescViolationKind",
+                4,
+                "MODDESC_INVALID_VALUE",
+            ),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ModDescViolationKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_unit_variant(
+            deserializer,
+            [
+                ModDescViolationKind::WrongRoot,
+                ModDescViolationKind::MissingElement,
+                ModDescViolationKind::EmptyElement,
+                ModDescViolationKind::MissingAttribute,
+                ModDescViolationKind::InvalidValue,
+            ],
+        )
+    }
+}
+
+/// A modDesc.xml structure problem, with its position in the file
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDescViolation {
+    /// kind of problem
+    pub kind: ModDescViolationKind,
+    /// element path, attributes as `@name` (e.g. `modDesc/multiplayer/@supported`)
+    pub path: String,
+    /// line in modDesc.xml (1 based)
+    pub line: u32,
+    /// column in modDesc.xml (1 based)
+    pub column: u32,
+    /// english explanation
+    pub message: String,
+}
+
 /// Entry for zip files inside a "mod" file.
-#[derive(serde::Serialize, PartialEq, PartialOrd, Eq, Ord, Hash, Debug)]
+#[derive(
+    serde::Serialize, serde::Deserialize, PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Clone,
+)]
 pub struct ZipPackFile {
     /// name of file (includes relative path)
     pub name: String,
@@ -228,12 +1265,56 @@ pub struct ZipPackFile {
     pub size: u64,
 }
 
+/// Audio (OGG / GLS) file in a mod
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct AudioFile {
+    /// name of file (includes relative path)
+    pub name: String,
+    /// size of file (unpacked)
+    pub size: u64,
+}
+
+/// File reference whose case differs from the file in the mod
+///
+/// Windows ignores case, so these load there but break on linux servers
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+pub struct ReferenceCaseMismatch {
+    /// file holding the reference (includes relative path)
+    pub source: String,
+    /// reference as written
+    pub reference: String,
+    /// name of the file in the mod (includes relative path)
+    pub actual: String,
+}
+
+/// File reference that points outside of the mod
+///
+/// Absolute paths and paths that climb out of the mod only work on the
+/// machine the mod was made on
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+pub struct ExternalReference {
+    /// file holding the reference (includes relative path)
+    pub source: String,
+    /// reference as written
+    pub reference: String,
+}
+
 /// File related metadata for a mod
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
+#[expect(clippy::struct_excessive_bools)]
 pub struct ModFile {
+    /// list of audio files, with sizes
+    pub audio_files: Vec<AudioFile>,
+    /// total size of audio files (unpacked)
+    pub audio_size: u64,
+    /// content hash of a folder mod (if requested), see [`crate::shared::files::folder_hash`]
+    pub content_hash: Option<String>,
     /// suggested name if this appears to be a copy of a mod
     pub copy_name: Option<String>,
+    /// file references that point outside of the mod (if requested), see
+    /// [`crate::ModParserOptions::external_references`]
+    pub external_references: Vec<ExternalReference>,
     /// list of extra files in mod
     pub extra_files: Vec<String>,
     /// mod file date
@@ -244,46 +1325,56 @@ pub struct ModFile {
     pub full_path: String,
     /// list of I3D files
     pub i3d_files: Vec<String>,
-    /// list of DDS files
-    #[serde(rename = "imageDDS")]
-    pub image_dds: Vec<String>,
-    /// list of non DDS images
-    #[serde(rename = "imageNonDDS")]
-    pub image_non_dds: Vec<String>,
+    /// DDS and PNG images, by role
+    pub images: ModImages,
+    /// disabled flag (renamed to e.g. `.zip.disabled`)
+    pub is_disabled: bool,
     /// folder flag (is this a folder?)
     pub is_folder: bool,
     /// save game flag (is this a save game?)
     pub is_save_game: bool,
     /// mod pack flag (is this a pack of mods?)
     pub is_mod_pack: bool,
-    /// list of PNG textures (false positives possible)
-    pub png_texture: Vec<String>,
     /// short name of mod (the bit before the .zip extension, or the folder name)
     pub short_name: String,
     /// list of files with spaces in them
     pub space_files: Vec<String>,
     /// list of oversized files
     pub too_big_files: Vec<String>,
-    /// list of zip files
+    /// list of zip files (mod pack contents, or the mods in a mods folder)
     pub zip_files: Vec<ZipPackFile>,
 }
 
 impl ModFile {
+    /// Every file name the record lists (I3D, images, audio and extra files)
+    fn listed_files(&self) -> BTreeSet<&str> {
+        self.i3d_files
+            .iter()
+            .chain(self.images.all())
+            .chain(&self.extra_files)
+            .map(String::as_str)
+            .chain(self.audio_files.iter().map(|n| n.name.as_str()))
+            .collect()
+    }
+
     /// Create an empty file metadata record
     fn new(file: &Path, is_folder: bool) -> ModFile {
         ModFile {
+            audio_files: vec![],
+            audio_size: 0,
+            content_hash: None,
             copy_name: None,
+            external_references: vec![],
             extra_files: vec![],
             file_date: String::new(),
             file_size: 0,
             full_path: file.to_string_lossy().to_string(),
             i3d_files: vec![],
-            image_dds: vec![],
-            image_non_dds: vec![],
+            images: ModImages::default(),
+            is_disabled: false,
             is_folder: is_folder.to_owned(),
             is_save_game: false,
             is_mod_pack: false,
-            png_texture: vec![],
             short_name: file
                 .file_stem()
                 .unwrap_or(file.as_os_str())
@@ -296,9 +1387,40 @@ impl ModFile {
     }
 }
 
+/// Images in a mod, by role
+///
+/// Roles are guessed from the file name, so false positives are possible
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModImages {
+    /// mod and store item icons (`icon` in the name, or in an `icons` folder)
+    pub icons: Vec<String>,
+    /// map overview images (`pda_map`, `overview`)
+    pub pda_maps: Vec<String>,
+    /// shop images and brand logos (`store_`, `brand`, `logo`)
+    pub store_images: Vec<String>,
+    /// every other image, textures for the 3D models
+    pub textures: Vec<String>,
+    /// terrain weight maps (`_weight`), only with
+    /// [`crate::ModParserOptions::weight_images`]
+    pub weight_maps: Vec<String>,
+}
+
+impl ModImages {
+    /// Every image, in role order
+    pub fn all(&self) -> impl Iterator<Item = &String> {
+        self.icons
+            .iter()
+            .chain(&self.pda_maps)
+            .chain(&self.store_images)
+            .chain(&self.textures)
+            .chain(&self.weight_maps)
+    }
+}
+
 /// Badge information for a mod
 #[expect(clippy::struct_excessive_bools)]
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Clone)]
 pub struct ModBadges {
     /// is broken (likely unusable)
     pub broken: bool,
@@ -332,13 +1454,10 @@ impl ModBadges {
             savegame: false,
         }
     }
-}
 
-impl Serialize for ModBadges {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    /// List of active badge names, in display order
+    #[must_use]
+    pub fn names(&self) -> Vec<String> {
         let mut name_array: Vec<String> = vec![];
         if self.broken {
             name_array.push(String::from("broken"));
@@ -364,6 +1483,91 @@ impl Serialize for ModBadges {
         if self.savegame {
             name_array.push(String::from("savegame"));
         }
-        name_array.serialize(serializer)
+        name_array
+    }
+
+    /// List of active badge labels in a language, in display order
+    ///
+    /// See [`crate::shared::l10n::badge_label`]
+    #[must_use]
+    pub fn labels(&self, lang: &str) -> Vec<&'static str> {
+        self.names()
+            .iter()
+            .filter_map(|name| badge_label(name, lang))
+            .collect()
+    }
+}
+
+/// Custom badge rule, see [`crate::ModParserOptions::badge_rules`]
+///
+/// ```rust
+/// use fs_mod_parser::shared::structs::BadgeRule;
+///
+/// let console_ready = BadgeRule::new("consoleReady", |record| {
+///     record.mod_desc.script_files == 0 && !record.badge_array.broken
+/// });
+/// ```
+#[derive(Clone)]
+pub struct BadgeRule {
+    /// badge name, as listed in [`ModRecord::custom_badges`]
+    pub name: String,
+    /// badge is active when this returns true
+    pub predicate: Arc<dyn Fn(&ModRecord) -> bool + Send + Sync>,
+}
+
+impl BadgeRule {
+    /// Create a badge rule from a name and a predicate
+    pub fn new<S, F>(name: S, predicate: F) -> BadgeRule
+    where
+        S: Into<String>,
+        F: Fn(&ModRecord) -> bool + Send + Sync + 'static,
+    {
+        BadgeRule {
+            name: name.into(),
+            predicate: Arc::new(predicate),
+        }
+    }
+
+    /// Check if the badge is active for a record
+    #[must_use]
+    pub fn matches(&self, record: &ModRecord) -> bool {
+        (self.predicate)(record)
+    }
+}
+
+impl std::fmt::Debug for BadgeRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BadgeRule")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Serialize for ModBadges {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.names().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ModBadges {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let names = Vec::<String>::deserialize(deserializer)?;
+        let has = |name: &str| names.iter().any(|n| n == name);
+        Ok(ModBadges {
+            broken: has("broken"),
+            folder: has("folder"),
+            malware: has("malware"),
+            no_mp: has("noMP"),
+            notmod: has("notmod"),
+            pconly: has("pconly"),
+            problem: has("problem"),
+            savegame: has("savegame"),
+        })
     }
 }
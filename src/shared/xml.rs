@@ -0,0 +1,196 @@
+//! Tolerant XML reading
+//!
+//! The game reads XML files that a strict parser refuses - Windows-1252
+//! text, HTML entities like `&nbsp;`, and stray `&` or `<` characters in
+//! descriptions. These helpers repair the text enough for `roxmltree` to
+//! read it, and say when they had to.
+
+/// Windows-1252 characters for bytes `0x80` to `0x9F` (the rest match
+/// Latin-1), undefined bytes are the replacement character
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{FFFD}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{FFFD}', '\u{017D}', '\u{FFFD}',
+    '\u{FFFD}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{FFFD}', '\u{017E}', '\u{0178}',
+];
+
+/// HTML entities often found in mod descriptions, with their characters
+const HTML_ENTITIES: [(&str, char); 12] = [
+    ("bull", '\u{2022}'),
+    ("copy", '\u{00A9}'),
+    ("deg", '\u{00B0}'),
+    ("euro", '\u{20AC}'),
+    ("hellip", '\u{2026}'),
+    ("laquo", '\u{00AB}'),
+    ("mdash", '\u{2014}'),
+    ("nbsp", '\u{00A0}'),
+    ("ndash", '\u{2013}'),
+    ("raquo", '\u{00BB}'),
+    ("reg", '\u{00AE}'),
+    ("trade", '\u{2122}'),
+];
+
+/// Longest entity name looked for after an `&`
+const MAX_ENTITY_LENGTH: usize = 10;
+
+/// Decode a single byte as Windows-1252
+fn windows_1252_char(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
+
+/// Decode XML file contents as UTF-8
+///
+/// Bytes that are not valid UTF-8 are read as Windows-1252, so a file
+/// saved in that encoding (or with a few pasted characters in it) keeps
+/// its accents. The flag is set if any byte needed that.
+#[must_use]
+pub fn decode_xml(bytes: Vec<u8>) -> (String, bool) {
+    let bytes = match String::from_utf8(bytes) {
+        Ok(text) => return (text, false),
+        Err(error) => error.into_bytes(),
+    };
+
+    let mut text = String::with_capacity(bytes.len());
+    let mut rest = bytes.as_slice();
+    while !rest.is_empty() {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                break;
+            }
+            Err(error) => {
+                let (valid, invalid) = rest.split_at(error.valid_up_to());
+                text.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                let bad_length = error.error_len().unwrap_or(invalid.len());
+                text.extend(invalid[..bad_length].iter().copied().map(windows_1252_char));
+                rest = &invalid[bad_length..];
+            }
+        }
+    }
+    (text, true)
+}
+
+/// Entity reference at the start of `text` (just after the `&`), as the
+/// text to keep
+///
+/// XML entities are kept, known HTML entities become their character,
+/// anything else is `None` (a stray `&`)
+fn entity_at(text: &str) -> Option<(String, usize)> {
+    let (name, _) = text
+        .get(..text.len().min(MAX_ENTITY_LENGTH + 1))
+        .unwrap_or(text)
+        .split_once(';')?;
+
+    let is_xml_entity = matches!(name, "amp" | "lt" | "gt" | "quot" | "apos")
+        || name
+            .strip_prefix("#x")
+            .is_some_and(|hex| !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        || name
+            .strip_prefix('#')
+            .is_some_and(|dec| !dec.is_empty() && dec.chars().all(|c| c.is_ascii_digit()));
+
+    if is_xml_entity {
+        return Some((format!("&{name};"), name.len() + 1));
+    }
+    HTML_ENTITIES
+        .iter()
+        .find(|(html_name, _)| *html_name == name)
+        .map(|(_, character)| (character.to_string(), name.len() + 1))
+}
+
+/// Character can follow `<` to start a tag, comment, or declaration
+fn is_tag_start(c: char) -> bool {
+    c.is_alphabetic() || matches!(c, '_' | ':' | '/' | '!' | '?')
+}
+
+/// Repair text a strict XML parser refuses
+///
+/// Known HTML entities become their characters, stray `&` and `<`
+/// characters are escaped, and control characters XML does not allow are
+/// dropped. Returns `None` if nothing needed repair.
+#[must_use]
+pub fn repair_xml(text: &str) -> Option<String> {
+    let mut repaired = String::with_capacity(text.len());
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '&' => match entity_at(&text[index + 1..]) {
+                Some((entity, skip)) => {
+                    repaired.push_str(&entity);
+                    for _ in 0..skip {
+                        chars.next();
+                    }
+                }
+                None => repaired.push_str("&amp;"),
+            },
+            '<' if !chars.peek().is_some_and(|(_, next)| is_tag_start(*next)) => {
+                repaired.push_str("&lt;");
+            }
+            '\t' | '\n' | '\r' => repaired.push(c),
+            c if u32::from(c) < 0x20 => {}
+            _ => repaired.push(c),
+        }
+    }
+
+    (repaired != text).then_some(repaired)
+}
+
+/// Read XML file contents, repairing encoding and entity problems
+///
+/// Returns the text, and if it needed repair. Text that can not be
+/// repaired is returned as decoded, the parse error is left to the caller.
+#[must_use]
+pub fn tolerant_xml(bytes: Vec<u8>) -> (String, bool) {
+    let (text, decoded) = decode_xml(bytes);
+    if roxmltree::Document::parse(&text).is_ok() {
+        return (text, decoded);
+    }
+    match repair_xml(&text) {
+        Some(repaired) if roxmltree::Document::parse(&repaired).is_ok() => (repaired, true),
+        _ => (text, decoded),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn windows_1252_text() {
+        let (text, decoded) = decode_xml(b"<l10n>Stra\xdfe \x96 \x80 5</l10n>".to_vec());
+        assert!(decoded);
+        assert_eq!(text, "<l10n>Stra\u{df}e \u{2013} \u{20ac} 5</l10n>");
+
+        let (text, decoded) = decode_xml("<l10n>Straße</l10n>".as_bytes().to_vec());
+        assert!(!decoded);
+        assert_eq!(text, "<l10n>Straße</l10n>");
+    }
+
+    #[test]
+    fn repair_entities() {
+        assert_eq!(repair_xml("<a>Fish &amp; Chips &#169; &#xA9;</a>"), None);
+        assert_eq!(
+            repair_xml("<a>Fish & Chips&nbsp;&trade; &foo;</a>").as_deref(),
+            Some("<a>Fish &amp; Chips\u{a0}\u{2122} &amp;foo;</a>")
+        );
+        assert_eq!(
+            repair_xml("<a>speed < 40\u{1}</a>").as_deref(),
+            Some("<a>speed &lt; 40</a>")
+        );
+
+        let (text, repaired) =
+            tolerant_xml(b"<modDesc><title>Caf\xe9 & Bar</title></modDesc>".to_vec());
+        assert!(repaired);
+        assert_eq!(
+            text,
+            "<modDesc><title>Caf\u{e9} &amp; Bar</title></modDesc>"
+        );
+
+        let (_, repaired) = tolerant_xml(b"<modDesc><title>Fine</title></modDesc>".to_vec());
+        assert!(!repaired);
+    }
+}
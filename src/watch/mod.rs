@@ -0,0 +1,148 @@
+//! Keep parsed records in sync with a mods folder
+//!
+//! Needs the `watch` feature. [`watch_mods`] watches a mods folder and sends
+//! a [`WatchEvent`] for every mod added, changed, or removed, parsed with
+//! the given options. Changes are collected until the folder is quiet for
+//! [`WATCH_SETTLE`], so a zip that is still being copied is parsed once.
+use crate::mod_basic::parser_with_options;
+use crate::shared::errors::FsModParserError;
+use crate::shared::structs::ModRecord;
+use crate::ModParserOptions;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+/// Quiet time before changed mods are parsed
+pub const WATCH_SETTLE: Duration = Duration::from_millis(500);
+
+/// Change to a mod in the watched folder
+pub enum WatchEvent {
+    /// Mod added or changed, with the new record
+    Changed(PathBuf, Box<ModRecord>),
+    /// Mod removed (or renamed away)
+    Removed(PathBuf),
+}
+
+/// Running watch on a mods folder, watching stops when this is dropped
+pub struct ModWatcher {
+    /// file system watcher
+    _watcher: RecommendedWatcher,
+    /// parsed changes
+    events: Receiver<WatchEvent>,
+}
+
+impl ModWatcher {
+    /// Wait for the next change
+    ///
+    /// Returns `None` if the watch stopped
+    #[must_use]
+    pub fn recv(&self) -> Option<WatchEvent> {
+        self.events.recv().ok()
+    }
+
+    /// Wait up to `timeout` for the next change
+    #[must_use]
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<WatchEvent> {
+        self.events.recv_timeout(timeout).ok()
+    }
+
+    /// Next change, if one is waiting
+    #[must_use]
+    pub fn try_recv(&self) -> Option<WatchEvent> {
+        self.events.try_recv().ok()
+    }
+}
+
+/// Watch a mods folder, sending re-parsed records as mods change
+///
+/// Zip files and unzipped mod folders directly in `mod_folder` are
+/// watched, a change to any file inside an unzipped mod re-parses that mod.
+/// Parsing runs on a background thread.
+///
+/// # Errors
+///
+/// Returns a [`FsModParserError`] when the folder is missing or can't be
+/// watched
+pub fn watch_mods<P: AsRef<Path>>(
+    mod_folder: P,
+    options: ModParserOptions,
+) -> Result<ModWatcher, FsModParserError> {
+    let mod_folder = mod_folder.as_ref();
+    FsModParserError::check_path(mod_folder)?;
+    let mod_folder = mod_folder.canonicalize()?;
+
+    let (path_sender, path_receiver) = channel::<PathBuf>();
+    let (event_sender, events) = channel();
+
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        if let Ok(event) = result {
+            for path in event.paths {
+                let _ = path_sender.send(path);
+            }
+        }
+    })
+    .map_err(std::io::Error::other)?;
+    watcher
+        .watch(&mod_folder, RecursiveMode::Recursive)
+        .map_err(std::io::Error::other)?;
+
+    std::thread::spawn(move || {
+        watch_worker(&mod_folder, &options, &path_receiver, &event_sender);
+    });
+
+    Ok(ModWatcher {
+        _watcher: watcher,
+        events,
+    })
+}
+
+/// Collect changed paths until the folder settles, then parse the changed mods
+fn watch_worker(
+    mod_folder: &Path,
+    options: &ModParserOptions,
+    paths: &Receiver<PathBuf>,
+    events: &Sender<WatchEvent>,
+) {
+    while let Ok(first) = paths.recv() {
+        let mut changed: BTreeSet<PathBuf> = mod_entry(mod_folder, &first).into_iter().collect();
+
+        while let Ok(path) = paths.recv_timeout(WATCH_SETTLE) {
+            changed.extend(mod_entry(mod_folder, &path));
+        }
+
+        for path in changed {
+            let event = if path.exists() {
+                let record = parser_with_options(&path, options);
+                WatchEvent::Changed(path, Box::new(record))
+            } else {
+                WatchEvent::Removed(path)
+            };
+            if events.send(event).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Mod a changed path belongs to
+///
+/// That is the zip file or folder directly in the mods folder. Other files
+/// there are skipped. A removed entry without an extension is taken to be
+/// a mod folder.
+fn mod_entry(mod_folder: &Path, path: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix(mod_folder).ok()?;
+    let entry = mod_folder.join(relative.components().next()?);
+
+    if relative.components().count() > 1 {
+        return entry.is_dir().then_some(entry);
+    }
+
+    let is_zip = entry
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+    let is_folder = entry.is_dir() || (!entry.exists() && entry.extension().is_none());
+
+    (is_zip || is_folder).then_some(entry)
+}
@@ -1,8 +1,62 @@
 use assert_json_diff::{assert_json_eq, assert_json_include};
+use fs_mod_parser::metrics::CounterMetrics;
 use fs_mod_parser::mod_basic::parser;
-use fs_mod_parser::shared::structs::{ModBadges, ModRecord};
+use fs_mod_parser::mod_detail::structs::ModDetailError;
+use fs_mod_parser::shared::errors::ModError;
+use fs_mod_parser::shared::files::{FolderHashMode, ReadLimits};
+use fs_mod_parser::shared::structs::{BadgeRule, ModBadges, ModDependency, ModRecord, ModSource};
+use fs_mod_parser::{parse_mod_with_options, ModParserOptions};
 use serde_json::json;
+use std::collections::HashSet;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn mod_record_round_trip() {
+    let options = ModParserOptions {
+        include_mod_detail: true,
+        skip_detail_icons: true,
+        ..Default::default()
+    };
+    let mod_record = parse_mod_with_options("./tests/test_mods/PASS_Good_Simple_Mod.zip", &options);
+
+    let stored: ModRecord = serde_json::from_str(&mod_record.to_json()).unwrap();
+    assert_eq!(stored.issues, mod_record.issues);
+    assert_eq!(stored.badge_array, mod_record.badge_array);
+    assert_eq!(stored.file_detail.images, mod_record.file_detail.images);
+    assert_eq!(json!(stored.mod_desc), json!(mod_record.mod_desc));
+    assert_eq!(json!(stored.l10n), json!(mod_record.l10n));
+    assert_eq!(
+        json!(stored.include_detail.as_ref().map(|n| &n.vehicles)),
+        json!(mod_record.include_detail.as_ref().map(|n| &n.vehicles))
+    );
+
+    let copy = stored.clone();
+    assert_eq!(copy.file_detail.short_name, "PASS_Good_Simple_Mod");
+}
+
+#[test]
+fn mod_record_round_trip_dependency_versions() {
+    let mut mod_record = ModRecord::new("FS22_Example.zip", false);
+    mod_record.mod_desc.depend = vec![
+        ModDependency {
+            name: String::from("FS22_Versioned"),
+            min_version: Some(String::from("2.0")),
+        },
+        ModDependency::new("FS22_Plain"),
+    ];
+    mod_record
+        .mod_desc
+        .depend_versions
+        .insert(String::from("FS22_Versioned"), String::from("2.0"));
+
+    let stored: ModRecord = serde_json::from_str(&mod_record.to_json()).unwrap();
+    assert_eq!(stored.mod_desc.depend, mod_record.mod_desc.depend);
+    assert_eq!(json!(stored), json!(mod_record));
+    assert!(!stored.mod_desc.depend[0].is_met_by("1.0"));
+    assert!(stored.mod_desc.depend[1].is_met_by("1.0"));
+}
 
 #[test]
 fn check_json_mod_record() {
@@ -24,12 +78,16 @@ fn check_json_mod_record() {
             "fileSize": 0,
             "fullPath": "foo.txt",
             "i3dFiles": [],
-            "imageDDS": [],
-            "imageNonDDS": [],
+            "images": {
+                "icons": [],
+                "pdaMaps": [],
+                "storeImages": [],
+                "textures": [],
+                "weightMaps": []
+            },
             "isFolder": false,
             "isModPack": false,
             "isSaveGame": false,
-            "pngTexture": [],
             "shortName": "foo",
             "spaceFiles": [],
             "tooBigFiles": [],
@@ -67,12 +125,391 @@ fn check_json_mod_record() {
             "mapImage": null,
             "multiPlayer": false,
             "version": "--"
-        }
+        },
+        "source": "unknown"
     });
 
     assert_json_include!(actual : json!(mod_record), expected : expected);
 }
 
+#[test]
+fn time_limit_skips_optional_stages() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Good_Simple_Mod.zip");
+    assert!(test_file_path.exists());
+
+    let options = ModParserOptions {
+        include_mod_detail: true,
+        time_limit: Some(Duration::ZERO),
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert!(mod_record.issues.contains(&ModError::InfoTimedOut));
+    assert_eq!(mod_record.mod_desc.icon_image, None);
+    assert!(mod_record.include_detail.is_none());
+    assert_eq!(mod_record.file_detail.short_name, "PASS_Good_Simple_Mod");
+}
+
+#[test]
+fn time_limit_keeps_malware_scan() {
+    let test_file_path = Path::new("./tests/test_mods/WARNING_Malicious_Code.zip");
+    assert!(test_file_path.exists());
+
+    let options = ModParserOptions {
+        time_limit: Some(Duration::ZERO),
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert!(mod_record.issues.contains(&ModError::InfoTimedOut));
+    assert!(mod_record.issues.contains(&ModError::InfoMaliciousCode));
+    assert!(mod_record.badge_array.malware);
+}
+
+#[test]
+fn slim_output() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Good_Simple_Mod.zip");
+    assert!(test_file_path.exists());
+
+    let options = ModParserOptions {
+        include_mod_detail: true,
+        slim_output: true,
+        badge_rules: vec![BadgeRule::new("hasImages", |record| {
+            !record.file_detail.images.icons.is_empty()
+        })],
+        ..Default::default()
+    };
+
+    let full_record = parser(test_file_path);
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+
+    assert_eq!(mod_record.custom_badges, vec!["hasImages"]);
+    assert_eq!(mod_record.mod_desc.icon_image, None);
+    assert!(mod_record.file_detail.images.all().next().is_none());
+    assert!(mod_record.file_detail.i3d_files.is_empty());
+    assert!(mod_record.l10n.description.is_empty());
+    assert!(mod_record.include_detail.is_none());
+    assert_eq!(mod_record.l10n.title, full_record.l10n.title);
+    assert_eq!(mod_record.issues, full_record.issues);
+    assert!(mod_record.to_json().len() < full_record.to_json().len());
+}
+
+#[test]
+fn texture_report() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Good_Simple_Mod.zip");
+    assert!(test_file_path.exists());
+
+    let mod_record = parser(test_file_path);
+    assert!(mod_record.texture_report.is_none());
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        texture_report: true,
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    let report = mod_record.texture_report.unwrap();
+    assert_eq!(report.checked, 1);
+    assert!(report.large.is_empty());
+    assert!(report.not_power_of_two.is_empty());
+    assert!(report.unreadable.is_empty());
+}
+
+#[test]
+fn custom_badge_rules() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Good_Simple_Mod.zip");
+    assert!(test_file_path.exists());
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        badge_rules: vec![
+            BadgeRule::new("consoleReady", |record| {
+                record.mod_desc.script_files == 0 && !record.badge_array.broken
+            }),
+            BadgeRule::new("mapMod", |record| record.mod_desc.map_config_file.is_some()),
+            BadgeRule::new("allItems", |record| record.mod_desc.store_items > 0),
+            BadgeRule::new("consoleReady", |_| true),
+        ],
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert_eq!(mod_record.custom_badges, vec!["allItems", "consoleReady"]);
+    assert_eq!(
+        json!(mod_record.to_summary())["customBadges"],
+        json!(["allItems", "consoleReady"])
+    );
+
+    let mod_record = parser(test_file_path);
+    assert!(mod_record.custom_badges.is_empty());
+}
+
+#[test]
+fn gallery_images() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Gallery_Images");
+    assert!(test_file_path.exists());
+
+    let mod_record = parser(test_file_path);
+    assert!(mod_record.mod_desc.gallery.is_empty());
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        gallery_images: 5,
+        ..Default::default()
+    };
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+
+    // images/farm.png from modDesc.xml, screenshots/field.dds by name
+    #[cfg(feature = "images")]
+    {
+        assert_eq!(mod_record.mod_desc.gallery.len(), 2);
+        assert!(mod_record
+            .mod_desc
+            .gallery
+            .iter()
+            .all(|image| image.starts_with("data:image/webp;base64, ")));
+    }
+    #[cfg(not(feature = "images"))]
+    assert!(mod_record.mod_desc.gallery.is_empty());
+}
+
+#[test]
+fn reference_case() {
+    let test_file_path = Path::new("./tests/test_mods/WARNING_Reference_Case");
+    assert!(test_file_path.exists());
+
+    let mod_record = parser(test_file_path);
+    assert!(mod_record.reference_case.is_none());
+    assert!(!mod_record
+        .issues
+        .contains(&ModError::FileErrorReferenceCase));
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        reference_case: true,
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert!(mod_record
+        .issues
+        .contains(&ModError::FileErrorReferenceCase));
+
+    let mismatches: Vec<(&str, &str, &str)> = mod_record
+        .reference_case
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|n| (n.source.as_str(), n.reference.as_str(), n.actual.as_str()))
+        .collect();
+    assert_eq!(
+        mismatches,
+        vec![
+            (
+                "items/dolly.i3d",
+                "Textures/diffuse.dds",
+                "items/textures/diffuse.dds"
+            ),
+            ("items/dolly.xml", "Items/Dolly.i3d", "items/dolly.i3d"),
+            ("modDesc.xml", "Items/Dolly.xml", "items/dolly.xml"),
+        ]
+    );
+
+    let clean = parse_mod_with_options("./tests/test_mods/PASS_Good_Simple_Mod.zip", &options);
+    assert_eq!(clean.reference_case, Some(vec![]));
+}
+
+#[test]
+fn external_references() {
+    let test_file_path = Path::new("./tests/test_mods/WARNING_External_Reference");
+    assert!(test_file_path.exists());
+
+    let references = |mod_record: &ModRecord| -> Vec<(String, String)> {
+        mod_record
+            .file_detail
+            .external_references
+            .iter()
+            .map(|n| (n.source.clone(), n.reference.clone()))
+            .collect()
+    };
+
+    let mod_record = parser(test_file_path);
+    assert!(references(&mod_record).is_empty());
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        external_references: true,
+        ..Default::default()
+    };
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert!(mod_record
+        .issues
+        .contains(&ModError::FileErrorExternalReference));
+    assert_eq!(
+        references(&mod_record),
+        vec![(
+            String::from("modDesc.xml"),
+            String::from("../textures/vehicleSchema.dds")
+        )]
+    );
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        include_mod_detail: true,
+        reference_case: true,
+        external_references: true,
+        ..Default::default()
+    };
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert_eq!(
+        references(&mod_record),
+        vec![
+            (
+                String::from("items/trailer.i3d"),
+                String::from("../../outside/normal.dds")
+            ),
+            (
+                String::from("items/trailer.xml"),
+                String::from("../shared/materials.xml")
+            ),
+            (
+                String::from("items/trailer.xml"),
+                String::from("D:\\work\\store_trailer.png")
+            ),
+            (
+                String::from("modDesc.xml"),
+                String::from("../textures/vehicleSchema.dds")
+            ),
+        ]
+    );
+
+    let detail = mod_record.include_detail.unwrap();
+    assert!(detail
+        .issues
+        .contains(&ModDetailError::StoreItemExternalReference));
+    assert!(detail.external_references.is_empty());
+
+    let clean = parse_mod_with_options("./tests/test_mods/PASS_Good_Simple_Mod.zip", &options);
+    assert!(clean.file_detail.external_references.is_empty());
+}
+
+#[test]
+fn folder_content_hash() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Good_Simple_Mod");
+    assert!(test_file_path.exists());
+
+    let mod_record = parser(test_file_path);
+    assert_eq!(mod_record.file_detail.content_hash, None);
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        folder_hash: FolderHashMode::Content,
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert_eq!(
+        mod_record.file_detail.content_hash,
+        Some(String::from("9936381c34242fd9a7dbf61f3a1f5c83"))
+    );
+
+    let zip_record = parse_mod_with_options("./tests/test_mods/PASS_Good_Simple_Mod.zip", &options);
+    assert_eq!(zip_record.file_detail.content_hash, None);
+}
+
+#[test]
+fn modhub_hash_lookup() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Good_Simple_Mod.zip");
+    assert!(test_file_path.exists());
+
+    let file_hash = format!("{:x}", md5::compute(std::fs::read(test_file_path).unwrap()));
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        modhub_hashes: Some(HashSet::from([file_hash.clone()])),
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert_eq!(mod_record.source, ModSource::ModHub);
+    assert_eq!(mod_record.md5_sum, Some(file_hash));
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        modhub_hashes: Some(HashSet::new()),
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert_eq!(mod_record.source, ModSource::ThirdParty);
+}
+
+#[test]
+fn disabled_mod_suffix() {
+    let work_dir =
+        std::env::temp_dir().join(format!("fs_mod_parser_disabled_{}", std::process::id()));
+    std::fs::create_dir_all(&work_dir).unwrap();
+    let test_file_path = work_dir.join("PASS_Good_Simple_Mod.zip.disabled");
+    std::fs::copy(
+        "./tests/test_mods/PASS_Good_Simple_Mod.zip",
+        &test_file_path,
+    )
+    .unwrap();
+
+    let mod_record = parse_mod_with_options(&test_file_path, &ModParserOptions::default());
+    assert!(mod_record.can_not_use);
+    assert!(mod_record.issues.contains(&ModError::FileErrorGarbageFile));
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        include_disabled: true,
+        ..Default::default()
+    };
+    let mod_record = parse_mod_with_options(&test_file_path, &options);
+    assert!(!mod_record.can_not_use);
+    assert!(mod_record.file_detail.is_disabled);
+    assert_eq!(mod_record.file_detail.short_name, "PASS_Good_Simple_Mod");
+    assert_eq!(mod_record.mod_desc.version, "1.0.0.0");
+
+    std::fs::remove_dir_all(&work_dir).unwrap();
+}
+
+#[test]
+fn read_limit_issue() {
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        read_limits: ReadLimits {
+            per_file: Some(64),
+            per_mod: None,
+        },
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options("./tests/test_mods/PASS_Good_Simple_Mod.zip", &options);
+    assert!(mod_record.can_not_use);
+    assert!(mod_record.issues.contains(&ModError::PerformanceReadLimit));
+}
+
+#[test]
+fn metrics_counters() {
+    let metrics = Arc::new(CounterMetrics::new());
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        metrics: Some(metrics.clone()),
+        ..Default::default()
+    };
+
+    let good_record =
+        parse_mod_with_options("./tests/test_mods/PASS_Good_Simple_Mod.zip", &options);
+    parse_mod_with_options("./tests/test_mods/FAILURE_Missing_ModDesc.zip", &options);
+
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.mods_parsed, 2);
+    assert!(snapshot.bytes_processed > good_record.file_detail.file_size);
+    assert_eq!(snapshot.cache_hits, 0);
+    assert_eq!(snapshot.issues.get("NOT_MOD_MODDESC_MISSING"), Some(&1));
+}
+
 #[test]
 fn check_json_badges() {
     let mod_badges = ModBadges {
@@ -116,6 +553,7 @@ fn simple_good_mod_unzipped() {
         }
     );
 
+    #[cfg(feature = "images")]
     assert_ne!(mod_record.mod_desc.icon_image, None);
 
     let expected = json!({
@@ -126,14 +564,16 @@ fn simple_good_mod_unzipped() {
             "copyName": null,
             "extraFiles": [],
             "i3dFiles": [],
-            "imageDDS": [
-                "modIcon.dds"
-            ],
-            "imageNonDDS": [],
+            "images": {
+                "icons": [ "modIcon.dds" ],
+                "pdaMaps": [],
+                "storeImages": [],
+                "textures": [],
+                "weightMaps": []
+            },
             "isFolder": true,
             "isSaveGame": false,
             "isModPack": false,
-            "pngTexture": [],
             "shortName": "PASS_Good_Simple_Mod",
             "spaceFiles": [],
             "tooBigFiles": [],
@@ -197,6 +637,7 @@ fn simple_good_mod() {
         }
     );
 
+    #[cfg(feature = "images")]
     assert_ne!(mod_record.mod_desc.icon_image, None);
 
     let expected = json!({
@@ -208,14 +649,16 @@ fn simple_good_mod() {
             "extraFiles": [],
             "fileSize": 12530,
             "i3dFiles": [],
-            "imageDDS": [
-                "modIcon.dds"
-            ],
-            "imageNonDDS": [],
+            "images": {
+                "icons": [ "modIcon.dds" ],
+                "pdaMaps": [],
+                "storeImages": [],
+                "textures": [],
+                "weightMaps": []
+            },
             "isFolder": false,
             "isSaveGame": false,
             "isModPack": false,
-            "pngTexture": [],
             "shortName": "PASS_Good_Simple_Mod",
             "spaceFiles": [],
             "tooBigFiles": [],
@@ -280,6 +723,7 @@ fn xml_recover() {
         }
     );
 
+    #[cfg(feature = "images")]
     assert_ne!(mod_record.mod_desc.icon_image, None);
 
     let expected = json!({
@@ -291,14 +735,16 @@ fn xml_recover() {
             "extraFiles": [],
             "fileSize": 12541,
             "i3dFiles": [],
-            "imageDDS": [
-                "modIcon.dds"
-            ],
-            "imageNonDDS": [],
+            "images": {
+                "icons": [ "modIcon.dds" ],
+                "pdaMaps": [],
+                "storeImages": [],
+                "textures": [],
+                "weightMaps": []
+            },
             "isFolder": false,
             "isSaveGame": false,
             "isModPack": false,
-            "pngTexture": [],
             "shortName": "PASS_Invalid_XML",
             "spaceFiles": [],
             "tooBigFiles": [],
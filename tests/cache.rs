@@ -0,0 +1,85 @@
+use fs_mod_parser::cache::ParseCache;
+use fs_mod_parser::metrics::CounterMetrics;
+use fs_mod_parser::{parse_mod_cached, ModParserOptions};
+use std::path::Path;
+use std::sync::Arc;
+
+#[test]
+fn cached_unchanged_mod() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Good_Simple_Mod.zip");
+    assert!(test_file_path.exists());
+
+    let metrics = Arc::new(CounterMetrics::new());
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        metrics: Some(metrics.clone()),
+        ..Default::default()
+    };
+    let mut cache = ParseCache::new();
+
+    let first = parse_mod_cached(test_file_path, &options, &mut cache);
+    let second = parse_mod_cached(test_file_path, &options, &mut cache);
+
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(cache.len(), 1);
+    assert_eq!(metrics.snapshot().mods_parsed, 1);
+    assert_eq!(metrics.snapshot().cache_hits, 1);
+
+    cache.remove(test_file_path);
+    let third = parse_mod_cached(test_file_path, &options, &mut cache);
+    assert!(!Arc::ptr_eq(&first, &third));
+    assert_eq!(metrics.snapshot().mods_parsed, 2);
+}
+
+#[test]
+fn cached_changed_options() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Good_Simple_Mod.zip");
+    assert!(test_file_path.exists());
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        ..Default::default()
+    };
+    let detail_options = ModParserOptions {
+        skip_mod_icons: true,
+        include_mod_detail: true,
+        ..Default::default()
+    };
+    let mut cache = ParseCache::new();
+
+    let first = parse_mod_cached(test_file_path, &options, &mut cache);
+    let second = parse_mod_cached(test_file_path, &detail_options, &mut cache);
+
+    assert!(!Arc::ptr_eq(&first, &second));
+    assert!(first.include_detail.is_none());
+    assert!(second.include_detail.is_some());
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn cached_changed_mod() {
+    let work_dir = std::env::temp_dir().join(format!("fs_mod_parser_cache_{}", std::process::id()));
+    std::fs::create_dir_all(&work_dir).unwrap();
+    let test_file_path = work_dir.join("FS22_Cache_Test.zip");
+    std::fs::copy(
+        "./tests/test_mods/PASS_Good_Simple_Mod.zip",
+        &test_file_path,
+    )
+    .unwrap();
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        ..Default::default()
+    };
+    let mut cache = ParseCache::new_hashed();
+
+    let first = parse_mod_cached(&test_file_path, &options, &mut cache);
+    assert!(!first.can_not_use);
+
+    std::fs::write(&test_file_path, b"not a zip file").unwrap();
+    let second = parse_mod_cached(&test_file_path, &options, &mut cache);
+    assert!(!Arc::ptr_eq(&first, &second));
+    assert!(second.can_not_use);
+
+    std::fs::remove_dir_all(&work_dir).unwrap();
+}
@@ -1,6 +1,13 @@
 use assert_json_diff::{assert_json_eq, assert_json_include};
-use fs_mod_parser::mod_detail::structs::{ModDetail, ModDetailError};
-use fs_mod_parser::{parse_detail, parse_mod_with_options, parse_detail_with_options, ModParserOptions};
+use fs_mod_parser::maps::structs::GrowthPeriodMode;
+use fs_mod_parser::mod_detail::structs::{
+    ModDetail, ModDetailError, ModDetailPlanCost, MotorUnits,
+};
+use fs_mod_parser::shared::files::{FolderHashMode, ReadLimits};
+use fs_mod_parser::{
+    parse_detail, parse_detail_with_options, parse_mod_with_options, plan_detail,
+    plan_detail_with_options, ModParserOptions,
+};
 use serde_json::json;
 use std::collections::HashSet;
 use std::path::Path;
@@ -10,6 +17,36 @@ static NO_ICONS: ModParserOptions = ModParserOptions {
     include_save_game: false,
     skip_detail_icons: true,
     skip_mod_icons: false,
+    include_disabled: false,
+    languages: None,
+    modhub_hashes: None,
+    time_limit: None,
+    raw_mod_desc_tags: None,
+    detail_threads: 0,
+    detail_diagnostics: false,
+    read_limits: ReadLimits {
+        per_file: None,
+        per_mod: None,
+    },
+    metrics: None,
+    growth_periods: GrowthPeriodMode::Wrap,
+    texture_report: false,
+    full_map_overview: false,
+    reference_case: false,
+    external_references: false,
+    store_item_dlcs: false,
+    script_api: false,
+    folder_hash: FolderHashMode::Off,
+    save_game_statistics: false,
+    motor_units: MotorUnits::Both,
+    map_image_size: None,
+    map_farmlands: false,
+    map_density_check: false,
+    gallery_images: 0,
+    weight_images: false,
+    allowed_extra_files: Vec::new(),
+    slim_output: false,
+    badge_rules: Vec::new(),
 };
 
 static PATH_TO_GOOD: &str = "./tests/test_mods/DETAIL_Samples.zip";
@@ -29,6 +66,17 @@ fn missing_file() {
     assert!(detail_record.to_json_pretty().len() > 10);
 }
 
+#[test]
+fn detail_round_trip() {
+    let detail_record = parse_detail_with_options(PATH_TO_GOOD, &NO_ICONS);
+
+    let stored: ModDetail = serde_json::from_str(&detail_record.to_json()).unwrap();
+    assert_eq!(stored.issues, detail_record.issues);
+    assert_eq!(json!(stored.brands), json!(detail_record.brands));
+    assert_eq!(json!(stored.placeables), json!(detail_record.placeables));
+    assert_eq!(json!(stored.vehicles), json!(detail_record.vehicles));
+}
+
 #[test]
 fn invalid_folder() {
     let test_file_path = Path::new("./tests/test_mods/FAILURE_Invalid_Folder");
@@ -93,7 +141,7 @@ fn good_store_items_overview() {
     assert_eq!(detail_record.item_categories, expect_cat);
 
     let byte_length = mod_record_json.len() as i32;
-    let byte_expected: i32 = 32075;
+    let byte_expected: i32 = 114839;
     let byte_margin = 100;
     assert!(
         (byte_length - byte_expected).abs() < byte_margin,
@@ -141,7 +189,7 @@ fn good_store_items_overview_full() {
     assert_eq!(detail_record.item_categories, expect_cat);
 
     let byte_length = detail_record.to_json_pretty().len() as i32;
-    let byte_expected: i32 = 108433;
+    let byte_expected: i32 = 184588;
     let byte_margin = 500;
     assert!(
         (byte_length - byte_expected).abs() < byte_margin,
@@ -154,6 +202,59 @@ fn good_store_items_overview_full() {
     );
 }
 
+#[test]
+fn good_store_items_plan() {
+    let plan = plan_detail(Path::new(PATH_TO_GOOD));
+
+    assert_eq!(plan.issues.len(), 0);
+    assert_eq!(plan.store_items, 7);
+    assert_eq!(plan.missing_store_items, 0);
+    assert_eq!(plan.store_item_bytes, 269_571);
+    assert_eq!(plan.brands, 2);
+    assert_eq!(plan.icons, 8);
+    assert_eq!(plan.cost, ModDetailPlanCost::Light);
+
+    let plan = plan_detail_with_options(Path::new(PATH_TO_GOOD), &NO_ICONS);
+    assert_eq!(plan.icons, 0);
+    assert_eq!(plan.cost, ModDetailPlanCost::Light);
+}
+
+#[test]
+fn bad_store_items_plan() {
+    let plan = plan_detail(Path::new(PATH_TO_BAD));
+
+    let expected_errors: HashSet<ModDetailError> =
+        HashSet::from([ModDetailError::StoreItemMissing]);
+    assert_eq!(plan.issues, expected_errors);
+    assert_eq!(plan.store_items, 2);
+    assert_eq!(plan.missing_store_items, 1);
+    assert_eq!(plan.store_item_bytes, 5);
+    assert_eq!(plan.icons, 2);
+}
+
+#[test]
+fn missing_file_plan() {
+    let plan = plan_detail(Path::new("./tests/test_mods/DETAIL_Fake_File.zip"));
+
+    assert!(plan.issues.contains(&ModDetailError::FileReadFail));
+    assert_eq!(plan.store_items, 0);
+}
+
+#[test]
+fn good_store_items_order() {
+    let test_file_path = Path::new("./tests/test_mods/DETAIL_Store_Order");
+    assert!(test_file_path.exists());
+
+    let detail_record = parse_detail_with_options(test_file_path, &NO_ICONS);
+    assert_eq!(detail_record.vehicles.len(), 3);
+
+    let expected_order: Vec<String> = vec![
+        String::from("xml/zetor.xml"),
+        String::from("xml/bale.xml"),
+        String::from("xml/mower.xml"),
+    ];
+    assert_eq!(detail_record.item_order, expected_order);
+}
 
 fn setup_good_store_items() -> ModDetail {
     let test_file_path = Path::new(PATH_TO_GOOD);
@@ -163,6 +264,75 @@ fn setup_good_store_items() -> ModDetail {
     detail_record
 }
 
+#[test]
+fn threaded_store_items_match_serial() {
+    let test_file_path = Path::new(PATH_TO_GOOD);
+    let threaded_options = ModParserOptions {
+        include_mod_detail: true,
+        skip_detail_icons: true,
+        detail_threads: 3,
+        ..Default::default()
+    };
+
+    let serial: serde_json::Value =
+        serde_json::from_str(&parse_detail_with_options(test_file_path, &NO_ICONS).to_json())
+            .unwrap();
+    let threaded: serde_json::Value = serde_json::from_str(
+        &parse_detail_with_options(test_file_path, &threaded_options).to_json(),
+    )
+    .unwrap();
+
+    assert!(serial["itemOrder"].as_array().unwrap().len() > 3);
+    assert_json_eq!(sorted_sets(serial), sorted_sets(threaded));
+}
+
+#[test]
+fn store_item_diagnostics() {
+    let test_file_path = Path::new(PATH_TO_GOOD);
+
+    assert!(parse_detail_with_options(test_file_path, &NO_ICONS)
+        .diagnostics
+        .is_none());
+
+    for detail_threads in [0, 3] {
+        let options = ModParserOptions {
+            include_mod_detail: true,
+            skip_detail_icons: true,
+            detail_threads,
+            detail_diagnostics: true,
+            ..Default::default()
+        };
+        let detail_record = parse_detail_with_options(test_file_path, &options);
+        let diagnostics = detail_record.diagnostics.unwrap();
+
+        assert_eq!(diagnostics.items_parsed, 7);
+        assert!(diagnostics.panicked_items.is_empty());
+        assert_eq!(diagnostics.slowest_items.len(), 7);
+        assert!(diagnostics
+            .slowest_items
+            .windows(2)
+            .all(|pair| pair[0].micros >= pair[1].micros));
+        assert!(
+            diagnostics.total_micros
+                >= diagnostics
+                    .slowest_items
+                    .iter()
+                    .map(|item| item.micros)
+                    .sum::<u64>()
+        );
+    }
+}
+
+/// Sort the `HashSet` backed lists so records can be compared
+fn sorted_sets(mut record: serde_json::Value) -> serde_json::Value {
+    for key in ["issues", "itemBrands", "itemCategories"] {
+        if let Some(list) = record[key].as_array_mut() {
+            list.sort_by_key(ToString::to_string);
+        }
+    }
+    record
+}
+
 #[test]
 fn good_store_l10n() {
     /* cSpell: disable */
@@ -212,11 +382,29 @@ fn good_place_husbandry() {
                 "husbandryExists": true,
                 "husbandryType": "CHICKEN"
             },
+            "i3dFile": "ChickenBarn.i3d",
             "iconBase": null,
             "iconFile": null,
             "iconOrig": null,
             "masterType": "placeable",
             "parentItem": null,
+            "placement": {
+                "clearAreas": 1,
+                "dailyUpkeep": 50,
+                "groundTypes": ["dirt"],
+                "levelAreas": 1,
+                "maxSlope": 75.0,
+                "paintAreas": 0,
+                "requireLeveling": true,
+                "testAreas": 1
+            },
+            "power": {
+                "exists": false,
+                "incomePerHour": 0,
+                "incomeCurve": [],
+                "kind": null,
+                "storageCapacity": null
+            },
             "productions": [],
             "showInStore": true,
             "sorting": {
@@ -228,6 +416,11 @@ fn good_place_husbandry() {
                 "incomePerHour": 0,
                 "name": "$l10n_storeItem_animalBarnChickenBig",
                 "price": 150000,
+                "shopCategory": {
+                    "subCategory": "animalpens",
+                    "subCategoryName": "Animal Pens",
+                    "topLevel": "placeables"
+                },
                 "typeName": "chickenHusbandry"
             },
             "storage": {
@@ -425,6 +618,7 @@ fn good_vehicle_fill_unit() {
                 "enterable": false,
                 "lights": true,
                 "motorized": false,
+                "passengerSeats": false,
                 "wheels": false
             },
             "iconBase": null,
@@ -488,6 +682,7 @@ fn good_vehicle_sprayer_types() {
                 "enterable": false,
                 "lights": true,
                 "motorized": false,
+                "passengerSeats": false,
                 "wheels": false
             },
             "iconBase": null,
@@ -559,9 +754,12 @@ fn bad_store_items_overview() {
     let expected_errors: HashSet<ModDetailError> = HashSet::from([
         ModDetailError::BrandMissingIcon,
         ModDetailError::StoreItemBroken,
+        ModDetailError::StoreItemCountMismatch,
         ModDetailError::StoreItemMissing,
     ]);
     assert_eq!(detail_record.issues, expected_errors);
+    assert_eq!(detail_record.item_count.parsed, 0);
+    assert!(detail_record.item_count.declared > 0);
 
     assert_eq!(detail_record.brands.len(), 2);
     assert_eq!(detail_record.l10n.len(), 2);
@@ -569,7 +767,7 @@ fn bad_store_items_overview() {
     assert_eq!(detail_record.vehicles.len(), 0);
 
     let byte_length = detail_record.to_json_pretty().len() as i32;
-    let byte_expected: i32 = 1497;
+    let byte_expected: i32 = 1894;
     let byte_margin = 100;
     assert!(
         (byte_length - byte_expected).abs() < byte_margin,
@@ -604,6 +802,7 @@ fn good_vehicle_parent_item() {
                 "enterable": false,
                 "lights": false,
                 "motorized": false,
+                "passengerSeats": false,
                 "wheels": false
             },
             "iconBase": null,
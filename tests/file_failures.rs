@@ -1,6 +1,7 @@
 use fs_mod_parser::mod_basic::parser;
 use fs_mod_parser::shared::errors::ModError;
-use fs_mod_parser::shared::structs::ModBadges;
+use fs_mod_parser::shared::structs::{ModBadges, ModDescViolationKind};
+use fs_mod_parser::{try_parse_detail, try_parse_mod, try_parse_savegame, FsModParserError};
 use std::collections::HashSet;
 use std::path::Path;
 
@@ -32,6 +33,42 @@ fn broken_zip_file() {
     );
 }
 
+#[test]
+fn try_parse_errors() {
+    let missing = Path::new("./tests/test_mods/FAILURE_Fake_File.zip");
+    assert!(!missing.exists());
+    assert!(matches!(
+        try_parse_mod(missing),
+        Err(FsModParserError::NotFound(path)) if path == missing
+    ));
+    assert!(matches!(
+        try_parse_savegame(missing),
+        Err(FsModParserError::NotFound(..))
+    ));
+    assert!(matches!(
+        try_parse_detail(missing),
+        Err(FsModParserError::NotFound(..))
+    ));
+
+    let broken = Path::new("./tests/test_mods/FAILURE_Broken_Zip_File.zip");
+    let Err(error) = try_parse_mod(broken) else {
+        panic!("broken zip file parsed");
+    };
+    assert!(matches!(error, FsModParserError::Unreadable(..)));
+    assert!(error.to_string().contains("FAILURE_Broken_Zip_File.zip"));
+    assert!(matches!(
+        try_parse_savegame(broken),
+        Err(FsModParserError::Unreadable(..))
+    ));
+    assert!(matches!(
+        try_parse_detail(broken),
+        Err(FsModParserError::Unreadable(..))
+    ));
+
+    let mod_record = try_parse_mod("./tests/test_mods/FAILURE_Missing_ModDesc.zip").unwrap();
+    assert!(mod_record.issues.contains(&ModError::ModDescMissing));
+}
+
 #[test]
 fn bad_crc_moddesc() {
     let test_file_path = Path::new("./tests/test_mods/FAILURE_Bad_ModDesc_CRC.zip");
@@ -108,6 +145,23 @@ fn missing_desc_version() {
     ]);
     assert_eq!(mod_record.issues, expected_errors);
 
+    let violations: Vec<(ModDescViolationKind, &str, u32)> = mod_record
+        .validation
+        .iter()
+        .map(|n| (n.kind, n.path.as_str(), n.line))
+        .collect();
+    assert_eq!(
+        violations,
+        vec![
+            (
+                ModDescViolationKind::MissingAttribute,
+                "modDesc/@descVersion",
+                2
+            ),
+            (ModDescViolationKind::MissingElement, "modDesc/version", 2),
+        ]
+    );
+
     assert_eq!(
         mod_record.badge_array,
         ModBadges {
@@ -0,0 +1,98 @@
+use fs_mod_parser::collection::{dependency_order, Index};
+use fs_mod_parser::metrics::CounterMetrics;
+use fs_mod_parser::shared::errors::FsModParserError;
+use fs_mod_parser::shared::structs::{ModDependency, ModRecord};
+use fs_mod_parser::ModParserOptions;
+use std::sync::Arc;
+
+#[test]
+fn index_save_and_load() {
+    let work_dir = std::env::temp_dir().join(format!("fs_mod_parser_index_{}", std::process::id()));
+    std::fs::create_dir_all(&work_dir).unwrap();
+    let test_file_path = work_dir.join("FS22_Index_Test.zip");
+    let index_path = work_dir.join("collection.index");
+    std::fs::copy(
+        "./tests/test_mods/PASS_Good_Simple_Mod.zip",
+        &test_file_path,
+    )
+    .unwrap();
+
+    let metrics = Arc::new(CounterMetrics::new());
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        metrics: Some(metrics.clone()),
+        ..Default::default()
+    };
+
+    let mut index = Index::new();
+    index
+        .metadata
+        .insert(String::from("name"), String::from("Server"));
+    let first = index.update(&test_file_path, &options);
+    assert!(!first.can_not_use);
+    index.save(&index_path).unwrap();
+
+    let mut loaded = Index::load(&index_path).unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded.metadata["name"], "Server");
+    assert!(loaded.saved.is_some());
+
+    let second = loaded.update(&test_file_path, &options);
+    assert_eq!(second.file_detail.short_name, "FS22_Index_Test");
+    assert_eq!(metrics.snapshot().mods_parsed, 1);
+    assert_eq!(metrics.snapshot().cache_hits, 1);
+
+    std::fs::write(&test_file_path, b"not a zip file").unwrap();
+    assert!(Index::load(&index_path).unwrap().is_empty());
+
+    let mut stale = index.clone();
+    stale.parser_version = String::from("0.0.0");
+    stale.save(&index_path).unwrap();
+    assert!(Index::load(&index_path).unwrap().is_empty());
+
+    std::fs::write(&index_path, b"garbage").unwrap();
+    assert!(matches!(
+        Index::load(&index_path),
+        Err(FsModParserError::InvalidIndex(..))
+    ));
+
+    std::fs::remove_dir_all(&work_dir).unwrap();
+}
+
+#[test]
+fn index_keeps_dependency_versions() {
+    let work_dir =
+        std::env::temp_dir().join(format!("fs_mod_parser_index_depend_{}", std::process::id()));
+    std::fs::create_dir_all(&work_dir).unwrap();
+    let lib_path = work_dir.join("FS22_Lib.zip");
+    let tractor_path = work_dir.join("FS22_Tractor.zip");
+    let index_path = work_dir.join("collection.index");
+    std::fs::write(&lib_path, b"lib").unwrap();
+    std::fs::write(&tractor_path, b"tractor").unwrap();
+
+    let mut lib = ModRecord::new(&lib_path, false);
+    lib.mod_desc.version = String::from("1.0.0.0");
+    let mut tractor = ModRecord::new(&tractor_path, false);
+    tractor.mod_desc.depend = vec![ModDependency {
+        name: String::from("FS22_Lib"),
+        min_version: Some(String::from("1.1.0.0")),
+    }];
+    tractor
+        .mod_desc
+        .depend_versions
+        .insert(String::from("FS22_Lib"), String::from("1.1.0.0"));
+
+    let mut index = Index::new();
+    assert!(index.insert(&lib_path, lib));
+    assert!(index.insert(&tractor_path, tractor));
+    let before = dependency_order(&index.records().cloned().collect::<Vec<_>>());
+    assert_eq!(before.outdated.len(), 1);
+    index.save(&index_path).unwrap();
+
+    let loaded = Index::load(&index_path).unwrap();
+    assert_eq!(loaded.len(), 2);
+    let after = dependency_order(&loaded.records().cloned().collect::<Vec<_>>());
+    assert_eq!(after.outdated, before.outdated);
+
+    std::fs::remove_dir_all(&work_dir).unwrap();
+}
@@ -1,6 +1,7 @@
 use assert_json_diff::assert_json_include;
 use fs_mod_parser::maps::structs::CropOutput;
-use fs_mod_parser::parse_mod;
+use fs_mod_parser::shared::errors::ModError;
+use fs_mod_parser::{parse_mod, parse_mod_with_options, ModParserOptions};
 use serde_json::json;
 
 #[test]
@@ -410,6 +411,80 @@ fn test_custom_growth() {
     );
 }
 
+#[test]
+fn test_custom_growth_diff() {
+    let result = parse_mod("./tests/test_mods/MAP_CustomGrowth.zip");
+
+    let Some(crop_diff) = result.mod_desc.crop_diff else {
+        panic!("custom growth should have a crop diff");
+    };
+
+    assert!(crop_diff.added.is_empty());
+    assert!(crop_diff.removed.is_empty());
+
+    let actual = json!(crop_diff.changed);
+    let expected = json!([
+        {
+            "name": "wheat",
+            "baseGrowthTime": 8,
+            "growthTime": 8,
+            "harvestAdded": [],
+            "harvestRemoved": [],
+            "plantAdded": [2, 3],
+            "plantRemoved": []
+        },
+        {
+            "name": "barley",
+            "baseGrowthTime": 7,
+            "growthTime": 7,
+            "harvestAdded": [],
+            "harvestRemoved": [5],
+            "plantAdded": [2, 3],
+            "plantRemoved": []
+        }
+    ]);
+    assert_json_include!(actual: actual, expected: expected);
+
+    let result = parse_mod("./tests/test_mods/MAP_NoCustoms.zip");
+    assert!(result.mod_desc.crop_diff.is_none());
+}
+
+#[test]
+fn test_crop_calendar() {
+    let result = parse_mod("./tests/test_mods/MAP_NoCustoms.zip");
+
+    assert_eq!(
+        result.mod_desc.crop_calendar.len(),
+        result.mod_desc.crop_info.len()
+    );
+
+    let Some(wheat) = result
+        .mod_desc
+        .crop_calendar
+        .iter()
+        .find(|crop| crop.name == "wheat")
+    else {
+        panic!("wheat missing from calendar");
+    };
+
+    let actual = json!(wheat.months);
+    let expected = json!([
+        { "plant": false, "harvest": false, "growing": true },
+        { "plant": false, "harvest": false, "growing": true },
+        { "plant": false, "harvest": false, "growing": true },
+        { "plant": false, "harvest": false, "growing": true },
+        { "plant": false, "harvest": false, "growing": true },
+        { "plant": false, "harvest": false, "growing": false },
+        { "plant": false, "harvest": true, "growing": false },
+        { "plant": false, "harvest": true, "growing": false },
+        { "plant": true, "harvest": false, "growing": false },
+        { "plant": true, "harvest": false, "growing": true },
+        { "plant": false, "harvest": false, "growing": true },
+        { "plant": false, "harvest": false, "growing": true }
+    ]);
+    assert_json_include!(actual: actual, expected: expected);
+}
+
 #[test]
 fn test_no_customs() {
     let result = parse_mod("./tests/test_mods/MAP_NoCustoms.zip");
@@ -431,6 +506,25 @@ fn test_no_customs() {
     assert_eq!(result.mod_desc.crop_info.len(), 17);
 }
 
+#[test]
+fn test_missing_density_files() {
+    let options = ModParserOptions {
+        map_density_check: true,
+        ..Default::default()
+    };
+    let result = parse_mod_with_options("./tests/test_mods/MAP_NoCustoms.zip", &options);
+
+    assert!(result.issues.contains(&ModError::MapDensityFileMissing));
+    assert_eq!(
+        result.mod_desc.map_density_missing,
+        vec!["maps/data/SoilMap.grle"]
+    );
+
+    let result = parse_mod("./tests/test_mods/MAP_NoCustoms.zip");
+    assert!(!result.issues.contains(&ModError::MapDensityFileMissing));
+    assert!(result.mod_desc.map_density_missing.is_empty());
+}
+
 #[test]
 fn test_added_crops() {
     let result = parse_mod("./tests/test_mods/MAP_AddedCrops.zip");
@@ -487,3 +581,45 @@ fn test_added_crops() {
         "silage_corn"
     );
 }
+
+#[test]
+fn test_crop_addon_merge() {
+    let map = parse_mod("./tests/test_mods/MAP_NoCustoms.zip");
+    let addon = parse_mod("./tests/test_mods/MAP_Crop_Addon");
+    let other = parse_mod("./tests/test_mods/PASS_Good_Simple_Mod.zip");
+
+    let mut addon_crops = addon.mod_desc.crop_info.names().collect::<Vec<_>>();
+    addon_crops.sort_unstable();
+    assert_eq!(addon_crops, ["alfalfa", "oat"]);
+    assert!(other.mod_desc.crop_info.is_empty());
+
+    let map_crops = map.mod_desc.crop_info.len();
+    assert!(!map.mod_desc.crop_info.names().any(|n| n == "alfalfa"));
+
+    let merged = fs_mod_parser::collection::crop_calendars(&[map, other, addon]);
+    let _ = merged[0].to_json();
+
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].map, "MAP_NoCustoms");
+    assert_eq!(merged[0].crop_info.len(), map_crops + 1);
+    assert_eq!(merged[0].crop_calendar.len(), map_crops + 1);
+    assert_eq!(merged[0].overridden, vec!["oat"]);
+    assert_eq!(
+        merged[0].sources.get("alfalfa").map(String::as_str),
+        Some("MAP_Crop_Addon")
+    );
+    assert_eq!(
+        merged[0].sources.get("oat").map(String::as_str),
+        Some("MAP_Crop_Addon")
+    );
+    assert_eq!(
+        merged[0].sources.get("wheat").map(String::as_str),
+        Some("MAP_NoCustoms")
+    );
+
+    let mut crop_info = merged.into_iter().next().unwrap().crop_info;
+    assert_eq!(
+        crop_info.get("oat").map(|n| n.plant_periods.clone()),
+        Some(vec![1, 2, 3])
+    );
+}
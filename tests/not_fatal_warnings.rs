@@ -1,6 +1,7 @@
 use fs_mod_parser::mod_basic::parser;
 use fs_mod_parser::shared::errors::ModError;
-use fs_mod_parser::shared::structs::ModBadges;
+use fs_mod_parser::shared::structs::{ModBadges, ModDescViolationKind};
+use fs_mod_parser::{parse_mod_with_options, ModParserOptions};
 use std::collections::HashSet;
 use std::path::Path;
 
@@ -19,6 +20,27 @@ fn piracy_warning() {
         ModError::PerformanceQuantityExtra,
     ]);
     assert_eq!(mod_record.issues, expected_errors);
+    assert_eq!(
+        mod_record.issue_context[&ModError::InfoLikelyPiracy],
+        vec!["empty-script.l64", "productID.dat"]
+    );
+    assert_eq!(
+        mod_record.issue_context[&ModError::PerformanceQuantityExtra].len(),
+        2
+    );
+
+    let icon_info = mod_record.mod_desc.icon_info.as_ref().unwrap();
+    assert_eq!((icon_info.width, icon_info.height), (256, 256));
+    assert_eq!(icon_info.format, "DXT1");
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        ..Default::default()
+    };
+    let header_record = parse_mod_with_options(test_file_path, &options);
+    let icon_info = header_record.mod_desc.icon_info.as_ref().unwrap();
+    assert_eq!((icon_info.width, icon_info.height), (256, 256));
+    assert_eq!(icon_info.format, "DXT1");
 
     assert_eq!(
         mod_record.badge_array,
@@ -35,6 +57,36 @@ fn piracy_warning() {
     );
 }
 
+#[test]
+fn allowed_extra_files() {
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        allowed_extra_files: vec![String::from("DAT"), String::from("empty-script.l64")],
+        ..Default::default()
+    };
+
+    let mod_record =
+        parse_mod_with_options("./tests/test_mods/WARNING_Fake_Cracked_DLC.zip", &options);
+    assert_eq!(
+        mod_record.issues,
+        HashSet::from([ModError::InfoLikelyPiracy])
+    );
+    assert_eq!(mod_record.file_detail.extra_files.len(), 2);
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        allowed_extra_files: vec![String::from("exe")],
+        ..Default::default()
+    };
+
+    let mod_record = parse_mod_with_options("./tests/test_mods/FAIL_Contains_EXE.zip", &options);
+    assert!(mod_record.can_not_use);
+    assert_eq!(
+        mod_record.issues,
+        HashSet::from([ModError::InfoDangerousFile])
+    );
+}
+
 #[test]
 fn icon_not_found() {
     let test_file_path = Path::new("./tests/test_mods/WARNING_Icon_Not_Found.zip");
@@ -63,6 +115,22 @@ fn icon_not_found() {
     );
 }
 
+#[test]
+fn icon_not_found_skipped_icons() {
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        ..Default::default()
+    };
+
+    let mod_record =
+        parse_mod_with_options("./tests/test_mods/WARNING_Icon_Not_Found.zip", &options);
+    assert_eq!(
+        mod_record.issues,
+        HashSet::from([ModError::ModDescNoModIcon])
+    );
+    assert!(mod_record.mod_desc.icon_image.is_none());
+}
+
 #[test]
 fn malicious_code_check() {
     let test_file_path = Path::new("./tests/test_mods/WARNING_Malicious_Code.zip");
@@ -105,6 +173,16 @@ fn no_version() {
     let expected_errors: HashSet<ModError> = HashSet::from([ModError::ModDescNoModVersion]);
     assert_eq!(mod_record.issues, expected_errors);
 
+    let violations: Vec<(ModDescViolationKind, &str, u32)> = mod_record
+        .validation
+        .iter()
+        .map(|n| (n.kind, n.path.as_str(), n.line))
+        .collect();
+    assert_eq!(
+        violations,
+        vec![(ModDescViolationKind::MissingElement, "modDesc/version", 2)]
+    );
+
     assert_eq!(
         mod_record.badge_array,
         ModBadges {
@@ -172,6 +250,10 @@ fn dangerous_file_check() {
     let expected_errors: HashSet<ModError> =
         HashSet::from([ModError::PerformanceQuantityExtra, ModError::InfoDangerousFile]);
     assert_eq!(mod_record.issues, expected_errors);
+    assert_eq!(
+        mod_record.issue_context[&ModError::InfoDangerousFile],
+        vec!["empty.exe"]
+    );
 
     assert_eq!(
         mod_record.badge_array,
@@ -186,4 +268,55 @@ fn dangerous_file_check() {
             savegame: false,
         }
     );
-}
\ No newline at end of file
+}
+#[test]
+fn name_case_fallback() {
+    let test_file_path = Path::new("./tests/test_mods/WARNING_Name_Case.zip");
+    assert!(test_file_path.exists());
+
+    let mod_record = parser(test_file_path);
+    let _ = mod_record.to_json();
+
+    assert_eq!(mod_record.can_not_use, false);
+    assert!(mod_record.issues.contains(&ModError::FileErrorNameCase));
+    assert!(!mod_record.issues.contains(&ModError::ModDescMissing));
+    assert_eq!(
+        mod_record.issue_context[&ModError::FileErrorNameCase],
+        vec!["dolly.xml", "moddesc.xml", "ModIcon.dds"]
+    );
+    assert!(mod_record.mod_desc.icon_info.is_some());
+    assert!(mod_record.badge_array.problem);
+
+    // store item icons are only read by the detail stage
+    let options = ModParserOptions {
+        include_mod_detail: true,
+        skip_detail_icons: false,
+        ..Default::default()
+    };
+    let mod_record = parse_mod_with_options(test_file_path, &options);
+    assert_eq!(
+        mod_record.issue_context[&ModError::FileErrorNameCase],
+        vec!["dolly.xml", "moddesc.xml", "ModIcon.dds", "Store_Dolly.dds"]
+    );
+    let mod_detail = mod_record.include_detail.as_ref().unwrap();
+    assert!(mod_detail.vehicles.contains_key("Dolly.xml"));
+    assert!(mod_detail.case_fallbacks.is_empty());
+}
+
+#[test]
+fn damaged_mod_desc_recovered() {
+    let test_file_path = Path::new("./tests/test_mods/WARNING_Damaged_ModDesc.zip");
+    assert!(test_file_path.exists());
+
+    let mod_record = parser(test_file_path);
+    let _ = mod_record.to_json();
+
+    assert_eq!(mod_record.can_not_use, false);
+    assert!(mod_record.issues.contains(&ModError::ModDescDamaged));
+    assert!(!mod_record.issues.contains(&ModError::ModDescParseError));
+    assert_eq!(
+        mod_record.l10n.title.get("en"),
+        Some(&String::from("Café & Bar – Damaged\u{a0}Mod"))
+    );
+    assert!(mod_record.badge_array.problem);
+}
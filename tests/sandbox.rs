@@ -0,0 +1,46 @@
+use fs_mod_parser::sandbox::{parse_mod_sandboxed, SandboxError, SandboxOptions};
+use fs_mod_parser::ModParserOptions;
+use std::path::Path;
+
+#[test]
+fn sandboxed_good_mod() {
+    let test_file_path = Path::new("./tests/test_mods/PASS_Good_Simple_Mod.zip");
+    assert!(test_file_path.exists());
+
+    let sandbox = SandboxOptions::new(env!("CARGO_BIN_EXE_parse_file"));
+    let output = parse_mod_sandboxed(test_file_path, &ModParserOptions::default(), &sandbox)
+        .expect("worker failed");
+
+    let actual: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(actual["canNotUse"], false);
+    assert_eq!(actual["fileDetail"]["shortName"], "PASS_Good_Simple_Mod");
+}
+
+#[test]
+fn sandboxed_garbage_file() {
+    let test_file_path = Path::new("./tests/test_mods/FAILURE_Garbage_File.txt");
+    assert!(test_file_path.exists());
+
+    let sandbox = SandboxOptions::new(env!("CARGO_BIN_EXE_parse_file"));
+    let output = parse_mod_sandboxed(test_file_path, &ModParserOptions::default(), &sandbox)
+        .expect("worker failed");
+
+    let actual: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(actual["canNotUse"], true);
+}
+
+#[test]
+fn sandboxed_output_too_large() {
+    let test_file_path = Path::new("./tests/test_mods/DETAIL_Samples.zip");
+    assert!(test_file_path.exists());
+
+    let options = ModParserOptions {
+        include_mod_detail: true,
+        ..Default::default()
+    };
+    let mut sandbox = SandboxOptions::new(env!("CARGO_BIN_EXE_parse_file"));
+    sandbox.max_output = 1024;
+
+    let result = parse_mod_sandboxed(test_file_path, &options, &sandbox);
+    assert!(matches!(result, Err(SandboxError::OutputTooLarge)));
+}
@@ -1,7 +1,13 @@
-use fs_mod_parser::savegame::SaveError;
+use fs_mod_parser::savegame::{
+    check_vehicles, SaveError, SaveGameForecast, SaveGameModUsage, SaveGameRecord,
+    SaveGameVehicleProblem,
+};
 use fs_mod_parser::shared::errors::ModError;
-use fs_mod_parser::shared::structs::ModBadges;
-use fs_mod_parser::{parse_mod, parse_mod_with_options, parse_savegame, ModParserOptions};
+use fs_mod_parser::shared::structs::{ModBadges, ModDependency, ModRecord};
+use fs_mod_parser::{
+    parse_mod, parse_mod_with_options, parse_savegame, parse_savegame_with_options,
+    ModParserOptions,
+};
 use std::collections::HashSet;
 use std::path::Path;
 
@@ -185,6 +191,208 @@ fn good_multiplayer() {
     assert_json_include!(actual : actual, expected : expected_mod);
 }
 
+#[test]
+fn good_multiplayer_finance_history() {
+    let test_file_path = Path::new("./tests/test_mods/SAVEGAME_Good.zip");
+    assert!(test_file_path.exists());
+
+    let save_record = parse_savegame(test_file_path);
+
+    assert_eq!(save_record.farms[&0].finance_history, None);
+
+    let Some(history) = &save_record.farms[&1].finance_history else {
+        panic!("farm 1 finance history missing");
+    };
+    assert_eq!(history.days_ago, vec![4, 3, 2, 1, 0]);
+    assert_eq!(
+        history.series.get("propertyIncome"),
+        Some(&vec![3069.0, 5022.0, 948.599976, 3943.199951, 7440.0])
+    );
+    assert_eq!(
+        history.series.get("harvestIncome"),
+        Some(&vec![0.0, 23971.910156, 0.0, 0.0, 0.0])
+    );
+
+    assert_eq!(&history.downsample(10), history);
+
+    let chart = history.downsample(3);
+    assert_eq!(chart.days_ago, vec![4, 2, 0]);
+    assert_eq!(
+        chart.series.get("propertyIncome"),
+        Some(&vec![3069.0, 948.599976, 7440.0])
+    );
+}
+
+#[test]
+fn good_multiplayer_round_trip() {
+    let save_record = parse_savegame("./tests/test_mods/SAVEGAME_Good.zip");
+
+    let stored: SaveGameRecord = serde_json::from_str(&save_record.to_json()).unwrap();
+    assert_eq!(json!(stored), json!(save_record));
+    assert_eq!(stored.unused_mods(), save_record.unused_mods());
+}
+
+#[test]
+fn good_multiplayer_manifest() {
+    let test_file_path = Path::new("./tests/test_mods/SAVEGAME_Good.zip");
+    assert!(test_file_path.exists());
+
+    let save_record = parse_savegame(test_file_path);
+
+    let manifest = save_record.mod_manifest(None);
+    assert_eq!(manifest.len(), 38);
+
+    let map_entry = manifest
+        .iter()
+        .find(|n| n.short_name == "FS22_BackRoadsCounty")
+        .expect("map missing from manifest");
+    assert_eq!(map_entry.version, "1.0.0.2");
+    assert_eq!(map_entry.is_dlc, false);
+    assert_eq!(map_entry.used_by, vec![0, 1, 4, 5, 15]);
+
+    let installed = vec![String::from("FS22_BackRoadsCounty")];
+    let filtered = save_record.mod_manifest(Some(&installed));
+    assert_eq!(filtered.len(), 37);
+    assert!(!filtered
+        .iter()
+        .any(|n| n.short_name == "FS22_BackRoadsCounty"));
+}
+
+#[test]
+fn good_multiplayer_mod_usage() {
+    let test_file_path = Path::new("./tests/test_mods/SAVEGAME_Good.zip");
+    assert!(test_file_path.exists());
+
+    let save_record = parse_savegame(test_file_path);
+
+    let map_mod = &save_record.mods["FS22_BackRoadsCounty"];
+    assert_eq!(map_mod.usage, SaveGameModUsage::Map);
+    assert_eq!((map_mod.vehicles, map_mod.placeables), (13, 55));
+
+    let sheds = &save_record.mods["FS22_DutchShedPack"];
+    assert_eq!(sheds.usage, SaveGameModUsage::Used);
+    assert_eq!((sheds.vehicles, sheds.placeables), (0, 2));
+
+    let balers = &save_record.mods["FS22_JDBalers"];
+    assert_eq!(balers.usage, SaveGameModUsage::Used);
+    assert_eq!((balers.vehicles, balers.placeables), (2, 0));
+
+    // nothing is certainly unused without the mod records
+    assert!(save_record.unused_mods().is_empty());
+    let no_items: Vec<&String> = save_record
+        .mods
+        .iter()
+        .filter(|(_, this_mod)| this_mod.usage == SaveGameModUsage::NoItems)
+        .map(|(short_name, _)| short_name)
+        .collect();
+    assert_eq!(no_items.len(), 21);
+
+    assert_json_include!(
+        actual : json!(save_record),
+        expected : json!({
+            "mods": {
+                "FS22_TreeBGone": { "placeables": 0, "vehicles": 0, "usage": "NO_ITEMS" },
+                "FS22_MPsignPack": { "placeables": 5, "vehicles": 0, "usage": "USED" },
+            }
+        })
+    );
+}
+
+#[test]
+fn good_multiplayer_mod_usage_with_records() {
+    let mut save_record = parse_savegame("./tests/test_mods/SAVEGAME_Good.zip");
+
+    let store_only = |file_name: &str| {
+        let mut record = ModRecord::new(file_name, false);
+        record.mod_desc.store_items = 1;
+        record
+    };
+    let mut series = store_only("FS22_2150_Series.zip");
+    series
+        .mod_desc
+        .depend
+        .push(ModDependency::new("FS22_TreeBGone"));
+    let mut inspector = store_only("FS22_SimpleInspector.zip");
+    inspector.mod_desc.script_files = 2;
+    let mut not_loaded = store_only("FS22_NotLoaded.zip");
+    not_loaded
+        .mod_desc
+        .depend
+        .push(ModDependency::new("FS22_2150_Series"));
+    let records = vec![
+        series,
+        inspector,
+        store_only("FS22_TreeBGone.zip"),
+        store_only("FS22_JDBalers.zip"),
+        not_loaded,
+    ];
+
+    save_record.classify_with_mods(&records);
+    assert_eq!(save_record.unused_mods(), vec!["FS22_2150_Series"]);
+    assert_eq!(
+        save_record.mods["FS22_SimpleInspector"].usage,
+        SaveGameModUsage::NoItems
+    );
+    assert_eq!(
+        save_record.mods["FS22_TreeBGone"].usage,
+        SaveGameModUsage::NoItems
+    );
+    assert_eq!(
+        save_record.mods["FS22_JDBalers"].usage,
+        SaveGameModUsage::Used
+    );
+}
+
+#[test]
+fn good_multiplayer_vehicle_check() {
+    let test_file_path = Path::new("./tests/test_mods/SAVEGAME_Good.zip");
+    let mods_folder = Path::new("./tests/test_mods/SAVEGAME_Mods_Folder");
+    assert!(test_file_path.exists());
+    assert!(mods_folder.exists());
+
+    let check = check_vehicles(test_file_path, mods_folder);
+    let _ = check.to_json();
+
+    assert!(check.error_list.is_empty());
+    assert_eq!(check.mod_vehicles, 24);
+    assert_eq!(check.missing.len(), 13);
+
+    let file_missing: Vec<(&str, &str, &str)> = check
+        .missing
+        .iter()
+        .filter(|n| n.problem == SaveGameVehicleProblem::FileMissing)
+        .map(|n| (n.id.as_str(), n.mod_name.as_str(), n.file_name.as_str()))
+        .collect();
+    assert_eq!(
+        file_missing,
+        vec![
+            (
+                "19",
+                "FS22_BackRoadsCounty",
+                "map/xml/Vehicles/timberRunner.xml"
+            ),
+            (
+                "20",
+                "FS22_BackRoadsCounty",
+                "map/xml/Vehicles/timberRunner.xml"
+            ),
+            ("34", "FS22_36ftLowLoader", "36FT_Low_Loader.xml"),
+        ]
+    );
+
+    let low_loader = check.missing.iter().find(|n| n.id == "34").unwrap();
+    assert_eq!(low_loader.farm_id, 1);
+    assert!(!check
+        .missing
+        .iter()
+        .any(|n| n.problem == SaveGameVehicleProblem::ModMissing
+            && (n.mod_name == "FS22_BackRoadsCounty" || n.mod_name == "FS22_36ftLowLoader")));
+
+    let unreadable = check_vehicles("./tests/test_mods/SAVEGAME_No_Vehicles.zip", mods_folder);
+    assert!(unreadable.error_list.contains(&SaveError::VehicleMissing));
+    assert!(unreadable.missing.is_empty());
+}
+
 #[test]
 fn good_single_player() {
     let test_file_path = Path::new("./tests/test_mods/SAVEGAME_Single_Farm.zip");
@@ -228,6 +436,25 @@ fn good_single_player() {
     assert_json_include!(actual : actual, expected : expected_farms);
 }
 
+#[test]
+fn nested_save_folder() {
+    let test_file_path = Path::new("./tests/test_mods/SAVEGAME_Nested.zip");
+    assert!(test_file_path.exists());
+
+    let mod_record = parse_mod(test_file_path);
+    assert!(mod_record.file_detail.is_save_game);
+    assert_eq!(
+        mod_record.issues,
+        HashSet::from([ModError::FileErrorLikelySaveGame])
+    );
+
+    let save_record = parse_savegame(test_file_path);
+    assert_eq!(save_record.is_valid, true);
+    assert_eq!(save_record.error_list.len(), 0);
+    assert_eq!(save_record.single_farm, true);
+    assert_eq!(save_record.map_mod, Some(String::from("MapFR")));
+}
+
 #[test]
 fn mod_parse_save_detection_with_scan() {
     let test_file_path = Path::new("./tests/test_mods/SAVEGAME_Good.zip");
@@ -262,7 +489,7 @@ fn mod_parse_save_detection_with_scan() {
     assert!(mod_record.include_save_game.is_some());
 
     let byte_length = mod_record.to_json_pretty().len() as i32;
-    let byte_expected: i32 = 7615;
+    let byte_expected: i32 = 32088;
     let byte_margin = 100;
     assert!(
         (byte_length - byte_expected).abs() < byte_margin,
@@ -274,3 +501,69 @@ fn mod_parse_save_detection_with_scan() {
         (byte_length - byte_expected).abs()
     );
 }
+
+#[test]
+fn good_multiplayer_statistics() {
+    let test_file_path = Path::new("./tests/test_mods/SAVEGAME_Good.zip");
+    assert!(test_file_path.exists());
+
+    let save_record = parse_savegame(test_file_path);
+    assert!(save_record.farms.values().all(|n| n.statistics.is_none()));
+
+    let options = ModParserOptions {
+        save_game_statistics: true,
+        ..Default::default()
+    };
+    let save_record = parse_savegame_with_options(test_file_path, &options);
+    let _ = save_record.to_json();
+
+    assert_eq!(save_record.farms[&0].statistics, None);
+
+    let Some(statistics) = &save_record.farms[&1].statistics else {
+        panic!("farm 1 statistics missing");
+    };
+    assert_eq!(statistics.get("baleCount"), Some(&259.0));
+    assert_eq!(statistics.get("plowedHectares"), Some(&12.345325));
+    assert_eq!(statistics.get("breedCowsCount"), Some(&0.0));
+
+    let Some(statistics) = &save_record.farms[&4].statistics else {
+        panic!("farm 4 statistics missing");
+    };
+    assert_eq!(statistics.get("baleCount"), Some(&355.0));
+}
+
+#[test]
+fn environment_date_and_weather() {
+    let test_file_path = Path::new("./tests/test_mods/SAVEGAME_Environment");
+    assert!(test_file_path.exists());
+
+    let save_record = parse_savegame(test_file_path);
+    let _ = save_record.to_json();
+
+    let Some(environment) = save_record.environment else {
+        panic!("environment missing");
+    };
+
+    assert_eq!(environment.day, 45);
+    assert_eq!(environment.time, "09:23");
+    assert_eq!(environment.days_per_period, 3);
+    assert_eq!(environment.day_in_period, 3);
+    assert_eq!(environment.period, 3);
+    assert_eq!(environment.month, 5);
+    assert_eq!(environment.year, 2);
+
+    assert_eq!(environment.forecast.len(), 4);
+    assert_eq!(
+        environment.forecast[2],
+        SaveGameForecast {
+            weather: String::from("RAIN"),
+            season: String::from("SPRING"),
+            start_day: 45,
+            start_time: String::from("18:00"),
+            duration: 600,
+        }
+    );
+
+    let save_record = parse_savegame(Path::new("./tests/test_mods/SAVEGAME_Good.zip"));
+    assert!(save_record.environment.is_none());
+}
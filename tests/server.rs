@@ -0,0 +1,54 @@
+use fs_mod_parser::scan_server_mods;
+use fs_mod_parser::server::{scan_server_mods_with_options, ServerModProblem, ServerRules};
+use fs_mod_parser::ModParserOptions;
+
+#[test]
+fn server_folder_readiness() {
+    let work_dir =
+        std::env::temp_dir().join(format!("fs_mod_parser_server_{}", std::process::id()));
+    std::fs::create_dir_all(work_dir.join("FS22_Unzipped")).unwrap();
+    for (source, target) in [
+        ("PASS_Good_Simple_Mod.zip", "FS22_Good.zip"),
+        ("WARNING_Malicious_Code.zip", "FS22_Scripted.zip"),
+        ("SAVEGAME_Good.zip", "savegame1.zip"),
+    ] {
+        std::fs::copy(format!("./tests/test_mods/{source}"), work_dir.join(target)).unwrap();
+    }
+    std::fs::write(work_dir.join("readme.txt"), b"not a mod").unwrap();
+
+    let report = scan_server_mods(&work_dir);
+
+    assert!(!report.all_ready);
+    assert_eq!(report.mods.len(), 4);
+    assert_eq!(report.ready_count, 2);
+    assert_eq!(report.not_ready_count, 2);
+
+    let names: Vec<&str> = report.mods.iter().map(|n| n.short_name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["FS22_Good", "FS22_Scripted", "FS22_Unzipped", "savegame1"]
+    );
+    assert!(report.mods[0].ready);
+    assert!(report.mods[1].ready);
+    assert_eq!(
+        report.mods[2].problems,
+        vec![ServerModProblem::CanNotUse, ServerModProblem::Unzipped]
+    );
+    assert_eq!(report.mods[3].problems, vec![ServerModProblem::CanNotUse]);
+
+    let strict = scan_server_mods_with_options(
+        &work_dir,
+        &ModParserOptions {
+            skip_mod_icons: true,
+            ..Default::default()
+        },
+        &ServerRules {
+            allow_scripts: false,
+        },
+    );
+    assert_eq!(strict.ready_count, 1);
+    assert_eq!(strict.mods[1].problems, vec![ServerModProblem::HasScripts]);
+    assert!(strict.to_json().contains("SERVER_HAS_SCRIPTS"));
+
+    std::fs::remove_dir_all(&work_dir).unwrap();
+}
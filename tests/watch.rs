@@ -0,0 +1,42 @@
+#![cfg(feature = "watch")]
+use fs_mod_parser::watch::{watch_mods, WatchEvent};
+use fs_mod_parser::ModParserOptions;
+use std::time::Duration;
+
+#[test]
+fn watch_added_and_removed_mod() {
+    let work_dir = std::env::temp_dir().join(format!("fs_mod_parser_watch_{}", std::process::id()));
+    std::fs::create_dir_all(&work_dir).unwrap();
+
+    let options = ModParserOptions {
+        skip_mod_icons: true,
+        ..Default::default()
+    };
+    let watcher = watch_mods(&work_dir, options).unwrap();
+    let test_file_path = work_dir.canonicalize().unwrap().join("FS22_Watch_Test.zip");
+
+    std::fs::copy(
+        "./tests/test_mods/PASS_Good_Simple_Mod.zip",
+        &test_file_path,
+    )
+    .unwrap();
+    std::fs::write(work_dir.join("notes.txt"), b"not a mod").unwrap();
+
+    match watcher.recv_timeout(Duration::from_secs(10)) {
+        Some(WatchEvent::Changed(path, record)) => {
+            assert_eq!(path, test_file_path);
+            assert_eq!(record.file_detail.short_name, "FS22_Watch_Test");
+            assert!(!record.can_not_use);
+        }
+        _ => panic!("expected a changed mod"),
+    }
+
+    std::fs::remove_file(&test_file_path).unwrap();
+    match watcher.recv_timeout(Duration::from_secs(10)) {
+        Some(WatchEvent::Removed(path)) => assert_eq!(path, test_file_path),
+        _ => panic!("expected a removed mod"),
+    }
+    assert!(watcher.try_recv().is_none());
+
+    std::fs::remove_dir_all(&work_dir).unwrap();
+}
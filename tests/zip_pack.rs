@@ -40,6 +40,34 @@ fn is_rar() {
     assert_eq!(mod_record.issues, expected_errors);
 }
 
+#[test]
+fn is_mod_folder() {
+    let test_file_path = Path::new("./tests/test_mods");
+    assert!(test_file_path.is_dir());
+
+    let mod_record = parser(test_file_path);
+    let _ = mod_record.to_json();
+
+    assert_eq!(mod_record.can_not_use, true);
+
+    let expected_errors: HashSet<ModError> = HashSet::from([ModError::FileErrorLikelyModFolder]);
+    assert_eq!(mod_record.issues, expected_errors);
+    assert!(mod_record.badge_array.notmod);
+    assert!(mod_record.badge_array.broken);
+
+    assert!(mod_record.file_detail.zip_files.len() > 2);
+    assert!(mod_record
+        .file_detail
+        .zip_files
+        .iter()
+        .any(|n| n.name == "VARIANT_Mod_Pack.zip"));
+
+    let unzipped_mod = parser(Path::new("./tests/test_mods/PASS_Good_Simple_Mod"));
+    assert!(!unzipped_mod
+        .issues
+        .contains(&ModError::FileErrorLikelyModFolder));
+}
+
 #[test]
 fn is_zip_pack() {
     let test_file_path = Path::new("./tests/test_mods/VARIANT_Mod_Pack.zip");